@@ -2,7 +2,13 @@ use prisma_client_rust_sdk::{prelude::*, GenerateArgs};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn generate(args: &GenerateArgs) -> TokenStream {
+use crate::header::VersionCheck;
+
+pub fn generate(
+    args: &GenerateArgs,
+    generate_model_actions: bool,
+    version_check: VersionCheck,
+) -> TokenStream {
     let model_actions = args
         .schema
         .db
@@ -22,6 +28,21 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
 
     let pcr = quote!(::prisma_client_rust);
 
+    let model_actions_fn = generate_model_actions.then(|| {
+        let models = args
+            .schema
+            .db
+            .walk_models()
+            .map(|model| snake_ident(model.name()));
+
+        quote! {
+            /// The actions of every model, for generic code that works with any model.
+            pub fn _model_actions(&self) -> Vec<Box<dyn #pcr::ModelActions<'_> + '_>> {
+                vec![#(Box::new(super::#models::Actions { client: &self.0 })),*]
+            }
+        }
+    });
+
     let migrate_fns = cfg!(feature = "migrations").then(|| {
         quote! {
             pub async fn _migrate_deploy(&self) -> Result<(), #pcr::migrations::MigrateDeployError> {
@@ -63,6 +84,177 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
         }
     });
 
+    let metrics_fn = cfg!(feature = "metrics").then(|| {
+        quote! {
+            pub fn _metrics(&self) -> #pcr::MetricsSnapshot {
+                self.0.metrics()
+            }
+        }
+    });
+
+    let disconnect_on_drop = cfg!(feature = "disconnect-on-drop");
+
+    let disconnect_field =
+        disconnect_on_drop.then(|| quote!(disconnect_on_drop: Option<#pcr::DisconnectOnDrop>,));
+    let disconnect_default = disconnect_on_drop.then(|| quote!(disconnect_on_drop: None,));
+
+    let disconnect_fn = disconnect_on_drop.then(|| {
+        quote! {
+            /// Disconnects from the database once the client, and every transaction created from it, is dropped.
+            pub fn disconnect_on_drop(mut self, mode: #pcr::DisconnectOnDrop) -> Self {
+                self.disconnect_on_drop = Some(mode);
+                self
+            }
+        }
+    });
+
+    let disconnect_build = disconnect_on_drop.then(|| {
+        quote! {
+            let internals = match self.disconnect_on_drop {
+                Some(mode) => internals.disconnect_on_drop(mode),
+                None => internals,
+            };
+        }
+    });
+
+    let with_runtime = cfg!(feature = "with-runtime");
+
+    let runtime_field =
+        with_runtime.then(|| quote!(runtime: Option<#pcr::tokio::runtime::Handle>,));
+    let runtime_default = with_runtime.then(|| quote!(runtime: None,));
+
+    let runtime_fn = with_runtime.then(|| {
+        quote! {
+            /// Runs the engine, and the tasks of its connection pool, on `runtime`
+            /// instead of the runtime that `build` and each query are awaited on.
+            /// The client can then be used from other runtimes, or outside of any runtime with an executor
+            /// like `futures::executor::block_on`, as long as `runtime` is still running.
+            pub fn with_runtime(mut self, runtime: #pcr::tokio::runtime::Handle) -> Self {
+                self.runtime = Some(runtime);
+                self
+            }
+        }
+    });
+
+    let runtime_engine = with_runtime.then(|| {
+        quote! {
+            let internals = match self.runtime {
+                Some(runtime) => internals.with_runtime(runtime),
+                None => internals,
+            };
+        }
+    });
+    let runtime_arg = with_runtime.then(|| quote!(self.runtime,));
+
+    let pool_events = cfg!(feature = "pool-events");
+
+    let pool_events_field =
+        pool_events.then(|| quote!(pool_event_listeners: Vec<#pcr::PoolEventListener>,));
+    let pool_events_default = pool_events.then(|| quote!(pool_event_listeners: vec![],));
+
+    let pool_events_fn = pool_events.then(|| {
+        quote! {
+            /// Calls `listener` with each event of the native engine's connection pool.
+            /// Pool events aren't specific to a client, so `listener` also receives those of other clients in the process.
+            pub fn on_pool_event(
+                mut self,
+                listener: impl Fn(&#pcr::PoolEvent) + Send + Sync + 'static,
+            ) -> Self {
+                self.pool_event_listeners.push(::std::sync::Arc::new(listener));
+                self
+            }
+        }
+    });
+
+    // subscribed before the client is created so that listeners see its eager connection
+    let pool_events_subscribe = pool_events.then(|| {
+        quote! {
+            let pool_events = #pcr::PoolEventSubscription::new(self.pool_event_listeners)?;
+        }
+    });
+    let pool_events_build =
+        pool_events.then(|| quote!(let internals = internals.with_pool_events(pool_events);));
+
+    let engine_logs = cfg!(feature = "engine-logs");
+
+    let engine_logs_fields = engine_logs.then(|| {
+        quote! {
+            engine_log_level: Option<#pcr::EngineLogLevel>,
+            engine_log_listener: Option<#pcr::EngineLogListener>,
+        }
+    });
+    let engine_logs_default = engine_logs.then(|| {
+        quote! {
+            engine_log_level: None,
+            engine_log_listener: None,
+        }
+    });
+
+    let engine_logs_fns = engine_logs.then(|| {
+        quote! {
+            /// Receives the native engine's logs at `level` and above,
+            /// which are emitted as `tracing` events with the target `prisma_client_rust::engine`
+            /// unless a listener is given with `on_engine_log`.
+            pub fn engine_log_level(mut self, level: #pcr::EngineLogLevel) -> Self {
+                self.engine_log_level = Some(level);
+                self
+            }
+
+            /// Calls `listener` with each of the native engine's logs instead of emitting them to `tracing`,
+            /// at `EngineLogLevel::Info` and above unless another level is set.
+            pub fn on_engine_log(
+                mut self,
+                listener: impl Fn(&#pcr::EngineLog) + Send + Sync + 'static,
+            ) -> Self {
+                self.engine_log_listener = Some(::std::sync::Arc::new(listener));
+                self
+            }
+        }
+    });
+
+    let engine_logs_arg = engine_logs
+        .then(|| quote!(#pcr::EngineLogs::new(self.engine_log_level, self.engine_log_listener),));
+
+    let version_warning = (version_check == VersionCheck::Warn)
+        .then(|| quote!(#pcr::warn_version_mismatch(super::GENERATED_WITH_VERSION);));
+
+    let sqlite_fns = (args.connector.name() == psl::builtin_connectors::SQLITE.name()).then(|| {
+        quote! {
+            pub fn with_sqlite_config(mut self, sqlite_config: #pcr::SqliteConfig) -> Self {
+                self.sqlite_config = sqlite_config;
+                self
+            }
+
+            pub fn busy_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.sqlite_config = self.sqlite_config.busy_timeout(timeout);
+                self
+            }
+
+            pub fn wal_mode(mut self, enabled: bool) -> Self {
+                self.sqlite_config = self.sqlite_config.wal_mode(enabled);
+                self
+            }
+        }
+    });
+
+    let socket_fn = [
+        psl::builtin_connectors::POSTGRES.name(),
+        psl::builtin_connectors::COCKROACH.name(),
+        psl::builtin_connectors::MYSQL.name(),
+    ]
+    .contains(&args.connector.name())
+    .then(|| {
+        quote! {
+            /// Connects over the Unix socket at `path` instead of TCP.
+            /// For PostgreSQL this is the directory containing the socket, eg. `/var/run/postgresql`,
+            /// and for MySQL the socket file itself.
+            pub fn with_socket(mut self, path: impl Into<::std::path::PathBuf>) -> Self {
+                self.socket = Some(path.into());
+                self
+            }
+        }
+    });
+
     let raw_queries = match args.connector.name() {
         name if psl::builtin_connectors::MONGODB.name() == name => {
             quote! {
@@ -90,20 +282,69 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                     super::DATABASE_STR,
                 )
             }
+
+            /// Runs DDL statements one at a time in order, in a transaction if the database supports it.
+            /// MySQL commits each statement as it runs, so a failure can leave earlier statements applied.
+            pub fn _execute_raw_ddl(
+                &self,
+                statements: impl IntoIterator<Item = #pcr::Raw>,
+            ) -> #pcr::ExecuteRawDdl {
+                #pcr::ExecuteRawDdl::new(
+                    &self.0,
+                    statements,
+                    super::DATABASE_STR,
+                )
+            }
+
+            pub fn _explain_raw(&self, query: #pcr::Raw) -> #pcr::ExplainRaw {
+                #pcr::ExplainRaw::new(
+                    &self.0,
+                    query,
+                    super::DATABASE_STR,
+                )
+            }
         },
     };
 
     quote! {
         pub struct PrismaClientBuilder {
             url: Option<String>,
+            env: Option<String>,
             action_notifier: #pcr::ActionNotifier,
+            pool_config: #pcr::PoolConfig,
+            sqlite_config: #pcr::SqliteConfig,
+            socket: Option<::std::path::PathBuf>,
+            eager_connect: bool,
+            engine: Option<::std::sync::Arc<dyn #pcr::QueryEngine>>,
+            middleware: Vec<#pcr::Middleware>,
+            count_cache: Option<::std::sync::Arc<dyn #pcr::CountCache>>,
+            in_chunk_size: Option<usize>,
+            query_error_detail: #pcr::QueryErrorDetail,
+            #runtime_field
+            #engine_logs_fields
+            #pool_events_field
+            #disconnect_field
         }
 
         impl PrismaClientBuilder {
             fn new() -> Self {
                 Self {
                     url: None,
-                    action_notifier: #pcr::ActionNotifier::new()
+                    env: None,
+                    action_notifier: #pcr::ActionNotifier::new(),
+                    pool_config: #pcr::PoolConfig::new(),
+                    sqlite_config: #pcr::SqliteConfig::new(),
+                    socket: None,
+                    eager_connect: true,
+                    engine: None,
+                    middleware: vec![],
+                    count_cache: None,
+                    in_chunk_size: None,
+                    query_error_detail: #pcr::QueryErrorDetail::None,
+                    #runtime_default
+                    #engine_logs_default
+                    #pool_events_default
+                    #disconnect_default
                 }
             }
 
@@ -112,19 +353,143 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 self
             }
 
+            pub fn with_env(mut self, name: &str) -> Self {
+                self.env = Some(name.to_string());
+                self
+            }
+
+            pub fn with_pool_config(mut self, pool_config: #pcr::PoolConfig) -> Self {
+                self.pool_config = pool_config;
+                self
+            }
+
+            pub fn connection_limit(mut self, limit: usize) -> Self {
+                self.pool_config = self.pool_config.connection_limit(limit);
+                self
+            }
+
+            pub fn pool_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.pool_config = self.pool_config.pool_timeout(timeout);
+                self
+            }
+
+            pub fn connect_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.pool_config = self.pool_config.connect_timeout(timeout);
+                self
+            }
+
+            pub fn idle_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.pool_config = self.pool_config.idle_timeout(timeout);
+                self
+            }
+
+            #sqlite_fns
+
+            #socket_fn
+
+            /// Whether `build` connects to the database before returning, which it does by default.
+            /// Connecting lazily instead skips the connection for programs that may not use the database,
+            /// at the cost of connection errors only surfacing with the first query.
+            pub fn eager_connect(mut self, eager: bool) -> Self {
+                self.eager_connect = eager;
+                self
+            }
+
+            /// Sends queries through `engine` instead of loading the native query engine.
+            /// The URL, pool, SQLite and socket options are ignored when an engine is provided.
+            pub fn with_engine(mut self, engine: impl #pcr::QueryEngine + 'static) -> Self {
+                self.engine = Some(::std::sync::Arc::new(engine));
+                self
+            }
+
+            /// Calls `middleware` before every query the client executes, after any middleware added before it.
+            /// Returning an error, eg. `QueryError::Rejected`, fails the query without executing it.
+            pub fn with_middleware(
+                mut self,
+                middleware: impl Fn(&#pcr::QueryInfo) -> #pcr::Result<()> + Send + Sync + 'static,
+            ) -> Self {
+                self.middleware.push(::std::sync::Arc::new(middleware));
+                self
+            }
+
+            /// Stores the results of `cached_count` in `cache` instead of in memory,
+            /// eg. to share them between processes.
+            pub fn with_count_cache(mut self, cache: impl #pcr::CountCache + 'static) -> Self {
+                self.count_cache = Some(::std::sync::Arc::new(cache));
+                self
+            }
+
+            /// Splits `find_many` queries whose `in` filters have more than `size` values
+            /// into a batch of queries, for databases that limit how many parameters a query can have.
+            /// Queries can also opt in individually with `chunked_in`.
+            pub fn in_chunk_size(mut self, size: usize) -> Self {
+                self.in_chunk_size = Some(size);
+                self
+            }
+
+            /// Includes the query in the errors of queries that fail, eg. `QueryErrorDetail::Operation`
+            /// so that logged errors name the operation they came from.
+            /// `QueryErrorDetail::Query` includes the query's arguments too, so it's best kept to debugging.
+            pub fn query_errors(mut self, detail: #pcr::QueryErrorDetail) -> Self {
+                self.query_error_detail = detail;
+                self
+            }
+
+            #runtime_fn
+
             #callback_fn
 
+            #engine_logs_fns
+
+            #pool_events_fn
+
+            #disconnect_fn
+
             pub async fn build(self) -> Result<PrismaClient, #pcr::NewClientError> {
-                let internals = #pcr::PrismaClientInternals::new(
-                    self.url,
-                    self.action_notifier,
-                    super::DATAMODEL_STR
-                ).await?;
+                #version_warning
+
+                #pool_events_subscribe
+
+                let internals = match self.engine {
+                    Some(engine) => {
+                        let internals = #pcr::PrismaClientInternals::new_with_engine(engine, self.action_notifier);
+
+                        #runtime_engine
+
+                        internals
+                    }
+                    None => #pcr::PrismaClientInternals::new(
+                        self.url,
+                        self.env,
+                        self.action_notifier,
+                        super::DATAMODEL_STR,
+                        self.pool_config,
+                        self.sqlite_config,
+                        self.socket,
+                        self.eager_connect,
+                        #runtime_arg
+                        #engine_logs_arg
+                    ).await?,
+                };
+
+                let internals = internals.with_middleware(self.middleware);
+                let internals = match self.count_cache {
+                    Some(cache) => internals.with_count_cache(cache),
+                    None => internals,
+                };
+                let internals = internals.with_in_chunk_size(self.in_chunk_size);
+                let internals = internals.with_query_error_detail(self.query_error_detail);
+
+                #pool_events_build
+
+                #disconnect_build
 
                 Ok(PrismaClient(internals))
             }
         }
 
+        /// Cloning the client is cheap, and clones share the same engine and connection pool.
+        #[derive(Clone)]
         pub struct PrismaClient(#pcr::PrismaClientInternals);
 
         impl ::std::fmt::Debug for PrismaClient {
@@ -139,6 +504,12 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 PrismaClientBuilder::new()
             }
 
+            /// Builds a client with the default options, connecting before returning
+            /// so an unreachable database is reported at startup.
+            pub async fn _new() -> Result<Self, #pcr::NewClientError> {
+                Self::_builder().build().await
+            }
+
             #mock_ctor
 
             #raw_queries
@@ -151,8 +522,53 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 #pcr::TransactionBuilder::_new(self, &self.0)
             }
 
+            /// Runs `tx` in a transaction that is always rolled back, for tests that shouldn't leave data behind.
+            /// See [`TransactionBuilder::run_and_roll_back`](::prisma_client_rust::TransactionBuilder::run_and_roll_back).
+            pub async fn _test_transaction<TErr, TRet, TFut, TFn>(&self, tx: TFn) -> Result<TRet, TErr>
+            where
+                TFut: ::std::future::Future<Output = Result<TRet, TErr>>,
+                TFn: FnOnce(Self) -> TFut,
+                TErr: From<#pcr::QueryError>,
+            {
+                self._transaction().run_and_roll_back(tx).await
+            }
+
+            /// The engine this client sends its queries through, or `None` for mock clients.
+            /// Useful for wrapping the native engine in a custom one.
+            pub fn _query_engine(&self) -> Option<::std::sync::Arc<dyn #pcr::QueryEngine>> {
+                self.0.query_engine()
+            }
+
+            /// A client for the tables in the Postgres schema `name`, eg. for a tenant's schema.
+            /// Each schema gets its own connection pool, which is created on first use and shared by later calls.
+            pub async fn _with_schema(&self, name: &str) -> Result<Self, #pcr::WithSchemaError> {
+                Ok(Self(self.0.with_schema(name).await?))
+            }
+
+            /// Closes the connection pool `_with_schema` created for `name`, eg. once a tenant is idle or deleted.
+            /// Pools are otherwise kept for the lifetime of the client. Returns whether there was one.
+            pub fn _evict_schema(&self, name: &str) -> bool {
+                self.0.evict_schema(name)
+            }
+
+            /// A client that passes `context` to middleware for each of its queries,
+            /// eg. the tenant or user a request is being handled for.
+            /// It shares the engine of this one, which itself is left without the context.
+            pub fn _with_context(&self, context: impl ::std::any::Any + Send + Sync) -> Self {
+                Self(self.0.with_context(::std::sync::Arc::new(context)))
+            }
+
+            /// Queries the version of the connected database.
+            pub async fn _db_version(&self) -> Result<#pcr::DbVersion, #pcr::DbVersionError> {
+                #pcr::db_version(&self.0, super::DATABASE_STR).await
+            }
+
             #migrate_fns
 
+            #metrics_fn
+
+            #model_actions_fn
+
             #(#model_actions)*
         }
 
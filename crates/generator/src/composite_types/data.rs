@@ -2,14 +2,15 @@ use prisma_client_rust_sdk::prisma::prisma_models::walkers::CompositeTypeWalker;
 
 use crate::prelude::*;
 
-pub fn struct_definition(ty: CompositeTypeWalker) -> TokenStream {
+pub fn struct_definition(ty: CompositeTypeWalker, args: &GenerateArgs) -> TokenStream {
     let fields = ty.fields().flat_map(|field| {
         let field_name_str = field.name();
         let field_name_snake = snake_ident(field.name());
         let field_ty = field.type_tokens(&quote!())?;
+        let rename = serde_rename(args.shared_config, ty.name(), field_name_str);
 
         Some(quote! {
-            #[serde(rename = #field_name_str)]
+            #[serde(#rename)]
             pub #field_name_snake: #field_ty
         })
     });
@@ -23,11 +24,35 @@ pub fn struct_definition(ty: CompositeTypeWalker) -> TokenStream {
         }
     });
 
+    // composite types have no type overrides, so every field has a `MockValue`
+    let mock_impl = cfg!(feature = "mocking").then(|| {
+        let field_names = ty.fields().flat_map(|field| {
+            field.type_tokens(&quote!())?;
+
+            Some(snake_ident(field.name()))
+        });
+
+        quote! {
+            impl ::prisma_client_rust::MockValue for Data {
+                fn mock() -> Self {
+                    Self {
+                        #(#field_names: ::prisma_client_rust::MockValue::mock()),*
+                    }
+                }
+            }
+        }
+    });
+
+    let non_exhaustive = non_exhaustive(args.shared_config);
+
     quote! {
         #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
         #specta_derive
+        #non_exhaustive
         pub struct Data {
             #(#fields),*
         }
+
+        #mock_impl
     }
 }
@@ -56,13 +56,13 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
         .map(|comp_type| {
             let scalar_selections_fn = scalar_selections_fn(comp_type, module_path);
 
-            let data_struct = data::struct_definition(comp_type);
+            let data_struct = data::struct_definition(comp_type, args);
             let order_by_enum = order_by::enum_definition(comp_type, args);
             let create_fn = set_params::create_fn(comp_type);
 
             let parts = CompositeTypeModulePart::combine(vec![
-                set_params::module_part(comp_type),
-                where_params::module_part(comp_type),
+                set_params::module_part(comp_type, args),
+                where_params::module_part(comp_type, args),
             ]);
 
             Module::new(
@@ -135,8 +135,11 @@ pub fn enum_definition(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> T
         })
         .unwrap_or_default();
 
+    let non_exhaustive = non_exhaustive(args.shared_config);
+
     quote! {
         #[derive(Debug, Clone)]
+        #non_exhaustive
         pub enum OrderByParam {
             #(#variants),*
         }
@@ -51,7 +51,8 @@ pub fn create_fn(comp_type: CompositeTypeWalker) -> Option<TokenStream> {
         })
 }
 
-pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
+pub fn module_part(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> CompositeTypeModulePart {
+    let non_exhaustive = non_exhaustive(args.shared_config);
     let ((variants, into_pv_arms), fields): ((Vec<_>, Vec<_>), _) = comp_type
         .fields()
         .flat_map(|field| {
@@ -87,6 +88,7 @@ pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
     CompositeTypeModulePart {
         data: quote! {
            #[derive(Debug, Clone)]
+           #non_exhaustive
            pub enum SetParam {
                #(#variants),*
            }
@@ -4,7 +4,8 @@ use crate::prelude::*;
 
 use super::CompositeTypeModulePart;
 
-pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
+pub fn module_part(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> CompositeTypeModulePart {
+    let non_exhaustive = non_exhaustive(args.shared_config);
     let pcr = quote!(::prisma_client_rust);
 
     let ((variants, match_arms), fields): ((Vec<_>, Vec<_>), _) = comp_type
@@ -44,6 +45,7 @@ pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
     CompositeTypeModulePart {
         data: quote! {
             #[derive(Debug, Clone)]
+            #non_exhaustive
             pub enum WhereParam {
                 #(#variants),*
             }
@@ -1,8 +1,104 @@
-use prisma_client_rust_sdk::{prelude::pascal_ident, GenerateArgs};
+use prisma_client_rust_sdk::{
+    prelude::{pascal_ident, psl},
+    GenerateArgs,
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn generate(args: &GenerateArgs) -> TokenStream {
+use crate::Error;
+
+/// Enums listed in the `enum_conversions` config, as `(from, to)` in both directions.
+pub type EnumConversions = Vec<(String, String)>;
+
+pub fn parse_conversions(args: &GenerateArgs) -> Result<EnumConversions, Error> {
+    let mut conversions = EnumConversions::new();
+
+    for entry in &args.shared_config.enum_conversions {
+        let invalid = |reason: &str| {
+            Error::InvalidEnumConversion(format!("[{}]", entry.join(", ")), reason.to_string())
+        };
+
+        let (a, b) = match entry.as_slice() {
+            [a, b] => (a, b),
+            _ => return Err(invalid("expected a pair of enum names")),
+        };
+
+        if a == b {
+            return Err(invalid("an enum can't be converted into itself"));
+        }
+
+        for name in [a, b] {
+            if !args.dmmf.data_model.enums.iter().any(|e| &e.name == name) {
+                return Err(invalid(&format!("enum '{name}' not found")));
+            }
+        }
+
+        if conversions.iter().any(|(from, to)| from == a && to == b) {
+            return Err(invalid("conversions between these enums are listed twice"));
+        }
+
+        conversions.push((a.clone(), b.clone()));
+        conversions.push((b.clone(), a.clone()));
+    }
+
+    Ok(conversions)
+}
+
+/// `From` if every variant of `from` has a variant of the same name in `to`, otherwise `TryFrom`.
+fn conversion(args: &GenerateArgs, from: &str, to: &str) -> TokenStream {
+    let find = |name: &str| {
+        args.dmmf
+            .data_model
+            .enums
+            .iter()
+            .find(|e| e.name == name)
+            .expect("enum_conversions are validated")
+    };
+    let (from_enum, to_enum) = (find(from), find(to));
+
+    let (from_ident, to_ident) = (pascal_ident(from), pascal_ident(to));
+
+    let (matched, unmatched): (Vec<_>, Vec<_>) = from_enum
+        .values
+        .iter()
+        .partition(|v| to_enum.values.iter().any(|t| t.name == v.name));
+
+    let matched = matched.iter().map(|v| pascal_ident(&v.name));
+
+    if unmatched.is_empty() {
+        return quote! {
+            impl From<#from_ident> for #to_ident {
+                fn from(value: #from_ident) -> Self {
+                    match value {
+                        #(#from_ident::#matched => Self::#matched),*
+                    }
+                }
+            }
+        };
+    }
+
+    let unmatched_names = unmatched.iter().map(|v| &v.name);
+    let unmatched = unmatched.iter().map(|v| pascal_ident(&v.name));
+
+    quote! {
+        impl ::std::convert::TryFrom<#from_ident> for #to_ident {
+            type Error = ::prisma_client_rust::EnumConversionError;
+
+            fn try_from(value: #from_ident) -> Result<Self, Self::Error> {
+                match value {
+                    #(#from_ident::#matched => Ok(Self::#matched),)*
+                    #(#from_ident::#unmatched => Err(::prisma_client_rust::EnumConversionError {
+                        from: #from,
+                        to: #to,
+                        variant: #unmatched_names,
+                    })),*
+                }
+            }
+        }
+    }
+}
+
+pub fn generate(args: &GenerateArgs, conversions: &EnumConversions) -> TokenStream {
     let enums = args.dmmf.data_model.enums.iter().map(|e| {
         let name = pascal_ident(&e.name);
 
@@ -20,16 +116,37 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
             })
             .collect::<Vec<_>>();
 
-        let match_arms = e
-            .values
-            .iter()
-            .map(|v| {
-                let name = &v.name;
-                let variant_name = pascal_ident(&v.name);
+        // Indexed by discriminant rather than matched on,
+        // as a match arm per variant slows compilation of very large enums
+        let variant_count = e.values.len();
+        let variant_idents = e.values.iter().map(|v| pascal_ident(&v.name));
 
-                quote!(Self::#variant_name => #name.to_string())
+        let db_names = args
+            .schema
+            .db
+            .walk_enums()
+            .find(|walker| walker.name() == e.name)
+            .map(|walker| {
+                walker
+                    .values()
+                    .map(|v| v.database_name().to_string())
+                    .collect::<Vec<_>>()
             })
-            .collect::<Vec<_>>();
+            .unwrap_or_else(|| e.values.iter().map(|v| v.name.clone()).collect());
+        let enum_name_str = &e.name;
+
+        let (from_str_doc, matches) =
+            if args.connector.name() == psl::builtin_connectors::MYSQL.name() {
+                (
+                    " Parses a value stored in the database, ignoring case as MySQL does.",
+                    quote!(db_name.eq_ignore_ascii_case(value)),
+                )
+            } else {
+                (
+                    " Parses a value stored in the database, case sensitively.",
+                    quote!(*db_name == value),
+                )
+            };
 
         let specta_derive = cfg!(feature = "specta").then(|| {
             let model_name_pascal_str = name.to_string();
@@ -40,6 +157,17 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
             }
         });
 
+        // the first variant is as plausible a placeholder as any
+        let mock_impl = cfg!(feature = "mocking").then(|| {
+            quote! {
+                impl ::prisma_client_rust::MockValue for #name {
+                    fn mock() -> Self {
+                        Self::VARIANTS[0]
+                    }
+                }
+            }
+        });
+
         quote! {
             #specta_derive
             #[derive(Debug, Clone, Copy, ::serde::Serialize, ::serde::Deserialize, PartialEq, Eq)]
@@ -47,17 +175,57 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 #(#variants),*
             }
 
-            impl ToString for #name {
-                fn to_string(&self) -> String {
-                    match self {
-                        #(#match_arms),*
-                    }
+            impl #name {
+                const DB_NAMES: [&'static str; #variant_count] = [#(#db_names),*];
+                const VARIANTS: [Self; #variant_count] = [#(Self::#variant_idents),*];
+
+                /// The value stored in the database for this variant, which differs from its name if it uses `@map`.
+                pub fn as_db_str(&self) -> &'static str {
+                    Self::DB_NAMES[*self as usize]
+                }
+            }
+
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.write_str(self.as_db_str())
+                }
+            }
+
+            #[doc = #from_str_doc]
+            impl ::std::str::FromStr for #name {
+                type Err = ::prisma_client_rust::ParseEnumError;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    Self::DB_NAMES
+                        .iter()
+                        .position(|db_name| #matches)
+                        .map(|i| Self::VARIANTS[i])
+                        .ok_or_else(|| ::prisma_client_rust::ParseEnumError {
+                            enum_name: #enum_name_str,
+                            value: value.to_string(),
+                        })
+                }
+            }
+
+            impl ::std::convert::TryFrom<&str> for #name {
+                type Error = ::prisma_client_rust::ParseEnumError;
+
+                fn try_from(value: &str) -> Result<Self, Self::Error> {
+                    value.parse()
                 }
             }
+
+            #mock_impl
         }
     });
 
+    let conversions = conversions
+        .iter()
+        .map(|(from, to)| conversion(args, from, to));
+
     quote! {
         #(#enums)*
+
+        #(#conversions)*
     }
 }
@@ -4,6 +4,19 @@ use std::path::*;
 
 use crate::GenerateArgs;
 
+/// What happens when the client is compiled against a different version of `prisma-client-rust`
+/// than the one that generated it.
+#[derive(serde::Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionCheck {
+    /// Fails compilation.
+    #[default]
+    Error,
+    /// Prints a warning when a client is built.
+    Warn,
+    Off,
+}
+
 fn find_migrations_path(schema_path: &PathBuf) -> PathBuf {
     schema_path
         .parent()
@@ -12,7 +25,7 @@ fn find_migrations_path(schema_path: &PathBuf) -> PathBuf {
         .expect("Migrations folder not found!")
 }
 
-pub fn generate(args: &GenerateArgs) -> TokenStream {
+pub fn generate(args: &GenerateArgs, version_check: VersionCheck) -> TokenStream {
     let database_string = &args.engine_dmmf.datasources[0].provider;
 
     let pcr = quote!(::prisma_client_rust);
@@ -35,7 +48,25 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
         })
         .unwrap_or_default();
 
+    let generated_with = env!("CARGO_PKG_VERSION");
+
+    let version_assert = (version_check == VersionCheck::Error).then(|| {
+        let message = format!(
+            "This client was generated by prisma-client-rust {generated_with}, \
+             which doesn't match the version of prisma-client-rust being compiled. \
+             Regenerate the client, or set version_check to \"warn\" or \"off\"."
+        );
+
+        quote!(
+            const _: () = assert!(#pcr::is_runtime_version(GENERATED_WITH_VERSION), #message);
+        )
+    });
+
     quote! {
+        /// The version of `prisma-client-rust` that generated this client.
+        pub const GENERATED_WITH_VERSION: &str = #generated_with;
+        #version_assert
+
         pub static DATAMODEL_STR: &'static str = include_str!(#schema_path_str);
         static DATABASE_STR: &'static str = #database_string;
 
@@ -5,6 +5,7 @@ mod header;
 mod internal_enums;
 mod models;
 mod read_filters;
+mod reset;
 mod write_params;
 
 use prisma_client_rust_sdk::prelude::*;
@@ -14,16 +15,57 @@ fn default_module_path() -> String {
     "crate::prisma".to_string()
 }
 
+fn default_max_include_depth() -> usize {
+    8
+}
+
 #[derive(serde::Deserialize)]
 pub struct Generator {
-    #[serde(default = "default_module_path")]
-    module_path: String,
+    /// Defaults to `client_name` if it is set.
+    module_path: Option<String>,
+    #[serde(default)]
+    union_models: Vec<String>,
+    /// Entries of the form `Model.field`, generating `update_if_version` for the model.
+    #[serde(default)]
+    version_fields: Vec<String>,
+    /// How many levels deep `include!` and `select!` selections can be nested,
+    /// with each relation that has a nested selection of its own counting as one.
+    #[serde(
+        default = "default_max_include_depth",
+        deserialize_with = "deserialize_parsed"
+    )]
+    max_include_depth: usize,
+    /// Implements `ModelActions` for every model and adds `_model_actions` to the client.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    model_actions: bool,
+    /// Generates a `Repository` trait for every model, implemented for the client.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    repositories: bool,
+    /// Checks that the client is compiled with the version of `prisma-client-rust` that generated it.
+    #[serde(default)]
+    version_check: header::VersionCheck,
 }
 
 #[derive(Debug, Serialize, thiserror::Error)]
 pub enum Error {
     #[error("Failed to parse module_path")]
     InvalidModulePath,
+    #[error("Invalid union_models entry '{0}': {1}")]
+    InvalidUnionModel(String, String),
+    #[error("Invalid version_fields entry '{0}': {1}")]
+    InvalidVersionField(String, String),
+    #[error("Invalid computed_fields entry '{0}': {1}")]
+    InvalidComputedField(String, String),
+    #[error("Invalid type_overrides entry '{0}': {1}")]
+    InvalidTypeOverride(String, String),
+    #[error("Invalid field_codecs entry '{0}': {1}")]
+    InvalidFieldCodec(String, String),
+    #[error("Invalid display_fields entry '{0}': {1}")]
+    InvalidDisplayField(String, String),
+    #[error("max_include_depth must be at least 1")]
+    InvalidMaxIncludeDepth,
+    #[error("Invalid enum_conversions entry '{0}': {1}")]
+    InvalidEnumConversion(String, String),
 }
 
 impl PrismaGenerator for Generator {
@@ -33,23 +75,44 @@ impl PrismaGenerator for Generator {
     type Error = Error;
 
     fn generate(self, args: GenerateArgs) -> Result<Module, Self::Error> {
-        let header = header::generate(&args);
+        let header = header::generate(&args, self.version_check);
 
         let module_path = {
             let provided: TokenStream = self
                 .module_path
+                .or_else(|| args.shared_config.client_name.clone())
+                .unwrap_or_else(default_module_path)
                 .parse()
                 .map_err(|_| Error::InvalidModulePath)?;
 
             quote!(#provided::)
         };
 
-        let enums = enums::generate(&args);
+        let union_models = models::union::parse_config(&args, &self.union_models)?;
+        let version_fields = models::version::parse_config(&args, &self.version_fields)?;
+        let computed_fields = models::computed::parse_config(&args)?;
+        let enum_conversions = enums::parse_conversions(&args)?;
+
+        if self.max_include_depth == 0 {
+            return Err(Error::InvalidMaxIncludeDepth);
+        }
+
+        models::type_overrides::validate(&args)?;
+        models::field_codecs::validate(&args)?;
+        models::display::validate(&args)?;
+
+        for warning in models::type_overrides::unsupported_warnings(&args) {
+            eprintln!("warning: {warning}");
+        }
+
+        let enums = enums::generate(&args, &enum_conversions);
+        let reset = reset::generate(&args);
 
         let mut module = Module::new(
             "client",
             quote! {
                 #header
+                #reset
 
                 pub use _prisma::*;
 
@@ -57,7 +120,7 @@ impl PrismaGenerator for Generator {
             },
         );
 
-        let client = client::generate(&args);
+        let client = client::generate(&args, self.model_actions, self.version_check);
         let internal_enums = internal_enums::generate(&args);
         let read_filters_module = read_filters::generate_module(&args);
         let write_params_module = write_params::generate_module(&args);
@@ -72,9 +135,18 @@ impl PrismaGenerator for Generator {
             },
         ));
 
-        models::modules(&args, &module_path)
-            .into_iter()
-            .for_each(|model| module.add_submodule(model));
+        models::modules(
+            &args,
+            &module_path,
+            &union_models,
+            &version_fields,
+            &computed_fields,
+            self.max_include_depth,
+            self.model_actions,
+            self.repositories,
+        )
+        .into_iter()
+        .for_each(|model| module.add_submodule(model));
         composite_types::modules(&args, &module_path)
             .into_iter()
             .for_each(|ct| module.add_submodule(ct));
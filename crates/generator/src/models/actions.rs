@@ -17,7 +17,21 @@ pub fn create_fn(model: ModelWalker) -> Option<TokenStream> {
         })
         .unzip();
 
+    // every field has a default or is optional, so a record can be created without any input
+    let create_empty_fn = names.is_empty().then(|| {
+        quote! {
+            pub fn create_empty(self) -> CreateQuery<'a> {
+                CreateQuery::new(
+                    self.client,
+                    vec![]
+                )
+            }
+        }
+    });
+
     Some(quote! {
+        #create_empty_fn
+
         pub fn create(self, #(#names: #types,)* mut _params: Vec<SetParam>) -> CreateQuery<'a> {
             _params.extend([
                 #(#names::#push_wrapper(#names)),*
@@ -53,7 +67,13 @@ pub fn create_unchecked_fn(model: ModelWalker) -> Option<TokenStream> {
 
                         quote!(super::#comp_type_snake::Create)
                     }
-                    _ => field.type_tokens(&quote!(super::))?,
+                    // the query engine can't write Unsupported fields
+                    ScalarFieldType::Unsupported(_) => return None,
+                    _ => {
+                        let field_name_snake = snake_ident(field.name());
+
+                        quote!(#field_name_snake::Type)
+                    }
                 },
             ))
         })
@@ -112,6 +132,31 @@ pub fn upsert_fn(model: ModelWalker) -> Option<TokenStream> {
     })
 }
 
+/// `first` and `last` order by the primary key, so are only generated for models that have one.
+pub fn first_last_fns(model: ModelWalker) -> Option<TokenStream> {
+    let primary_key_fields = model
+        .primary_key()?
+        .fields()
+        .map(|field| snake_ident(field.name()))
+        .collect::<Vec<_>>();
+
+    Some(quote! {
+        pub fn first(self, _where: Vec<WhereParam>) -> FindFirstQuery<'a> {
+            FindFirstQuery::new(
+                self.client,
+                _where
+            ).default_order_by(vec![#(#primary_key_fields::order(SortOrder::Asc)),*])
+        }
+
+        pub fn last(self, _where: Vec<WhereParam>) -> FindFirstQuery<'a> {
+            FindFirstQuery::new(
+                self.client,
+                _where
+            ).default_order_by(vec![#(#primary_key_fields::order(SortOrder::Desc)),*])
+        }
+    })
+}
+
 pub fn mongo_raw_fns() -> Option<TokenStream> {
     cfg!(feature = "mongodb").then(|| {
         quote! {
@@ -132,6 +177,7 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
     let create_fn = create_fn(model);
     let create_unchecked_fn = create_unchecked_fn(model);
     let upsert_fn = upsert_fn(model);
+    let first_last_fns = first_last_fns(model);
     let monogo_raw_fns = mongo_raw_fns();
 
     let create_many_fn = (args
@@ -161,6 +207,8 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            #first_last_fns
+
             pub fn find_many(self, _where: Vec<WhereParam>) -> FindManyQuery<'a> {
                 FindManyQuery::new(
                     self.client,
@@ -168,6 +216,14 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            /// Finds the records matching any of `keys`, which can be compound keys.
+            pub fn find_many_by_unique(self, keys: Vec<UniqueWhereParam>) -> FindManyQuery<'a> {
+                FindManyQuery::new(
+                    self.client,
+                    vec![WhereParam::Or(keys.into_iter().map(UniqueWhereParam::into_filter).collect())]
+                )
+            }
+
             #create_fn
             #create_unchecked_fn
 
@@ -223,6 +279,12 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            /// Counts the records matching `_where`, reusing a count from the last `ttl` if there is one.
+            /// See [`CachedCount`](::prisma_client_rust::CachedCount) for when results can be stale.
+            pub fn cached_count(self, ttl: ::std::time::Duration, _where: Vec<WhereParam>) -> CachedCountQuery<'a> {
+                self.count(_where).cached(ttl)
+            }
+
             #monogo_raw_fns
         }
     }
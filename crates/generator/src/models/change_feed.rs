@@ -0,0 +1,142 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::{ModelWalker, ScalarFieldWalker};
+
+use crate::prelude::*;
+
+use super::{id_newtypes, native_types, ModelModulePart};
+
+// overridden and encoded fields are only generated with `equals`
+fn supports_gt(args: &GenerateArgs, field: ScalarFieldWalker) -> bool {
+    args.shared_config
+        .type_override(field.model().name(), field.name())
+        .is_none()
+        && args
+            .shared_config
+            .field_codec(field.model().name(), field.name())
+            .is_none()
+        && args
+            .read_filter(field)
+            .map(|filter| filter.fields.iter().any(|f| f.name == "gt"))
+            .unwrap_or(false)
+}
+
+fn is_unique(model: ModelWalker, field: ScalarFieldWalker) -> bool {
+    field.is_single_pk()
+        || model.indexes().any(|index| {
+            let mut fields = index.fields();
+
+            index.is_unique()
+                && fields.len() == 1
+                && fields.next().map(|f| f.field_id()) == Some(field.field_id())
+        })
+}
+
+/// `gt(value)`, converting `value` from the id newtype or native type of `field` if it has one,
+/// since `gt` takes the field's scalar type.
+fn gt(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    module: TokenStream,
+    value: &Ident,
+) -> TokenStream {
+    match id_newtypes::into_original(args, field, value)
+        .or_else(|| native_types::into_original(args, field, value))
+    {
+        Some(converted) => quote!(#module gt(#converted)),
+        None => quote!(#module gt(#value)),
+    }
+}
+
+/// `feed_cursor()` for each required field that can be filtered with `gt`,
+/// which is what a change feed needs to find records past the last one it saw.
+///
+/// Fields that aren't unique are paired with the model's primary key,
+/// so that records sharing a cursor value are still ordered and none are skipped.
+/// They don't get a cursor if the model's primary key is compound or can't be filtered with `gt`.
+pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let primary_key = model
+        .primary_key()
+        .and_then(|primary_key| {
+            let mut fields = primary_key.fields();
+
+            match (fields.next(), fields.next()) {
+                (Some(field), None) => Some(field),
+                _ => None,
+            }
+        })
+        .filter(|field| supports_gt(args, *field));
+
+    let fields = cfg!(feature = "change-feed")
+        .then(|| {
+            model
+                .scalar_fields()
+                .filter(|field| field.ast_field().arity.is_required())
+                .filter(|field| supports_gt(args, *field))
+                .filter_map(|field| {
+                    let field_name_snake = snake_ident(field.name());
+
+                    let value = format_ident!("value");
+                    let after = gt(args, field, quote!(), &value);
+
+                    let cursor = match (is_unique(model, field), primary_key) {
+                        (true, _) => quote! {
+                            pub fn feed_cursor() -> #pcr::FeedCursor<super::Types, Type> {
+                                #pcr::FeedCursor::new(
+                                    |value| #after,
+                                    || vec![order(SortOrder::Asc)],
+                                    |data| data.#field_name_snake.clone()
+                                )
+                            }
+                        },
+                        (false, Some(primary_key)) => {
+                            let primary_key_snake = snake_ident(primary_key.name());
+
+                            let key_after = gt(
+                                args,
+                                primary_key,
+                                quote!(super::#primary_key_snake::),
+                                &format_ident!("key"),
+                            );
+
+                            quote! {
+                                /// Paired with the primary key, since more than one record can have the same value.
+                                pub fn feed_cursor() -> #pcr::FeedCursor<
+                                    super::Types,
+                                    (Type, super::#primary_key_snake::Type)
+                                > {
+                                    #pcr::FeedCursor::new(
+                                        |(value, key)| {
+                                            let tied = equals(value.clone());
+
+                                            #pcr::operator::or(vec![
+                                                #after,
+                                                #pcr::operator::and(vec![tied, #key_after]),
+                                            ])
+                                        },
+                                        || vec![
+                                            order(SortOrder::Asc),
+                                            super::#primary_key_snake::order(SortOrder::Asc),
+                                        ],
+                                        |data| (
+                                            data.#field_name_snake.clone(),
+                                            data.#primary_key_snake.clone(),
+                                        )
+                                    )
+                                }
+                            }
+                        }
+                        (false, None) => return None,
+                    };
+
+                    Some((field.name().to_string(), cursor))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ModelModulePart {
+        data: quote!(),
+        fields,
+    }
+}
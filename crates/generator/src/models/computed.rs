@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::{ModelWalker, ScalarFieldWalker},
+    psl::parser_database::ScalarType,
+};
+
+use crate::{prelude::*, Error};
+
+use super::id_newtypes;
+
+pub struct ComputedField {
+    name: String,
+    typ: ScalarType,
+    sql: String,
+}
+
+/// The entries of the `computed_fields` config, grouped by model.
+pub type ComputedFields = BTreeMap<String, Vec<ComputedField>>;
+
+pub fn parse_config(args: &GenerateArgs) -> Result<ComputedFields, Error> {
+    let mut computed_fields = ComputedFields::new();
+
+    for entry in &args.shared_config.computed_fields {
+        let invalid = |reason: String| Error::InvalidComputedField(entry.clone(), reason);
+
+        let (declaration, sql) = entry
+            .split_once('=')
+            .and_then(|(declaration, sql)| {
+                let (path, typ) = declaration.split_once(':')?;
+                let (model, field) = path.trim().split_once('.')?;
+
+                Some(((model, field, typ.trim()), sql.trim()))
+            })
+            .ok_or_else(|| invalid("expected the form `Model.field: Type = SQL`".to_string()))?;
+        let (model_name, field_name, typ) = declaration;
+
+        if args.engine_dmmf.datasources[0].provider == "mongodb" {
+            return Err(invalid(
+                "computed fields are only supported by SQL databases".to_string(),
+            ));
+        }
+
+        let model = args
+            .schema
+            .db
+            .walk_models()
+            .find(|m| m.name() == model_name)
+            .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+        if model.primary_key().map(|pk| pk.fields().count()) != Some(1) {
+            return Err(invalid(format!(
+                "model '{model_name}' must have a single field primary key"
+            )));
+        }
+
+        if model.fields().any(|f| f.name() == "computed") {
+            return Err(invalid(format!(
+                "model '{model_name}' has a field named 'computed', which would clash with the generated module"
+            )));
+        }
+
+        if model.fields().any(|f| f.name() == field_name)
+            || computed_fields
+                .get(model_name)
+                .map(|fields| fields.iter().any(|f| f.name == field_name))
+                .unwrap_or(false)
+        {
+            return Err(invalid(format!(
+                "model '{model_name}' already has a field named '{field_name}'"
+            )));
+        }
+
+        let typ = ScalarType::try_from_str(typ)
+            .filter(|typ| *typ != ScalarType::Bytes)
+            .ok_or_else(|| invalid(format!("'{typ}' is not a supported scalar type")))?;
+
+        if sql.is_empty() || sql.contains(';') || sql.contains("{}") {
+            return Err(invalid(
+                "the SQL must be a single expression without placeholders".to_string(),
+            ));
+        }
+
+        computed_fields
+            .entry(model_name.to_string())
+            .or_default()
+            .push(ComputedField {
+                name: field_name.to_string(),
+                typ,
+                sql: sql.to_string(),
+            });
+    }
+
+    Ok(computed_fields)
+}
+
+/// The names of `model`'s computed fields, which `select!` accepts alongside its other fields.
+pub fn names<'a>(model: ModelWalker, computed_fields: &'a ComputedFields) -> Vec<&'a str> {
+    computed_fields
+        .get(model.name())
+        .map(|fields| fields.iter().map(|f| f.name.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// `model`'s computed fields and the primary key they're matched to records by, if it has any.
+fn with_primary_key<'a, 'b>(
+    model: ModelWalker<'a>,
+    computed_fields: &'b ComputedFields,
+) -> Option<(&'b Vec<ComputedField>, ScalarFieldWalker<'a>)> {
+    Some((
+        computed_fields.get(model.name())?,
+        model.primary_key()?.fields().next()?,
+    ))
+}
+
+/// The contents of each computed field's module, next to those of the model's regular fields.
+pub fn field_modules(
+    model: ModelWalker,
+    computed_fields: &ComputedFields,
+) -> Vec<(String, TokenStream)> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let (fields, pk_field) = match with_primary_key(model, computed_fields) {
+        Some(fields) => fields,
+        None => return vec![],
+    };
+    let pk_field_name_snake = snake_ident(pk_field.name());
+
+    fields
+        .iter()
+        .map(|field| {
+            let field_name_pascal = pascal_ident(&field.name);
+            let typ = field.typ.to_tokens();
+
+            let module = quote! {
+                pub type Type = Option<#typ>;
+
+                /// Selects the field in `select!`, computing it once the rest of the selection has been fetched.
+                pub struct Select;
+
+                impl Into<super::SelectParam> for Select {
+                    fn into(self) -> super::SelectParam {
+                        super::SelectParam::#field_name_pascal(self)
+                    }
+                }
+
+                impl Select {
+                    /// The primary key, which the computed values are matched to records by.
+                    pub fn to_selection(self) -> #pcr::Selection {
+                        #pcr::sel(super::#pk_field_name_snake::NAME)
+                    }
+                }
+            };
+
+            (field.name.clone(), module)
+        })
+        .collect()
+}
+
+/// `SelectParam::split`, separating the computed fields a selection includes from the rest.
+pub fn select_split(model: ModelWalker, computed_fields: &ComputedFields) -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    let split_doc = quote! {
+        /// Splits `params` into the selections sent to the engine and the fields configured in `computed_fields`,
+        /// which are computed once the rest of the selection has been fetched.
+    };
+
+    let (fields, pk_field) = match with_primary_key(model, computed_fields) {
+        Some(fields) => fields,
+        None => {
+            return quote! {
+                impl SelectParam {
+                    #split_doc
+                    pub fn split(
+                        params: impl IntoIterator<Item = Self>
+                    ) -> (Vec<#pcr::Selection>, Option<#pcr::ComputedSelection>) {
+                        (params.into_iter().map(Self::to_selection).collect(), None)
+                    }
+                }
+            }
+        }
+    };
+
+    let pk_field_name_snake = snake_ident(pk_field.name());
+    let pk_field_name_pascal = pascal_ident(pk_field.name());
+
+    let computed_arms = fields.iter().map(|field| {
+        let field_name_snake = snake_ident(&field.name);
+        let field_name_pascal = pascal_ident(&field.name);
+
+        quote!(Self::#field_name_pascal(_) => fields.push(#field_name_snake::NAME))
+    });
+
+    quote! {
+        impl SelectParam {
+            #split_doc
+            pub fn split(
+                params: impl IntoIterator<Item = Self>
+            ) -> (Vec<#pcr::Selection>, Option<#pcr::ComputedSelection>) {
+                let mut selections = vec![];
+                let mut fields = vec![];
+                let mut has_id = false;
+
+                for param in params {
+                    match param {
+                        #(#computed_arms,)*
+                        param => {
+                            has_id |= matches!(param, Self::#pk_field_name_pascal(_));
+                            selections.push(param.to_selection());
+                        }
+                    }
+                }
+
+                if fields.is_empty() {
+                    return (selections, None);
+                }
+
+                if !has_id {
+                    selections.push(#pcr::sel(#pk_field_name_snake::NAME));
+                }
+
+                (
+                    selections,
+                    Some(#pcr::ComputedSelection {
+                        id: #pk_field_name_snake::NAME,
+                        remove_id: !has_id,
+                        fields,
+                        query: computed::select_query,
+                    })
+                )
+            }
+        }
+    }
+}
+
+pub fn module(
+    model: ModelWalker,
+    args: &GenerateArgs,
+    computed_fields: &ComputedFields,
+) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let fields = computed_fields.get(model.name())?;
+    let pk_field = model.primary_key()?.fields().next()?;
+
+    let quote_ident = |name: &str| match args.engine_dmmf.datasources[0].provider.as_str() {
+        "mysql" => format!("`{name}`"),
+        _ => format!("\"{name}\""),
+    };
+
+    let pk_field_name_snake = snake_ident(pk_field.name());
+    let pk_column = format!(
+        "{}.{}",
+        quote_ident(model.database_name()),
+        quote_ident(pk_field.database_name())
+    );
+
+    let columns = [format!(
+        "{} AS {}",
+        pk_column,
+        quote_ident(&pk_field_name_snake.to_string())
+    )]
+    .into_iter()
+    .chain(fields.iter().map(|field| {
+        let alias = snake_ident(&field.name).to_string();
+
+        format!("({}) AS {}", field.sql, quote_ident(&alias))
+    }))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let from = quote_ident(model.database_name());
+
+    let id = format_ident!("id");
+    let id_prisma_value = pk_field.type_prisma_value(&id)?;
+    let unwrap_ids =
+        id_newtypes::into_original(args, pk_field, &id).map(|value| quote!(.map(|#id| #value)));
+
+    let struct_fields = fields.iter().map(|field| {
+        let name = snake_ident(&field.name);
+        let typ = field.typ.to_tokens();
+
+        quote!(pub #name: Option<#typ>)
+    });
+
+    // `select!` selections are matched to their records by the names of the fields
+    let selected_pk_column = format!("{} AS {}", pk_column, quote_ident(pk_field.name()));
+    let selected_columns = fields.iter().map(|field| {
+        let name = &field.name;
+        let column = format!("({}) AS {}", field.sql, quote_ident(name));
+
+        quote!(#name => Some(#column))
+    });
+
+    Some(quote! {
+        /// Fields configured in `computed_fields`, available through `Actions::computed` or by selecting them in `select!`.
+        pub mod computed {
+            #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
+            pub struct Data {
+                pub #pk_field_name_snake: super::#pk_field_name_snake::Type,
+                #(#struct_fields),*
+            }
+
+            /// Computes `fields` for the records with the given primary keys,
+            /// filling in `select!` selections that include them.
+            pub fn select_query<'a>(
+                client: &'a #pcr::PrismaClientInternals,
+                fields: &[&'static str],
+                ids: Vec<#pcr::serde_json::Value>,
+            ) -> #pcr::Result<#pcr::QueryRaw<'a, #pcr::serde_json::Map<String, #pcr::serde_json::Value>>> {
+                let ids = ids
+                    .into_iter()
+                    .map(#pcr::serde_json::from_value::<super::#pk_field_name_snake::Type>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| #pcr::QueryError::Deserialize(e.to_string()))?;
+
+                let columns = std::iter::once(#selected_pk_column)
+                    .chain(fields.iter().filter_map(|field| match *field {
+                        #(#selected_columns,)*
+                        _ => None,
+                    }))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let filter = match ids.len() {
+                    0 => "1 = 0".to_string(),
+                    len => format!("{} IN ({})", #pk_column, vec!["{}"; len].join(", ")),
+                };
+
+                Ok(#pcr::QueryRaw::new(
+                    client,
+                    #pcr::Raw::new(
+                        &format!("SELECT {} FROM {} WHERE {}", columns, #from, filter),
+                        ids.into_iter()#unwrap_ids.map(|#id| #id_prisma_value).collect(),
+                    ),
+                    super::super::DATABASE_STR,
+                ))
+            }
+        }
+
+        impl<'a> Actions<'a> {
+            /// Computes the fields configured in `computed_fields` for the records with the given IDs,
+            /// using a raw query that is separate from any other query for the records.
+            pub fn computed(
+                self,
+                ids: Vec<#pk_field_name_snake::Type>
+            ) -> #pcr::QueryRaw<'a, computed::Data> {
+                let filter = match ids.len() {
+                    0 => "1 = 0".to_string(),
+                    len => format!("{} IN ({})", #pk_column, vec!["{}"; len].join(", ")),
+                };
+
+                #pcr::QueryRaw::new(
+                    self.client,
+                    #pcr::Raw::new(
+                        &format!("SELECT {} FROM {} WHERE {}", #columns, #from, filter),
+                        ids.into_iter()#unwrap_ids.map(|#id| #id_prisma_value).collect(),
+                    ),
+                    super::DATABASE_STR,
+                )
+            }
+        }
+    })
+}
@@ -1,10 +1,11 @@
 use prisma_client_rust_sdk::prisma::{
-    prisma_models::walkers::ModelWalker, psl::parser_database::ScalarFieldType,
+    prisma_models::walkers::{ModelWalker, RelationFieldWalker},
+    psl::parser_database::ScalarFieldType,
 };
 
 use crate::prelude::*;
 
-use super::required_fields;
+use super::{required_fields, required_fields_in};
 
 fn create_unchecked(model: ModelWalker) -> Option<TokenStream> {
     required_fields(model)?;
@@ -30,7 +31,13 @@ fn create_unchecked(model: ModelWalker) -> Option<TokenStream> {
 
                         quote!(super::#comp_type_snake::Create)
                     }
-                    _ => field.type_tokens(&quote!(super::))?,
+                    // the query engine can't write Unsupported fields
+                    ScalarFieldType::Unsupported(_) => return None,
+                    _ => {
+                        let field_name_snake = snake_ident(field.name());
+
+                        quote!(#field_name_snake::Type)
+                    }
                 },
             ))
         })
@@ -84,6 +91,18 @@ fn create(model: ModelWalker) -> Option<TokenStream> {
         })
         .unzip();
 
+    let default_impl = names.is_empty().then(|| {
+        quote! {
+            impl Default for Create {
+                fn default() -> Self {
+                    Self {
+                        _params: vec![]
+                    }
+                }
+            }
+        }
+    });
+
     Some(quote! {
        #[derive(Debug, Clone)]
         pub struct Create {
@@ -109,6 +128,8 @@ fn create(model: ModelWalker) -> Option<TokenStream> {
             }
         }
 
+        #default_impl
+
         pub fn create(#(#names: #types,)* _params: Vec<SetParam>)
             -> Create {
             Create {
@@ -119,6 +140,53 @@ fn create(model: ModelWalker) -> Option<TokenStream> {
     })
 }
 
+/// Arguments for creating a related record from inside a relation field's module.
+/// The relation back to the record being written is left out,
+/// since the engine connects it (and sets its scalars) itself.
+pub fn nested_create(field: RelationFieldWalker) -> Option<TokenStream> {
+    let related_model = field.related_model();
+    let related_model_snake = snake_ident(related_model.name());
+    let opposite_field = field.opposite_relation_field();
+
+    let (names, (types, push_wrappers)): (Vec<_>, (Vec<_>, Vec<_>)) =
+        required_fields_in(related_model, &quote!())?
+            .into_iter()
+            .filter(|f| !matches!(opposite_field, Some(o) if o.name() == f.inner.name()))
+            .map(|field| {
+                (
+                    snake_ident(field.inner.name()),
+                    (field.typ, field.push_wrapper),
+                )
+            })
+            .unzip();
+
+    Some(quote! {
+        #[derive(Debug, Clone)]
+        pub struct Create {
+            #(pub #names: #types,)*
+            pub _params: Vec<#related_model_snake::SetParam>
+        }
+
+        impl Create {
+            pub fn to_params(mut self) -> Vec<#related_model_snake::SetParam> {
+                self._params.extend([
+                    #(#related_model_snake::#names::#push_wrappers(self.#names)),*
+                ]);
+
+                self._params
+            }
+        }
+
+        pub fn create_params(#(#names: #types,)* _params: Vec<#related_model_snake::SetParam>)
+            -> Create {
+            Create {
+                #(#names,)*
+                _params
+            }
+        }
+    })
+}
+
 pub fn types(model: ModelWalker) -> TokenStream {
     let create_unchecked = create_unchecked(model);
     let create = create(model);
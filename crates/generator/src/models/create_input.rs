@@ -0,0 +1,119 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::{ModelWalker, ScalarFieldWalker},
+    psl::parser_database::ScalarFieldType,
+};
+
+use crate::prelude::*;
+
+use super::required_fields;
+
+/// The scalar fields that `CreateUnchecked` can be built from, split into those required on create
+/// and those that can be left out, or `None` if a required field can't be written.
+pub fn fields<'a>(
+    model: ModelWalker<'a>,
+    args: &GenerateArgs,
+) -> Option<(Vec<ScalarFieldWalker<'a>>, Vec<ScalarFieldWalker<'a>>)> {
+    required_fields(model)?;
+
+    let fields = model
+        .scalar_fields()
+        // left out of `create_unchecked` too
+        .filter(|field| field.type_tokens(&quote!(super::)).is_some())
+        .map(|field| {
+            let writable = !matches!(field.scalar_field_type(), ScalarFieldType::CompositeType(_))
+                && args.write_param(field).is_some();
+
+            (writable || !field.required_on_create()).then_some((field, writable))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(
+        fields
+            .into_iter()
+            .filter(|(_, writable)| *writable)
+            .map(|(field, _)| field)
+            .partition(|field| field.required_on_create()),
+    )
+}
+
+/// A deserializable counterpart to `CreateUnchecked`, for creating records from (eg. HTTP POST) request bodies.
+/// Fields required on create must be present, while the rest are `#[serde(default)]`
+/// so that leaving them out falls back to the schema's default or null.
+pub fn r#struct(model: ModelWalker, args: &GenerateArgs) -> Option<TokenStream> {
+    let (required, optional) = fields(model, args)?;
+
+    let required_names = required
+        .iter()
+        .map(|field| snake_ident(field.name()))
+        .collect::<Vec<_>>();
+
+    let required_fields = required.iter().map(|field| {
+        let field_name_snake = snake_ident(field.name());
+        let rename = serde_rename(args.shared_config, model.name(), field.name());
+
+        quote! {
+            #[serde(#rename)]
+            pub #field_name_snake: #field_name_snake::Type
+        }
+    });
+    let optional_fields = optional.iter().map(|field| {
+        let field_name_snake = snake_ident(field.name());
+        let rename = serde_rename(args.shared_config, model.name(), field.name());
+
+        // nullable fields are already options, where `None` and leaving them out both mean null
+        let typ = match field.ast_field().arity.is_optional() {
+            true => quote!(#field_name_snake::Type),
+            false => quote!(Option<#field_name_snake::Type>),
+        };
+
+        quote! {
+            #[serde(#rename, default)]
+            pub #field_name_snake: #typ
+        }
+    });
+
+    let optional_pushes = optional.iter().map(|field| {
+        let field_name_snake = snake_ident(field.name());
+
+        match field.ast_field().arity.is_optional() {
+            true => quote! {
+                if self.#field_name_snake.is_some() {
+                    params.push(#field_name_snake::set(self.#field_name_snake));
+                }
+            },
+            false => quote! {
+                if let Some(value) = self.#field_name_snake {
+                    params.push(#field_name_snake::set(value));
+                }
+            },
+        }
+    });
+
+    Some(quote! {
+        #[derive(Debug, Clone, ::serde::Deserialize)]
+        pub struct CreateInput {
+            #(#required_fields,)*
+            #(#optional_fields),*
+        }
+
+        impl CreateInput {
+            pub fn to_create(self) -> CreateUnchecked {
+                let mut params: Vec<UncheckedSetParam> = vec![];
+
+                #(#optional_pushes)*
+
+                create_unchecked(#(self.#required_names,)* params)
+            }
+
+            pub fn to_query<'a>(self, client: &'a PrismaClient) -> CreateUncheckedQuery<'a> {
+                self.to_create().to_query(client)
+            }
+        }
+
+        impl From<CreateInput> for CreateUnchecked {
+            fn from(input: CreateInput) -> Self {
+                input.to_create()
+            }
+        }
+    })
+}
@@ -1,6 +1,6 @@
 use prisma_client_rust_sdk::prisma::{
     prisma_models::{
-        walkers::{ModelWalker, RefinedFieldWalker},
+        walkers::{ModelWalker, RefinedFieldWalker, RelationFieldWalker, ScalarFieldWalker},
         FieldArity,
     },
     psl::parser_database::ScalarFieldType,
@@ -8,9 +8,9 @@ use prisma_client_rust_sdk::prisma::{
 
 use crate::prelude::*;
 
-use super::ModelModulePart;
+use super::{field_codecs, id_newtypes, native_types, type_overrides, ModelModulePart};
 
-pub fn model_data(model: ModelWalker) -> ModelModulePart {
+pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
     let fields = model
         .fields()
         .flat_map(|field| {
@@ -57,7 +57,13 @@ pub fn model_data(model: ModelWalker) -> ModelModulePart {
 
                             typ
                         }
-                        _ => (field.type_tokens(&quote!(super::super::))?, None),
+                        _ => (
+                            type_overrides::field_type(args, scalar_field)
+                                .or_else(|| id_newtypes::field_type(args, scalar_field))
+                                .or_else(|| native_types::field_type(args, scalar_field))
+                                .or_else(|| field.type_tokens(&quote!(super::super::)))?,
+                            None,
+                        ),
                     }
                 }
             };
@@ -80,7 +86,139 @@ pub fn model_data(model: ModelWalker) -> ModelModulePart {
     }
 }
 
-pub fn r#struct(model: ModelWalker) -> TokenStream {
+/// A `load_` method for a relation field, preparing a query for the related records
+/// with the keys of an already fetched record, for when the relation wasn't fetched with it.
+/// Returned with the fields of this model that it reads, so that partial types
+/// only get the method when they have them.
+///
+/// `prefix` is the path to the generated client's module, and `client` the path to its `PrismaClient`.
+///
+/// Relations through fields with overridden or native types are skipped,
+/// as their filters may not take the type of the field they're compared with.
+pub fn relation_loader<'a>(
+    args: &GenerateArgs,
+    field: RelationFieldWalker<'a>,
+    prefix: &TokenStream,
+    client: &TokenStream,
+) -> Option<(Vec<ScalarFieldWalker<'a>>, TokenStream)> {
+    let related_model = field.related_model();
+    let related_model_snake = snake_ident(related_model.name());
+    let loader_name = format_ident!("load_{}", field.name().to_case(Case::Snake, true));
+
+    // (field of this model, field of the related model it's compared with)
+    let pairs: Vec<(ScalarFieldWalker, ScalarFieldWalker)> =
+        match (field.fields(), field.referenced_fields()) {
+            (Some(fields), Some(referenced)) => fields.zip(referenced).collect(),
+            _ => {
+                let opposite = field.opposite_relation_field()?;
+
+                match (opposite.referenced_fields(), opposite.fields()) {
+                    (Some(referenced), Some(fields)) => referenced.zip(fields).collect(),
+                    _ => vec![],
+                }
+            }
+        };
+
+    let is_many = field.ast_field().arity.is_list();
+
+    let (query_type, query_fn) = match is_many {
+        true => (quote!(FindManyQuery), quote!(find_many)),
+        false => (quote!(FindFirstQuery), quote!(find_first)),
+    };
+
+    // implicit many-to-many relations have no fields and are filtered through the join table
+    if pairs.is_empty() {
+        let opposite_snake = snake_ident(field.opposite_relation_field()?.name());
+        let model_snake = snake_ident(field.model().name());
+
+        let pk = field.model().primary_key()?;
+        if pk.fields().count() != 1 {
+            return None;
+        }
+        let pk_field = pk.fields().next()?;
+        if has_custom_type(args, pk_field) {
+            return None;
+        }
+        let pk_snake = snake_ident(pk_field.name());
+
+        return Some((
+            vec![pk_field],
+            quote! {
+                pub fn #loader_name<'a>(&self, client: &'a #client) -> #prefix #related_model_snake::#query_type<'a> {
+                    client.#related_model_snake().#query_fn(vec![
+                        #prefix #related_model_snake::#opposite_snake::some(vec![
+                            #prefix #model_snake::#pk_snake::equals(self.#pk_snake.clone())
+                        ])
+                    ])
+                }
+            },
+        ));
+    }
+
+    if pairs
+        .iter()
+        .any(|(field, related)| has_custom_type(args, *field) || has_custom_type(args, *related))
+    {
+        return None;
+    }
+
+    // a relation is unset if any of its fields are null
+    let nullable = pairs
+        .iter()
+        .any(|(field, _)| field.ast_field().arity.is_optional());
+
+    let (bindings, filters): (Vec<_>, Vec<_>) = pairs
+        .iter()
+        .map(|(field, related)| {
+            let field_snake = snake_ident(field.name());
+            let related_snake = snake_ident(related.name());
+
+            let binding = match field.ast_field().arity.is_optional() {
+                true => quote!(let #field_snake = self.#field_snake.clone()?;),
+                false => quote!(let #field_snake = self.#field_snake.clone();),
+            };
+
+            let value = match related.ast_field().arity.is_optional() {
+                true => quote!(Some(#field_snake)),
+                false => quote!(#field_snake),
+            };
+
+            (
+                binding,
+                quote!(#prefix #related_model_snake::#related_snake::equals(#value)),
+            )
+        })
+        .unzip();
+
+    let query = quote!(client.#related_model_snake().#query_fn(vec![#(#filters),*]));
+
+    let loader = match nullable {
+        true => quote! {
+            /// `None` if the relation isn't set.
+            pub fn #loader_name<'a>(&self, client: &'a #client) -> Option<#prefix #related_model_snake::#query_type<'a>> {
+                #(#bindings)*
+
+                Some(#query)
+            }
+        },
+        false => quote! {
+            pub fn #loader_name<'a>(&self, client: &'a #client) -> #prefix #related_model_snake::#query_type<'a> {
+                #(#bindings)*
+
+                #query
+            }
+        },
+    };
+
+    Some((pairs.into_iter().map(|(field, _)| field).collect(), loader))
+}
+
+fn has_custom_type(args: &GenerateArgs, field: ScalarFieldWalker) -> bool {
+    type_overrides::field_type(args, field).is_some()
+        || native_types::field_type(args, field).is_some()
+}
+
+pub fn r#struct(model: ModelWalker, args: &GenerateArgs) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
     let struct_fields = model
@@ -90,12 +228,13 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
             RefinedFieldWalker::Relation(field) => {
                 let field_name_str = field.name();
                 let field_name_snake = snake_ident(field_name_str);
+                let rename = serde_rename(args.shared_config, model.name(), field_name_str);
 
                 let attrs = match field.ast_field().arity {
                     FieldArity::Optional => {
                         quote! {
                             #[serde(
-                                rename = #field_name_str,
+                                #rename,
                                 default,
                                 skip_serializing_if = "Option::is_none",
                                 with = "prisma_client_rust::serde::double_option"
@@ -103,7 +242,7 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
                         }
                     }
                     _ => quote! {
-                        #[serde(rename = #field_name_str)]
+                        #[serde(#rename)]
                     },
                 };
 
@@ -118,9 +257,12 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
             RefinedFieldWalker::Scalar(field) => {
                 let field_name_str = field.name();
                 let field_name_snake = snake_ident(field_name_str);
+                let rename = serde_rename(args.shared_config, model.name(), field_name_str);
+                let bigint = bigint_serde(args.shared_config, field).map(|with| quote!(, #with));
+                let codec = field_codecs::data_serde(args, field).map(|with| quote!(, #with));
 
                 quote! {
-                    #[serde(rename = #field_name_str)]
+                    #[serde(#rename #bigint #codec)]
                     pub #field_name_snake: #field_name_snake::Type
                 }
             }
@@ -155,6 +297,13 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
         _ => None,
     });
 
+    let relation_loaders = model
+        .relation_fields()
+        .filter_map(|field| relation_loader(args, field, &quote!(super::), &quote!(PrismaClient)))
+        .map(|(_, loader)| loader);
+
+    let mock_fn = cfg!(feature = "mocking").then(|| mock_fn(model, args));
+
     let specta_derive = cfg!(feature = "specta").then(|| {
         let model_name_pascal_str = pascal_ident(model.name()).to_string();
 
@@ -164,15 +313,65 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
         }
     });
 
+    let non_exhaustive = non_exhaustive(args.shared_config);
+
     quote! {
         #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
         #specta_derive
+        #non_exhaustive
         pub struct Data {
             #(#struct_fields),*
         }
 
         impl Data {
             #(#relation_accessors)*
+
+            #(#relation_loaders)*
+
+            #mock_fn
+        }
+    }
+}
+
+/// `Data::mock()`, which fills each field with its `MockValue`.
+/// Required fields with overridden types may not have one, so they're taken as arguments instead.
+fn mock_fn(model: ModelWalker, args: &GenerateArgs) -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    let (params, values): (Vec<_>, Vec<_>) = model
+        .fields()
+        .filter(|f| f.ast_field().field_type.as_unsupported().is_none())
+        .map(|field| {
+            let field_name_snake = snake_ident(field.name());
+
+            let overridden = match field.refine() {
+                RefinedFieldWalker::Scalar(scalar_field) => {
+                    field.ast_field().arity.is_required()
+                        && type_overrides::field_type(args, scalar_field).is_some()
+                }
+                RefinedFieldWalker::Relation(_) => false,
+            };
+
+            match overridden {
+                true => (
+                    Some(quote!(#field_name_snake: #field_name_snake::Type)),
+                    quote!(#field_name_snake),
+                ),
+                false => (None, quote!(#field_name_snake: #pcr::MockValue::mock())),
+            }
+        })
+        .unzip();
+
+    let params = params.into_iter().flatten();
+
+    quote! {
+        /// A record with placeholder values, for testing code that takes one without a database.
+        /// Optional fields are `None`, lists are empty and relations aren't fetched,
+        /// and others can be set with struct update syntax, eg. `Data { name, ..Data::mock() }`.
+        pub fn mock(#(#params),*) -> Self {
+            Self {
+                #(#values),*
+            }
         }
     }
 }
@@ -0,0 +1,78 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ModelWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::{prelude::*, Error};
+
+/// Checks that each `display_fields` entry points at a required scalar field
+/// whose generated type can be converted to a string.
+pub fn validate(args: &GenerateArgs) -> Result<(), Error> {
+    for (model_name, field_name) in &args.shared_config.display_fields {
+        let invalid = |reason: String| {
+            Error::InvalidDisplayField(format!("{model_name}.{field_name}"), reason)
+        };
+
+        let model = args
+            .schema
+            .db
+            .walk_models()
+            .find(|m| m.name() == model_name)
+            .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+        let field = model
+            .scalar_fields()
+            .find(|f| f.name() == field_name)
+            .ok_or_else(|| invalid(format!("scalar field '{field_name}' not found")))?;
+
+        if !field.ast_field().arity.is_required() {
+            return Err(invalid(
+                "optional and list fields can't be displayed".to_string(),
+            ));
+        }
+
+        if args
+            .shared_config
+            .type_override(model_name, field_name)
+            .is_some()
+        {
+            return Err(invalid(
+                "fields with a type override can't be displayed".to_string(),
+            ));
+        }
+
+        if !matches!(
+            field.scalar_field_type(),
+            ScalarFieldType::Enum(_)
+                | ScalarFieldType::BuiltInScalar(
+                    ScalarType::String
+                        | ScalarType::Int
+                        | ScalarType::BigInt
+                        | ScalarType::Float
+                        | ScalarType::Decimal
+                        | ScalarType::Boolean
+                        | ScalarType::DateTime
+                        | ScalarType::Json
+                )
+        ) {
+            return Err(invalid(
+                "only String, Int, BigInt, Float, Decimal, Boolean, DateTime, Json and enum fields can be displayed"
+                    .to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn r#impl(model: ModelWalker, args: &GenerateArgs) -> Option<TokenStream> {
+    let field_name_snake = snake_ident(args.shared_config.display_field(model.name())?);
+
+    Some(quote! {
+        impl ::std::fmt::Display for Data {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.pad(&self.#field_name_snake.to_string())
+            }
+        }
+    })
+}
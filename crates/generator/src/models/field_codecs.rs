@@ -0,0 +1,128 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ScalarFieldWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::{prelude::*, Error};
+
+/// Checks that each `field_codecs` entry points at a `String` field without an overridden type,
+/// and that its codec is a plain path like `crate::Encrypted`.
+pub fn validate(args: &GenerateArgs) -> Result<(), Error> {
+    for parsed in args.shared_config.parsed_field_codecs() {
+        let (model_name, field_name, codec) = parsed.map_err(|entry| {
+            Error::InvalidFieldCodec(
+                entry.to_string(),
+                "expected the form `Model.field=path::to::Codec`".to_string(),
+            )
+        })?;
+
+        let invalid = |reason: String| {
+            Error::InvalidFieldCodec(format!("{model_name}.{field_name}={codec}"), reason)
+        };
+
+        let model = args
+            .schema
+            .db
+            .walk_models()
+            .find(|m| m.name() == model_name)
+            .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+        let field = model
+            .scalar_fields()
+            .find(|f| f.name() == field_name)
+            .ok_or_else(|| invalid(format!("scalar field '{field_name}' not found")))?;
+
+        if !matches!(
+            field.scalar_field_type(),
+            ScalarFieldType::BuiltInScalar(ScalarType::String)
+        ) || field.ast_field().arity.is_list()
+        {
+            return Err(invalid(
+                "only String fields that aren't lists can have a codec".to_string(),
+            ));
+        }
+
+        if args
+            .shared_config
+            .type_override(model_name, field_name)
+            .is_some()
+        {
+            return Err(invalid(
+                "fields with an overridden type can't have a codec".to_string(),
+            ));
+        }
+
+        // unique filters and relations compare values without encoding them
+        let id = field.field_id();
+        let in_primary_key = model
+            .primary_key()
+            .map(|pk| pk.fields().any(|f| f.field_id() == id))
+            .unwrap_or(false);
+        let in_unique = model
+            .indexes()
+            .any(|index| index.is_unique() && index.fields().any(|f| f.field_id() == id));
+
+        if in_primary_key || in_unique || model.scalar_field_has_relation(field) {
+            return Err(invalid(
+                "fields that are part of a primary key, unique constraint or relation can't have a codec"
+                    .to_string(),
+            ));
+        }
+
+        syn::parse_str::<syn::TypePath>(codec)
+            .map_err(|_| invalid(format!("'{codec}' is not a type path")))?;
+    }
+
+    Ok(())
+}
+
+fn codec(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    args.shared_config
+        .field_codec(field.model().name(), field.name())?
+        .parse()
+        .ok()
+}
+
+/// Encodes `value`, which has the type of `field`, with the field's codec.
+pub fn encode(args: &GenerateArgs, field: ScalarFieldWalker, value: &Ident) -> Option<TokenStream> {
+    let codec = codec(args, field)?;
+
+    let pcr = quote!(::prisma_client_rust);
+    let encoded = quote!(<#codec as #pcr::FieldCodec>::encode(#value));
+
+    Some(match field.ast_field().arity.is_optional() {
+        true => quote!(#value.map(|#value| #encoded)),
+        false => encoded,
+    })
+}
+
+/// The `deserialize_with` entry of `field`'s `#[serde(...)]` attribute in `Data`.
+pub fn data_serde(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    let codec = codec(args, field)?.to_string().replace(' ', "");
+
+    let deserialize_fn = match field.ast_field().arity.is_optional() {
+        true => "deserialize_option",
+        false => "deserialize",
+    };
+    let path = format!("::prisma_client_rust::serde::codec::{deserialize_fn}::<{codec}, _>");
+
+    Some(quote!(deserialize_with = #path))
+}
+
+/// Reads the value of `field` from `map`, a `MapAccess`, in a `select!` or `include!` visitor.
+pub fn next_value(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    map: &TokenStream,
+) -> Option<TokenStream> {
+    let codec = codec(args, field)?;
+
+    let pcr = quote!(::prisma_client_rust);
+
+    Some(match field.ast_field().arity.is_optional() {
+        true => quote! {
+            #map.next_value::<Option<#pcr::Decoded<#codec>>>()?.map(|decoded| decoded.0)
+        },
+        false => quote!(#map.next_value::<#pcr::Decoded<#codec>>()?.0),
+    })
+}
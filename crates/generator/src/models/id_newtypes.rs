@@ -0,0 +1,213 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::{ModelWalker, ScalarFieldWalker},
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::prelude::*;
+
+/// Whether `model` gets an id newtype when `id_newtypes` is enabled,
+/// which is the case for single field `String` and `Int` primary keys
+/// that aren't themselves a relation to another model.
+fn has_newtype(args: &GenerateArgs, model: ModelWalker) -> bool {
+    if !args.shared_config.id_newtypes {
+        return false;
+    }
+
+    let primary_key = match model.primary_key() {
+        Some(primary_key) => primary_key,
+        None => return false,
+    };
+
+    let mut fields = primary_key.fields();
+
+    let field = match (fields.next(), fields.next()) {
+        (Some(field), None) => field,
+        _ => return false,
+    };
+
+    matches!(
+        field.scalar_field_type(),
+        ScalarFieldType::BuiltInScalar(ScalarType::String | ScalarType::Int)
+    ) && !field.ast_field().arity.is_list()
+        && !model.scalar_field_has_relation(field)
+}
+
+/// The model whose id newtype `field` takes, either because it's the model's primary key
+/// or because it's the only field of a relation to another model's primary key.
+fn owner<'a>(args: &GenerateArgs, field: ScalarFieldWalker<'a>) -> Option<ModelWalker<'a>> {
+    let model = field.model();
+
+    if field.is_single_pk() && has_newtype(args, model) {
+        return Some(model);
+    }
+
+    if args
+        .shared_config
+        .type_override(model.name(), field.name())
+        .is_some()
+    {
+        return None;
+    }
+
+    model.relation_fields().find_map(|relation| {
+        let mut fields = relation.fields()?;
+        let mut referenced = relation.referenced_fields()?;
+
+        match (
+            fields.next(),
+            fields.next(),
+            referenced.next(),
+            referenced.next(),
+        ) {
+            (Some(fk), None, Some(pk), None)
+                if fk.field_id() == field.field_id()
+                    && pk.is_single_pk()
+                    && has_newtype(args, pk.model()) =>
+            {
+                Some(pk.model())
+            }
+            _ => None,
+        }
+    })
+}
+
+fn name(model: ModelWalker) -> Ident {
+    format_ident!("{}Id", pascal_ident(model.name()))
+}
+
+/// The id newtype of `field` relative to `prefix`, without being wrapped according to its arity.
+pub fn required_type(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    prefix: &TokenStream,
+) -> Option<TokenStream> {
+    let model = owner(args, field)?;
+
+    let model_name_snake = snake_ident(model.name());
+    let name = name(model);
+
+    Some(quote!(#prefix #model_name_snake::#name))
+}
+
+/// The id newtype of `field`, wrapped according to its arity, for use in the field's module.
+pub fn field_type(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    required_type(args, field, &quote!(super::super::))
+        .map(|typ| field.ast_field().arity.wrap_type(&typ))
+}
+
+/// Converts `value`, which has the id newtype of `field`, into the field's scalar type.
+pub fn into_original(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    value: &Ident,
+) -> Option<TokenStream> {
+    owner(args, field)?;
+
+    Some(match field.ast_field().arity.is_optional() {
+        true => quote!(#value.map(|#value| #value.0)),
+        false => quote!(#value.0),
+    })
+}
+
+/// `expr`, which has the required id newtype of `field` if it has one, as the field's scalar type.
+pub fn unwrap(args: &GenerateArgs, field: ScalarFieldWalker, expr: TokenStream) -> TokenStream {
+    match owner(args, field) {
+        Some(_) => quote!(#expr.0),
+        None => expr,
+    }
+}
+
+/// The id newtype of `model`, if it has one.
+pub fn definition(args: &GenerateArgs, model: ModelWalker) -> Option<TokenStream> {
+    if !has_newtype(args, model) {
+        return None;
+    }
+
+    let field = model.primary_key()?.fields().next()?;
+    let inner = field.type_tokens(&quote!())?;
+
+    let name = name(model);
+    let doc = format!(
+        " The primary key of `{}`, distinct from the keys of other models.",
+        model.name()
+    );
+
+    let specta_derive = cfg!(feature = "specta").then(|| {
+        let name_str = name.to_string();
+
+        quote! {
+            #[derive(::prisma_client_rust::specta::Type)]
+            #[specta(rename = #name_str, crate = prisma_client_rust::specta)]
+        }
+    });
+
+    let str_conversions = matches!(
+        field.scalar_field_type(),
+        ScalarFieldType::BuiltInScalar(ScalarType::String)
+    )
+    .then(|| {
+        quote! {
+            impl From<&str> for #name {
+                fn from(value: &str) -> Self {
+                    Self(value.to_string())
+                }
+            }
+
+            impl AsRef<str> for #name {
+                fn as_ref(&self) -> &str {
+                    &self.0
+                }
+            }
+        }
+    });
+
+    let mock_impl = cfg!(feature = "mocking").then(|| {
+        quote! {
+            impl ::prisma_client_rust::MockValue for #name {
+                fn mock() -> Self {
+                    Self(::prisma_client_rust::MockValue::mock())
+                }
+            }
+        }
+    });
+
+    Some(quote! {
+        #[doc = #doc]
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            ::serde::Serialize,
+            ::serde::Deserialize
+        )]
+        #[serde(transparent)]
+        #specta_derive
+        pub struct #name(pub #inner);
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl From<#inner> for #name {
+            fn from(value: #inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<#name> for #inner {
+            fn from(value: #name) -> Self {
+                value.0
+            }
+        }
+
+        #str_conversions
+
+        #mock_impl
+    })
+}
@@ -1,13 +1,19 @@
 use prisma_client_rust_sdk::prisma::{
     prisma_models::{
-        walkers::{FieldWalker, ModelWalker, RefinedFieldWalker, ScalarFieldWalker},
+        walkers::{
+            FieldWalker, ModelWalker, RefinedFieldWalker, RelationFieldWalker, ScalarFieldWalker,
+        },
         FieldArity,
     },
     psl::parser_database::ScalarFieldType,
 };
 
+use std::collections::BTreeMap;
+
 use crate::prelude::*;
 
+use super::{computed, data, field_codecs};
+
 enum Variant {
     Select,
     Include,
@@ -34,14 +40,26 @@ impl Variant {
     }
 }
 
+/// Relations that can be counted with a `_count` selection.
+fn counted_relations(model: ModelWalker) -> Vec<RelationFieldWalker> {
+    model
+        .relation_fields()
+        .filter(|f| f.ast_field().arity.is_list())
+        .collect()
+}
+
 fn model_macro<'a>(
     model: ModelWalker<'a>,
+    args: &GenerateArgs,
     module_path: &TokenStream,
     variant: Variant,
     // Fields that should always be included
     base_fields: impl Iterator<Item = ScalarFieldWalker<'a>> + Clone,
     // Fields that can be picked from
     selection_fields: impl Iterator<Item = FieldWalker<'a>> + Clone,
+    // Fields configured in `computed_fields`, which can be picked from at the top level
+    computed_fields: &[&str],
+    max_depth: usize,
 ) -> TokenStream {
     let model_name_pascal_str = pascal_ident(model.name()).to_string();
     let model_name_snake = snake_ident(model.name());
@@ -83,13 +101,94 @@ fn model_macro<'a>(
         quote!(#selection_type_impl)
     });
 
+    let counted_relations = counted_relations(model);
+
+    // `_count: select { relation ... }` is accepted alongside the model's fields,
+    // fetching how many records each listed relation has
+    let count_arms = (!counted_relations.is_empty()).then(|| {
+        let count_relation_arms = counted_relations.iter().map(|field| {
+            let field_name_snake = snake_ident(field.name());
+
+            quote!((@count_relation; #field_name_snake) => { #model_module::#field_name_snake::NAME };)
+        });
+
+        let counted_relations_str = counted_relations
+            .iter()
+            .map(|f| snake_ident(f.name()).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let specta_derive = cfg!(feature = "specta").then(|| {
+            quote! {
+                #[derive(::prisma_client_rust::specta::Type)]
+                #[specta(crate = prisma_client_rust::specta)]
+                #[specta(inline)]
+            }
+        });
+
+        quote! {
+            (@field_type; _count : $selection_mode:ident { $($relation:ident)+ }) => { _count::Data };
+            (@field_module; $depth:tt; _count : $selection_mode:ident { $($relation:ident)+ }) => {
+                #[allow(warnings)]
+                #[derive(std::fmt::Debug, Clone)]
+                #specta_derive
+                pub struct Data {
+                    $(pub $relation: i64),+
+                }
+
+                impl ::serde::Serialize for Data {
+                    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                    where
+                        S: ::serde::Serializer,
+                    {
+                        use ::serde::ser::SerializeStruct;
+
+                        let mut state = serializer.serialize_struct(
+                            "Data",
+                            [$(stringify!($relation)),+].len()
+                        )?;
+                        $(state.serialize_field(#model_module::$relation::NAME, &self.$relation)?;)+
+                        state.end()
+                    }
+                }
+
+                impl<'de> ::serde::Deserialize<'de> for Data {
+                    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+                    where
+                        D: ::serde::Deserializer<'de>,
+                    {
+                        let mut counts: ::std::collections::HashMap<String, i64> =
+                            ::serde::Deserialize::deserialize(deserializer)?;
+
+                        Ok(Data {
+                            $($relation: counts.remove(#model_module::$relation::NAME).ok_or_else(||
+                                ::serde::de::Error::missing_field(#model_module::$relation::NAME)
+                            )?),+
+                        })
+                    }
+                }
+            };
+            (@selection_field_to_selection_param; _count : $selection_mode:ident { $($relation:ident)+ }) => {{
+                Into::<#model_module::#selection_param>::into(
+                    #model_module::_count::Selection(vec![
+                        $(#model_module::#variant_ident!(@count_relation; $relation)),+
+                    ])
+                )
+            }};
+            #(#count_relation_arms)*
+            (@count_relation; $relation:ident) => {
+                compile_error!(stringify!(Cannot count $relation on model #model_name_pascal_str, available relations are #counted_relations_str))
+            };
+        }
+    });
+
     let field_module_impls = model.relation_fields().map(|field| {
         let field_name_snake = snake_ident(field.name());
         let relation_model_name_snake = snake_ident(field.related_model().name());
 
         quote! {
-            (@field_module; #field_name_snake #selections_pattern_produce) => {
-                $crate::#module_path #relation_model_name_snake::#variant_ident!(@definitions; ; $($selections)+);
+            (@field_module; $depth:tt; #field_name_snake #selections_pattern_produce) => {
+                $crate::#module_path #relation_model_name_snake::#variant_ident!(@nested_definitions; $depth; $($selections)+);
             };
         }
     });
@@ -116,7 +215,7 @@ fn model_macro<'a>(
                                             $($($filters)+)?
                                         )) $($(.$arg($($arg_params)*))*)?,
                                         #relation_model_module::select!(
-                                            @selections_to_params;
+                                            @nested_selections_to_params;
                                             #selections_pattern_consume
                                         ).into_iter().collect()
                                     )
@@ -139,7 +238,7 @@ fn model_macro<'a>(
                             Into::<#model_module::#selection_param>::into(
                                 #field_module::#variant_pascal::$selection_mode(
                                     #relation_model_module::select!(
-                                        @selections_to_params;
+                                        @nested_selections_to_params;
                                         #selections_pattern_consume
                                     ).into_iter().collect()
                                 )
@@ -168,9 +267,34 @@ fn model_macro<'a>(
         }
     });
 
+    let computed_field_names_snake = computed_fields
+        .iter()
+        .map(|name| snake_ident(name))
+        .collect::<Vec<_>>();
+
+    let computed_selection_param_impls =
+        computed_field_names_snake.iter().map(|field_name_snake| {
+            quote! {
+                (@selection_field_to_selection_param; #field_name_snake) => {
+                    Into::<#model_module::#selection_param>::into(
+                        #model_module::#field_name_snake::#variant_pascal
+                    )
+                };
+            }
+        });
+
+    // computed fields are filled in by a raw query for the top level records only
+    let computed_nested_errors = computed_field_names_snake.iter().map(|field_name_snake| {
+        let error = format!(
+            "Computed field {field_name_snake} of model {model_name_pascal_str} can only be selected at the top level of select!"
+        );
+
+        quote!((@nested_selection_field_to_selection_param; $macro_name:ident; #field_name_snake) => { compile_error!(#error) };)
+    });
+
     let data_struct_scalar_fields = base_fields.clone().map(|f| {
         let field_name_snake = snake_ident(f.name());
-        let field_type = f.type_tokens(module_path);
+        let field_type = quote!(#model_module::#field_name_snake::Type);
 
         let specta_rename = cfg!(feature = "specta").then(|| {
             quote!(#[specta(rename_from_path = $crate::#module_path #model_name_snake::#field_name_snake::NAME)])
@@ -182,10 +306,14 @@ fn model_macro<'a>(
         }
     });
 
-    let fields_enum_variants = selection_fields.clone().map(|f| {
-        let i = snake_ident(f.name());
-        quote!(#i)
-    });
+    let fields_enum_variants = selection_fields
+        .clone()
+        .map(|f| {
+            let i = snake_ident(f.name());
+            quote!(#i)
+        })
+        .chain(computed_field_names_snake.iter().map(|i| quote!(#i)))
+        .chain((!counted_relations.is_empty()).then(|| quote!(_count)));
 
     let field_serde_names = model
         .fields()
@@ -202,6 +330,26 @@ fn model_macro<'a>(
         .map(|f| snake_ident(f.name()))
         .collect::<Vec<_>>();
 
+    let base_field_values = base_fields
+        .clone()
+        .map(|f| {
+            field_codecs::next_value(args, f, &quote!(map))
+                .unwrap_or_else(|| quote!(map.next_value()?))
+        })
+        .collect::<Vec<_>>();
+
+    // fields with a codec are decoded as they're read, the rest are deserialized as is
+    let field_value_arms = selection_fields.clone().filter_map(|f| {
+        let field_name_snake = snake_ident(f.name());
+        let scalar_field = match f.refine() {
+            RefinedFieldWalker::Scalar(scalar_field) => scalar_field,
+            RefinedFieldWalker::Relation(_) => return None,
+        };
+        let next_value = field_codecs::next_value(args, scalar_field, &quote!($map))?;
+
+        Some(quote!((@field_value; $map:ident; #field_name_snake) => { #next_value };))
+    });
+
     let deserialize_impl = {
         let field_names_str = model.fields().map(|f| f.name());
 
@@ -269,7 +417,7 @@ fn model_macro<'a>(
                                         #model_module::#base_field_names_snake::NAME
                                     ));
                                 }
-                                #base_field_names_snake = Some(map.next_value()?);
+                                #base_field_names_snake = Some(#base_field_values);
                             })*
                             $(Field::$field => {
                                 if $field.is_some() {
@@ -277,7 +425,7 @@ fn model_macro<'a>(
                                         #model_module::$field::NAME
                                     ));
                                 }
-                                $field = Some(map.next_value()?);
+                                $field = Some(#model_module::#variant_ident!(@field_value; map; $field));
                             })*
                         }
                     }
@@ -315,6 +463,51 @@ fn model_macro<'a>(
         }
     };
 
+    let loaders = model
+        .relation_fields()
+        .filter_map(|field| {
+            data::relation_loader(
+                args,
+                field,
+                &quote!($crate::#module_path),
+                &quote!($crate::#module_path PrismaClient),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // `include!` has every scalar field, so it gets all of the model's `load_` methods,
+    // while `select!` gets those that only read one field once that field is selected
+    let (loaders_impl, loader_arms) = match variant {
+        Variant::Include => {
+            let loaders = loaders.iter().map(|(_, loader)| loader);
+
+            (quote!(impl Data { #(#loaders)* }), None)
+        }
+        Variant::Select => {
+            let mut by_field = BTreeMap::<&str, Vec<&TokenStream>>::new();
+
+            for (fields, loader) in &loaders {
+                if let [field] = fields.as_slice() {
+                    by_field.entry(field.name()).or_default().push(loader);
+                }
+            }
+
+            let arms = by_field.into_iter().map(|(field, loaders)| {
+                let field_name_snake = snake_ident(field);
+
+                quote!((@relation_loaders; #field_name_snake) => { impl Data { #(#loaders)* } };)
+            });
+
+            (
+                quote!($(#model_module::#variant_ident!(@relation_loaders; $field);)+),
+                Some(quote! {
+                    #(#arms)*
+                    (@relation_loaders; $field:ident) => {};
+                }),
+            )
+        }
+    };
+
     let all_fields_str = selection_fields
         .clone()
         .map(|f| snake_ident(f.name()).to_string())
@@ -377,50 +570,121 @@ fn model_macro<'a>(
         })
         .unwrap_or(quote!( #data_struct_attrs #data_struct ));
 
-    let selection = {
-        let scalar_selections = matches!(variant, Variant::Include).then(||
+    let selection_params = quote! {
+        $crate::#module_path #model_name_snake::#variant_ident!(
+            @selections_to_params; : #variant_ident
+            { $(#selection_pattern_consume)+ }
+        )
+    };
+
+    let (selection, selection_struct) = match variant {
+        Variant::Include => (
+            quote!(Selection(
+                [
+                    #selection_params
+                        .into_iter()
+                        .map(|p| p.to_selection())
+                        .collect::<Vec<_>>(),
+                    <$crate::#module_path #model_name_snake::Types as ::prisma_client_rust::ModelTypes>::scalar_selections()
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+            )),
             quote! {
-                <$crate::#module_path #model_name_snake::Types as ::prisma_client_rust::ModelTypes>::scalar_selections()
-            }
-        );
+                pub struct Selection(Vec<::prisma_client_rust::Selection>);
 
-        quote!(Selection(
-            [
-                $crate::#module_path #model_name_snake::#variant_ident!(
-                    @selections_to_params; : #variant_ident
-                    { $(#selection_pattern_consume)+ }
-                )
-                    .into_iter()
-                    .map(|p| p.to_selection())
-                    .collect::<Vec<_>>(),
-                #scalar_selections
-            ]
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-        ))
-    };
+                impl ::prisma_client_rust::#selection_type for Selection {
+                    type Data = Data;
+                    type ModelData = #model_module::Data;
 
-    let selection_struct = quote! {
-        pub struct Selection(Vec<::prisma_client_rust::Selection>);
+                    fn to_selections(self) -> Vec<::prisma_client_rust::Selection> {
+                        self.0
+                    }
+                }
+            },
+        ),
+        Variant::Select => (
+            quote!({
+                let (selections, computed) = #model_module::#selection_param::split(#selection_params);
 
-        impl ::prisma_client_rust::#selection_type for Selection {
-            type Data = Data;
-            type ModelData = #model_module::Data;
+                Selection(selections, computed)
+            }),
+            quote! {
+                pub struct Selection(
+                    Vec<::prisma_client_rust::Selection>,
+                    Option<::prisma_client_rust::ComputedSelection>
+                );
 
-            fn to_selections(self) -> Vec<::prisma_client_rust::Selection> {
-                self.0
-            }
-        }
+                impl ::prisma_client_rust::#selection_type for Selection {
+                    type Data = Data;
+                    type ModelData = #model_module::Data;
+
+                    fn computed(&self) -> Option<::prisma_client_rust::ComputedSelection> {
+                        self.1.clone()
+                    }
+
+                    fn to_selections(self) -> Vec<::prisma_client_rust::Selection> {
+                        self.0
+                    }
+                }
+            },
+        ),
     };
 
+    // Consumed one token per nested relation, so that `@nested_definitions` errors once none are left
+    let depth = (0..max_depth).map(|_| quote!(_));
+    let depth = quote!([#(#depth)*]);
+
+    // `#scalars` is replaced with every field that `find` queries fetch by default,
+    // so the list stays in sync with the schema
+    let scalars_arms = matches!(variant, Variant::Select).then(|| {
+        let hash = Punct::new('#', Spacing::Alone);
+        let scalars = quote!(#hash scalars);
+
+        let scalar_field_names = model
+            .scalar_fields()
+            .filter(|f| {
+                !matches!(
+                    f.scalar_field_type(),
+                    ScalarFieldType::Unsupported(_) | ScalarFieldType::CompositeType(_)
+                )
+            })
+            .map(|f| snake_ident(f.name()))
+            .collect::<Vec<_>>();
+
+        quote! {
+            ($(($($func_arg:ident: $func_arg_ty:ty),+) =>)? $module_name:ident { #scalars $($selections:tt)* }) => {
+                #model_module::select!($(($($func_arg: $func_arg_ty),+) =>)? $module_name { #(#scalar_field_names)* $($selections)* });
+            };
+            ({ #scalars $($selections:tt)* }) => {
+                #model_module::select!({ #(#scalar_field_names)* $($selections)* })
+            };
+            (@nested_definitions; $depth:tt; #scalars $($selections:tt)*) => {
+                #model_module::select!(@nested_definitions; $depth; #(#scalar_field_names)* $($selections)*);
+            };
+            (@selections_to_params; : $macro_name:ident { #scalars $($selections:tt)* }) => {
+                #model_module::select!(@selections_to_params; : $macro_name { #(#scalar_field_names)* $($selections)* })
+            };
+            (@nested_selections_to_params; : $macro_name:ident { #scalars $($selections:tt)* }) => {
+                #model_module::select!(@nested_selections_to_params; : $macro_name { #(#scalar_field_names)* $($selections)* })
+            };
+        }
+    });
+
+    let depth_error = format!(
+        "{variant}! selections can only be nested {max_depth} levels deep, \
+         increase the generator's max_include_depth to allow deeper nesting"
+    );
+
     quote! {
         #[macro_export]
         macro_rules! #macro_name {
+            #scalars_arms
             ($(($($func_arg:ident: $func_arg_ty:ty),+) =>)? $module_name:ident { $(#selection_pattern_produce)+ }) => {
                 #[allow(warnings)]
                 pub mod $module_name {
-                    #model_module::#variant_ident!(@definitions; $module_name; $(#selection_pattern_consume)+);
+                    #model_module::#variant_ident!(@definitions; $module_name; #depth; $(#selection_pattern_consume)+);
 
                     use super::*;
 
@@ -432,13 +696,19 @@ fn model_macro<'a>(
                 }
             };
             ({ $(#selection_pattern_produce)+ }) => {{
-                #model_module::#variant_ident!(@definitions; ; $(#selection_pattern_consume)+);
+                #model_module::#variant_ident!(@definitions; ; #depth; $(#selection_pattern_consume)+);
 
                 #selection_struct
 
                 #selection
             }};
-            (@definitions; $($module_name:ident)?; $(#selection_pattern_produce)+) => {
+            (@nested_definitions; []; $($tokens:tt)*) => {
+                compile_error!(#depth_error);
+            };
+            (@nested_definitions; [_ $($depth:tt)*]; $($tokens:tt)*) => {
+                #model_module::#variant_ident!(@definitions; ; [$($depth)*]; $($tokens)*);
+            };
+            (@definitions; $($module_name:ident)?; $depth:tt; $(#selection_pattern_produce)+) => {
                 #[allow(warnings)]
                 enum Fields {
                     #(#fields_enum_variants),*
@@ -471,11 +741,17 @@ fn model_macro<'a>(
                     }
                 }
 
+                #loaders_impl
+
                 $($(pub mod $field {
-                    #model_module::$selection_mode!(@field_module; $field #selections_pattern_consume);
+                    #model_module::$selection_mode!(@field_module; $depth; $field #selections_pattern_consume);
                 })?)+
             };
 
+            #loader_arms
+
+            #count_arms
+
             #(#field_type_impls)*
             (@field_type; $field:ident) => { #model_module::$field::Type };
             (@field_type; $field:ident $($tokens:tt)*) => { compile_error!(stringify!(Cannot include nonexistent relation $field on model #model_name_pascal_str, available relations are #all_fields_str)) };
@@ -484,11 +760,20 @@ fn model_macro<'a>(
             (@field_module; $($tokens:tt)*) => {};
 
             #(#selection_field_to_selection_param_impls)*
+            #(#computed_selection_param_impls)*
             (@selection_field_to_selection_param; $($tokens:tt)*) => { compile_error!(stringify!($($tokens)*)) }; // ::prisma_client_rust::Selection::builder("").build() };
 
+            #(#computed_nested_errors)*
+            (@nested_selection_field_to_selection_param; $macro_name:ident; $($tokens:tt)*) => {
+                $crate::#module_path #model_name_snake::$macro_name!(@selection_field_to_selection_param; $($tokens)*)
+            };
+
             (@selections_to_params; : $macro_name:ident {$(#selection_pattern_produce)+}) => {
                 [ $($crate::#module_path #model_name_snake::$macro_name!(@selection_field_to_selection_param; #selection_pattern_consume),)+]
             };
+            (@nested_selections_to_params; : $macro_name:ident {$(#selection_pattern_produce)+}) => {
+                [ $($crate::#module_path #model_name_snake::$macro_name!(@nested_selection_field_to_selection_param; $macro_name; #selection_pattern_consume),)+]
+            };
 
             (@filters_to_args;) => {
                 vec![]
@@ -499,6 +784,9 @@ fn model_macro<'a>(
 
             #(#field_serde_names)*
 
+            #(#field_value_arms)*
+            (@field_value; $map:ident; $field:ident) => { $map.next_value()? };
+
             #specta_macro_arms
         }
         pub use #macro_name as #variant_ident;
@@ -633,7 +921,11 @@ fn field_module_enum(field: FieldWalker, variant: Variant) -> Option<TokenStream
     })
 }
 
-fn model_module_enum(model: ModelWalker, variant: Variant) -> TokenStream {
+fn model_module_enum(
+    model: ModelWalker,
+    variant: Variant,
+    computed_fields: &[&str],
+) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
     let variant_pascal = pascal_ident(&variant.to_string());
@@ -653,23 +945,75 @@ fn model_module_enum(model: ModelWalker, variant: Variant) -> TokenStream {
         .filter(|f| !f.ast_field().field_type.as_unsupported().is_some())
         .map(|field| pascal_ident(field.name()));
 
+    let computed_variants = computed_fields.iter().map(|name| {
+        let field_name_snake = snake_ident(name);
+        let field_name_pascal = pascal_ident(name);
+
+        quote!(#field_name_pascal(#field_name_snake::#variant_pascal))
+    });
+
+    let computed_names_pascal = computed_fields.iter().map(|name| pascal_ident(name));
+
     let variant_param = variant.param();
 
+    let has_count = !counted_relations(model).is_empty();
+    let count_variant = has_count.then(|| quote!(RelationCount(_count::Selection)));
+    let count_arm = has_count.then(|| quote!(Self::RelationCount(data) => data.to_selection()));
+
     quote! {
         pub enum #variant_param {
-            #(#variants),*
+            #(#variants,)*
+            #(#computed_variants,)*
+            #count_variant
         }
 
         impl #variant_param {
             pub fn to_selection(self) -> #pcr::Selection {
                 match self {
-                    #(Self::#field_names_pascal(data) => data.to_selection()),*
+                    #(Self::#field_names_pascal(data) => data.to_selection(),)*
+                    #(Self::#computed_names_pascal(data) => data.to_selection(),)*
+                    #count_arm
                 }
             }
         }
     }
 }
 
+/// Shared by `select!` and `include!`, placed in the `_count` module alongside its `order`.
+fn count_selection(model: ModelWalker) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    (!counted_relations(model).is_empty()).then(|| {
+        quote! {
+            /// The names of the relations counted by a `_count` selection.
+            pub struct Selection(pub Vec<&'static str>);
+
+            impl Selection {
+                pub fn to_selection(self) -> #pcr::Selection {
+                    #pcr::Selection::new(
+                        NAME,
+                        None,
+                        [],
+                        self.0.into_iter().map(#pcr::sel).collect::<Vec<_>>()
+                    )
+                }
+            }
+
+            impl Into<super::SelectParam> for Selection {
+                fn into(self) -> super::SelectParam {
+                    super::SelectParam::RelationCount(self)
+                }
+            }
+
+            impl Into<super::IncludeParam> for Selection {
+                fn into(self) -> super::IncludeParam {
+                    super::IncludeParam::RelationCount(self)
+                }
+            }
+        }
+    })
+}
+
 pub mod include {
     use prisma_client_rust_sdk::prisma::prisma_models::walkers::{ModelWalker, RefinedFieldWalker};
 
@@ -677,9 +1021,15 @@ pub mod include {
 
     use super::*;
 
-    pub fn model_data(model: ModelWalker, module_path: &TokenStream) -> ModelModulePart {
+    pub fn model_data(
+        model: ModelWalker,
+        args: &GenerateArgs,
+        module_path: &TokenStream,
+        max_depth: usize,
+    ) -> ModelModulePart {
         let r#macro = super::model_macro(
             model,
+            args,
             module_path,
             Variant::Include,
             model
@@ -690,9 +1040,11 @@ pub mod include {
             model
                 .fields()
                 .filter(|f| matches!(f.refine(), RefinedFieldWalker::Relation(_))),
+            &[],
+            max_depth,
         );
 
-        let r#enum = super::model_module_enum(model, Variant::Include);
+        let r#enum = super::model_module_enum(model, Variant::Include, &[]);
 
         ModelModulePart {
             data: quote! {
@@ -718,23 +1070,36 @@ pub mod select {
 
     use super::*;
 
-    pub fn model_data(model: ModelWalker, module_path: &TokenStream) -> ModelModulePart {
+    pub fn model_data(
+        model: ModelWalker,
+        args: &GenerateArgs,
+        module_path: &TokenStream,
+        computed_fields: &computed::ComputedFields,
+        max_depth: usize,
+    ) -> ModelModulePart {
+        let computed_names = computed::names(model, computed_fields);
+
         let r#macro = super::model_macro(
             model,
+            args,
             module_path,
             Variant::Select,
             vec![].into_iter(),
             model
                 .fields()
                 .filter(|f| f.ast_field().field_type.as_unsupported().is_none()),
+            &computed_names,
+            max_depth,
         );
 
-        let r#enum = super::model_module_enum(model, Variant::Select);
+        let r#enum = super::model_module_enum(model, Variant::Select, &computed_names);
+        let split = computed::select_split(model, computed_fields);
 
         ModelModulePart {
             data: quote! {
                 #r#macro
                 #r#enum
+                #split
             },
             fields: model
                 .fields()
@@ -743,6 +1108,8 @@ pub mod select {
                     super::field_module_enum(field, Variant::Select)
                         .map(|e| (field.name().to_string(), e))
                 })
+                .chain(super::count_selection(model).map(|s| ("_count".to_string(), s)))
+                .chain(computed::field_modules(model, computed_fields))
                 .collect(),
         }
     }
@@ -0,0 +1,92 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::ModelWalker;
+
+use crate::prelude::*;
+
+use super::ModelModulePart;
+
+pub fn model_data(model: ModelWalker) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let field_name = |id| {
+        model
+            .scalar_fields()
+            .find(|f| f.field_id() == id)
+            .map(|f| f.name())
+    };
+
+    let primary_key = model.primary_key().map(|primary_key| {
+        (
+            quote!(PrimaryKey),
+            primary_key
+                .fields()
+                .filter_map(|f| field_name(f.field_id()))
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    let indexes = primary_key
+        .into_iter()
+        .chain(model.indexes().map(|index| {
+            let kind = if index.is_unique() {
+                quote!(Unique)
+            } else if index.is_fulltext() {
+                quote!(Fulltext)
+            } else {
+                quote!(Normal)
+            };
+
+            (
+                kind,
+                index
+                    .fields()
+                    .filter_map(|f| field_name(f.field_id()))
+                    .collect(),
+            )
+        }))
+        .collect::<Vec<_>>();
+
+    let scalar_fields = model
+        .scalar_fields()
+        .filter(|f| !f.scalar_field_type().is_unsupported())
+        .collect::<Vec<_>>();
+
+    let descriptors = indexes.iter().map(|(kind, fields)| {
+        quote! {
+            #pcr::IndexDescriptor {
+                kind: #pcr::IndexKind::#kind,
+                fields: &[#(#fields),*],
+            }
+        }
+    });
+
+    let scalar_field_names = scalar_fields.iter().map(|f| f.name());
+
+    ModelModulePart {
+        data: quote! {
+            /// The model's primary key and every index declared on it.
+            pub const INDEXES: &[#pcr::IndexDescriptor] = &[#(#descriptors),*];
+
+            /// The fields filtered on by `params` that no index starts with,
+            /// for warning about filters that may need a full table scan.
+            pub fn unindexed_filters(params: &[WhereParam]) -> Vec<&'static str> {
+                #pcr::unindexed_filters(INDEXES, &[#(#scalar_field_names),*], params)
+            }
+        },
+        fields: scalar_fields
+            .iter()
+            .map(|field| {
+                let indexed = indexes
+                    .iter()
+                    .any(|(_, fields)| fields.contains(&field.name()));
+
+                (
+                    field.name().to_string(),
+                    quote! {
+                        /// Whether the field is part of the model's primary key or any of its indexes.
+                        pub const INDEXED: bool = #indexed;
+                    },
+                )
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,53 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::ModelWalker;
+
+use crate::prelude::*;
+
+use super::create_input;
+
+/// Forwards the input of `#[derive(IntoPrismaCreate)]` to the `into_create!` proc macro,
+/// along with the fields that `create_unchecked` is built from.
+pub fn r#macro(
+    model: ModelWalker,
+    args: &GenerateArgs,
+    module_path: &TokenStream,
+) -> Option<TokenStream> {
+    let (required, optional) = create_input::fields(model, args)?;
+
+    let model_name_str = model.name();
+    let model_name_snake = snake_ident(model.name());
+    let model_name_snake_raw = snake_ident_raw(model.name());
+    let macro_name = format_ident!("_into_create_{model_name_snake_raw}");
+
+    let model_module = quote!($crate::#module_path #model_name_snake);
+
+    let required = required.iter().map(|field| {
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+
+        quote!(#field_name_snake: #field_name_str required)
+    });
+    let optional = optional.iter().map(|field| {
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+
+        match field.ast_field().arity.is_optional() {
+            true => quote!(#field_name_snake: #field_name_str nullable),
+            false => quote!(#field_name_snake: #field_name_str optional),
+        }
+    });
+
+    Some(quote! {
+        #[macro_export]
+        macro_rules! #macro_name {
+            ($($input:tt)+) => {
+                ::prisma_client_rust::macros::into_create! {
+                    #model_module #model_name_str
+                    [#(#required,)* #(#optional),*]
+                    $($input)+
+                }
+            };
+        }
+
+        pub use #macro_name as into_create;
+    })
+}
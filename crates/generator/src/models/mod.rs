@@ -1,13 +1,30 @@
 mod actions;
+mod change_feed;
+pub mod computed;
 mod create;
+mod create_input;
 mod data;
+pub mod display;
+pub mod field_codecs;
 mod filter;
+mod id_newtypes;
 mod include_select;
+mod indexes;
+mod into_create;
+mod model_actions;
+pub mod native_types;
+mod ord;
 mod order_by;
 mod pagination;
 mod partial_unchecked;
+mod primary_key;
+mod repository;
 mod set_params;
+pub mod type_overrides;
 mod types;
+pub mod union;
+mod update_input;
+pub mod version;
 mod where_params;
 mod with_params;
 
@@ -29,6 +46,15 @@ pub struct RequiredField<'a> {
 }
 
 pub fn required_fields<'a>(model: ModelWalker<'a>) -> Option<Vec<RequiredField<'a>>> {
+    required_fields_in(model, &quote!(super::))
+}
+
+/// Like [`required_fields`], but with types relative to a module where generated
+/// items are accessible at `prefix` instead of `super::`.
+pub fn required_fields_in<'a>(
+    model: ModelWalker<'a>,
+    prefix: &TokenStream,
+) -> Option<Vec<RequiredField<'a>>> {
     model
         .fields()
         .filter(|field| match field.refine() {
@@ -48,16 +74,23 @@ pub fn required_fields<'a>(model: ModelWalker<'a>) -> Option<Vec<RequiredField<'
 
                                 let type_snake = snake_ident(comp_type.name());
 
-                                quote!(super::#type_snake::Create)
+                                quote!(#prefix #type_snake::Create)
+                            }
+                            ScalarFieldType::Unsupported(_) => return None,
+                            // the field module's alias respects type overrides
+                            _ => {
+                                let model_name_snake = snake_ident(model.name());
+                                let field_name_snake = snake_ident(field.name());
+
+                                quote!(#prefix #model_name_snake::#field_name_snake::Type)
                             }
-                            _ => field.type_tokens(&quote!(super::))?,
                         }
                     }
                     RefinedFieldWalker::Relation(relation_field) => {
                         let relation_model_name_snake =
                             snake_ident(relation_field.related_model().name());
 
-                        quote!(super::#relation_model_name_snake::UniqueWhereParam)
+                        quote!(#prefix #relation_model_name_snake::UniqueWhereParam)
                     }
                 };
 
@@ -76,7 +109,26 @@ pub fn required_fields<'a>(model: ModelWalker<'a>) -> Option<Vec<RequiredField<'
         .collect()
 }
 
-pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
+/// The model's table name, quoted and qualified with its schema if it has one.
+pub fn qualified_db_name(model: ModelWalker) -> String {
+    let db_name = model.database_name();
+
+    match model.schema() {
+        Some((schema, _)) => format!("\"{schema}\".\"{db_name}\""),
+        None => format!("\"{db_name}\""),
+    }
+}
+
+pub fn modules(
+    args: &GenerateArgs,
+    module_path: &TokenStream,
+    union_models: &union::UnionModels,
+    version_fields: &version::VersionFields,
+    computed_fields: &computed::ComputedFields,
+    max_include_depth: usize,
+    generate_model_actions: bool,
+    generate_repositories: bool,
+) -> Vec<Module> {
     let pcr = quote!(::prisma_client_rust);
 
     args.schema
@@ -89,21 +141,49 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
             let actions_struct = actions::struct_definition(model, args);
 
             let (field_stuff, field_modules) = ModelModulePart::combine(vec![
-                data::model_data(model),
+                data::model_data(model, args),
                 where_params::model_data(model, args, module_path),
                 order_by::model_data(model, args),
-                with_params::model_data(model),
+                with_params::model_data(model, args),
                 set_params::model_data(model, args),
-                select::model_data(model, &module_path),
-                include::model_data(model, &module_path),
+                select::model_data(
+                    model,
+                    args,
+                    &module_path,
+                    computed_fields,
+                    max_include_depth,
+                ),
+                include::model_data(model, args, &module_path, max_include_depth),
+                change_feed::model_data(model, args),
+                indexes::model_data(model),
             ]);
 
             let create_types = create::types(model);
-            let types_struct = types::r#struct(model, module_path);
-            let data_struct = data::r#struct(model);
+            let create_input = create_input::r#struct(model, args);
+            let update_input = update_input::r#struct(model, args);
+            let types_struct = types::r#struct(model, args, module_path);
+            let data_struct = data::r#struct(model, args);
+            let id_newtype = id_newtypes::definition(args, model);
+            let display_impl = display::r#impl(model, args);
+            let ord_impl = ord::r#impl(model, args);
+            let primary_key_impl = primary_key::r#impl(model);
+            let union_enum = union::r#enum(model, args, union_models);
+            let update_if_version_fn = version::update_fn(model, version_fields);
+            let computed_module = computed::module(model, args, computed_fields);
+            let model_actions_impl = generate_model_actions.then(model_actions::r#impl);
+            let repository_trait = generate_repositories.then(|| repository::r#trait(model));
             let partial_unchecked_macro = partial_unchecked::r#macro(model, &module_path);
+            let into_create_macro = into_create::r#macro(model, args, &module_path);
             let filter_macro = filter::r#macro(model, module_path);
 
+            let schema = model.schema().map(|(schema, _)| schema);
+            let db_name = model.database_name();
+            let qualified_db_name = qualified_db_name(model);
+            let schema_tokens = match schema {
+                Some(schema) => quote!(Some(#schema)),
+                None => quote!(None),
+            };
+
             let mongo_raw_types = cfg!(feature = "mongodb").then(|| quote! {
 	            pub type FindRawQuery<'a, T: #pcr::Data> = #pcr::FindRaw<'a, Types, T>;
 	            pub type AggregateRawQuery<'a, T: #pcr::Data> = #pcr::AggregateRaw<'a, Types, T>;
@@ -115,18 +195,30 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
                     use super::_prisma::*;
 
                     pub const NAME: &str = #model_name;
+                    pub const DB_NAME: &str = #db_name;
+                    pub const SCHEMA: Option<&str> = #schema_tokens;
+                    pub const QUALIFIED_DB_NAME: &str = #qualified_db_name;
 
                     #filter_macro
                     #field_stuff
                     #create_types
+                    #create_input
+                    #update_input
                     #types_struct
                     #data_struct
+                    #id_newtype
+                    #display_impl
+                    #ord_impl
+                    #primary_key_impl
+                    #union_enum
                     #partial_unchecked_macro
+                    #into_create_macro
 
                     pub type UniqueArgs = #pcr::UniqueArgs<Types>;
                     pub type ManyArgs = #pcr::ManyArgs<Types>;
 
                     pub type CountQuery<'a> = #pcr::Count<'a, Types>;
+                    pub type CachedCountQuery<'a> = #pcr::CachedCount<'a, Types>;
                     pub type CreateQuery<'a> = #pcr::Create<'a, Types>;
                     pub type CreateUncheckedQuery<'a> = #pcr::CreateUnchecked<'a, Types>;
                     pub type CreateManyQuery<'a> = #pcr::CreateMany<'a, Types>;
@@ -143,6 +235,10 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
                     #mongo_raw_types
 
                     #actions_struct
+                    #update_if_version_fn
+                    #computed_module
+                    #model_actions_impl
+                    #repository_trait
                 },
             );
 
@@ -150,7 +246,10 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
                 .into_iter()
                 .for_each(|field| module.add_submodule(field));
 
-            module
+            match schema {
+                Some(schema) => module.with_namespace(schema),
+                None => module,
+            }
         })
         .collect()
 }
@@ -0,0 +1,49 @@
+use crate::prelude::*;
+
+pub fn r#impl() -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    quote! {
+        impl<'a> #pcr::ModelActions<'a> for Actions<'a> {
+            fn model_name(&self) -> &'static str {
+                NAME
+            }
+
+            fn find_many(
+                &self,
+                skip: Option<i64>,
+                take: Option<i64>,
+            ) -> #pcr::futures::future::BoxFuture<'a, #pcr::Result<Vec<#pcr::serde_json::Value>>> {
+                let mut query = FindManyQuery::new(self.client, vec![]);
+
+                if let Some(skip) = skip {
+                    query = query.skip(skip);
+                }
+
+                if let Some(take) = take {
+                    query = query.take(take);
+                }
+
+                Box::pin(async move {
+                    query
+                        .exec()
+                        .await?
+                        .into_iter()
+                        .map(|data| {
+                            #pcr::serde_json::to_value(data)
+                                .map_err(|e| #pcr::QueryError::Serialize(e.to_string()))
+                        })
+                        .collect()
+                })
+            }
+
+            fn count(&self) -> #pcr::futures::future::BoxFuture<'a, #pcr::Result<i64>> {
+                Box::pin(CountQuery::new(self.client, vec![]).exec())
+            }
+
+            fn delete_many(&self) -> #pcr::futures::future::BoxFuture<'a, #pcr::Result<i64>> {
+                Box::pin(DeleteManyQuery::new(self.client, vec![]).all().exec())
+            }
+        }
+    }
+}
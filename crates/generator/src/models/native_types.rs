@@ -0,0 +1,73 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ScalarFieldWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::prelude::*;
+
+/// The Rust type a field's `@db.*` attribute maps to when `native_types` is enabled,
+/// before being wrapped according to its arity.
+///
+/// Fields in a primary key or unique constraint keep their usual type,
+/// since unique filters and relations are built from it.
+fn native_type(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    if !args.shared_config.native_types || field.ast_field().arity.is_list() {
+        return None;
+    }
+
+    let model = field.model();
+
+    if args
+        .shared_config
+        .type_override(model.name(), field.name())
+        .is_some()
+    {
+        return None;
+    }
+
+    let id = field.field_id();
+    let in_primary_key = model
+        .primary_key()
+        .map(|pk| pk.fields().any(|f| f.field_id() == id))
+        .unwrap_or(false);
+    let in_unique = model
+        .indexes()
+        .any(|index| index.is_unique() && index.fields().any(|f| f.field_id() == id));
+
+    if in_primary_key || in_unique {
+        return None;
+    }
+
+    let (_, native_type, _, _) = field.raw_native_type()?;
+
+    let pcr = quote!(::prisma_client_rust);
+
+    Some(match (field.scalar_field_type(), native_type) {
+        (ScalarFieldType::BuiltInScalar(ScalarType::Int), "SmallInt" | "Int2") => quote!(i16),
+        // timestamps without a time zone are stored as UTC
+        (ScalarFieldType::BuiltInScalar(ScalarType::DateTime), "Timestamp") => {
+            quote!(#pcr::chrono::DateTime<#pcr::chrono::Utc>)
+        }
+        _ => return None,
+    })
+}
+
+/// The native type of `field`, wrapped according to its arity.
+pub fn field_type(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    native_type(args, field).map(|typ| field.ast_field().arity.wrap_type(&typ))
+}
+
+/// Converts `value`, which has the native type of `field`,
+/// into the type the engine's filters and writes take.
+pub fn into_original(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    value: &Ident,
+) -> Option<TokenStream> {
+    native_type(args, field)?;
+
+    Some(match field.ast_field().arity.is_optional() {
+        true => quote!(#value.map(Into::into)),
+        false => quote!(#value.into()),
+    })
+}
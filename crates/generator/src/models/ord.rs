@@ -0,0 +1,68 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ModelWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::prelude::*;
+
+/// Compares `Data` by its primary key when `ord_by_id` is enabled,
+/// for models whose primary key is a single field with a totally ordered type.
+pub fn r#impl(model: ModelWalker, args: &GenerateArgs) -> Option<TokenStream> {
+    if !args.shared_config.ord_by_id {
+        return None;
+    }
+
+    let primary_key = model.primary_key()?;
+    if primary_key.fields().count() != 1 {
+        return None;
+    }
+
+    let field = primary_key.fields().next()?;
+
+    if args
+        .shared_config
+        .type_override(model.name(), field.name())
+        .is_some()
+    {
+        return None;
+    }
+
+    // floats aren't totally ordered, and generated enums don't implement `Ord`
+    if !matches!(
+        field.scalar_field_type(),
+        ScalarFieldType::BuiltInScalar(
+            ScalarType::String
+                | ScalarType::Int
+                | ScalarType::BigInt
+                | ScalarType::Boolean
+                | ScalarType::DateTime
+                | ScalarType::Bytes
+        )
+    ) {
+        return None;
+    }
+
+    let field_name_snake = snake_ident(field.name());
+
+    Some(quote! {
+        impl PartialEq for Data {
+            fn eq(&self, other: &Self) -> bool {
+                self.#field_name_snake == other.#field_name_snake
+            }
+        }
+
+        impl Eq for Data {}
+
+        impl PartialOrd for Data {
+            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Data {
+            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+                self.#field_name_snake.cmp(&other.#field_name_snake)
+            }
+        }
+    })
+}
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use prisma_client_rust_sdk::prisma::{
     dmmf::TypeLocation,
     prisma_models::{walkers::ModelWalker, FieldArity},
+    psl::parser_database::{ScalarFieldType, ScalarType},
 };
 
 use crate::prelude::*;
@@ -18,8 +19,181 @@ pub fn fetch_builder_fn(model_name_snake: &Ident) -> TokenStream {
     }
 }
 
+/// An ordering the engine's schema doesn't describe, added to `OrderByWithRelationParam` by hand.
+struct ExtraOrder {
+    /// The field module `field_fn` goes in, or `None` for the model's own module.
+    field_name: Option<String>,
+    variant: TokenStream,
+    into_pv_arm: TokenStream,
+    field_fn: TokenStream,
+}
+
+/// Ordering by a path inside a JSON field, for PostgreSQL schemas only.
+/// The bundled engine rejects these orderings, so they're only generated with the `json-path-order` feature
+/// for custom engines that support them.
+fn json_path_orders(model: ModelWalker, args: &GenerateArgs) -> Vec<ExtraOrder> {
+    let pcr = quote!(::prisma_client_rust);
+
+    if !cfg!(feature = "json-path-order")
+        || args.connector.name() != psl::builtin_connectors::POSTGRES.name()
+    {
+        return vec![];
+    }
+
+    model
+        .scalar_fields()
+        .filter(|field| {
+            !field.ast_field().arity.is_list()
+                && matches!(
+                    field.scalar_field_type(),
+                    ScalarFieldType::BuiltInScalar(ScalarType::Json)
+                )
+        })
+        .map(|field| {
+            let field_name_str = field.name();
+            let variant_name = format_ident!("{}Path", pascal_ident(field_name_str));
+
+            ExtraOrder {
+                field_name: Some(field_name_str.to_string()),
+                variant: quote!(#variant_name(Vec<String>, super::SortOrder)),
+                into_pv_arm: quote! {
+                    Self::#variant_name(path, direction) => (
+                        #field_name_str,
+                        #pcr::PrismaValue::Object(vec![
+                            (
+                                "path".to_string(),
+                                #pcr::PrismaValue::List(
+                                    path.into_iter().map(#pcr::PrismaValue::String).collect()
+                                )
+                            ),
+                            ("sort".to_string(), direction.into())
+                        ])
+                    )
+                },
+                field_fn: quote! {
+                    /// Orders by the value at `path` inside this field.
+                    /// Not supported by the bundled query engine, see `QueryError::UnsupportedJsonPathOrder`.
+                    pub fn order_by_path(path: Vec<String>, direction: SortOrder) -> super::OrderByWithRelationParam {
+                        super::OrderByWithRelationParam::#variant_name(path, direction)
+                    }
+                },
+            }
+        })
+        .collect()
+}
+
+/// Ordering a string field by a collation, for the providers that let columns be sorted by one.
+/// The bundled engine rejects these orderings too, so they need the `collated-order` feature.
+fn collated_orders(model: ModelWalker, args: &GenerateArgs) -> Vec<ExtraOrder> {
+    let pcr = quote!(::prisma_client_rust);
+
+    if !cfg!(feature = "collated-order")
+        || ![
+            psl::builtin_connectors::POSTGRES.name(),
+            psl::builtin_connectors::COCKROACH.name(),
+            psl::builtin_connectors::MYSQL.name(),
+            psl::builtin_connectors::MSSQL.name(),
+            psl::builtin_connectors::SQLITE.name(),
+        ]
+        .contains(&args.connector.name())
+    {
+        return vec![];
+    }
+
+    model
+        .scalar_fields()
+        .filter(|field| {
+            !field.ast_field().arity.is_list()
+                && matches!(
+                    field.scalar_field_type(),
+                    ScalarFieldType::BuiltInScalar(ScalarType::String)
+                )
+        })
+        .map(|field| {
+            let field_name_str = field.name();
+            let variant_name = format_ident!("{}Collated", pascal_ident(field_name_str));
+
+            ExtraOrder {
+                field_name: Some(field_name_str.to_string()),
+                variant: quote!(#variant_name(super::SortOrder, String)),
+                into_pv_arm: quote! {
+                    Self::#variant_name(direction, collation) => (
+                        #field_name_str,
+                        #pcr::PrismaValue::Object(vec![
+                            ("sort".to_string(), direction.into()),
+                            ("collation".to_string(), #pcr::PrismaValue::String(collation))
+                        ])
+                    )
+                },
+                field_fn: quote! {
+                    /// Orders by this field compared using the database's `collation`, eg. `sv-SE-x-icu` on PostgreSQL.
+                    /// Not supported by the bundled query engine, see `QueryError::UnsupportedCollatedOrder`.
+                    pub fn order_by_collated(direction: SortOrder, collation: impl Into<String>) -> super::OrderByWithRelationParam {
+                        super::OrderByWithRelationParam::#variant_name(direction, collation.into())
+                    }
+                },
+            }
+        })
+        .collect()
+}
+
+/// Ordering by how well the model's text fields match a full text search,
+/// which the engine only describes for providers that support `fullTextSearch` once the preview feature is enabled.
+fn relevance_order(model: ModelWalker, args: &GenerateArgs) -> Option<ExtraOrder> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let input_type = args
+        .dmmf
+        .schema
+        .find_input_type(&format!("{}OrderByRelevanceInput", model.name()))?;
+
+    // only the model's text fields can be searched, which the engine lists in an enum
+    let fields_enum = input_type
+        .fields
+        .iter()
+        .find(|field| field.name == "fields")?
+        .input_types
+        .iter()
+        .find(|typ| matches!(typ.location, TypeLocation::EnumTypes))?;
+    let fields_enum = pascal_ident(&fields_enum.typ);
+
+    Some(ExtraOrder {
+        field_name: None,
+        variant: quote!(Relevance(Vec<RelevanceField>, String, super::SortOrder)),
+        into_pv_arm: quote! {
+            Self::Relevance(fields, search, direction) => (
+                "_relevance",
+                #pcr::PrismaValue::Object(vec![
+                    (
+                        "fields".to_string(),
+                        #pcr::PrismaValue::List(
+                            fields.into_iter().map(|f| #pcr::PrismaValue::Enum(f.to_string())).collect()
+                        )
+                    ),
+                    ("search".to_string(), #pcr::PrismaValue::String(search)),
+                    ("sort".to_string(), direction.into())
+                ])
+            )
+        },
+        field_fn: quote! {
+            /// The model's fields that `order_by_relevance` can rank by.
+            pub type RelevanceField = #fields_enum;
+
+            /// Orders by how well `fields` match the full text `search`, eg. `SortOrder::Desc` for the best matches first.
+            pub fn order_by_relevance(
+                fields: Vec<RelevanceField>,
+                search: impl Into<String>,
+                direction: SortOrder,
+            ) -> OrderByWithRelationParam {
+                OrderByWithRelationParam::Relevance(fields, search.into(), direction)
+            }
+        },
+    })
+}
+
 pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
     let pcr = quote!(::prisma_client_rust);
+    let non_exhaustive = non_exhaustive(args.shared_config);
 
     let (order_by_relation_aggregate_param, aggregate_field_stuff) = args
         .dmmf
@@ -86,6 +260,12 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
         })
         .unwrap_or_default();
 
+    let extra_orders = json_path_orders(model, args)
+        .into_iter()
+        .chain(collated_orders(model, args))
+        .chain(relevance_order(model, args))
+        .collect::<Vec<_>>();
+
     let (order_by_with_relation_param, relation_field_stuff) = args
         .dmmf
         .schema
@@ -146,9 +326,17 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                 })
                 .unzip();
 
+            let variants = variants
+                .into_iter()
+                .chain(extra_orders.iter().map(|o| o.variant.clone()));
+            let into_pv_arms = into_pv_arms
+                .into_iter()
+                .chain(extra_orders.iter().map(|o| o.into_pv_arm.clone()));
+
             (
                 quote! {
                     #[derive(Debug, Clone)]
+                    #non_exhaustive
                     pub enum OrderByWithRelationParam {
                        #(#variants),*
                     }
@@ -168,41 +356,55 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
         })
         .unwrap_or_default();
 
+    let mut fields: BTreeMap<String, TokenStream> = aggregate_field_stuff
+        .into_iter()
+        .chain(relation_field_stuff)
+        .fold(BTreeMap::new(), |mut acc, (name, data)| {
+            let entry = acc.entry(name.to_string()).or_insert_with(|| vec![]);
+            entry.push(data);
+            acc
+        })
+        .into_iter()
+        .map(|(name, data)| {
+            let Some(typ) = data
+                .iter()
+                .find_map(|(typ, _)| (typ.to_string() == data[0].0.to_string()).then_some(typ))
+            else {
+                panic!();
+            };
+
+            let impls = data.iter().map(|(_, impls)| impls);
+
+            (
+                name,
+                quote! {
+                    pub struct Order(#typ);
+
+                    pub fn order<T: From<Order>>(v: #typ) -> T {
+                        Order(v).into()
+                    }
+
+                    #(#impls)*
+                },
+            )
+        })
+        .collect();
+
+    let mut model_fns = vec![];
+
+    for order in extra_orders {
+        match order.field_name {
+            Some(field_name) => fields.entry(field_name).or_default().extend(order.field_fn),
+            None => model_fns.push(order.field_fn),
+        }
+    }
+
     ModelModulePart {
         data: quote! {
             #order_by_with_relation_param
             #order_by_relation_aggregate_param
+            #(#model_fns)*
         },
-        fields: aggregate_field_stuff
-            .into_iter()
-            .chain(relation_field_stuff)
-            .fold(BTreeMap::new(), |mut acc, (name, data)| {
-                let entry = acc.entry(name.to_string()).or_insert_with(|| vec![]);
-                entry.push(data);
-                acc
-            })
-            .into_iter()
-            .map(|(name, data)| {
-                let Some(typ) = data.iter()
-                	.find_map(|(typ, _)| (typ.to_string() == data[0].0.to_string()).then_some(typ)) else {
-                    panic!();
-                };
-
-                let impls = data.iter().map(|(_, impls)| impls);
-
-                (
-                    name,
-                    quote! {
-                        pub struct Order(#typ);
-
-                        pub fn order<T: From<Order>>(v: #typ) -> T {
-                            Order(v).into()
-                        }
-
-                        #(#impls)*
-                    },
-                )
-            })
-            .collect(),
+        fields,
     }
 }
@@ -0,0 +1,42 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::ModelWalker;
+
+use crate::prelude::*;
+
+/// An `Id` alias for the type of the model's primary key and an `id` accessor returning it,
+/// with compound primary keys as tuples of their fields in the order they're declared.
+pub fn r#impl(model: ModelWalker) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let primary_key = model.primary_key()?;
+
+    let field_names_snake = primary_key
+        .fields()
+        .map(|field| snake_ident(field.name()))
+        .collect::<Vec<_>>();
+
+    let (typ, value) = match field_names_snake.as_slice() {
+        [field] => (quote!(#field::Type), quote!(self.#field.clone())),
+        fields => (
+            quote!((#(#fields::Type),*)),
+            quote!((#(self.#fields.clone()),*)),
+        ),
+    };
+
+    Some(quote! {
+        pub type Id = #typ;
+
+        impl Data {
+            pub fn id(&self) -> Id {
+                #value
+            }
+        }
+
+        impl #pcr::HasId for Data {
+            type Id = Id;
+
+            fn id(&self) -> Id {
+                Data::id(self)
+            }
+        }
+    })
+}
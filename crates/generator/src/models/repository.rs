@@ -0,0 +1,84 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::ModelWalker;
+
+use crate::prelude::*;
+
+use super::required_fields;
+
+/// A `Repository` trait with the model's common queries, implemented for the client.
+/// Services that take a `Repository` instead of the client can be given another implementation in tests.
+pub fn r#trait(model: ModelWalker) -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+    let model_name_snake = snake_ident(model.name());
+
+    let future =
+        |output: TokenStream| quote!(#pcr::futures::future::BoxFuture<'_, #pcr::Result<#output>>);
+
+    let data_future = future(quote!(Data));
+    let optional_data_future = future(quote!(Option<Data>));
+    let many_data_future = future(quote!(Vec<Data>));
+    let count_future = future(quote!(i64));
+
+    // models with unsupported required fields can't be created through the client
+    let has_create = required_fields(model).is_some();
+
+    let create_decl = has_create.then(|| {
+        quote! {
+            fn create(&self, create: Create) -> #data_future;
+        }
+    });
+
+    let create_impl = has_create.then(|| {
+        quote! {
+            fn create(&self, create: Create) -> #data_future {
+                Box::pin(create.to_query(self).exec())
+            }
+        }
+    });
+
+    quote! {
+        /// The common queries of this model, implemented for [`PrismaClient`].
+        pub trait Repository: Send + Sync {
+            fn find_unique(&self, _where: UniqueWhereParam) -> #optional_data_future;
+
+            fn find_first(&self, _where: Vec<WhereParam>) -> #optional_data_future;
+
+            fn find_many(&self, _where: Vec<WhereParam>) -> #many_data_future;
+
+            fn count(&self, _where: Vec<WhereParam>) -> #count_future;
+
+            #create_decl
+
+            fn update(&self, _where: UniqueWhereParam, _params: Vec<SetParam>) -> #data_future;
+
+            fn delete(&self, _where: UniqueWhereParam) -> #data_future;
+        }
+
+        impl Repository for PrismaClient {
+            fn find_unique(&self, _where: UniqueWhereParam) -> #optional_data_future {
+                Box::pin(self.#model_name_snake().find_unique(_where).exec())
+            }
+
+            fn find_first(&self, _where: Vec<WhereParam>) -> #optional_data_future {
+                Box::pin(self.#model_name_snake().find_first(_where).exec())
+            }
+
+            fn find_many(&self, _where: Vec<WhereParam>) -> #many_data_future {
+                Box::pin(self.#model_name_snake().find_many(_where).exec())
+            }
+
+            fn count(&self, _where: Vec<WhereParam>) -> #count_future {
+                Box::pin(self.#model_name_snake().count(_where).exec())
+            }
+
+            #create_impl
+
+            fn update(&self, _where: UniqueWhereParam, _params: Vec<SetParam>) -> #data_future {
+                Box::pin(self.#model_name_snake().update(_where, _params).exec())
+            }
+
+            fn delete(&self, _where: UniqueWhereParam) -> #data_future {
+                Box::pin(self.#model_name_snake().delete(_where).exec())
+            }
+        }
+    }
+}
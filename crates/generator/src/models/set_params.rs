@@ -10,7 +10,7 @@ use prisma_client_rust_sdk::prisma::{
 
 use crate::{prelude::*, write_params};
 
-use super::ModelModulePart;
+use super::{create, field_codecs, id_newtypes, native_types, type_overrides, ModelModulePart};
 
 pub struct RelationSetParamConfig {
     pub action: &'static str,
@@ -30,7 +30,7 @@ fn relation_field_set_params(field: RelationFieldWalker) -> Vec<RelationSetParam
     let arity = field.ast_field().arity;
 
     match arity.is_list() {
-        true => ["connect", "disconnect", "set"]
+        true => ["connect", "disconnect", "set", "delete"]
             .iter()
             .map(|action| RelationSetParamConfig {
                 action,
@@ -44,10 +44,12 @@ fn relation_field_set_params(field: RelationFieldWalker) -> Vec<RelationSetParam
             }];
 
             if arity.is_optional() {
-                params.push(RelationSetParamConfig {
-                    action: "disconnect",
-                    typ: RelationSetParamType::True,
-                });
+                params.extend(["disconnect", "delete"].iter().map(|action| {
+                    RelationSetParamConfig {
+                        action,
+                        typ: RelationSetParamType::True,
+                    }
+                }));
             }
 
             params
@@ -381,12 +383,69 @@ fn field_set_params(
 
                     let param_enum_path = quote!(_prisma::write_params::#param_enum);
 
+                    let value_ident = format_ident!("value");
+                    // overridden values are serialized once the param is built, where they can fail
+                    let overridden =
+                        type_overrides::into_original(args, scalar_field, &format_ident!("v"));
+                    let converted = id_newtypes::into_original(args, scalar_field, &value_ident)
+                        .or_else(|| native_types::into_original(args, scalar_field, &value_ident))
+                        .or_else(|| field_codecs::encode(args, scalar_field, &value_ident));
+                    let (set_type, set_fn) = match (&overridden, converted) {
+                        (Some(_), _) => (
+                            quote!(Type),
+                            quote! {
+                                pub fn set<T: From<Set>>(value: Type) -> T {
+                                    Set(value).into()
+                                }
+                            },
+                        ),
+                        (None, Some(converted)) => (
+                            field_type.clone(),
+                            quote! {
+                                pub fn set<T: From<Set>>(value: Type) -> T {
+                                    Set(#converted).into()
+                                }
+                            },
+                        ),
+                        (None, None) => (
+                            field_type.clone(),
+                            quote! {
+                                pub fn set<T: From<Set>>(value: #field_type) -> T {
+                                    Set(value).into()
+                                }
+                            },
+                        ),
+                    };
+                    let (set_param, update_param) = match overridden {
+                        Some(converted) => (
+                            quote!(#pcr::serde::Overridden::new(#converted, #param_enum_path::Set)),
+                            quote!(v.into()),
+                        ),
+                        None => (quote!(#param_enum_path::Set(v)), quote!(v)),
+                    };
+
                     let other_fns = write_param
 	                    .fields
 	                    .iter()
 	                    .flat_map(|field| {
 		                    if field.name == "set" { return None }
 
+		                    if field.name == "push" && arity.is_list() {
+			                    let element_type = scalar_field
+				                    .scalar_field_type()
+				                    .to_tokens(&quote!(), &FieldArity::Required, scalar_field.db)?;
+
+			                    return Some(quote! {
+				                    pub fn push<T: From<UpdateOperation>>(value: #element_type) -> T {
+					                    UpdateOperation(#param_enum_path::Push(vec![value])).into()
+				                    }
+
+				                    pub fn push_many<T: From<UpdateOperation>>(values: #field_type) -> T {
+					                    UpdateOperation(#param_enum_path::Push(values)).into()
+				                    }
+			                    });
+		                    }
+
 		                    let method_name_snake = snake_ident(&field.name);
 		                    let method_name_pascal = pascal_ident(&field.name);
 
@@ -400,9 +459,27 @@ fn field_set_params(
 	                    })
 	                    .collect::<TokenStream>();
 
-                    variants.push(
-                        quote!(#field_name_pascal(super::_prisma::write_params::#param_enum)),
+                    let json_null_fns = (write_param.name == "JsonNullable").then(|| {
+                        quote! {
+                            /// Sets the field to the JSON value `null`, rather than the database's `NULL` like `set(None)`.
+                            pub fn set_json_null<T: From<UpdateOperation>>() -> T {
+                                UpdateOperation(#param_enum_path::JsonNull).into()
+                            }
+
+                            /// Sets the field to the database's `NULL`, the same as `set(None)`.
+                            pub fn set_db_null<T: From<UpdateOperation>>() -> T {
+                                UpdateOperation(#param_enum_path::DbNull).into()
+                            }
+                        }
+                    });
+
+                    let param_type = type_overrides::param_type(
+                        args,
+                        scalar_field,
+                        quote!(super::_prisma::write_params::#param_enum),
                     );
+
+                    variants.push(quote!(#field_name_pascal(#param_type)));
                     functions.push(quote! {
                         Self::#field_name_pascal(value) => (
                             #field_name_snake::NAME,
@@ -413,27 +490,27 @@ fn field_set_params(
                     (
                         field.name().to_string(),
                         quote! {
-                            pub struct Set(pub #field_type);
+                            pub struct Set(pub #set_type);
 
                             impl From<Set> for SetParam {
                                 fn from(Set(v): Set) -> Self {
-                                    Self::#field_name_pascal(#param_enum_path::Set(v))
+                                    Self::#field_name_pascal(#set_param)
                                 }
                             }
 
-                            pub fn set<T: From<Set>>(value: #field_type) -> T {
-                                Set(value).into()
-                            }
+                            #set_fn
 
                             pub struct UpdateOperation(pub #param_enum_path);
 
                             impl From<UpdateOperation> for SetParam {
                                 fn from(UpdateOperation(v): UpdateOperation) -> Self {
-                                    Self::#field_name_pascal(v)
+                                    Self::#field_name_pascal(#update_param)
                                 }
                             }
 
                             #other_fns
+
+                            #json_null_fns
                         },
                     )
                 } else {
@@ -514,10 +591,261 @@ fn field_set_params(
 
             let relation_model_name_snake = snake_ident(relation_field.related_model().name());
 
+            let create_variant = format_ident!("Create{field_name_pascal}");
+            let connect_or_create_variant = format_ident!("ConnectOrCreate{field_name_pascal}");
+
+            let serialize_where = quote! {
+                .map(#pcr::WhereInput::serialize)
+                .map(#pcr::SerializedWhereInput::transform_equals)
+                .collect()
+            };
+
+            // `where` and `create` of a single connectOrCreate, with `where_param` and `params` in scope
+            let connect_or_create_value = quote! {
+                #pcr::PrismaValue::Object(vec![
+                    (
+                        "where".to_string(),
+                        #pcr::PrismaValue::Object(
+                            [where_param]
+                                .into_iter()
+                                .map(Into::<super::#relation_model_name_snake::WhereParam>::into)
+                                #serialize_where
+                        )
+                    ),
+                    (
+                        "create".to_string(),
+                        #pcr::PrismaValue::Object(
+                            params.into_iter().map(Into::into).collect()
+                        )
+                    )
+                ])
+            };
+
+            let nested_create = create::nested_create(relation_field).map(|create_struct| {
+                let (variant, arm, create_fn) = match arity {
+                    FieldArity::List => (
+                        quote!(#create_variant(Vec<Vec<super::#relation_model_name_snake::SetParam>>)),
+                        quote! {
+                            Self::#create_variant(creates) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "create".to_string(),
+                                    #pcr::PrismaValue::List(
+                                        creates
+                                            .into_iter()
+                                            .map(|params| #pcr::PrismaValue::Object(
+                                                params.into_iter().map(Into::into).collect()
+                                            ))
+                                            .collect()
+                                    )
+                                )])
+                            )
+                        },
+                        quote! {
+                            pub fn create(creates: Vec<Create>) -> SetParam {
+                                SetParam::#create_variant(
+                                    creates.into_iter().map(Create::to_params).collect()
+                                )
+                            }
+                        },
+                    ),
+                    _ => (
+                        quote!(#create_variant(Vec<super::#relation_model_name_snake::SetParam>)),
+                        quote! {
+                            Self::#create_variant(params) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "create".to_string(),
+                                    #pcr::PrismaValue::Object(
+                                        params.into_iter().map(Into::into).collect()
+                                    )
+                                )])
+                            )
+                        },
+                        quote! {
+                            pub fn create(create: Create) -> SetParam {
+                                SetParam::#create_variant(create.to_params())
+                            }
+                        },
+                    ),
+                };
+
+                variants.push(variant);
+                functions.push(arm);
+
+                let (variant, arm, connect_or_create_fn) = match arity {
+                    FieldArity::List => (
+                        quote!(#connect_or_create_variant(Vec<(
+                            super::#relation_model_name_snake::UniqueWhereParam,
+                            Vec<super::#relation_model_name_snake::SetParam>
+                        )>)),
+                        quote! {
+                            Self::#connect_or_create_variant(items) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "connectOrCreate".to_string(),
+                                    #pcr::PrismaValue::List(
+                                        items
+                                            .into_iter()
+                                            .map(|(where_param, params)| #connect_or_create_value)
+                                            .collect()
+                                    )
+                                )])
+                            )
+                        },
+                        quote! {
+                            /// Connects the record matching each unique filter, or creates it if there isn't one.
+                            /// Compound unique filters such as `title_author_id` can be used like any other.
+                            pub fn connect_or_create(
+                                items: Vec<(#relation_model_name_snake::UniqueWhereParam, Create)>
+                            ) -> SetParam {
+                                SetParam::#connect_or_create_variant(
+                                    items
+                                        .into_iter()
+                                        .map(|(where_param, create)| (where_param, create.to_params()))
+                                        .collect()
+                                )
+                            }
+                        },
+                    ),
+                    _ => (
+                        quote!(#connect_or_create_variant(
+                            super::#relation_model_name_snake::UniqueWhereParam,
+                            Vec<super::#relation_model_name_snake::SetParam>
+                        )),
+                        quote! {
+                            Self::#connect_or_create_variant(where_param, params) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "connectOrCreate".to_string(),
+                                    #connect_or_create_value
+                                )])
+                            )
+                        },
+                        quote! {
+                            /// Connects the record matching `where_param`, or creates it if there isn't one.
+                            pub fn connect_or_create(
+                                where_param: #relation_model_name_snake::UniqueWhereParam,
+                                create: Create
+                            ) -> SetParam {
+                                SetParam::#connect_or_create_variant(where_param, create.to_params())
+                            }
+                        },
+                    ),
+                };
+
+                variants.push(variant);
+                functions.push(arm);
+
+                quote! {
+                    #create_struct
+                    #create_fn
+                    #connect_or_create_fn
+                }
+            });
+
             let connect_variant = format_ident!("Connect{field_name_pascal}");
             let disconnect_variant = format_ident!("Disconnect{field_name_pascal}");
             let set_variant = format_ident!("Set{field_name_pascal}");
+            let delete_variant = format_ident!("Delete{field_name_pascal}");
+            let update_variant = format_ident!("Update{field_name_pascal}");
+            let update_many_variant = format_ident!("UpdateMany{field_name_pascal}");
+            let delete_many_variant = format_ident!("DeleteMany{field_name_pascal}");
             let is_null_variant = format_ident!("{field_name_pascal}IsNull");
+            let is_not_null_variant = format_ident!("{field_name_pascal}IsNotNull");
+
+            match arity {
+                FieldArity::List => {
+                    variants.extend([
+                        quote!(#update_variant(Vec<(
+                            super::#relation_model_name_snake::UniqueWhereParam,
+                            Vec<super::#relation_model_name_snake::SetParam>
+                        )>)),
+                        quote!(#update_many_variant(
+                            Vec<super::#relation_model_name_snake::WhereParam>,
+                            Vec<super::#relation_model_name_snake::SetParam>
+                        )),
+                        quote!(#delete_many_variant(Vec<super::#relation_model_name_snake::WhereParam>)),
+                    ]);
+                    functions.extend([
+                        quote! {
+                            Self::#update_variant(updates) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "update".to_string(),
+                                    #pcr::PrismaValue::List(
+                                        updates
+                                            .into_iter()
+                                            .map(|(where_param, data)| #pcr::PrismaValue::Object(vec![
+                                                (
+                                                    "where".to_string(),
+                                                    #pcr::PrismaValue::Object(
+                                                        [where_param]
+                                                            .into_iter()
+                                                            .map(Into::<super::#relation_model_name_snake::WhereParam>::into)
+                                                            #serialize_where
+                                                    )
+                                                ),
+                                                (
+                                                    "data".to_string(),
+                                                    #pcr::PrismaValue::Object(
+                                                        data.into_iter().map(Into::into).collect()
+                                                    )
+                                                )
+                                            ]))
+                                            .collect()
+                                    )
+                                )])
+                            )
+                        },
+                        quote! {
+                            Self::#update_many_variant(_where, data) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "updateMany".to_string(),
+                                    #pcr::PrismaValue::Object(vec![
+                                        (
+                                            "where".to_string(),
+                                            #pcr::PrismaValue::Object(_where.into_iter() #serialize_where)
+                                        ),
+                                        (
+                                            "data".to_string(),
+                                            #pcr::PrismaValue::Object(
+                                                data.into_iter().map(Into::into).collect()
+                                            )
+                                        )
+                                    ])
+                                )])
+                            )
+                        },
+                        quote! {
+                            Self::#delete_many_variant(_where) => (
+                                #field_name_snake::NAME,
+                                #pcr::PrismaValue::Object(vec![(
+                                    "deleteMany".to_string(),
+                                    #pcr::PrismaValue::Object(_where.into_iter() #serialize_where)
+                                )])
+                            )
+                        },
+                    ]);
+                }
+                _ => {
+                    variants.push(
+                        quote!(#update_variant(Vec<super::#relation_model_name_snake::SetParam>)),
+                    );
+                    functions.push(quote! {
+                        Self::#update_variant(data) => (
+                            #field_name_snake::NAME,
+                            #pcr::PrismaValue::Object(vec![(
+                                "update".to_string(),
+                                #pcr::PrismaValue::Object(
+                                    data.into_iter().map(Into::into).collect()
+                                )
+                            )])
+                        )
+                    });
+                }
+            }
 
             let base = match arity {
                 FieldArity::List => {
@@ -541,6 +869,27 @@ fn field_set_params(
                         pub fn set(params: Vec<#relation_model_name_snake::UniqueWhereParam>) -> SetParam {
                             SetParam::#set_variant(params)
                         }
+
+                        pub fn delete(params: Vec<#relation_model_name_snake::UniqueWhereParam>) -> SetParam {
+                            SetParam::#delete_variant(params)
+                        }
+
+                        pub fn update(
+                            updates: Vec<(#relation_model_name_snake::UniqueWhereParam, Vec<#relation_model_name_snake::SetParam>)>
+                        ) -> SetParam {
+                            SetParam::#update_variant(updates)
+                        }
+
+                        pub fn update_many(
+                            _where: Vec<#relation_model_name_snake::WhereParam>,
+                            data: Vec<#relation_model_name_snake::SetParam>
+                        ) -> SetParam {
+                            SetParam::#update_many_variant(_where, data)
+                        }
+
+                        pub fn delete_many(_where: Vec<#relation_model_name_snake::WhereParam>) -> SetParam {
+                            SetParam::#delete_many_variant(_where)
+                        }
                     }
                 }
                 _ => {
@@ -550,9 +899,19 @@ fn field_set_params(
                                 SetParam::#disconnect_variant
                             }
 
+                            pub fn delete() -> SetParam {
+                                SetParam::#delete_variant
+                            }
+
+                            /// Records without a related record.
                             pub fn is_null() -> WhereParam {
                                 WhereParam::#is_null_variant
                             }
+
+                            /// Records with a related record.
+                            pub fn is_not_null() -> WhereParam {
+                                WhereParam::#is_not_null_variant
+                            }
                         }
                     });
 
@@ -569,6 +928,10 @@ fn field_set_params(
                             Connect(value).into()
                         }
 
+                        pub fn update(data: Vec<#relation_model_name_snake::SetParam>) -> SetParam {
+                            SetParam::#update_variant(data)
+                        }
+
                         #optional_fns
                     }
                 }
@@ -577,7 +940,13 @@ fn field_set_params(
             variants.extend(v);
             functions.extend(f);
 
-            (field.name().to_string(), base)
+            (
+                field.name().to_string(),
+                quote! {
+                    #base
+                    #nested_create
+                },
+            )
         }
     };
 
@@ -585,6 +954,8 @@ fn field_set_params(
 }
 
 pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
+    let non_exhaustive = non_exhaustive(args.shared_config);
+
     let (variants, into_pv_arms, field_stuff) =
         model.fields().flat_map(|f| field_set_params(f, args)).fold(
             (vec![], vec![], BTreeMap::new()),
@@ -654,10 +1025,24 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                     _ => args.write_param(field).map(|write_param| {
                         let param_enum = write_params::enum_name(write_param);
                         let param_enum_path = quote!(_prisma::write_params::#param_enum);
+                        let param_type = type_overrides::param_type(
+                            args,
+                            field,
+                            quote!(super::_prisma::write_params::#param_enum),
+                        );
+
+                        let (set_param, update_param) =
+                            match type_overrides::into_original(args, field, &format_ident!("v")) {
+                                Some(converted) => (
+                                    quote!(#pcr::serde::Overridden::new(#converted, #param_enum_path::Set)),
+                                    quote!(v.into()),
+                                ),
+                                None => (quote!(#param_enum_path::Set(v)), quote!(v)),
+                            };
 
                         (
                             (
-	                            quote!(#field_name_pascal(super::_prisma::write_params::#param_enum)),
+	                            quote!(#field_name_pascal(#param_type)),
 	                            quote! {
 	                                Self::#field_name_pascal(value) => (
 	                                    #field_name_str,
@@ -669,13 +1054,13 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                                 quote! {
                                     impl From<Set> for UncheckedSetParam {
                                         fn from(Set(v): Set) -> Self {
-                                            Self::#field_name_pascal(#param_enum_path::Set(v))
+                                            Self::#field_name_pascal(#set_param)
                                         }
                                     }
 
                                     impl From<UpdateOperation> for UncheckedSetParam {
                                         fn from(UpdateOperation(v): UpdateOperation) -> Self {
-                                            Self::#field_name_pascal(v)
+                                            Self::#field_name_pascal(#update_param)
                                         }
                                     }
                                 },
@@ -689,6 +1074,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
         (
             quote! {
                 #[derive(Debug, Clone)]
+                #non_exhaustive
                 pub enum UncheckedSetParam {
                       #(#variants),*
                 }
@@ -710,6 +1096,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
     ModelModulePart {
         data: quote! {
             #[derive(Debug, Clone)]
+            #non_exhaustive
             pub enum SetParam {
                 #(#variants),*
             }
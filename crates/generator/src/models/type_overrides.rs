@@ -0,0 +1,143 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ScalarFieldWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::{prelude::*, Error};
+
+/// Checks that each `type_overrides` entry points at a field that can be overridden
+/// and that its type is a plain path like `crate::Settings`.
+pub fn validate(args: &GenerateArgs) -> Result<(), Error> {
+    for parsed in args.shared_config.parsed_type_overrides() {
+        let (model_name, field_name, typ) = parsed.map_err(|entry| {
+            Error::InvalidTypeOverride(
+                entry.to_string(),
+                "expected the form `Model.field=path::to::Type`".to_string(),
+            )
+        })?;
+
+        let invalid = |reason: String| {
+            Error::InvalidTypeOverride(format!("{model_name}.{field_name}={typ}"), reason)
+        };
+
+        let model = args
+            .schema
+            .db
+            .walk_models()
+            .find(|m| m.name() == model_name)
+            .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+        let field = model
+            .scalar_fields()
+            .find(|f| f.name() == field_name)
+            .ok_or_else(|| invalid(format!("scalar field '{field_name}' not found")))?;
+
+        if !matches!(
+            field.scalar_field_type(),
+            ScalarFieldType::Unsupported(_)
+                | ScalarFieldType::BuiltInScalar(ScalarType::Json | ScalarType::String)
+        ) {
+            return Err(invalid(
+                "only Json, String and Unsupported fields can be overridden".to_string(),
+            ));
+        }
+
+        if field.ast_field().arity.is_list() {
+            return Err(invalid("list fields can't be overridden".to_string()));
+        }
+
+        let id = field.field_id();
+        let in_primary_key = model
+            .primary_key()
+            .map(|pk| pk.fields().any(|f| f.field_id() == id))
+            .unwrap_or(false);
+        let in_unique = model
+            .indexes()
+            .any(|index| index.is_unique() && index.fields().any(|f| f.field_id() == id));
+
+        if in_primary_key || in_unique {
+            return Err(invalid(
+                "fields that are part of a primary key or unique constraint can't be overridden"
+                    .to_string(),
+            ));
+        }
+
+        syn::parse_str::<syn::TypePath>(typ)
+            .map_err(|_| invalid(format!("'{typ}' is not a type path")))?;
+    }
+
+    Ok(())
+}
+
+/// The overridden type of `field`, wrapped according to its arity.
+pub fn field_type(args: &GenerateArgs, field: ScalarFieldWalker) -> Option<TokenStream> {
+    let typ: TokenStream = args
+        .shared_config
+        .type_override(field.model().name(), field.name())?
+        .parse()
+        .ok()?;
+
+    Some(field.ast_field().arity.wrap_type(&typ))
+}
+
+/// Converts `value`, which has the overridden type of `field`,
+/// into a `Result` of the type the field's params would otherwise take.
+pub fn into_original(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    value: &Ident,
+) -> Option<TokenStream> {
+    args.shared_config
+        .type_override(field.model().name(), field.name())?;
+
+    let pcr = quote!(::prisma_client_rust);
+
+    let converted = match field.scalar_field_type() {
+        ScalarFieldType::BuiltInScalar(ScalarType::Json) => {
+            quote!(#pcr::serde::to_json_value(#value))
+        }
+        ScalarFieldType::BuiltInScalar(ScalarType::String) => {
+            quote!(#pcr::serde::to_string_value(#value))
+        }
+        _ => return None,
+    };
+
+    Some(match field.ast_field().arity.is_optional() {
+        true => quote!(#value.map(|#value| #converted).transpose()),
+        false => converted,
+    })
+}
+
+/// The `Overridden` wrapper of `param`, the type held by `SetParam`s and `WhereParam`s of `field`
+/// if its type has been overridden.
+pub fn param_type(
+    args: &GenerateArgs,
+    field: ScalarFieldWalker,
+    param: TokenStream,
+) -> TokenStream {
+    match args
+        .shared_config
+        .type_override(field.model().name(), field.name())
+    {
+        Some(_) => quote!(::prisma_client_rust::serde::Overridden<#param>),
+        None => param,
+    }
+}
+
+/// Unsupported fields can't be read or written by the query engine,
+/// so they're left out of everything except their field module.
+pub fn unsupported_warnings(args: &GenerateArgs) -> Vec<String> {
+    args.schema
+        .db
+        .walk_models()
+        .flat_map(|model| model.scalar_fields())
+        .filter_map(|field| match field.scalar_field_type() {
+            ScalarFieldType::Unsupported(_) if field_type(args, field).is_none() => Some(format!(
+                "Field '{}.{}' has an Unsupported type and will only be accessible with raw queries",
+                field.model().name(),
+                field.name()
+            )),
+            _ => None,
+        })
+        .collect()
+}
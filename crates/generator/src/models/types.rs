@@ -34,12 +34,49 @@ fn scalar_selections_fn(model: ModelWalker, module_path: &TokenStream) -> TokenS
     }
 }
 
-pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
+pub fn r#struct(model: ModelWalker, args: &GenerateArgs, module_path: &TokenStream) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
     let scalar_selections_fn = scalar_selections_fn(model, module_path);
 
+    let extended_where_unique = args
+        .has_preview_feature("extendedWhereUnique")
+        .then(|| quote!(impl #pcr::ExtendedWhereUnique for Types {}));
+
+    let primary_key_filter = model.primary_key().map(|primary_key| {
+        let mut fields = primary_key.fields();
+
+        let filter = match (fields.next(), fields.next()) {
+            // one `in` filter rather than a condition per record, except for
+            // overridden types which only have an equals filter
+            (Some(field), None)
+                if args
+                    .shared_config
+                    .type_override(model.name(), field.name())
+                    .is_none() =>
+            {
+                let field_name_snake = snake_ident(field.name());
+
+                quote!(#field_name_snake::in_vec(records.iter().map(|record| record.#field_name_snake.clone()).collect()))
+            }
+            _ => quote!(WhereParam::Or(records.iter().map(|record| record.to_unique().into_filter()).collect())),
+        };
+
+        quote! {
+            impl #pcr::PrimaryKeyFilter for Types {
+                fn primary_key_filter(records: &[Data]) -> WhereParam {
+                    #filter
+                }
+            }
+        }
+    });
+
+    let scalar_field_enum = pascal_ident(&format!("{}ScalarFieldEnum", model.name()));
+
     quote! {
+        /// The model's scalar fields, as passed to `distinct`.
+        pub type ScalarField = #scalar_field_enum;
+
         #[derive(Debug, Clone)]
         pub struct Types;
 
@@ -51,10 +88,14 @@ pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
             type With = WithParam;
             type OrderBy = OrderByWithRelationParam;
             type Cursor = UniqueWhereParam;
+            type ScalarField = ScalarField;
 
             const MODEL: &'static str = NAME;
 
             #scalar_selections_fn
         }
+
+        #extended_where_unique
+        #primary_key_filter
     }
 }
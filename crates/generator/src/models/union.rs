@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::{
+        walkers::{ModelWalker, RefinedFieldWalker, ScalarFieldWalker},
+        FieldArity,
+    },
+    psl::parser_database::ScalarFieldType,
+};
+
+use crate::{prelude::*, Error};
+
+use super::id_newtypes;
+
+/// Models listed in the `union_models` config, mapped to their discriminator field.
+pub type UnionModels = BTreeMap<String, String>;
+
+pub fn parse_config(args: &GenerateArgs, entries: &[String]) -> Result<UnionModels, Error> {
+    entries
+        .iter()
+        .map(|entry| {
+            let invalid = |reason: String| Error::InvalidUnionModel(entry.clone(), reason);
+
+            let (model_name, discriminator) = entry
+                .split_once('.')
+                .ok_or_else(|| invalid("expected the form `Model.field`".to_string()))?;
+
+            let model = args
+                .schema
+                .db
+                .walk_models()
+                .find(|m| m.name() == model_name)
+                .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+            let values = discriminator_field(model, discriminator)
+                .map(discriminator_values)
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "discriminator '{discriminator}' must be a non-list enum field"
+                    ))
+                })?;
+
+            for field in model.fields() {
+                for variant in field_variants(args, model, field.name()) {
+                    if !values.contains(&variant) {
+                        return Err(invalid(format!(
+                            "field '{}' references unknown variant '{variant}'",
+                            field.name()
+                        )));
+                    }
+                }
+            }
+
+            Ok((model_name.to_string(), discriminator.to_string()))
+        })
+        .collect()
+}
+
+fn discriminator_field<'a>(model: ModelWalker<'a>, name: &str) -> Option<ScalarFieldWalker<'a>> {
+    model
+        .scalar_fields()
+        .find(|f| f.name() == name)
+        .filter(|f| !f.ast_field().arity.is_list())
+        .filter(|f| matches!(f.scalar_field_type(), ScalarFieldType::Enum(_)))
+}
+
+fn discriminator_values(field: ScalarFieldWalker) -> Vec<String> {
+    match field.scalar_field_type() {
+        ScalarFieldType::Enum(id) => field
+            .db
+            .walk(id)
+            .values()
+            .map(|v| v.name().to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Variants a field is restricted to, declared with `/// @variant(A, B)` in the schema.
+/// Fields without this belong to every variant.
+fn field_variants(args: &GenerateArgs, model: ModelWalker, field_name: &str) -> Vec<String> {
+    args.dmmf
+        .data_model
+        .models
+        .iter()
+        .find(|m| m.name == model.name())
+        .and_then(|m| m.fields.iter().find(|f| f.name == field_name))
+        .and_then(|f| f.documentation.as_deref())
+        .and_then(|docs| {
+            docs.lines().find_map(|line| {
+                let variants = line.trim().strip_prefix("@variant(")?.strip_suffix(')')?;
+
+                Some(variants.split(',').map(|v| v.trim().to_string()).collect())
+            })
+        })
+        .unwrap_or_default()
+}
+
+pub fn r#enum(
+    model: ModelWalker,
+    args: &GenerateArgs,
+    union_models: &UnionModels,
+) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let discriminator = union_models.get(model.name())?;
+    let discriminator_field = discriminator_field(model, discriminator)?;
+    let enum_id = match discriminator_field.scalar_field_type() {
+        ScalarFieldType::Enum(id) => id,
+        _ => return None,
+    };
+
+    let enum_name_pascal = pascal_ident(discriminator_field.db.walk(enum_id).name());
+    let discriminator_snake = snake_ident(discriminator);
+
+    let fields = model
+        .fields()
+        .filter(|f| f.name() != discriminator)
+        .filter(|f| f.ast_field().field_type.as_unsupported().is_none())
+        .map(|f| (f, field_variants(args, model, f.name())))
+        .collect::<Vec<_>>();
+
+    let (variants, match_arms): (Vec<_>, Vec<_>) = discriminator_values(discriminator_field)
+        .iter()
+        .map(|variant_name_str| {
+            let variant_name_pascal = pascal_ident(variant_name_str);
+
+            let (definitions, initializers): (Vec<_>, Vec<_>) = fields
+                .iter()
+                .filter(|(_, variants)| variants.is_empty() || variants.contains(variant_name_str))
+                .flat_map(|(field, variants)| {
+                    let field_name_str = field.name();
+                    let field_name_snake = snake_ident(field_name_str);
+
+                    Some(match field.refine() {
+                        RefinedFieldWalker::Relation(_) => (
+                            quote!(#field_name_snake: Option<#field_name_snake::RecursiveSafeType>),
+                            quote!(#field_name_snake: data.#field_name_snake),
+                        ),
+                        RefinedFieldWalker::Scalar(scalar_field)
+                            if !variants.is_empty() && field.ast_field().arity.is_optional() =>
+                        {
+                            let typ = match id_newtypes::required_type(
+                                args,
+                                scalar_field,
+                                &quote!(super::),
+                            ) {
+                                Some(newtype) => newtype,
+                                None => scalar_field.scalar_field_type().to_tokens(
+                                    &quote!(super::),
+                                    &FieldArity::Required,
+                                    &field.db,
+                                )?,
+                            };
+
+                            (
+                                quote!(#field_name_snake: #typ),
+                                quote! {
+                                    #field_name_snake: data.#field_name_snake.ok_or(
+                                        #pcr::UnionConversionError::MissingField {
+                                            variant: #variant_name_str,
+                                            field: #field_name_str,
+                                        }
+                                    )?
+                                },
+                            )
+                        }
+                        RefinedFieldWalker::Scalar(_) => (
+                            quote!(#field_name_snake: #field_name_snake::Type),
+                            quote!(#field_name_snake: data.#field_name_snake),
+                        ),
+                    })
+                })
+                .unzip();
+
+            let pattern = quote!(super::#enum_name_pascal::#variant_name_pascal);
+            let pattern = if discriminator_field.ast_field().arity.is_optional() {
+                quote!(Some(#pattern))
+            } else {
+                pattern
+            };
+
+            (
+                quote!(#variant_name_pascal { #(#definitions),* }),
+                quote!(#pattern => Self::#variant_name_pascal { #(#initializers),* }),
+            )
+        })
+        .unzip();
+
+    let missing_arm = discriminator_field
+        .ast_field()
+        .arity
+        .is_optional()
+        .then(|| {
+            quote!(None => return Err(#pcr::UnionConversionError::MissingDiscriminator(#discriminator)))
+        });
+
+    Some(quote! {
+        #[derive(Debug, Clone)]
+        pub enum Union {
+            #(#variants),*
+        }
+
+        impl TryFrom<Data> for Union {
+            type Error = #pcr::UnionConversionError;
+
+            fn try_from(data: Data) -> Result<Self, Self::Error> {
+                Ok(match data.#discriminator_snake {
+                    #(#match_arms,)*
+                    #missing_arm
+                })
+            }
+        }
+    })
+}
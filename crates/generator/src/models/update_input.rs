@@ -0,0 +1,66 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ModelWalker, psl::parser_database::ScalarFieldType,
+};
+
+use crate::prelude::*;
+
+/// A struct of optional scalar fields for sparse (eg. HTTP PATCH) updates,
+/// where `None` leaves a field untouched.
+/// Nullable fields are double options so that `Some(None)` can set them to null.
+pub fn r#struct(model: ModelWalker, args: &GenerateArgs) -> TokenStream {
+    let (fields, pushes): (Vec<_>, Vec<_>) = model
+        .scalar_fields()
+        .filter(|field| {
+            !matches!(
+                field.scalar_field_type(),
+                ScalarFieldType::Unsupported(_) | ScalarFieldType::CompositeType(_)
+            ) && args.write_param(*field).is_some()
+        })
+        .map(|field| {
+            let field_name_str = field.name();
+            let field_name_snake = snake_ident(field_name_str);
+            let rename = serde_rename(args.shared_config, model.name(), field_name_str);
+
+            let double_option = field
+                .ast_field()
+                .arity
+                .is_optional()
+                .then(|| quote!(, with = "::prisma_client_rust::serde::double_option"));
+
+            (
+                quote! {
+                    #[serde(#rename, default #double_option)]
+                    pub #field_name_snake: Option<#field_name_snake::Type>
+                },
+                quote! {
+                    if let Some(value) = self.#field_name_snake {
+                        params.push(#field_name_snake::set(value));
+                    }
+                },
+            )
+        })
+        .unzip();
+
+    quote! {
+        #[derive(Debug, Clone, Default, ::serde::Deserialize)]
+        pub struct UpdateInput {
+            #(#fields),*
+        }
+
+        impl UpdateInput {
+            pub fn to_params(self) -> Vec<SetParam> {
+                let mut params: Vec<SetParam> = vec![];
+
+                #(#pushes)*
+
+                params
+            }
+        }
+
+        impl From<UpdateInput> for Vec<SetParam> {
+            fn from(input: UpdateInput) -> Self {
+                input.to_params()
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ModelWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::{prelude::*, Error};
+
+/// Models listed in the `version_fields` config, mapped to their version field.
+pub type VersionFields = BTreeMap<String, String>;
+
+pub fn parse_config(args: &GenerateArgs, entries: &[String]) -> Result<VersionFields, Error> {
+    entries
+        .iter()
+        .map(|entry| {
+            let invalid = |reason: String| Error::InvalidVersionField(entry.clone(), reason);
+
+            if !args.has_preview_feature("extendedWhereUnique") {
+                return Err(invalid(
+                    "the extendedWhereUnique preview feature must be enabled".to_string(),
+                ));
+            }
+
+            let (model_name, field_name) = entry
+                .split_once('.')
+                .ok_or_else(|| invalid("expected the form `Model.field`".to_string()))?;
+
+            let model = args
+                .schema
+                .db
+                .walk_models()
+                .find(|m| m.name() == model_name)
+                .ok_or_else(|| invalid(format!("model '{model_name}' not found")))?;
+
+            model
+                .scalar_fields()
+                .find(|f| f.name() == field_name)
+                .filter(|f| f.ast_field().arity.is_required())
+                .filter(|f| {
+                    matches!(
+                        f.scalar_field_type(),
+                        ScalarFieldType::BuiltInScalar(ScalarType::Int | ScalarType::BigInt)
+                    )
+                })
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "version field '{field_name}' must be a required Int or BigInt field"
+                    ))
+                })?;
+
+            Ok((model_name.to_string(), field_name.to_string()))
+        })
+        .collect()
+}
+
+pub fn update_fn(model: ModelWalker, version_fields: &VersionFields) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let field_name_str = version_fields.get(model.name())?;
+    let field_name_snake = snake_ident(field_name_str);
+
+    let doc = format!(
+        "Updates the record only if its `{field_name_str}` is `expected_version`, incrementing it by one.\n\
+         Fails with `QueryError::VersionConflict` if the version has changed."
+    );
+
+    Some(quote! {
+        pub type UpdateIfVersionQuery<'a> = #pcr::UpdateIfVersion<'a, Types>;
+
+        impl<'a> Actions<'a> {
+            #[doc = #doc]
+            pub fn update_if_version(
+                self,
+                _where: UniqueWhereParam,
+                expected_version: #field_name_snake::Type,
+                mut _params: Vec<SetParam>
+            ) -> UpdateIfVersionQuery<'a> {
+                _params.push(#field_name_snake::increment(1));
+
+                #pcr::UpdateIfVersion::new(
+                    self.update(_where, _params)
+                        .and_where(vec![#field_name_snake::equals(expected_version)])
+                )
+            }
+        }
+    })
+}
@@ -3,12 +3,12 @@ use prisma_client_rust_sdk::prisma::{
         walkers::{FieldWalker, ModelWalker, RefinedFieldWalker, ScalarFieldWalker},
         FieldArity,
     },
-    psl::parser_database::ScalarFieldType,
+    psl::parser_database::{ScalarFieldType, ScalarType},
 };
 
 use crate::prelude::*;
 
-use super::ModelModulePart;
+use super::{field_codecs, id_newtypes, native_types, type_overrides, ModelModulePart};
 
 pub struct Operator {
     pub name: &'static str,
@@ -45,16 +45,22 @@ pub enum Variant {
         field_required_type: TokenStream,
         read_filter_name: String,
         optional: bool,
+        /// The field's id newtype, which optional unique filters take in place of its scalar type.
+        newtype: Option<TokenStream>,
     },
     CompoundUniqueVariant {
         field_names_string: String,
         variant_data_destructured: Vec<Ident>,
         variant_data_types: Vec<TokenStream>,
+        /// An equals filter for each of the key's fields, since compound keys
+        /// can't be used as a filter outside of unique queries.
+        field_filters: Vec<TokenStream>,
     },
 }
 
 impl Variant {
     pub fn unique(
+        args: &GenerateArgs,
         field: ScalarFieldWalker,
         read_filter: &Filter,
         module_path: &TokenStream,
@@ -67,12 +73,14 @@ impl Variant {
                 .unwrap(),
             read_filter_name: read_filter.name.to_string(),
             optional: field.ast_field().arity.is_optional(),
+            newtype: id_newtypes::required_type(args, field, &quote!(super::)),
         }
     }
 }
 
-pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
+pub fn collate_entries(entries: Vec<Variant>, args: &GenerateArgs) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
+    let non_exhaustive = non_exhaustive(args.shared_config);
 
     let (variants, to_serialized_where): (Vec<_>, Vec<_>) = entries
         .iter()
@@ -91,6 +99,7 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
             field_required_type,
             read_filter_name,
             optional,
+            newtype,
         } => {
             let field_pascal = pascal_ident(field_name);
             let field_snake = snake_ident(field_name);
@@ -99,20 +108,25 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
             let filter_enum = format_ident!("{}Filter", &read_filter_name);
 
             let optional_unique_impls = optional.then(|| {
+                let (arg_type, arg, optional_arg) = match newtype {
+                    Some(newtype) => (newtype, quote!(arg.0), quote!(arg.map(|arg| arg.0))),
+                    None => (field_required_type, quote!(arg), quote!(arg)),
+                };
+
                 quote!{
                     impl ::prisma_client_rust::FromOptionalUniqueArg<#field_snake::Set> for WhereParam {
-                        type Arg = Option<#field_required_type>;
+                        type Arg = Option<#arg_type>;
 
                         fn from_arg(arg: Self::Arg) -> Self where Self: Sized {
-                            Self::#field_pascal(super::_prisma::read_filters::#filter_enum::Equals(arg))
+                            Self::#field_pascal(super::_prisma::read_filters::#filter_enum::Equals(#optional_arg))
                         }
                     }
 
                     impl ::prisma_client_rust::FromOptionalUniqueArg<#field_snake::Set> for UniqueWhereParam {
-                        type Arg = #field_required_type;
+                        type Arg = #arg_type;
 
                         fn from_arg(arg: Self::Arg) -> Self where Self: Sized {
-                            Self::#variant_name(arg)
+                            Self::#variant_name(#arg)
                         }
                     }
                 }
@@ -130,7 +144,7 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
                 )
             ))
         }
-        Variant::CompoundUniqueVariant { field_names_string, variant_data_destructured, variant_data_types } => {
+        Variant::CompoundUniqueVariant { field_names_string, variant_data_destructured, variant_data_types, .. } => {
             let variant_name = format_ident!("{}Equals", field_names_string);
 
             Some((
@@ -146,8 +160,25 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
         _ => None,
     }).unzip();
 
+    let unique_to_filter_arms = entries.iter().filter_map(|e| match e {
+        Variant::UniqueVariant { field_name, .. } => {
+            let variant_name = format_ident!("{}Equals", pascal_ident(field_name));
+
+            Some(quote!(UniqueWhereParam::#variant_name(value) => WhereParam::from(UniqueWhereParam::#variant_name(value))))
+        }
+        Variant::CompoundUniqueVariant { field_names_string, variant_data_destructured, field_filters, .. } => {
+            let variant_name = format_ident!("{}Equals", field_names_string);
+
+            Some(quote!(UniqueWhereParam::#variant_name(#(#variant_data_destructured),*)
+                => WhereParam::And(vec![#(#field_filters),*])
+            ))
+        }
+        _ => None,
+    });
+
     quote! {
         #[derive(Debug, Clone)]
+        #non_exhaustive
         pub enum WhereParam {
             #(#variants),*
         }
@@ -163,6 +194,7 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
         }
 
         #[derive(Debug, Clone)]
+        #non_exhaustive
         pub enum UniqueWhereParam {
             #(#unique_variants),*
         }
@@ -175,6 +207,16 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
             }
         }
 
+        impl UniqueWhereParam {
+            /// The same filter in a form that can be combined with non-unique filters,
+            /// splitting compound keys into a filter for each of their fields.
+            pub fn into_filter(self) -> WhereParam {
+                match self {
+                    #(#unique_to_filter_arms),*
+                }
+            }
+        }
+
         #(#optional_unique_impls)*
 
         impl From<#pcr::Operator<Self>> for WhereParam {
@@ -244,7 +286,7 @@ pub fn model_data(
                 field
             ).unwrap();
 
-            entries.push(Variant::unique(field, read_filter, module_path));
+            entries.push(Variant::unique(args, field, read_filter, module_path));
 
             None
         } else {
@@ -253,8 +295,8 @@ pub fn model_data(
 
             let variant_data_names = fields.iter().map(|f| snake_ident(f.name())).collect::<Vec<_>>();
 
-            let ((field_defs, field_types), (prisma_values, field_names_snake)):
-                ((Vec<_>, Vec<_>), (Vec<_>, Vec<_>)) = fields.into_iter().map(|field| {
+            let (field_types, (prisma_values, field_names_snake)):
+                (Vec<_>, (Vec<_>, Vec<_>)) = fields.into_iter().map(|field| {
                 let field_type = match field.ast_field().arity {
                     FieldArity::List | FieldArity::Required => field.type_tokens(module_path),
                     FieldArity::Optional => field.scalar_field_type().to_tokens(module_path, &FieldArity::Required, field.db)
@@ -263,13 +305,38 @@ pub fn model_data(
                 let field_name_snake = snake_ident(field.name());
 
                 (
-                    (quote!(#field_name_snake: #field_type), field_type),
+                    field_type,
                     (field.scalar_field_type().to_prisma_value(&field_name_snake, &FieldArity::Required), field_name_snake)
                 )
             }).unzip();
 
+            // id newtypes are taken by the accessor and input, but the variants hold the scalar values
+            let (arg_types, (args_unwrapped, inputs_unwrapped)): (Vec<_>, (Vec<_>, Vec<_>)) = fields.iter().zip(&field_types).map(|(field, field_type)| {
+                let field_name_snake = snake_ident(field.name());
+
+                match id_newtypes::required_type(args, *field, &quote!(super::)) {
+                    Some(newtype) => (newtype, (quote!(#field_name_snake.0), quote!(#field_name_snake.0))),
+                    None => (
+                        field_type.clone(),
+                        (quote!(#field_name_snake), quote!(#field_name_snake))
+                    ),
+                }
+            }).unzip();
+
             let field_names_joined = fields.iter().map(|f| f.name()).collect::<Vec<_>>().join("_");
 
+            let field_filters = fields.iter().map(|field| {
+                let field_pascal = pascal_ident(field.name());
+                let field_snake = snake_ident(field.name());
+                let filter_enum = format_ident!("{}Filter", args.read_filter(*field).unwrap().name);
+
+                let value = field.ast_field().arity.is_optional()
+                    .then(|| quote!(Some(#field_snake)))
+                    .unwrap_or_else(|| quote!(#field_snake));
+
+                quote!(WhereParam::#field_pascal(super::_prisma::read_filters::#filter_enum::Equals(#value)))
+            }).collect();
+
             entries.extend([
                 Variant::BaseVariant {
                     definition: quote!(#variant_name(#(#field_types),*)),
@@ -283,20 +350,154 @@ pub fn model_data(
                 Variant::CompoundUniqueVariant {
                     field_names_string: variant_name_string.clone(),
                     variant_data_destructured: field_names_snake.clone(),
-                    variant_data_types: field_types
+                    variant_data_types: field_types.clone(),
+                    field_filters
                 }
             ]);
 
             let accessor_name = snake_ident(&variant_name_string);
+            let input_name = format_ident!("{}CompoundUniqueInput", &variant_name_string);
+
+            let fields_doc = format!(
+                " Unique filter for the compound key ({}).",
+                fields
+                    .iter()
+                    .map(|f| format!("`{}`", f.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let input_doc = format!(
+                " Corresponds to the query engine's `{}{}CompoundUniqueInput` type.",
+                model.name(),
+                fields
+                    .iter()
+                    .map(|f| {
+                        let mut chars = f.name().chars();
+                        chars
+                            .next()
+                            .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                            .unwrap_or_default()
+                    })
+                    .collect::<String>()
+            );
+
+            let data_values = fields.iter().map(|field| {
+                let field_name_snake = snake_ident(field.name());
+
+                match field.ast_field().arity {
+                    FieldArity::Optional => quote! {
+                        data.#field_name_snake.clone().ok_or(#pcr::NullUniqueFieldError(#field_name_snake::NAME))?
+                    },
+                    _ => quote!(data.#field_name_snake.clone()),
+                }
+            });
 
             Some(quote! {
-                pub fn #accessor_name<T: From<UniqueWhereParam>>(#(#field_defs),*) -> T {
-                    UniqueWhereParam::#variant_name(#(#field_names_snake),*).into()
+                #[doc = #fields_doc]
+                pub fn #accessor_name<T: From<UniqueWhereParam>>(#(#field_names_snake: #arg_types),*) -> T {
+                    UniqueWhereParam::#variant_name(#(#args_unwrapped),*).into()
+                }
+
+                #[doc = #fields_doc]
+                #[doc = ""]
+                #[doc = #input_doc]
+                #[derive(Debug, Clone)]
+                pub struct #input_name {
+                    #(pub #field_names_snake: #arg_types),*
+                }
+
+                impl From<#input_name> for UniqueWhereParam {
+                    fn from(#input_name { #(#field_names_snake),* }: #input_name) -> Self {
+                        Self::#variant_name(#(#inputs_unwrapped),*)
+                    }
+                }
+
+                impl ::std::convert::TryFrom<&Data> for #input_name {
+                    type Error = #pcr::NullUniqueFieldError;
+
+                    fn try_from(data: &Data) -> Result<Self, Self::Error> {
+                        Ok(Self {
+                            #(#field_names_snake: #data_values),*
+                        })
+                    }
                 }
             })
         }
     }).collect::<TokenStream>();
 
+    let to_unique = model.primary_key().and_then(|primary_key| {
+        // a unique index covering the same fields takes the place of the primary key's variant,
+        // and may list them in a different order
+        let fields = unique_field_combos(model).into_iter().find(|fields| {
+            fields.len() == primary_key.fields().count()
+                && primary_key
+                    .fields()
+                    .all(|pk| fields.iter().any(|f| f.field_id() == pk.field_id()))
+        })?;
+
+        let variant_name = format_ident!(
+            "{}Equals",
+            fields
+                .iter()
+                .map(|f| pascal_ident(f.name()).to_string())
+                .collect::<String>()
+        );
+        let values = fields.iter().map(|f| {
+            let field_name_snake = snake_ident(f.name());
+
+            id_newtypes::unwrap(args, *f, quote!(self.#field_name_snake.clone()))
+        });
+
+        Some(quote! {
+            impl Data {
+                /// Unique filter for this record's primary key,
+                /// for targeting it in an update or delete.
+                pub fn to_unique(&self) -> UniqueWhereParam {
+                    UniqueWhereParam::#variant_name(#(#values),*)
+                }
+            }
+
+            impl From<&Data> for UniqueWhereParam {
+                fn from(data: &Data) -> Self {
+                    data.to_unique()
+                }
+            }
+        })
+    });
+
+    let relation_existence_fns = model
+        .relation_fields()
+        .filter(|field| field.ast_field().arity.is_list())
+        .map(|field| {
+            let field_name_snake = snake_ident(field.name());
+            let with_fn = format_ident!("with_{field_name_snake}");
+            let without_fn = format_ident!("without_{field_name_snake}");
+
+            let with_doc = format!(
+                " Records with at least one `{}`, the same as `{}::some(vec![])`.",
+                field.name(),
+                field_name_snake
+            );
+            let without_doc = format!(
+                " Records without any `{}`, the same as `{}::none(vec![])`.",
+                field.name(),
+                field_name_snake
+            );
+
+            quote! {
+                #[doc = #with_doc]
+                pub fn #with_fn() -> WhereParam {
+                    #field_name_snake::some(vec![])
+                }
+
+                #[doc = #without_doc]
+                pub fn #without_fn() -> WhereParam {
+                    #field_name_snake::none(vec![])
+                }
+            }
+        })
+        .collect::<TokenStream>();
+
     let (field_stuff, field_where_param_entries): (_, Vec<_>) = model
         .fields()
         .filter(|f| f.ast_field().field_type.as_unsupported().is_none())
@@ -305,11 +506,13 @@ pub fn model_data(
 
     entries.extend(field_where_param_entries.into_iter().flatten());
 
-    let collated_entries = collate_entries(entries);
+    let collated_entries = collate_entries(entries, args);
 
     ModelModulePart {
         data: quote! {
             #compound_field_accessors
+            #to_unique
+            #relation_existence_fns
             #collated_entries
         },
         fields: field_stuff,
@@ -374,6 +577,7 @@ pub fn field_module(
     let field_type = field.type_tokens(&quote!());
 
     let is_null_variant = format_ident!("{field_name_pascal}IsNull");
+    let is_not_null_variant = format_ident!("{field_name_pascal}IsNotNull");
     let equals_variant = format_ident!("{field_name_pascal}Equals");
 
     let arity = field.ast_field().arity;
@@ -388,7 +592,22 @@ pub fn field_module(
                     match_arm: quote! {
                         Self::#is_null_variant => (
                             #field_name_snake::NAME,
-                            #pcr::SerializedWhereValue::Value(#pcr::PrismaValue::Null)
+                            #pcr::SerializedWhereValue::Object(vec![(
+                                "is".to_string(),
+                                #pcr::PrismaValue::Null
+                            )])
+                        )
+                    },
+                });
+                where_param_entries.push(Variant::BaseVariant {
+                    definition: quote!(#is_not_null_variant),
+                    match_arm: quote! {
+                        Self::#is_not_null_variant => (
+                            #field_name_snake::NAME,
+                            #pcr::SerializedWhereValue::Object(vec![(
+                                "isNot".to_string(),
+                                #pcr::PrismaValue::Null
+                            )])
                         )
                     },
                 });
@@ -620,6 +839,8 @@ pub fn field_module(
 
 					let model = field.model();
 
+					let newtype = id_newtypes::required_type(args, scalar_field, &quote!(super::super::));
+
 					// Add equals query functions. Unique/Where enum variants are added in unique/primary key sections earlier on.
 					let equals = match (
 						scalar_field.is_single_pk(),
@@ -629,25 +850,49 @@ pub fn field_module(
 						}),
 						arity.is_required()
 					) {
+						(true, _, _) | (_, true, true) if newtype.is_some() => quote! {
+							pub fn equals<T: From<UniqueWhereParam>>(value: #newtype) -> T {
+								UniqueWhereParam::#equals_variant(value.0).into()
+							}
+
+							/// Like `equals`, but also accepts a reference to the value.
+							pub fn equals_ref<T: From<UniqueWhereParam>>(value: impl #pcr::UniqueArg<#newtype>) -> T {
+								equals(#pcr::UniqueArg::<#newtype>::into_unique_arg(value))
+							}
+						},
 						(true, _, _) | (_, true, true) => quote! {
 							pub fn equals<T: From<UniqueWhereParam>>(value: #field_type) -> T {
 								UniqueWhereParam::#equals_variant(value).into()
 							}
+
+							/// Like `equals`, but also accepts a reference to the value, or a `&str` or `uuid::Uuid` for `String` fields.
+							pub fn equals_ref<T: From<UniqueWhereParam>>(value: impl #pcr::UniqueArg<#field_type>) -> T {
+								equals(#pcr::UniqueArg::<#field_type>::into_unique_arg(value))
+							}
 						},
 						(_, true, false) => quote! {
 							pub fn equals<A, T: #pcr::FromOptionalUniqueArg<Set, Arg = A>>(value: A) -> T {
 								T::from_arg(value)
 							}
 						},
-						(_, _, _) => quote! {
-							pub fn equals(value: #field_type) -> WhereParam {
-								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(value))
+						(_, _, _) => match id_newtypes::into_original(args, scalar_field, &format_ident!("value")) {
+							Some(converted) => quote! {
+								pub fn equals(value: Type) -> WhereParam {
+									WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(#converted))
+								}
+							},
+							None => quote! {
+								pub fn equals(value: #field_type) -> WhereParam {
+									WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(value))
+								}
 							}
 						}
 					};
 
+					let filter_type = type_overrides::param_type(args, scalar_field, quote!(super::_prisma::read_filters::#filter_enum));
+
 					where_param_entries.push(Variant::BaseVariant {
-						definition: quote!(#field_name_pascal(super::_prisma::read_filters::#filter_enum)),
+						definition: quote!(#field_name_pascal(#filter_type)),
 						match_arm: quote! {
 							Self::#field_name_pascal(value) => (
 								#field_name_snake::NAME,
@@ -656,9 +901,87 @@ pub fn field_module(
 						},
 					});
 
+					// overridden types can't be compared with the engine's other filters
+					if let Some(converted) = type_overrides::into_original(args, scalar_field, &format_ident!("value")) {
+						return quote! {
+							pub fn equals(value: Type) -> WhereParam {
+								WhereParam::#field_name_pascal(#pcr::serde::Overridden::new(#converted, _prisma::read_filters::#filter_enum::Equals))
+							}
+						};
+					}
+
+					// and encoded values can only be compared for equality
+					if let Some(converted) = field_codecs::encode(args, scalar_field, &format_ident!("value")) {
+						return quote! {
+							pub fn equals(value: Type) -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(#converted))
+							}
+						};
+					}
+
+					// native types are only used for equals, other filters take the engine's type
+					let equals = match native_types::into_original(args, scalar_field, &format_ident!("value")) {
+						Some(converted) => quote! {
+							pub fn equals(value: Type) -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(#converted))
+							}
+						},
+						None => equals,
+					};
+
+					// lists of ids take the newtype too, the engine's other filters take its scalar type
+					let newtype_list_fns = newtype.as_ref().map(|newtype| {
+						[("inVec", "in_vec"), ("notInVec", "not_in_vec")].into_iter().map(|(variant, method)| {
+							let variant = pascal_ident(variant);
+							let method = format_ident!("{method}");
+
+							quote! {
+								pub fn #method(value: Vec<#newtype>) -> WhereParam {
+									WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::#variant(
+										value.into_iter().map(|value| value.0).collect()
+									))
+								}
+							}
+						}).collect::<TokenStream>()
+					});
+
+					// flag columns read better as `is_true()` than `equals(true)`
+					let bool_fns = (matches!(
+						scalar_field.scalar_field_type(),
+						ScalarFieldType::BuiltInScalar(ScalarType::Boolean)
+					) && !arity.is_list()).then(|| {
+						let (true_value, false_value) = match arity.is_optional() {
+							true => (quote!(Some(true)), quote!(Some(false))),
+							false => (quote!(true), quote!(false)),
+						};
+
+						let null_fns = arity.is_optional().then(|| quote! {
+							pub fn is_null() -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(None))
+							}
+
+							pub fn is_not_null() -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Not(None))
+							}
+						});
+
+						quote! {
+							pub fn is_true() -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(#true_value))
+							}
+
+							pub fn is_false() -> WhereParam {
+								WhereParam::#field_name_pascal(_prisma::read_filters::#filter_enum::Equals(#false_value))
+							}
+
+							#null_fns
+						}
+					});
+
 					let read_methods = read_filter.fields.iter().filter_map(|field| {
 						let name = match field.name.as_str() {
 							"equals" => return None,
+							"in" | "notIn" if newtype.is_some() => return None,
 							"in" => "inVec",
 							"notIn" => "notInVec",
 							n => n
@@ -674,6 +997,8 @@ pub fn field_module(
 
 					quote! {
 						#equals
+						#newtype_list_fns
+						#bool_fns
 
 						#pcr::scalar_where_param_fns!(
 							_prisma::read_filters::#filter_enum,
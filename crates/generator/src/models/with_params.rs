@@ -69,7 +69,8 @@ fn into_selection_arm(field: RelationFieldWalker) -> TokenStream {
     }
 }
 
-pub fn model_data(model: ModelWalker) -> ModelModulePart {
+pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
+    let non_exhaustive = non_exhaustive(args.shared_config);
     let variants = model.relation_fields().map(enum_variant);
     let into_selection_arms = model.relation_fields().map(into_selection_arm);
 
@@ -136,6 +137,7 @@ pub fn model_data(model: ModelWalker) -> ModelModulePart {
     ModelModulePart {
         data: quote! {
             #[derive(Debug, Clone)]
+            #non_exhaustive
             pub enum WithParam {
                 #(#variants),*
             }
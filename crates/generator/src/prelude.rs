@@ -6,6 +6,11 @@ pub use proc_macro2::*;
 pub use quote::*;
 pub use syn::Ident;
 
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::walkers::ScalarFieldWalker,
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
 pub fn snake_ident(name: &str) -> Ident {
     format_ident!("{}", name.to_case(Case::Snake))
 }
@@ -13,3 +18,38 @@ pub fn snake_ident(name: &str) -> Ident {
 pub fn pascal_ident(name: &str) -> Ident {
     format_ident!("{}", name.to_case(Case::Pascal))
 }
+
+/// `#[non_exhaustive]`, if generated types are configured to be non-exhaustive.
+pub fn non_exhaustive(config: &SharedConfig) -> Option<TokenStream> {
+    config.non_exhaustive.then(|| quote!(#[non_exhaustive]))
+}
+
+/// Contents of a field's `#[serde(...)]` renaming attribute.
+/// The schema name is kept as an alias so that engine responses still deserialize.
+pub fn serde_rename(config: &SharedConfig, parent: &str, field: &str) -> TokenStream {
+    let serde_name = config.serde_name(parent, field);
+
+    if serde_name == field {
+        quote!(rename = #field)
+    } else {
+        quote!(rename = #serde_name, alias = #field)
+    }
+}
+
+/// Contents of a `BigInt` field's `#[serde(...)]` attribute, if it has that type.
+pub fn bigint_serde(config: &SharedConfig, field: ScalarFieldWalker) -> Option<TokenStream> {
+    if !matches!(
+        field.scalar_field_type(),
+        ScalarFieldType::BuiltInScalar(ScalarType::BigInt)
+    ) || config
+        .type_override(field.model().name(), field.name())
+        .is_some()
+    {
+        return None;
+    }
+
+    Some(match config.bigint_as_string {
+        true => quote!(with = "prisma_client_rust::serde::bigint::as_string"),
+        false => quote!(with = "prisma_client_rust::serde::bigint"),
+    })
+}
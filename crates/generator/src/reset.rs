@@ -0,0 +1,83 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::ModelWalker;
+
+use crate::{models::qualified_db_name, prelude::*};
+
+/// Whether `referencing` has a foreign key to `referenced`, ignoring self relations.
+fn references(referencing: ModelWalker, referenced: ModelWalker) -> bool {
+    referencing.name() != referenced.name()
+        && referencing.relation_fields().any(|field| {
+            field.fields().is_some() && field.related_model().name() == referenced.name()
+        })
+}
+
+/// The schema's models, each before any model that it references,
+/// so that deleting their records in order doesn't violate a foreign key.
+/// Relations that form a cycle are ordered by the schema instead.
+fn deletion_order(args: &GenerateArgs) -> Vec<ModelWalker> {
+    let mut remaining = args.schema.db.walk_models().collect::<Vec<_>>();
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .position(|model| !remaining.iter().any(|other| references(*other, *model)))
+            .unwrap_or(0);
+
+        ordered.push(remaining.remove(next));
+    }
+
+    ordered
+}
+
+pub fn generate(args: &GenerateArgs) -> Option<TokenStream> {
+    if !cfg!(feature = "reset-database") {
+        return None;
+    }
+
+    let pcr = quote!(::prisma_client_rust);
+
+    let models = deletion_order(args);
+
+    let statements = match args.connector.name() {
+        // truncating skips the per-row work of a delete, and `CASCADE` takes care of the order
+        name if name == psl::builtin_connectors::POSTGRES.name() && !models.is_empty() => {
+            let sql = format!(
+                "TRUNCATE TABLE {} RESTART IDENTITY CASCADE",
+                models
+                    .iter()
+                    .map(|model| qualified_db_name(*model))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            quote! {
+                client._execute_raw(#pcr::Raw::new(#sql, vec![])).exec().await?;
+            }
+        }
+        _ => {
+            let models = models.iter().map(|model| snake_ident(model.name()));
+
+            quote! {
+                #(client.#models().delete_many(vec![]).all().exec().await?;)*
+            }
+        }
+    };
+
+    Some(quote! {
+        /// Deletes the records of every model, so that tests can start from an empty database
+        /// without pushing the schema again.
+        /// Only generated with the `reset-database` feature, and only compiled in tests
+        /// or when the crate containing the client enables its own `reset-database` feature,
+        /// so that it can't end up in production builds.
+        ///
+        /// PostgreSQL tables are truncated, which also restarts their sequences.
+        /// Other databases delete the records of each model before those of the models it references,
+        /// one model at a time rather than in a transaction.
+        #[cfg(any(test, feature = "reset-database"))]
+        pub async fn reset(client: &PrismaClient) -> #pcr::Result<()> {
+            #statements
+
+            Ok(())
+        }
+    })
+}
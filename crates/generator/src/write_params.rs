@@ -30,16 +30,54 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
                             vec![quote!(Set(::prisma_client_rust::serde_json::Value))],
                             vec![quote!(Self::Set(#var) => #prisma_value)],
                         )
+                    } else if write_param.name == "JsonNullable" {
+                        let var = format_ident!("value");
+                        let prisma_value = ScalarType::Json.to_prisma_value(&var);
+
+                        // the engine doesn't accept null for Json fields,
+                        // distinguishing JSON `null` from the database's `NULL` with enum values instead
+                        (
+                            vec![
+                                quote!(Set(Option<::prisma_client_rust::serde_json::Value>)),
+                                quote!(JsonNull),
+                                quote!(DbNull),
+                            ],
+                            vec![
+                                quote!(Self::Set(Some(#var)) => #prisma_value),
+                                quote! {
+                                    Self::Set(None) | Self::DbNull =>
+                                        ::prisma_client_rust::PrismaValue::Enum("DbNull".to_string())
+                                },
+                                quote! {
+                                    Self::JsonNull =>
+                                        ::prisma_client_rust::PrismaValue::Enum("JsonNull".to_string())
+                                },
+                            ],
+                        )
                     } else {
+                        // `push` accepts a single value or a list, so list params always push a list,
+                        // leaving single values to the field's `push` function
+                        let list_set_field = write_param
+                            .name
+                            .ends_with("List")
+                            .then(|| write_param.fields.iter().find(|f| f.name == "set"))
+                            .flatten();
+
                         write_param
                             .fields
                             .iter()
                             .flat_map(|field| {
-                                let typ = field.type_tokens(&quote!(super::super::));
                                 let action = &field.name;
 
+                                let value_field = match list_set_field {
+                                    Some(set_field) if action == "push" => set_field,
+                                    _ => field,
+                                };
+
+                                let typ = value_field.type_tokens(&quote!(super::super::));
+
                                 let prisma_value_converter =
-                                    field.to_prisma_value(&format_ident!("value"));
+                                    value_field.to_prisma_value(&format_ident!("value"));
 
                                 let method_name_pascal = pascal_ident(&field.name);
 
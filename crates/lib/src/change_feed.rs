@@ -0,0 +1,147 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures::Stream;
+
+use crate::{FindMany, ModelTypes, QueryError};
+
+/// A field that a [`ChangeFeed`] orders by and filters on to find records it hasn't seen yet,
+/// such as an autoincrementing id or an `@updatedAt` timestamp.
+///
+/// Generated as `feed_cursor()` in the module of each required field that supports `gt` filters.
+/// The cursors of fields that aren't unique are paired with the model's primary key,
+/// so that `C` is a `(value, primary key)` tuple and records sharing a value are ordered by their key.
+pub struct FeedCursor<Actions: ModelTypes, C> {
+    after: fn(C) -> Actions::Where,
+    order: fn() -> Vec<Actions::OrderBy>,
+    value: fn(&Actions::Data) -> C,
+}
+
+impl<Actions: ModelTypes, C> FeedCursor<Actions, C> {
+    pub fn new(
+        after: fn(C) -> Actions::Where,
+        order: fn() -> Vec<Actions::OrderBy>,
+        value: fn(&Actions::Data) -> C,
+    ) -> Self {
+        Self {
+            after,
+            order,
+            value,
+        }
+    }
+
+    /// The cursor value of `data`, for persisting the position of a feed.
+    pub fn value_of(&self, data: &Actions::Data) -> C {
+        (self.value)(data)
+    }
+}
+
+/// Polls a model for records past the last seen cursor value, yielding them in cursor order.
+///
+/// The cursor field must only ever increase.
+/// Records written with a cursor value below the last one yielded are never seen,
+/// nor are records with the same value and a lower primary key.
+pub struct ChangeFeed<'a, Actions: ModelTypes, C> {
+    query: FindMany<'a, Actions>,
+    cursor: FeedCursor<Actions, C>,
+    last: Option<C>,
+    interval: Duration,
+    batch_size: i64,
+}
+
+impl<'a, Actions: ModelTypes, C: Clone> ChangeFeed<'a, Actions, C>
+where
+    FindMany<'a, Actions>: Clone,
+{
+    pub fn new(query: FindMany<'a, Actions>, cursor: FeedCursor<Actions, C>) -> Self {
+        Self {
+            query,
+            cursor,
+            last: None,
+            interval: Duration::from_secs(1),
+            batch_size: 100,
+        }
+    }
+
+    /// How long to wait before polling again once there are no new records. Defaults to 1 second.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The maximum number of records fetched per poll. Defaults to 100.
+    pub fn batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Only yields records past `cursor`, eg. the cursor of the last record handled before a restart.
+    /// Without this the feed starts from the first record.
+    pub fn after(mut self, cursor: C) -> Self {
+        self.last = Some(cursor);
+        self
+    }
+
+    async fn poll(&self) -> Result<Vec<Actions::Data>, QueryError> {
+        let mut query = self.query.clone();
+
+        if let Some(last) = self.last.clone() {
+            query.where_params.push((self.cursor.after)(last));
+        }
+
+        // the order is unique, so a batch never cuts between records that the cursor can't tell apart
+        query.order_by_params.extend((self.cursor.order)());
+
+        query.take(self.batch_size).exec().await
+    }
+
+    /// Yields records forever, waiting `interval` between polls that find nothing new.
+    ///
+    /// A failed poll yields its error and is retried after `interval`,
+    /// starting from the same cursor.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Actions::Data, QueryError>> + 'a
+    where
+        Actions: 'a,
+        C: 'a,
+    {
+        futures::stream::unfold(
+            (self, VecDeque::new(), false),
+            |(mut feed, mut buffer, mut failed)| async move {
+                loop {
+                    if let Some(data) = buffer.pop_front() {
+                        feed.last = Some(feed.cursor.value_of(&data));
+                        return Some((Ok(data), (feed, buffer, false)));
+                    }
+
+                    if failed {
+                        tokio::time::sleep(feed.interval).await;
+                        failed = false;
+                    }
+
+                    match feed.poll().await {
+                        Ok(records) if records.is_empty() => {
+                            tokio::time::sleep(feed.interval).await
+                        }
+                        Ok(records) => buffer.extend(records),
+                        Err(e) => return Some((Err(e), (feed, buffer, true))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
+    /// A [`ChangeFeed`] of the records matching this query's filters.
+    /// Ordering and pagination are replaced by the feed's own.
+    pub fn change_feed<C: Clone>(
+        mut self,
+        cursor: FeedCursor<Actions, C>,
+    ) -> ChangeFeed<'a, Actions, C> {
+        self.order_by_params.clear();
+        self.cursor_params.clear();
+        self.skip = None;
+        self.take = None;
+
+        ChangeFeed::new(self, cursor)
+    }
+}
@@ -1,17 +1,24 @@
 use crate::ActionNotifier;
 use psl::Diagnostics;
-use query_core::{
-    protocol::EngineProtocol,
-    schema::{self, QuerySchema},
-    BatchDocumentTransaction, CoreError, Operation, TxId,
-};
+use query_core::{schema, CoreError, Operation, TxId};
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use thiserror::Error;
-
-use crate::{prisma_value, QueryError, Result};
-
-pub type Executor = Box<dyn query_core::QueryExecutor + Send + Sync + 'static>;
+#[cfg(feature = "with-runtime")]
+use tokio::runtime::Handle;
+
+#[cfg(feature = "with-runtime")]
+use crate::engine::{spawn_on, RuntimeEngine};
+#[cfg(feature = "engine-logs")]
+use crate::engine_logs::LoggedEngine;
+use crate::{
+    apply_socket_to_url, count_cache::NamespacedCountCache, engine::detached,
+    identity_map::IdentityMap, middleware::run_middleware, queries::ensure_mutation_filtered,
+    schema_override::SchemaEngines, serde::ensure_serialized, Context, CountCache, Executor,
+    ExecutorConnector, FailedQuery, MemoryCountCache, Middleware, PoolConfig, PoolConfigError,
+    QueryEngine, QueryErrorDetail, QueryRaw, Raw, Result, SocketError, SqliteConfig,
+    SqliteConfigError, WithSchemaError,
+};
 
 pub trait PrismaClient {
     fn internals(&self) -> &PrismaClientInternals;
@@ -19,16 +26,10 @@ pub trait PrismaClient {
     fn with_tx_id(&self, tx_id: Option<TxId>) -> Self;
 }
 
-pub struct ExecutorConnector {
-    pub executor: Executor,
-    pub query_schema: Arc<QuerySchema>,
-    pub url: String,
-}
-
 #[derive(Clone)]
 pub(crate) enum ExecutionEngine {
     Real {
-        connector: Arc<ExecutorConnector>,
+        engine: Arc<dyn QueryEngine>,
         tx_id: Option<TxId>,
     },
     #[cfg(feature = "mocking")]
@@ -38,26 +39,10 @@ pub(crate) enum ExecutionEngine {
 impl ExecutionEngine {
     async fn execute(&self, op: Operation) -> Result<serde_value::Value> {
         match self {
-            Self::Real { connector, tx_id } => {
-                let response = connector
-                    .executor
-                    .execute(
-                        tx_id.clone(),
-                        op,
-                        connector.query_schema.clone(),
-                        None,
-                        EngineProtocol::Graphql,
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
-
-                let data: prisma_value::Item = response.data.into();
-
-                let data = serde_value::to_value(data)
-                    .map_err(|e| e.to_string())
-                    .map_err(QueryError::Deserialize)?;
+            Self::Real { engine, tx_id } => {
+                let (engine, tx_id) = (engine.clone(), tx_id.clone());
 
-                Ok(data)
+                detached(async move { engine.execute(tx_id, op).await }).await
             }
             #[cfg(feature = "mocking")]
             Self::Mock(store) => Ok(store.get_op(&op).await.expect("Mock data not found")),
@@ -69,33 +54,10 @@ impl ExecutionEngine {
         ops: Vec<Operation>,
     ) -> Result<Vec<Result<serde_value::Value>>> {
         match self {
-            Self::Real { connector, .. } => {
-                let response = connector
-                    .executor
-                    .execute_all(
-                        None,
-                        ops,
-                        Some(BatchDocumentTransaction::new(None)),
-                        connector.query_schema.clone(),
-                        None,
-                        EngineProtocol::Graphql,
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
-
-                Ok(response
-                    .into_iter()
-                    .map(|result| {
-                        let data: prisma_value::Item = result
-                            .map_err(|e| QueryError::Execute(e.into()))?
-                            .data
-                            .into();
-
-                        Ok(serde_value::to_value(data)
-                            .map_err(|e| e.to_string())
-                            .map_err(QueryError::Deserialize)?)
-                    })
-                    .collect())
+            Self::Real { engine, .. } => {
+                let engine = engine.clone();
+
+                detached(async move { engine.execute_all(ops).await }).await
             }
             #[cfg(feature = "mocking")]
             Self::Mock(store) => {
@@ -112,8 +74,8 @@ impl ExecutionEngine {
 
     fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         match self {
-            Self::Real { connector, .. } => Self::Real {
-                connector: connector.clone(),
+            Self::Real { engine, .. } => Self::Real {
+                engine: engine.clone(),
                 tx_id,
             },
             #[cfg(feature = "mocking")]
@@ -128,11 +90,135 @@ impl ExecutionEngine {
 pub struct PrismaClientInternals {
     pub(crate) engine: ExecutionEngine,
     pub action_notifier: Arc<crate::ActionNotifier>,
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: Arc<crate::metrics::Metrics>,
+    pub(crate) identity_map: Option<Arc<IdentityMap>>,
+    middleware: Arc<Vec<Middleware>>,
+    context: Option<Context>,
+    pub(crate) count_cache: Arc<dyn CountCache>,
+    pub(crate) in_chunk_size: Option<usize>,
+    query_error_detail: QueryErrorDetail,
+    schema_engines: Option<Arc<SchemaEngines>>,
+    #[cfg(feature = "with-runtime")]
+    pub(crate) runtime: Option<Handle>,
+    #[cfg(feature = "engine-logs")]
+    engine_logger: Option<crate::engine_logs::EngineLogger>,
+    #[cfg(feature = "pool-events")]
+    pool_events: Option<Arc<crate::PoolEventSubscription>>,
+    // declared after `engine` so that it holds the last reference to the executor when dropped
+    #[cfg(feature = "disconnect-on-drop")]
+    disconnect_guard: Option<Arc<crate::disconnect::DisconnectGuard>>,
 }
 
 impl PrismaClientInternals {
     pub(crate) async fn execute(&self, operation: Operation) -> Result<serde_value::Value> {
-        self.engine.execute(operation).await
+        let query = self.failed_query(&operation);
+
+        self.execute_operation(operation)
+            .await
+            .map_err(|e| e.in_query(query))
+    }
+
+    async fn execute_operation(&self, operation: Operation) -> Result<serde_value::Value> {
+        ensure_mutation_filtered(&operation)?;
+        ensure_serialized(&operation)?;
+        run_middleware(&self.middleware, &operation, self.context.as_ref())?;
+
+        let identity_key = match &self.identity_map {
+            Some(identity_map) => match IdentityMap::key(&operation) {
+                Some(key) => {
+                    if let Some(value) = identity_map.get(&key) {
+                        return Ok(value);
+                    }
+
+                    Some(key)
+                }
+                None => {
+                    if matches!(operation, Operation::Write(_)) {
+                        identity_map.clear();
+                    }
+
+                    None
+                }
+            },
+            None => None,
+        };
+
+        #[cfg(feature = "metrics")]
+        let _timer = self.metrics.start([operation_name(&operation)]);
+
+        let result = self.engine.execute(operation).await;
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            self.metrics.record_error(e);
+        }
+
+        if let (Some(identity_map), Some(key), Ok(value)) =
+            (&self.identity_map, identity_key, &result)
+        {
+            identity_map.insert(key, value.clone());
+        }
+
+        result
+    }
+
+    pub(crate) async fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> Result<Vec<Result<serde_value::Value>>> {
+        operations.iter().try_for_each(|op| {
+            ensure_mutation_filtered(op)
+                .and_then(|()| ensure_serialized(op))
+                .map_err(|e| e.in_query(self.failed_query(op)))
+        })?;
+        operations.iter().try_for_each(|op| {
+            run_middleware(&self.middleware, op, self.context.as_ref())
+                .map_err(|e| e.in_query(self.failed_query(op)))
+        })?;
+
+        // errors of the batch as a whole, rather than of one of its queries, are returned as they are
+        let queries = operations
+            .iter()
+            .map(|op| self.failed_query(op))
+            .collect::<Vec<_>>();
+
+        if let Some(identity_map) = &self.identity_map {
+            if operations
+                .iter()
+                .any(|op| matches!(op, Operation::Write(_)))
+            {
+                identity_map.clear();
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        let _timer = self.metrics.start(operations.iter().map(operation_name));
+
+        let results = self.engine.execute_all(operations).await;
+
+        #[cfg(feature = "metrics")]
+        match &results {
+            Ok(results) => results
+                .iter()
+                .filter_map(|r| r.as_ref().err())
+                .for_each(|e| self.metrics.record_error(e)),
+            Err(e) => self.metrics.record_error(e),
+        }
+
+        results.map(|results| {
+            results
+                .into_iter()
+                .zip(queries)
+                .map(|(result, query)| result.map_err(|e| e.in_query(query)))
+                .collect()
+        })
+    }
+
+    /// A snapshot of the queries this client and its transactions have executed.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> crate::MetricsSnapshot {
+        self.metrics.snapshot()
     }
 
     // pub fn notify_model_mutation<'a, Action>(&self)
@@ -156,8 +242,15 @@ impl PrismaClientInternals {
 
     pub async fn new(
         url: Option<String>,
+        env: Option<String>,
         action_notifier: ActionNotifier,
         datamodel: &str,
+        pool_config: PoolConfig,
+        sqlite_config: SqliteConfig,
+        socket: Option<PathBuf>,
+        eager_connect: bool,
+        #[cfg(feature = "with-runtime")] runtime: Option<Handle>,
+        #[cfg(feature = "engine-logs")] engine_logs: Option<crate::EngineLogs>,
     ) -> std::result::Result<Self, NewClientError> {
         let schema = Arc::new(psl::validate(datamodel.into()));
         let config = &schema.configuration;
@@ -170,11 +263,19 @@ impl PrismaClientInternals {
         let url = match url {
             Some(url) => url,
             None => {
-                let url = match source.load_url(|key| dotenv::var(key).ok()) {
-                    Ok(url) => Some(url),
-                    Err(_) => source.load_shadow_database_url()?,
-                }
-                .unwrap();
+                let url = match env {
+                    Some(env) => match dotenv::var(&env) {
+                        Ok(url) if !url.is_empty() => url,
+                        _ => return Err(NewClientError::MissingEnv(env)),
+                    },
+                    None => match source.load_url(|key| dotenv::var(key).ok()) {
+                        Ok(url) => url,
+                        Err(diagnostics) => match source.load_shadow_database_url()? {
+                            Some(url) => url,
+                            None => return Err(diagnostics.into()),
+                        },
+                    },
+                };
 
                 match url.starts_with("file:") {
                     true => {
@@ -190,22 +291,117 @@ impl PrismaClientInternals {
             }
         };
 
-        let executor =
-            request_handlers::load_executor(source, config.preview_features(), &url).await?;
+        let url = pool_config.apply_to_url(&url)?;
+        let url = sqlite_config.apply_to_url(source.active_provider, &url)?;
+        let url = apply_socket_to_url(socket.as_deref(), source.active_provider, &url)?;
+
+        // the executor spawns its pool's tasks on whichever runtime it's loaded on
+        #[cfg(feature = "with-runtime")]
+        let executor = match &runtime {
+            Some(runtime) => {
+                let schema = schema.clone();
+                let url = url.clone();
+
+                spawn_on(runtime, async move {
+                    load_executor(&schema, &url, eager_connect).await
+                })
+                .await
+                .ok_or(NewClientError::Cancelled)??
+            }
+            None => load_executor(&schema, &url, eager_connect).await?,
+        };
+        #[cfg(not(feature = "with-runtime"))]
+        let executor = load_executor(&schema, &url, eager_connect).await?;
+
+        let query_schema = Arc::new(schema::build(schema.clone(), true));
+
+        let engine: Arc<dyn QueryEngine> = Arc::new(ExecutorConnector {
+            executor,
+            query_schema: query_schema.clone(),
+            url: url.clone(),
+        });
+
+        #[cfg(feature = "engine-logs")]
+        let engine_logger = engine_logs.map(crate::engine_logs::EngineLogger::new);
+        #[cfg(feature = "engine-logs")]
+        let engine: Arc<dyn QueryEngine> = match &engine_logger {
+            Some(logger) => Arc::new(LoggedEngine::new(engine, logger.clone())),
+            None => engine,
+        };
 
-        executor.primary_connector().get_connection().await?;
+        let internals = Self {
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(crate::metrics::Metrics::new(pool_config.connection_limit)),
+            schema_engines: Some(Arc::new(SchemaEngines::new(
+                schema.clone(),
+                query_schema,
+                url,
+            ))),
+            #[cfg(feature = "engine-logs")]
+            engine_logger,
+            ..Self::base(
+                ExecutionEngine::Real {
+                    engine,
+                    tx_id: None,
+                },
+                action_notifier,
+            )
+        };
 
-        Ok(Self {
-            engine: ExecutionEngine::Real {
-                connector: Arc::new(ExecutorConnector {
-                    executor,
-                    query_schema: Arc::new(schema::build(schema.clone(), true)),
-                    url,
-                }),
+        #[cfg(feature = "with-runtime")]
+        let internals = match runtime {
+            Some(runtime) => internals.with_runtime(runtime),
+            None => internals,
+        };
+
+        if sqlite_config.wal_mode {
+            QueryRaw::<serde_json::Value>::new(
+                &internals,
+                Raw::new("PRAGMA journal_mode = WAL", vec![]),
+                source.active_provider,
+            )
+            .exec()
+            .await
+            .map_err(SqliteConfigError::WalMode)?;
+        }
+
+        Ok(internals)
+    }
+
+    /// The state of a client that hasn't been configured yet, with its queries executed by `engine`.
+    fn base(engine: ExecutionEngine, action_notifier: ActionNotifier) -> Self {
+        Self {
+            engine,
+            action_notifier: Arc::new(action_notifier),
+            #[cfg(feature = "metrics")]
+            metrics: Default::default(),
+            identity_map: None,
+            middleware: Default::default(),
+            context: None,
+            count_cache: Arc::new(MemoryCountCache::new()),
+            in_chunk_size: None,
+            query_error_detail: QueryErrorDetail::None,
+            schema_engines: None,
+            #[cfg(feature = "with-runtime")]
+            runtime: None,
+            #[cfg(feature = "engine-logs")]
+            engine_logger: None,
+            #[cfg(feature = "pool-events")]
+            pool_events: None,
+            #[cfg(feature = "disconnect-on-drop")]
+            disconnect_guard: None,
+        }
+    }
+
+    /// Creates a client that sends its queries through `engine` instead of the native query engine.
+    pub fn new_with_engine(engine: Arc<dyn QueryEngine>, action_notifier: ActionNotifier) -> Self {
+        Self::base(
+            ExecutionEngine::Real {
+                engine,
                 tx_id: None,
             },
-            action_notifier: Arc::new(action_notifier),
-        })
+            action_notifier,
+        )
     }
 
     #[cfg(feature = "mocking")]
@@ -213,28 +409,199 @@ impl PrismaClientInternals {
         let mock_store = crate::MockStore::new();
 
         (
-            Self {
-                engine: ExecutionEngine::Mock(mock_store.clone()),
-                action_notifier: Arc::new(action_notifier),
-            },
+            Self::base(ExecutionEngine::Mock(mock_store.clone()), action_notifier),
             mock_store,
         )
     }
 
+    /// The engine this client sends its queries through, or `None` for mock clients.
+    pub fn query_engine(&self) -> Option<Arc<dyn QueryEngine>> {
+        match &self.engine {
+            ExecutionEngine::Real { engine, .. } => Some(engine.clone()),
+            #[cfg(feature = "mocking")]
+            ExecutionEngine::Mock(_) => None,
+        }
+    }
+
     pub fn url(&self) -> &str {
         match &self.engine {
             #[cfg(feature = "mocking")]
             ExecutionEngine::Mock(_) => "mock",
-            ExecutionEngine::Real { connector, .. } => &connector.url,
+            ExecutionEngine::Real { engine, .. } => engine.url(),
         }
     }
 
     pub fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         Self {
             engine: self.engine.with_tx_id(tx_id),
-            action_notifier: self.action_notifier.clone(),
+            identity_map: None,
+            ..self.clone()
         }
     }
+
+    /// A client whose queries use the tables in the Postgres schema `name`
+    /// instead of the one in the datasource URL.
+    pub async fn with_schema(&self, name: &str) -> std::result::Result<Self, WithSchemaError> {
+        let schema_engines = match (&self.schema_engines, &self.engine) {
+            (Some(schema_engines), ExecutionEngine::Real { tx_id: None, .. }) => schema_engines,
+            _ => return Err(WithSchemaError::UnsupportedClient),
+        };
+
+        #[cfg(feature = "with-runtime")]
+        let engine = match &self.runtime {
+            Some(runtime) => {
+                let schema_engines = schema_engines.clone();
+                let name = name.to_string();
+
+                spawn_on(runtime, async move { schema_engines.get(&name).await })
+                    .await
+                    .ok_or(WithSchemaError::Cancelled)??
+            }
+            None => schema_engines.get(name).await?,
+        };
+        #[cfg(not(feature = "with-runtime"))]
+        let engine = schema_engines.get(name).await?;
+
+        // logged inside of the runtime's tasks, where the engine runs
+        #[cfg(feature = "engine-logs")]
+        let engine: Arc<dyn QueryEngine> = match &self.engine_logger {
+            Some(logger) => Arc::new(LoggedEngine::new(engine, logger.clone())),
+            None => engine,
+        };
+
+        #[cfg(feature = "with-runtime")]
+        let engine: Arc<dyn QueryEngine> = match &self.runtime {
+            Some(runtime) => Arc::new(RuntimeEngine::new(engine, runtime.clone())),
+            None => engine,
+        };
+
+        Ok(Self {
+            engine: ExecutionEngine::Real {
+                engine,
+                tx_id: None,
+            },
+            identity_map: None,
+            count_cache: Arc::new(NamespacedCountCache::new(self.count_cache.clone(), name)),
+            ..self.clone()
+        })
+    }
+
+    /// Drops this client's connection pool for the schema `name`, returning whether there was one.
+    /// Clients already returned by `with_schema` keep working, and the next call reconnects.
+    pub fn evict_schema(&self, name: &str) -> bool {
+        self.schema_engines
+            .as_ref()
+            .map(|schema_engines| schema_engines.evict(name))
+            .unwrap_or(false)
+    }
+
+    /// Runs `middleware` before every query, in order, replacing any middleware already set.
+    pub fn with_middleware(mut self, middleware: Vec<Middleware>) -> Self {
+        self.middleware = Arc::new(middleware);
+        self
+    }
+
+    /// Stores cached counts in `count_cache` instead of in memory.
+    pub fn with_count_cache(mut self, count_cache: Arc<dyn CountCache>) -> Self {
+        self.count_cache = count_cache;
+        self
+    }
+
+    /// The number of `in` filter values above which `find_many` queries are split into batches,
+    /// or `None` to only split queries that use `chunked_in`.
+    pub fn with_in_chunk_size(mut self, in_chunk_size: Option<usize>) -> Self {
+        self.in_chunk_size = in_chunk_size;
+        self
+    }
+
+    /// How much of a failing query is included in the errors this client returns.
+    pub fn with_query_error_detail(mut self, detail: QueryErrorDetail) -> Self {
+        self.query_error_detail = detail;
+        self
+    }
+
+    /// Runs this client's queries and transactions in tasks spawned on `runtime`,
+    /// so that they can be awaited from a different runtime, or from another executor entirely.
+    /// Mock clients are unaffected.
+    #[cfg(feature = "with-runtime")]
+    pub fn with_runtime(mut self, runtime: Handle) -> Self {
+        if let ExecutionEngine::Real { engine, tx_id } = self.engine {
+            self.engine = ExecutionEngine::Real {
+                engine: Arc::new(RuntimeEngine::new(engine, runtime.clone())),
+                tx_id,
+            };
+        }
+
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// The query to attach to errors from `operation`, or `None` if errors don't include their query.
+    pub(crate) fn failed_query(&self, operation: &Operation) -> Option<FailedQuery> {
+        FailedQuery::new(operation, self.query_error_detail)
+    }
+
+    /// Keeps `subscription`'s listeners receiving pool events for as long as this client,
+    /// or any client created from it, is alive.
+    #[cfg(feature = "pool-events")]
+    pub fn with_pool_events(mut self, subscription: Option<crate::PoolEventSubscription>) -> Self {
+        self.pool_events = subscription.map(Arc::new);
+        self
+    }
+
+    /// A client sharing this one's engine and middleware,
+    /// whose middleware receives `context` for each query it executes.
+    /// Transactions started from the returned client carry the context too.
+    pub fn with_context(&self, context: Context) -> Self {
+        Self {
+            context: Some(context),
+            ..self.clone()
+        }
+    }
+
+    /// Disconnects from the database according to `mode` once this client,
+    /// and every client created from it, has been dropped.
+    #[cfg(feature = "disconnect-on-drop")]
+    pub fn disconnect_on_drop(mut self, mode: crate::DisconnectOnDrop) -> Self {
+        if let ExecutionEngine::Real { engine, .. } = &self.engine {
+            self.disconnect_guard = Some(Arc::new(crate::disconnect::DisconnectGuard::new(
+                engine.clone(),
+                mode,
+            )));
+        }
+
+        self
+    }
+}
+
+/// Loads the executor for `schema`'s datasource,
+/// connecting to the database straight away if `eager_connect` is set.
+async fn load_executor(
+    schema: &psl::ValidatedSchema,
+    url: &str,
+    eager_connect: bool,
+) -> std::result::Result<Executor, NewClientError> {
+    let config = &schema.configuration;
+    let source = config
+        .datasources
+        .first()
+        .expect("Please supply a datasource in your schema.prisma file");
+
+    let executor = request_handlers::load_executor(source, config.preview_features(), url).await?;
+
+    // the connection goes back to the pool once dropped, ready for the first query
+    if eager_connect {
+        executor.primary_connector().get_connection().await?;
+    }
+
+    Ok(executor)
+}
+
+#[cfg(feature = "metrics")]
+fn operation_name(operation: &Operation) -> &str {
+    match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection.name(),
+    }
 }
 
 trait DiagnosticsToString {
@@ -258,6 +625,25 @@ pub enum NewClientError {
 
     #[error("Error getting database connection: {0}")]
     Connection(#[from] query_core::ConnectorError),
+
+    #[error("Environment variable '{0}' containing the database URL is not set")]
+    MissingEnv(String),
+
+    #[error("Invalid connection pool configuration: {0}")]
+    PoolConfig(#[from] PoolConfigError),
+
+    #[error("Invalid SQLite configuration: {0}")]
+    SqliteConfig(#[from] SqliteConfigError),
+
+    #[error("Invalid socket configuration: {0}")]
+    Socket(#[from] SocketError),
+
+    #[cfg(feature = "pool-events")]
+    #[error("Unable to observe connection pool events: {0}")]
+    PoolEvents(#[from] crate::PoolEventsError),
+
+    #[error("The runtime the client was being built on shut down before it finished")]
+    Cancelled,
 }
 
 impl From<Diagnostics> for NewClientError {
@@ -0,0 +1,97 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+
+/// Stores the results of cached counts, keyed by the count query's [`cache_key`](crate::cache_key).
+///
+/// Clients use a [`MemoryCountCache`] unless another store is provided to the builder,
+/// eg. one backed by Redis so that counts are shared between processes.
+pub trait CountCache: Send + Sync {
+    /// The count stored under `key`, or `None` if there isn't one or it has expired.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<i64>>;
+
+    /// Stores `count` under `key` until `ttl` has passed.
+    fn set<'a>(&'a self, key: &'a str, count: i64, ttl: Duration) -> BoxFuture<'a, ()>;
+}
+
+/// An in-process [`CountCache`], shared by a client and every client created from it.
+#[derive(Default)]
+pub struct MemoryCountCache {
+    entries: Mutex<HashMap<String, (i64, Instant)>>,
+}
+
+impl MemoryCountCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CountCache for MemoryCountCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<i64>> {
+        let entries = self.entries.lock().unwrap();
+
+        let count = entries
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(count, _)| *count);
+
+        async move { count }.boxed()
+    }
+
+    fn set<'a>(&'a self, key: &'a str, count: i64, ttl: Duration) -> BoxFuture<'a, ()> {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+
+        // expired entries are only replaced when their filter is counted again,
+        // so they're dropped here to stop one-off filters from accumulating
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        entries.insert(key.to_string(), (count, now + ttl));
+
+        async {}.boxed()
+    }
+}
+
+/// Prefixes the keys of another store, so that clients for different Postgres schemas
+/// don't read each other's counts.
+pub(crate) struct NamespacedCountCache {
+    inner: Arc<dyn CountCache>,
+    prefix: String,
+}
+
+impl NamespacedCountCache {
+    pub(crate) fn new(inner: Arc<dyn CountCache>, namespace: &str) -> Self {
+        Self {
+            inner,
+            prefix: format!("{namespace}/"),
+        }
+    }
+}
+
+impl CountCache for NamespacedCountCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<i64>> {
+        async move { self.inner.get(&format!("{}{key}", self.prefix)).await }.boxed()
+    }
+
+    fn set<'a>(&'a self, key: &'a str, count: i64, ttl: Duration) -> BoxFuture<'a, ()> {
+        async move {
+            self.inner
+                .set(&format!("{}{key}", self.prefix), count, ttl)
+                .await
+        }
+        .boxed()
+    }
+}
+
+impl<T: CountCache + ?Sized> CountCache for Arc<T> {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<i64>> {
+        (**self).get(key)
+    }
+
+    fn set<'a>(&'a self, key: &'a str, count: i64, ttl: Duration) -> BoxFuture<'a, ()> {
+        (**self).set(key, count, ttl)
+    }
+}
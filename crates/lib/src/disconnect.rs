@@ -0,0 +1,73 @@
+use std::{
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+
+use tokio::runtime::Handle;
+
+use crate::QueryEngine;
+
+/// How a client disconnects from the database once it is dropped,
+/// set with the client builder's `disconnect_on_drop`.
+///
+/// Transactions and other clients created from the client share its connection,
+/// so it is only disconnected once all of them have been dropped.
+/// Queries that are still running aren't waited for or cancelled,
+/// and any left running in spawned tasks keep the connection open until they finish.
+#[derive(Debug, Clone)]
+pub enum DisconnectOnDrop {
+    /// Disconnects in a task spawned on the runtime without waiting for it to finish.
+    /// The disconnect may not complete if the runtime shuts down first.
+    Spawn(Handle),
+    /// Blocks the thread dropping the client until its engine has been dropped, or for at most the given duration.
+    /// The disconnect happens on a separate thread, so this is safe to use inside of the runtime.
+    Block(Handle, Duration),
+}
+
+/// Holds the last reference to a client's engine,
+/// so that the engine and its connection pool are dropped inside the runtime.
+pub(crate) struct DisconnectGuard {
+    connector: Option<Arc<dyn QueryEngine>>,
+    mode: DisconnectOnDrop,
+}
+
+impl DisconnectGuard {
+    pub(crate) fn new(connector: Arc<dyn QueryEngine>, mode: DisconnectOnDrop) -> Self {
+        Self {
+            connector: Some(connector),
+            mode,
+        }
+    }
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        let connector = match self.connector.take() {
+            Some(connector) => connector,
+            None => return,
+        };
+
+        let disconnect = async move {
+            drop(connector);
+            // lets any tasks spawned by the pool while closing its connections run
+            tokio::task::yield_now().await;
+        };
+
+        match &self.mode {
+            DisconnectOnDrop::Spawn(handle) => {
+                handle.spawn(disconnect);
+            }
+            DisconnectOnDrop::Block(handle, timeout) => {
+                let handle = handle.clone();
+                let (done_tx, done_rx) = mpsc::channel();
+
+                std::thread::spawn(move || {
+                    handle.block_on(disconnect);
+                    done_tx.send(()).ok();
+                });
+
+                done_rx.recv_timeout(*timeout).ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::{future::Future, sync::Arc};
+
+use futures::{future::BoxFuture, FutureExt};
+use query_core::{
+    protocol::EngineProtocol, schema::QuerySchema, BatchDocumentTransaction, Operation,
+    TransactionOptions, TxId,
+};
+#[cfg(any(feature = "cancel-safe", feature = "with-runtime"))]
+use tokio::runtime::Handle;
+
+use crate::{prisma_value, queries::ensure_relation_load_strategy_supported, QueryError, Result};
+
+#[cfg(feature = "collated-order")]
+use crate::queries::ensure_collated_order_supported;
+
+#[cfg(feature = "json-path-order")]
+use crate::queries::ensure_json_path_order_supported;
+
+/// Runs `future` in a task on the current runtime and waits for it to finish,
+/// or just awaits it without the `cancel-safe` feature.
+///
+/// Dropping the returned future only stops waiting, so an operation that the engine has started
+/// still runs to completion instead of being abandoned halfway through,
+/// which could leave a pooled connection inside of the transaction a nested write opened.
+pub(crate) async fn detached<T: Send + 'static>(
+    future: impl Future<Output = Result<T>> + Send + 'static,
+) -> Result<T> {
+    #[cfg(feature = "cancel-safe")]
+    if let Ok(handle) = Handle::try_current() {
+        return cancelled(spawn_on(&handle, future).await);
+    }
+
+    future.await
+}
+
+/// Runs `future` in a task on `runtime` and waits for it to finish, propagating any panic.
+/// The returned future can be awaited from any executor, not just one driven by `runtime`.
+///
+/// `None` if the task was cancelled before it finished, eg. because `runtime` is shutting down.
+#[cfg(any(feature = "cancel-safe", feature = "with-runtime"))]
+pub(crate) async fn spawn_on<T: Send + 'static>(
+    runtime: &Handle,
+    future: impl Future<Output = T> + Send + 'static,
+) -> Option<T> {
+    match runtime.spawn(future).await {
+        Ok(value) => Some(value),
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => None,
+    }
+}
+
+/// The result of an engine call run with [`spawn_on`], failing if its task was cancelled.
+#[cfg(any(feature = "cancel-safe", feature = "with-runtime"))]
+fn cancelled<T>(result: Option<Result<T>>) -> Result<T> {
+    result.unwrap_or(Err(QueryError::Cancelled))
+}
+
+pub type Executor = Box<dyn query_core::QueryExecutor + Send + Sync + 'static>;
+
+/// The transport that a client sends its queries through.
+///
+/// The native query engine is used by default,
+/// but any implementation can be supplied with the client builder's `with_engine`,
+/// eg. to forward queries to a remote engine or to record them in tests.
+///
+/// Responses are the data selected by the operation, in the same shape that the native engine returns,
+/// eg. a list of objects for `findMany` or an object with a `count` field for `updateMany`.
+///
+/// Engines that run in another process can send operations as JSON with [`operation_to_json`](crate::operation_to_json),
+/// which the other process turns back into an [`Operation`] with [`operation_from_json`](crate::operation_from_json)
+/// to execute with its own engine, and return the JSON response for [`response_from_json`](crate::response_from_json) to convert.
+pub trait QueryEngine: Send + Sync {
+    /// Executes `operation`, inside of the transaction `tx_id` if one is provided.
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, Result<serde_value::Value>>;
+
+    /// Executes `operations` in a single batch transaction, returning a result for each of them.
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<'_, Result<Vec<Result<serde_value::Value>>>>;
+
+    fn start_tx(&self, options: TransactionOptions) -> BoxFuture<'_, Result<TxId>>;
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>>;
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>>;
+
+    /// The URL of the database, used when applying migrations.
+    fn url(&self) -> &str;
+}
+
+/// The native query engine, loaded by the client builder when no other engine is supplied.
+pub struct ExecutorConnector {
+    pub executor: Executor,
+    pub query_schema: Arc<QuerySchema>,
+    pub url: String,
+}
+
+fn item_to_value(data: prisma_value::Item) -> Result<serde_value::Value> {
+    serde_value::to_value(data)
+        .map_err(|e| e.to_string())
+        .map_err(QueryError::Deserialize)
+}
+
+impl QueryEngine for ExecutorConnector {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, Result<serde_value::Value>> {
+        async move {
+            ensure_relation_load_strategy_supported(&operation)?;
+            #[cfg(feature = "json-path-order")]
+            ensure_json_path_order_supported(&operation)?;
+            #[cfg(feature = "collated-order")]
+            ensure_collated_order_supported(&operation)?;
+
+            let response = self
+                .executor
+                .execute(
+                    tx_id,
+                    operation,
+                    self.query_schema.clone(),
+                    None,
+                    EngineProtocol::Graphql,
+                )
+                .await
+                .map_err(|e| QueryError::Execute(e.into()))?;
+
+            item_to_value(response.data.into())
+        }
+        .boxed()
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<'_, Result<Vec<Result<serde_value::Value>>>> {
+        async move {
+            operations.iter().try_for_each(|operation| {
+                ensure_relation_load_strategy_supported(operation)?;
+                #[cfg(feature = "json-path-order")]
+                ensure_json_path_order_supported(operation)?;
+                #[cfg(feature = "collated-order")]
+                ensure_collated_order_supported(operation)?;
+                Ok(())
+            })?;
+
+            let response = self
+                .executor
+                .execute_all(
+                    None,
+                    operations,
+                    Some(BatchDocumentTransaction::new(None)),
+                    self.query_schema.clone(),
+                    None,
+                    EngineProtocol::Graphql,
+                )
+                .await
+                .map_err(|e| QueryError::Execute(e.into()))?;
+
+            Ok(response
+                .into_iter()
+                .map(|result| {
+                    item_to_value(
+                        result
+                            .map_err(|e| QueryError::Execute(e.into()))?
+                            .data
+                            .into(),
+                    )
+                })
+                .collect())
+        }
+        .boxed()
+    }
+
+    fn start_tx(&self, options: TransactionOptions) -> BoxFuture<'_, Result<TxId>> {
+        async move {
+            self.executor
+                .start_tx(self.query_schema.clone(), EngineProtocol::Graphql, options)
+                .await
+                .map_err(|e| QueryError::Execute(e.into()))
+        }
+        .boxed()
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        async move {
+            self.executor
+                .commit_tx(tx_id)
+                .await
+                .map_err(|e| QueryError::Execute(e.into()))
+        }
+        .boxed()
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        async move {
+            self.executor
+                .rollback_tx(tx_id)
+                .await
+                .map_err(|e| QueryError::Execute(e.into()))
+        }
+        .boxed()
+    }
+
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Runs every call to `inner` on `runtime`, so that the engine and the tasks of its connection pool
+/// stay on the runtime provided to the client builder's `with_runtime`.
+#[cfg(feature = "with-runtime")]
+pub(crate) struct RuntimeEngine {
+    inner: Arc<dyn QueryEngine>,
+    runtime: Handle,
+}
+
+#[cfg(feature = "with-runtime")]
+impl RuntimeEngine {
+    pub(crate) fn new(inner: Arc<dyn QueryEngine>, runtime: Handle) -> Self {
+        Self { inner, runtime }
+    }
+}
+
+#[cfg(feature = "with-runtime")]
+impl QueryEngine for RuntimeEngine {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, Result<serde_value::Value>> {
+        let inner = self.inner.clone();
+
+        spawn_on(&self.runtime, async move {
+            inner.execute(tx_id, operation).await
+        })
+        .map(cancelled)
+        .boxed()
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<'_, Result<Vec<Result<serde_value::Value>>>> {
+        let inner = self.inner.clone();
+
+        spawn_on(
+            &self.runtime,
+            async move { inner.execute_all(operations).await },
+        )
+        .map(cancelled)
+        .boxed()
+    }
+
+    fn start_tx(&self, options: TransactionOptions) -> BoxFuture<'_, Result<TxId>> {
+        let inner = self.inner.clone();
+
+        spawn_on(&self.runtime, async move { inner.start_tx(options).await })
+            .map(cancelled)
+            .boxed()
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        let inner = self.inner.clone();
+
+        spawn_on(&self.runtime, async move { inner.commit_tx(tx_id).await })
+            .map(cancelled)
+            .boxed()
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        let inner = self.inner.clone();
+
+        spawn_on(&self.runtime, async move { inner.rollback_tx(tx_id).await })
+            .map(cancelled)
+            .boxed()
+    }
+
+    fn url(&self) -> &str {
+        self.inner.url()
+    }
+}
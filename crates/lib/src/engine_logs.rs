@@ -0,0 +1,322 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use query_core::{Operation, TransactionOptions, TxId};
+use tracing::{
+    field::{Field, Visit},
+    span, Dispatch, Event, Level, Metadata, Subscriber,
+};
+
+use crate::{QueryEngine, Result};
+
+/// How much of the native query engine's logging is received, from most to least verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EngineLogLevel {
+    /// Every query the engine sends to the database, along with everything from `Info`.
+    Query,
+    Info,
+    Warn,
+    Error,
+}
+
+impl EngineLogLevel {
+    /// The level of events emitted at `level`, or `None` for those too verbose to be received.
+    /// The engine logs its queries at `DEBUG`, so that's where `Query` starts.
+    fn of(level: &Level) -> Option<Self> {
+        match *level {
+            Level::ERROR => Some(Self::Error),
+            Level::WARN => Some(Self::Warn),
+            Level::INFO => Some(Self::Info),
+            Level::DEBUG => Some(Self::Query),
+            _ => None,
+        }
+    }
+}
+
+/// A log emitted by the native query engine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineLog {
+    pub level: EngineLogLevel,
+    /// The module of the engine that emitted the log, eg. `quaint::connector::metrics`.
+    pub target: String,
+    pub message: Option<String>,
+    /// The SQL, or MongoDB command, of a `Query` log.
+    pub query: Option<String>,
+    /// The parameters bound to `query`.
+    pub params: Option<String>,
+    /// How long `query` took to run.
+    pub duration: Option<Duration>,
+    /// The log's other fields, formatted with `Debug`.
+    pub fields: Vec<(String, String)>,
+}
+
+/// Called with each [`EngineLog`] at or above the client's [`EngineLogLevel`].
+pub type EngineLogListener = Arc<dyn Fn(&EngineLog) + Send + Sync>;
+
+/// The engine log configuration of a client builder.
+pub struct EngineLogs {
+    level: EngineLogLevel,
+    listener: Option<EngineLogListener>,
+}
+
+impl EngineLogs {
+    /// `None` if neither a level nor a listener has been set,
+    /// otherwise `level` defaults to `Info` and `listener` to forwarding logs to `tracing`.
+    pub fn new(level: Option<EngineLogLevel>, listener: Option<EngineLogListener>) -> Option<Self> {
+        if level.is_none() && listener.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            level: level.unwrap_or(EngineLogLevel::Info),
+            listener,
+        })
+    }
+}
+
+/// Receives a client's engine logs and hands them to its listener.
+#[derive(Clone)]
+pub(crate) struct EngineLogger {
+    dispatch: Dispatch,
+    listener: Option<EngineLogListener>,
+}
+
+impl EngineLogger {
+    pub(crate) fn new(logs: EngineLogs) -> Self {
+        Self {
+            dispatch: Dispatch::new(EngineLogSubscriber { level: logs.level }),
+            listener: logs.listener,
+        }
+    }
+
+    fn deliver(&self) {
+        for log in PENDING.with(|pending| pending.take()) {
+            match &self.listener {
+                Some(listener) => listener(&log),
+                None => forward(&log),
+            }
+        }
+    }
+}
+
+thread_local! {
+    // events emitted from inside of a subscriber are dropped by `tracing`,
+    // so logs are delivered once the engine's poll has returned
+    static PENDING: RefCell<Vec<EngineLog>> = RefCell::new(vec![]);
+}
+
+/// Receives the `tracing` events that the engine emits while executing a client's operations.
+/// Spans aren't received, so the engine's spans aren't recorded anywhere while engine logs are enabled.
+struct EngineLogSubscriber {
+    level: EngineLogLevel,
+}
+
+#[derive(Default)]
+struct EngineLogFields {
+    message: Option<String>,
+    query: Option<String>,
+    params: Option<String>,
+    duration_ms: Option<u64>,
+    is_query: bool,
+    fields: Vec<(String, String)>,
+}
+
+impl EngineLogFields {
+    fn record(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = Some(value),
+            "query" => self.query = Some(value),
+            "params" => self.params = Some(value),
+            // also set by the engine's `is_query` field
+            "item_type" if value == "query" => self.is_query = true,
+            name => self.fields.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Visit for EngineLogFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{value:?}"))
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string())
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "duration_ms" => self.duration_ms = Some(value),
+            _ => self.record(field, value.to_string()),
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        match field.name() {
+            "duration_ms" => self.duration_ms = Some(value.max(0) as u64),
+            _ => self.record(field, value.to_string()),
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        match field.name() {
+            "is_query" => self.is_query |= value,
+            _ => self.record(field, value.to_string()),
+        }
+    }
+}
+
+impl Subscriber for EngineLogSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_event()
+            && EngineLogLevel::of(metadata.level()).map_or(false, |level| level >= self.level)
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+
+        let mut fields = EngineLogFields::default();
+        event.record(&mut fields);
+
+        let level = match (fields.is_query, EngineLogLevel::of(metadata.level())) {
+            (true, _) => EngineLogLevel::Query,
+            // the engine's other debug events are internal details rather than queries
+            (false, Some(EngineLogLevel::Query) | None) => return,
+            (false, Some(level)) => level,
+        };
+
+        if level < self.level {
+            return;
+        }
+
+        let log = EngineLog {
+            level,
+            target: metadata.target().to_string(),
+            message: fields.message,
+            query: fields.query,
+            params: fields.params,
+            duration: fields.duration_ms.map(Duration::from_millis),
+            fields: fields.fields,
+        };
+
+        PENDING.with(|pending| pending.borrow_mut().push(log));
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn forward(log: &EngineLog) {
+    macro_rules! forward {
+        ($level:expr) => {
+            tracing::event!(
+                target: "prisma_client_rust::engine",
+                $level,
+                engine_target = %log.target,
+                query = log.query.as_deref(),
+                params = log.params.as_deref(),
+                duration_ms = log.duration.map(|d| d.as_millis() as u64),
+                fields = ?log.fields,
+                "{}",
+                log.message.as_deref().unwrap_or_default()
+            )
+        };
+    }
+
+    // queries are only received once they've been asked for, so they aren't hidden at `DEBUG`
+    match log.level {
+        EngineLogLevel::Query | EngineLogLevel::Info => forward!(Level::INFO),
+        EngineLogLevel::Warn => forward!(Level::WARN),
+        EngineLogLevel::Error => forward!(Level::ERROR),
+    }
+}
+
+/// Polls `inner` with a client's engine log subscriber as the default `tracing` dispatcher.
+struct Logged<'a, T> {
+    inner: BoxFuture<'a, T>,
+    logger: EngineLogger,
+}
+
+impl<T> Future for Logged<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let poll =
+            tracing::dispatcher::with_default(&this.logger.dispatch, || this.inner.poll_unpin(cx));
+
+        this.logger.deliver();
+
+        poll
+    }
+}
+
+/// Receives the logs that the engine emits while executing each call to `inner`.
+pub(crate) struct LoggedEngine {
+    inner: Arc<dyn QueryEngine>,
+    logger: EngineLogger,
+}
+
+impl LoggedEngine {
+    pub(crate) fn new(inner: Arc<dyn QueryEngine>, logger: EngineLogger) -> Self {
+        Self { inner, logger }
+    }
+
+    fn logged<'a, T: 'a>(&self, inner: BoxFuture<'a, T>) -> BoxFuture<'a, T> {
+        Logged {
+            inner,
+            logger: self.logger.clone(),
+        }
+        .boxed()
+    }
+}
+
+impl QueryEngine for LoggedEngine {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, Result<serde_value::Value>> {
+        self.logged(self.inner.execute(tx_id, operation))
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<'_, Result<Vec<Result<serde_value::Value>>>> {
+        self.logged(self.inner.execute_all(operations))
+    }
+
+    fn start_tx(&self, options: TransactionOptions) -> BoxFuture<'_, Result<TxId>> {
+        self.logged(self.inner.start_tx(options))
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        self.logged(self.inner.commit_tx(tx_id))
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, Result<()>> {
+        self.logged(self.inner.rollback_tx(tx_id))
+    }
+
+    fn url(&self) -> &str {
+        self.inner.url()
+    }
+}
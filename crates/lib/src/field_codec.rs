@@ -0,0 +1,31 @@
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Deserializer};
+
+/// Transforms the values of a `String` field between what an application works with
+/// and what is stored in the database, eg. to encrypt them.
+///
+/// Codecs are assigned to fields with the generator's `field_codecs` option,
+/// after which `set` and `equals` encode values and generated `Data` structs decode them.
+/// They have no state of their own, so keys and other configuration need to be
+/// read from somewhere global like a `static`.
+pub trait FieldCodec {
+    fn encode(value: String) -> String;
+
+    /// Fails if `stored` isn't something [`encode`](FieldCodec::encode) could have produced,
+    /// failing the query with a deserialization error.
+    fn decode(stored: String) -> Result<String, String>;
+}
+
+/// A field's stored value, which is decoded by `C` when deserialized.
+pub struct Decoded<C>(pub String, PhantomData<C>);
+
+impl<'de, C: FieldCodec> Deserialize<'de> for Decoded<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = String::deserialize(deserializer)?;
+
+        C::decode(stored)
+            .map(|value| Self(value, PhantomData))
+            .map_err(serde::de::Error::custom)
+    }
+}
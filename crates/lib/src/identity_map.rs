@@ -0,0 +1,40 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use query_core::Operation;
+
+use crate::queries::selection_key;
+
+/// Memoizes the results of `findUnique` queries made inside a transaction,
+/// enabled with [`TransactionBuilder::with_identity_map`](crate::TransactionBuilder::with_identity_map).
+///
+/// Entries are keyed by the query's model, unique filter and selection.
+/// Every write made through the transaction clears the map,
+/// since nested writes and raw queries can change records of any model.
+#[derive(Default)]
+pub(crate) struct IdentityMap {
+    entries: Mutex<HashMap<String, serde_value::Value>>,
+}
+
+impl IdentityMap {
+    /// The key `operation` is stored under, if it is a `findUnique` query.
+    pub(crate) fn key(operation: &Operation) -> Option<String> {
+        match operation {
+            Operation::Read(selection) if selection.name().starts_with("findUnique") => {
+                Some(selection_key(selection))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<serde_value::Value> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    pub(crate) fn insert(&self, key: String, value: serde_value::Value) {
+        self.entries.lock().unwrap().insert(key, value);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,74 @@
+use prisma_models::PrismaValue;
+
+use crate::WhereInput;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexKind {
+    /// The model's `@id` or `@@id`.
+    PrimaryKey,
+    /// A `@unique` or `@@unique`.
+    Unique,
+    /// An `@@index`.
+    Normal,
+    /// A `@@fulltext`, which only applies to `search` filters.
+    Fulltext,
+}
+
+/// An index declared in the schema, generated in each model's `INDEXES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexDescriptor {
+    pub kind: IndexKind,
+    /// The names of the indexed fields in the Prisma schema, in index order.
+    pub fields: &'static [&'static str],
+}
+
+impl IndexDescriptor {
+    /// Whether filtering on `field` alone can use this index,
+    /// which is only the case for the first of an index's fields.
+    pub fn leads_with(&self, field: &str) -> bool {
+        self.kind != IndexKind::Fulltext && self.fields.first() == Some(&field)
+    }
+}
+
+/// The scalar fields filtered on by `params`, including inside of `and`, `or` and `not`,
+/// that none of `indexes` lead with. Relation filters are ignored.
+///
+/// Used by each model's generated `unindexed_filters`.
+pub fn unindexed_filters<W: WhereInput + Clone>(
+    indexes: &[IndexDescriptor],
+    scalar_fields: &[&'static str],
+    params: &[W],
+) -> Vec<&'static str> {
+    fn collect(entries: Vec<(String, PrismaValue)>, fields: &mut Vec<String>) {
+        for (field, value) in entries {
+            match (field.as_str(), value) {
+                ("AND" | "OR" | "NOT", PrismaValue::Object(entries)) => collect(entries, fields),
+                ("AND" | "OR" | "NOT", PrismaValue::List(values)) => {
+                    for value in values {
+                        if let PrismaValue::Object(entries) = value {
+                            collect(entries, fields)
+                        }
+                    }
+                }
+                _ => fields.push(field),
+            }
+        }
+    }
+
+    let mut fields = vec![];
+
+    collect(
+        params
+            .iter()
+            .map(|param| param.clone().serialize().into())
+            .collect(),
+        &mut fields,
+    );
+
+    scalar_fields
+        .iter()
+        .copied()
+        .filter(|scalar| fields.iter().any(|f| f.as_str() == *scalar))
+        .filter(|scalar| !indexes.iter().any(|index| index.leads_with(scalar)))
+        .collect()
+}
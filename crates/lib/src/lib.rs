@@ -1,43 +1,92 @@
 pub mod actions;
+#[cfg(feature = "change-feed")]
+mod change_feed;
 mod client;
+mod count_cache;
+#[cfg(feature = "disconnect-on-drop")]
+mod disconnect;
+mod engine;
+#[cfg(feature = "engine-logs")]
+mod engine_logs;
+mod field_codec;
 mod gen_macros;
+mod identity_map;
+mod indexes;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod middleware;
 #[cfg(feature = "migrations")]
 pub mod migrations;
 #[cfg(feature = "mocking")]
 mod mock;
+mod model_actions;
 pub mod operator;
+mod operation_json;
+mod pool;
+#[cfg(feature = "pool-events")]
+mod pool_events;
 mod prisma_value;
 pub mod queries;
 pub mod raw;
+mod raw_placeholders;
+mod schema_override;
 pub mod serde;
+mod socket;
+mod sqlite;
 mod traits;
 mod transaction;
+mod version;
 
 use std::collections::HashMap;
 
 pub use bigdecimal;
 pub use chrono;
+pub use futures;
 pub use prisma_models::{self, PrismaValue};
 pub use psl;
 pub use query_core;
 pub use query_core::{schema, Selection};
 pub use serde_json;
+pub use serde_value;
 use thiserror::Error;
-#[cfg(feature = "migrations")]
+#[cfg(any(feature = "migrations", feature = "with-runtime"))]
 pub use tokio;
 pub use user_facing_errors as prisma_errors;
 
 pub use actions::*;
+#[cfg(feature = "change-feed")]
+pub use change_feed::*;
 pub use client::*;
+pub use count_cache::{CountCache, MemoryCountCache};
+#[cfg(feature = "disconnect-on-drop")]
+pub use disconnect::DisconnectOnDrop;
+pub use engine::*;
+#[cfg(feature = "engine-logs")]
+pub use engine_logs::{EngineLog, EngineLogLevel, EngineLogListener, EngineLogs};
+pub use field_codec::{Decoded, FieldCodec};
+pub use indexes::*;
+#[cfg(feature = "metrics")]
+pub use self::metrics::{DurationHistogram, MetricsSnapshot, PoolMetrics};
+pub use middleware::{Context, Middleware, QueryInfo};
 #[cfg(feature = "mocking")]
 pub use mock::*;
+pub use model_actions::ModelActions;
+pub use operation_json::{operation_from_json, operation_to_json, response_from_json};
 pub use operator::Operator;
+pub use pool::*;
+#[cfg(feature = "pool-events")]
+pub use pool_events::{PoolEvent, PoolEventListener, PoolEventSubscription, PoolEventsError};
 pub use queries::*;
 pub use raw::*;
+pub use schema_override::{is_valid_schema_name, WithSchemaError};
+pub use socket::*;
+pub use sqlite::*;
 pub use traits::*;
 pub use transaction::*;
+pub use version::*;
 
 pub use prisma_client_rust_macros as macros;
+pub use prisma_client_rust_macros::IntoPrismaCreate;
 
 #[cfg(feature = "specta")]
 pub use specta;
@@ -80,6 +129,43 @@ impl std::fmt::Display for RelationNotFetchedError {
     }
 }
 
+/// Returned when converting a model's `Data` into its generated `Union`
+/// and the record doesn't have the fields its discriminator requires.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UnionConversionError {
+    #[error("Discriminator '{0}' is null")]
+    MissingDiscriminator(&'static str),
+    #[error("Variant '{variant}' requires field '{field}' but it is null")]
+    MissingField {
+        variant: &'static str,
+        field: &'static str,
+    },
+}
+
+/// Returned when building a compound unique filter from a model's `Data`
+/// and one of the unique's optional fields is null.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Compound unique field '{0}' is null")]
+pub struct NullUniqueFieldError(pub &'static str);
+
+/// Returned when parsing a string that isn't one of an enum's database values.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("'{value}' is not a value of enum '{enum_name}'")]
+pub struct ParseEnumError {
+    pub enum_name: &'static str,
+    pub value: String,
+}
+
+/// Returned when converting between enums listed in `enum_conversions`
+/// if a variant has no counterpart of the same name.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Variant '{variant}' of enum '{from}' has no equivalent in enum '{to}'")]
+pub struct EnumConversionError {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub variant: &'static str,
+}
+
 #[macro_export]
 macro_rules! not {
     ($($x:expr),+ $(,)?) => {
@@ -101,6 +187,34 @@ macro_rules! or {
     };
 }
 
+/// Executes named queries in a single `_batch`, resolving to a struct with a field for each name.
+///
+/// ```ignore
+/// let data = batch!(client, {
+///     user: client.user().find_unique(user::id::equals(id)),
+///     posts: client.post().find_many(vec![]),
+/// })
+/// .await?;
+///
+/// println!("{} has {} posts", data.user.unwrap().name, data.posts.len());
+/// ```
+#[macro_export]
+macro_rules! batch {
+    ($client:expr, { $($name:ident: $query:expr),+ $(,)? }) => {
+        async {
+            #[allow(dead_code, non_camel_case_types)]
+            struct Batch<$($name),+> {
+                $(pub $name: $name),+
+            }
+
+            #[allow(unused_parens)]
+            let ($($name),+) = $client._batch(($($query),+)).await?;
+
+            Ok::<_, $crate::QueryError>(Batch { $($name),+ })
+        }
+    };
+}
+
 pub type ObjectFields = Vec<(String, PrismaValue)>;
 
 /// Creates a PrismaValue::Object from a list of key-value pairs.
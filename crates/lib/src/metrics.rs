@@ -0,0 +1,248 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::QueryError;
+
+/// Upper bounds of the query duration histogram's buckets, in milliseconds.
+const DURATION_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Counters shared by a client and every transaction client created from it.
+///
+/// Recording only touches atomics, apart from the per-operation and per-error
+/// maps which are locked for a single increment.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    connection_limit: Option<usize>,
+    queries: Mutex<BTreeMap<String, u64>>,
+    errors: Mutex<BTreeMap<String, u64>>,
+    buckets: [AtomicU64; DURATION_BUCKETS_MS.len()],
+    duration_count: AtomicU64,
+    duration_sum_us: AtomicU64,
+    in_flight: AtomicU64,
+    max_in_flight: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new(connection_limit: Option<usize>) -> Self {
+        Self {
+            connection_limit,
+            ..Default::default()
+        }
+    }
+
+    /// Marks a call to the engine as started.
+    /// The call is recorded as finished when the returned timer is dropped.
+    pub fn start<'a>(&self, operations: impl IntoIterator<Item = &'a str>) -> QueryTimer<'_> {
+        {
+            let mut queries = self.queries.lock().unwrap();
+
+            for operation in operations {
+                *queries.entry(operation.to_string()).or_default() += 1;
+            }
+        }
+
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::Relaxed);
+
+        QueryTimer {
+            metrics: self,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn record_error(&self, error: &QueryError) {
+        let kind = match error {
+            QueryError::InQuery { source, .. } => return self.record_error(source),
+            QueryError::Execute(e) => e
+                .as_known()
+                .map(|k| k.error_code.to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            QueryError::Serialize(_) => "Serialize".to_string(),
+            QueryError::Deserialize(_) => "Deserialize".to_string(),
+            QueryError::UnexpectedNull { .. } => "UnexpectedNull".to_string(),
+            QueryError::SerializeOverride(_) => "SerializeOverride".to_string(),
+            QueryError::VersionConflict => "VersionConflict".to_string(),
+            QueryError::UnsupportedRelationLoadStrategy => {
+                "UnsupportedRelationLoadStrategy".to_string()
+            }
+            QueryError::UnsupportedJsonPathOrder => "UnsupportedJsonPathOrder".to_string(),
+            QueryError::UnsupportedCollatedOrder => "UnsupportedCollatedOrder".to_string(),
+            QueryError::UnsupportedCreateManyReturning => {
+                "UnsupportedCreateManyReturning".to_string()
+            }
+            QueryError::UnfilteredMutation => "UnfilteredMutation".to_string(),
+            QueryError::RawParameters { .. } => "RawParameters".to_string(),
+            QueryError::Cancelled => "Cancelled".to_string(),
+            QueryError::Rejected(_) => "Rejected".to_string(),
+        };
+
+        *self.errors.lock().unwrap().entry(kind).or_default() += 1;
+    }
+
+    fn record_duration(&self, duration: Duration) {
+        // compared in microseconds, so that eg. 1.9ms isn't counted as taking at most 1ms
+        let us = duration.as_micros();
+
+        for (bucket, bound) in self.buckets.iter().zip(DURATION_BUCKETS_MS) {
+            if us <= bound as u128 * 1000 {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        self.duration_count.fetch_add(1, Ordering::Relaxed);
+        self.duration_sum_us
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            queries: self.queries.lock().unwrap().clone(),
+            errors: self.errors.lock().unwrap().clone(),
+            duration: DurationHistogram {
+                buckets: DURATION_BUCKETS_MS
+                    .iter()
+                    .zip(&self.buckets)
+                    .map(|(bound, count)| {
+                        (Duration::from_millis(*bound), count.load(Ordering::Relaxed))
+                    })
+                    .collect(),
+                count: self.duration_count.load(Ordering::Relaxed),
+                sum: Duration::from_micros(self.duration_sum_us.load(Ordering::Relaxed)),
+            },
+            pool: PoolMetrics {
+                connection_limit: self.connection_limit,
+                in_flight: self.in_flight.load(Ordering::Relaxed),
+                max_in_flight: self.max_in_flight.load(Ordering::Relaxed),
+            },
+        }
+    }
+}
+
+pub(crate) struct QueryTimer<'a> {
+    metrics: &'a Metrics,
+    start: Instant,
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        self.metrics.record_duration(self.start.elapsed());
+        self.metrics.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A point-in-time copy of a client's metrics, returned by `PrismaClient::metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MetricsSnapshot {
+    /// Number of operations sent to the engine, keyed by operation name (eg. `findManyUser`).
+    /// Each operation in a batch is counted individually.
+    pub queries: BTreeMap<String, u64>,
+    /// Number of failed operations, keyed by Prisma error code (eg. `P2002`),
+    /// `Unknown` for engine errors without a code, or `Serialize`/`Deserialize`.
+    pub errors: BTreeMap<String, u64>,
+    /// Duration of each call to the engine. A batch is a single call.
+    pub duration: DurationHistogram,
+    pub pool: PoolMetrics,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DurationHistogram {
+    /// Cumulative counts of calls that took at most each bucket's duration.
+    pub buckets: Vec<(Duration, u64)>,
+    pub count: u64,
+    pub sum: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PoolMetrics {
+    /// The configured connection limit, or `None` if the engine's default is used.
+    pub connection_limit: Option<usize>,
+    /// Calls to the engine currently waiting on a connection or a result.
+    pub in_flight: u64,
+    /// The most calls that have been in flight at once.
+    pub max_in_flight: u64,
+}
+
+impl MetricsSnapshot {
+    pub fn total_queries(&self) -> u64 {
+        self.queries.values().sum()
+    }
+
+    pub fn total_errors(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// Renders the snapshot in the Prometheus text exposition format,
+    /// with every metric name prefixed by `prisma_client_`.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# TYPE prisma_client_queries_total counter\n");
+        for (operation, count) in &self.queries {
+            writeln!(
+                out,
+                "prisma_client_queries_total{{operation=\"{operation}\"}} {count}"
+            )
+            .ok();
+        }
+
+        out.push_str("# TYPE prisma_client_errors_total counter\n");
+        for (kind, count) in &self.errors {
+            writeln!(out, "prisma_client_errors_total{{kind=\"{kind}\"}} {count}").ok();
+        }
+
+        out.push_str("# TYPE prisma_client_query_duration_seconds histogram\n");
+        for (bound, count) in &self.duration.buckets {
+            writeln!(
+                out,
+                "prisma_client_query_duration_seconds_bucket{{le=\"{}\"}} {count}",
+                bound.as_secs_f64()
+            )
+            .ok();
+        }
+        writeln!(
+            out,
+            "prisma_client_query_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            self.duration.count
+        )
+        .ok();
+        writeln!(
+            out,
+            "prisma_client_query_duration_seconds_sum {}",
+            self.duration.sum.as_secs_f64()
+        )
+        .ok();
+        writeln!(
+            out,
+            "prisma_client_query_duration_seconds_count {}",
+            self.duration.count
+        )
+        .ok();
+
+        if let Some(limit) = self.pool.connection_limit {
+            out.push_str("# TYPE prisma_client_pool_connection_limit gauge\n");
+            writeln!(out, "prisma_client_pool_connection_limit {limit}").ok();
+        }
+
+        out.push_str("# TYPE prisma_client_pool_in_flight gauge\n");
+        writeln!(out, "prisma_client_pool_in_flight {}", self.pool.in_flight).ok();
+
+        out.push_str("# TYPE prisma_client_pool_max_in_flight gauge\n");
+        writeln!(
+            out,
+            "prisma_client_pool_max_in_flight {}",
+            self.pool.max_in_flight
+        )
+        .ok();
+
+        out
+    }
+}
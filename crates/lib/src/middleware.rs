@@ -0,0 +1,57 @@
+use std::{any::Any, sync::Arc};
+
+use query_core::Operation;
+
+use crate::Result;
+
+/// Data attached to a client with `_with_context`, eg. the current tenant or user.
+pub type Context = Arc<dyn Any + Send + Sync>;
+
+/// Called before every query a client executes, including those in batches and transactions.
+/// Returning an error prevents the query from being executed.
+pub type Middleware = Arc<dyn Fn(&QueryInfo) -> Result<()> + Send + Sync>;
+
+/// The query a [`Middleware`] is being called for.
+pub struct QueryInfo<'a> {
+    operation: &'a Operation,
+    context: Option<&'a Context>,
+}
+
+impl<'a> QueryInfo<'a> {
+    pub(crate) fn new(operation: &'a Operation, context: Option<&'a Context>) -> Self {
+        Self { operation, context }
+    }
+
+    /// The name of the engine operation, eg. `findManyPost` or `deleteOneUser`.
+    pub fn name(&self) -> &str {
+        match self.operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection.name(),
+        }
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self.operation, Operation::Write(_))
+    }
+
+    pub fn operation(&self) -> &Operation {
+        self.operation
+    }
+
+    /// The context of the client executing the query,
+    /// or `None` if it has no context or its context isn't a `T`.
+    pub fn context<T: Any>(&self) -> Option<&T> {
+        self.context?.downcast_ref()
+    }
+}
+
+pub(crate) fn run_middleware(
+    middleware: &[Middleware],
+    operation: &Operation,
+    context: Option<&Context>,
+) -> Result<()> {
+    let info = QueryInfo::new(operation, context);
+
+    middleware
+        .iter()
+        .try_for_each(|middleware| middleware(&info))
+}
@@ -50,3 +50,53 @@ impl MockStore {
             .map(|(_, e)| e.clone())
     }
 }
+
+/// A placeholder value for a field of a record created with a model's `Data::mock()`.
+///
+/// Implemented for every type a field can have by default,
+/// so types set with `type_overrides` only need it when they're used by a required field.
+pub trait MockValue {
+    fn mock() -> Self;
+}
+
+macro_rules! mock_default {
+    ($($typ:ty),+) => {
+        $(impl MockValue for $typ {
+            fn mock() -> Self {
+                Default::default()
+            }
+        })+
+    };
+}
+
+mock_default!(String, bool, i16, i32, i64, f64, bigdecimal::BigDecimal);
+
+impl<T> MockValue for Option<T> {
+    fn mock() -> Self {
+        None
+    }
+}
+
+impl<T> MockValue for Vec<T> {
+    fn mock() -> Self {
+        vec![]
+    }
+}
+
+impl MockValue for serde_json::Value {
+    fn mock() -> Self {
+        serde_json::Value::Null
+    }
+}
+
+impl MockValue for chrono::DateTime<chrono::Utc> {
+    fn mock() -> Self {
+        std::time::UNIX_EPOCH.into()
+    }
+}
+
+impl MockValue for chrono::DateTime<chrono::FixedOffset> {
+    fn mock() -> Self {
+        chrono::DateTime::<chrono::Utc>::mock().into()
+    }
+}
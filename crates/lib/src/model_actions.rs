@@ -0,0 +1,24 @@
+use futures::future::BoxFuture;
+
+use crate::Result;
+
+/// Operations common to every model, for generic code such as admin panels that works with any schema.
+///
+/// Implemented by each model's `Actions` when the generator's `model_actions` option is enabled,
+/// and listed for every model by the client's `_model_actions`.
+/// Records are returned as JSON, serialized the same way as the model's `Data`.
+pub trait ModelActions<'a>: Send + Sync {
+    /// The model's name in the Prisma schema.
+    fn model_name(&self) -> &'static str;
+
+    fn find_many(
+        &self,
+        skip: Option<i64>,
+        take: Option<i64>,
+    ) -> BoxFuture<'a, Result<Vec<serde_json::Value>>>;
+
+    fn count(&self) -> BoxFuture<'a, Result<i64>>;
+
+    /// Deletes every record of the model, returning how many were deleted.
+    fn delete_many(&self) -> BoxFuture<'a, Result<i64>>;
+}
@@ -0,0 +1,244 @@
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, FromPrimitive};
+use chrono::DateTime;
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::QueryError;
+
+/// Key of the objects that values without a JSON equivalent are wrapped in,
+/// alongside a `value` holding their string representation.
+const TYPE_KEY: &str = "$type";
+
+/// Converts `operation` to JSON, so that a [`QueryEngine`](crate::QueryEngine) can send it to another process,
+/// which can get the operation back with [`operation_from_json`].
+///
+/// ```json
+/// {
+///   "kind": "read",
+///   "selection": {
+///     "name": "findManyUser",
+///     "alias": null,
+///     "arguments": { "where": { "name": { "equals": "Brendan" } } },
+///     "selections": [{ "name": "id", "alias": null, "arguments": {}, "selections": [] }]
+///   }
+/// }
+/// ```
+///
+/// Strings, booleans, integers, lists, objects and nulls are written as they are.
+/// Other values are written as an object of their type and a string,
+/// eg. `{ "$type": "DateTime", "value": "2023-01-01T00:00:00+00:00" }`,
+/// with `Enum`, `Uuid`, `Json`, `Xml`, `DateTime`, `Decimal`, `BigInt` and `Bytes` (hex encoded) as types.
+pub fn operation_to_json(operation: &Operation) -> Result<Value, QueryError> {
+    let (kind, selection) = match operation {
+        Operation::Read(selection) => ("read", selection),
+        Operation::Write(selection) => ("write", selection),
+    };
+
+    Ok(json!({
+        "kind": kind,
+        "selection": selection_to_json(selection)?,
+    }))
+}
+
+/// The operation that [`operation_to_json`] converted to `value`.
+pub fn operation_from_json(value: Value) -> Result<Operation, QueryError> {
+    let mut fields = into_object(value, "operation")?;
+
+    let selection = selection_from_json(take(&mut fields, "selection")?)?;
+
+    match take(&mut fields, "kind")?.as_str() {
+        Some("read") => Ok(Operation::Read(selection)),
+        Some("write") => Ok(Operation::Write(selection)),
+        _ => Err(invalid("an operation's kind must be \"read\" or \"write\"")),
+    }
+}
+
+/// Converts the JSON response of an engine in another process into the value
+/// that [`QueryEngine`](crate::QueryEngine) methods return.
+pub fn response_from_json(value: Value) -> Result<serde_value::Value, QueryError> {
+    serde_value::to_value(value).map_err(|e| QueryError::Deserialize(e.to_string()))
+}
+
+fn selection_to_json(selection: &Selection) -> Result<Value, QueryError> {
+    let arguments = selection
+        .arguments()
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), argument_to_json(value)?)))
+        .collect::<Result<Map<_, _>, QueryError>>()?;
+
+    let selections = selection
+        .nested_selections()
+        .iter()
+        .map(selection_to_json)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(json!({
+        "name": selection.name(),
+        "alias": selection.alias(),
+        "arguments": arguments,
+        "selections": selections,
+    }))
+}
+
+fn selection_from_json(value: Value) -> Result<Selection, QueryError> {
+    let mut fields = into_object(value, "selection")?;
+
+    let name = match take(&mut fields, "name")? {
+        Value::String(name) => name,
+        _ => return Err(invalid("a selection's name must be a string")),
+    };
+
+    let alias = match take(&mut fields, "alias")? {
+        Value::String(alias) => Some(alias),
+        Value::Null => None,
+        _ => return Err(invalid("a selection's alias must be a string or null")),
+    };
+
+    let arguments = into_object(take(&mut fields, "arguments")?, "arguments")?
+        .into_iter()
+        .map(|(name, value)| Ok((name, value_from_json(value)?.into())))
+        .collect::<Result<Vec<(String, ArgumentValue)>, QueryError>>()?;
+
+    let selections = match take(&mut fields, "selections")? {
+        Value::Array(selections) => selections
+            .into_iter()
+            .map(selection_from_json)
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => return Err(invalid("a selection's selections must be a list")),
+    };
+
+    Ok(Selection::new(name, alias, arguments, selections))
+}
+
+pub(crate) fn argument_to_json(value: &ArgumentValue) -> Result<Value, QueryError> {
+    match value {
+        ArgumentValue::Scalar(value) => Ok(value_to_json(value)),
+        ArgumentValue::Object(fields) => fields
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), argument_to_json(value)?)))
+            .collect::<Result<Map<_, _>, QueryError>>()
+            .map(Value::Object),
+        ArgumentValue::List(values) => values
+            .iter()
+            .map(argument_to_json)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        // only built by the engine itself, never by the generated client
+        other => Err(QueryError::Serialize(format!(
+            "{other:?} can't be converted to JSON"
+        ))),
+    }
+}
+
+pub(crate) fn value_to_json(value: &PrismaValue) -> Value {
+    let typed = |ty: &str, value: String| json!({ TYPE_KEY: ty, "value": value });
+
+    match value {
+        PrismaValue::String(value) => Value::String(value.clone()),
+        PrismaValue::Boolean(value) => Value::Bool(*value),
+        PrismaValue::Int(value) => Value::from(*value),
+        PrismaValue::Null => Value::Null,
+        PrismaValue::List(values) => Value::Array(values.iter().map(value_to_json).collect()),
+        PrismaValue::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value_to_json(value)))
+                .collect(),
+        ),
+        PrismaValue::Enum(value) => typed("Enum", value.clone()),
+        PrismaValue::Uuid(value) => typed("Uuid", value.to_string()),
+        PrismaValue::Json(value) => typed("Json", value.clone()),
+        PrismaValue::Xml(value) => typed("Xml", value.clone()),
+        PrismaValue::DateTime(value) => typed("DateTime", value.to_rfc3339()),
+        PrismaValue::Float(value) => typed("Decimal", value.to_string()),
+        PrismaValue::BigInt(value) => typed("BigInt", value.to_string()),
+        PrismaValue::Bytes(value) => typed(
+            "Bytes",
+            value.iter().map(|byte| format!("{byte:02x}")).collect(),
+        ),
+    }
+}
+
+fn value_from_json(value: Value) -> Result<PrismaValue, QueryError> {
+    Ok(match value {
+        Value::String(value) => PrismaValue::String(value),
+        Value::Bool(value) => PrismaValue::Boolean(value),
+        Value::Null => PrismaValue::Null,
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => PrismaValue::Int(value),
+            None => number
+                .as_f64()
+                .and_then(BigDecimal::from_f64)
+                .map(PrismaValue::Float)
+                .ok_or_else(|| invalid(format!("{number} is not a valid number")))?,
+        },
+        Value::Array(values) => PrismaValue::List(
+            values
+                .into_iter()
+                .map(value_from_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(mut fields) => match fields.remove(TYPE_KEY) {
+            Some(Value::String(ty)) => match fields.remove("value") {
+                Some(Value::String(value)) => typed_from_json(&ty, value)?,
+                _ => return Err(invalid(format!("a {ty} value must be a string"))),
+            },
+            Some(_) => return Err(invalid(format!("{TYPE_KEY} must be a string"))),
+            None => PrismaValue::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| Ok((name, value_from_json(value)?)))
+                    .collect::<Result<_, QueryError>>()?,
+            ),
+        },
+    })
+}
+
+fn typed_from_json(ty: &str, value: String) -> Result<PrismaValue, QueryError> {
+    let parse_error = || invalid(format!("'{value}' is not a valid {ty}"));
+
+    Ok(match ty {
+        "Enum" => PrismaValue::Enum(value),
+        "Json" => PrismaValue::Json(value),
+        "Xml" => PrismaValue::Xml(value),
+        "Uuid" => PrismaValue::Uuid(Uuid::parse_str(&value).map_err(|_| parse_error())?),
+        "DateTime" => {
+            PrismaValue::DateTime(DateTime::parse_from_rfc3339(&value).map_err(|_| parse_error())?)
+        }
+        "Decimal" => PrismaValue::Float(BigDecimal::from_str(&value).map_err(|_| parse_error())?),
+        "BigInt" => PrismaValue::BigInt(value.parse().map_err(|_| parse_error())?),
+        "Bytes" => PrismaValue::Bytes(
+            (0..value.len())
+                .step_by(2)
+                .map(|i| {
+                    value
+                        .get(i..i + 2)
+                        .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                })
+                .collect::<Option<_>>()
+                .ok_or_else(parse_error)?,
+        ),
+        _ => return Err(invalid(format!("'{ty}' is not a known value type"))),
+    })
+}
+
+fn into_object(value: Value, what: &str) -> Result<Map<String, Value>, QueryError> {
+    match value {
+        Value::Object(fields) => Ok(fields),
+        _ => Err(invalid(format!("{what} must be an object"))),
+    }
+}
+
+fn take(fields: &mut Map<String, Value>, name: &str) -> Result<Value, QueryError> {
+    fields
+        .remove(name)
+        .ok_or_else(|| invalid(format!("missing field '{name}'")))
+}
+
+fn invalid(message: impl Into<String>) -> QueryError {
+    QueryError::Deserialize(message.into())
+}
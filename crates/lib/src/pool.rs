@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// Connection pool settings for the query engine.
+///
+/// The engine reads these from the datasource URL's query parameters,
+/// so they are appended to the URL before the executor is loaded.
+/// Any setting left as `None` falls back to the engine's default:
+///
+/// | Setting            | URL parameter                  | Engine default      |
+/// | ------------------ | ------------------------------ | ------------------- |
+/// | `connection_limit` | `connection_limit`             | `num_cpus * 2 + 1`  |
+/// | `pool_timeout`     | `pool_timeout`                 | 10 seconds          |
+/// | `connect_timeout`  | `connect_timeout`              | 5 seconds           |
+/// | `idle_timeout`     | `max_idle_connection_lifetime` | no limit            |
+///
+/// Timeouts are passed to the engine in whole seconds, so durations with a fractional part are rejected.
+/// A `pool_timeout` of zero disables the timeout, waiting for a free connection indefinitely.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub connection_limit: Option<usize>,
+    pub pool_timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub idle_timeout: Option<Duration>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PoolConfigError {
+    #[error("connection_limit must be at least 1")]
+    ZeroConnectionLimit,
+    #[error("{0} must be at least one second")]
+    TimeoutTooShort(&'static str),
+    #[error("{0} must be a whole number of seconds")]
+    FractionalTimeout(&'static str),
+}
+
+impl PoolConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn connection_limit(mut self, limit: usize) -> Self {
+        self.connection_limit = Some(limit);
+        self
+    }
+
+    pub fn pool_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), PoolConfigError> {
+        if self.connection_limit == Some(0) {
+            return Err(PoolConfigError::ZeroConnectionLimit);
+        }
+
+        for (name, timeout) in self.timeouts() {
+            let timeout = match timeout {
+                Some(timeout) => timeout,
+                None => continue,
+            };
+
+            if timeout.subsec_nanos() != 0 {
+                return Err(PoolConfigError::FractionalTimeout(name));
+            }
+
+            // the engine treats a pool timeout of 0 as waiting forever
+            if timeout.is_zero() && name != "pool_timeout" {
+                return Err(PoolConfigError::TimeoutTooShort(name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn timeouts(&self) -> [(&'static str, Option<Duration>); 3] {
+        [
+            ("pool_timeout", self.pool_timeout),
+            ("connect_timeout", self.connect_timeout),
+            ("idle_timeout", self.idle_timeout),
+        ]
+    }
+
+    fn url_params(&self) -> Vec<(&'static str, String)> {
+        [
+            self.connection_limit
+                .map(|limit| ("connection_limit", limit.to_string())),
+            self.pool_timeout
+                .map(|t| ("pool_timeout", t.as_secs().to_string())),
+            self.connect_timeout
+                .map(|t| ("connect_timeout", t.as_secs().to_string())),
+            self.idle_timeout
+                .map(|t| ("max_idle_connection_lifetime", t.as_secs().to_string())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Validates the config and writes it into `url`'s query string,
+    /// replacing any parameters of the same name that are already present.
+    pub fn apply_to_url(&self, url: &str) -> Result<String, PoolConfigError> {
+        self.validate()?;
+
+        Ok(set_url_params(url, self.url_params()))
+    }
+}
+
+/// Writes `params` into `url`'s query string,
+/// replacing any parameters of the same name that are already present.
+pub(crate) fn set_url_params(url: &str, params: Vec<(&'static str, String)>) -> String {
+    if params.is_empty() {
+        return url.to_string();
+    }
+
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+
+    let pairs = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !params.iter().any(|(name, _)| *name == key)
+        })
+        .map(ToString::to_string)
+        .chain(
+            params
+                .into_iter()
+                .map(|(name, value)| format!("{name}={value}")),
+        )
+        .collect::<Vec<_>>();
+
+    format!("{base}?{}", pairs.join("&"))
+}
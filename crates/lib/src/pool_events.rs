@@ -0,0 +1,189 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use metrics_rs::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Recorder, Unit,
+};
+use once_cell::sync::Lazy;
+use thiserror::Error;
+
+/// A change in the state of the query engine's connection pool,
+/// as reported by the pool through the `metrics` crate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolEvent {
+    /// A connection to the database was opened.
+    ConnectionCreated,
+    /// A connection to the database was closed, eg. after being idle for the pool's `idle_timeout`.
+    ConnectionClosed,
+    /// A connection was taken from the pool to run a query or transaction.
+    ConnectionAcquired,
+    /// A connection was returned to the pool.
+    ConnectionReleased,
+    /// How long a connection took to acquire, which grows once every connection is in use.
+    Waited(Duration),
+}
+
+/// Called with each [`PoolEvent`], from whichever thread the pool reports it on.
+pub type PoolEventListener = Arc<dyn Fn(&PoolEvent) + Send + Sync>;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PoolEventsError {
+    /// Pool events are received by installing a global `metrics` recorder,
+    /// and only one can be installed per process.
+    #[error("A metrics recorder is already installed, so pool events can't be observed")]
+    RecorderInstalled,
+}
+
+// copied on write so that listeners aren't called with the lock held
+static LISTENERS: Lazy<RwLock<Arc<Vec<(u64, PoolEventListener)>>>> = Lazy::new(Default::default);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static RECORDER: PoolEventRecorder = PoolEventRecorder;
+static INSTALLED: Lazy<bool> = Lazy::new(|| metrics_rs::set_recorder(&RECORDER).is_ok());
+
+/// Keeps a client's listeners registered until it, and every client created from it, is dropped.
+pub struct PoolEventSubscription {
+    id: u64,
+}
+
+impl PoolEventSubscription {
+    /// Registers `listeners`, or returns `None` without installing the recorder if there are none.
+    pub fn new(listeners: Vec<PoolEventListener>) -> Result<Option<Self>, PoolEventsError> {
+        if listeners.is_empty() {
+            return Ok(None);
+        }
+
+        if !*INSTALLED {
+            return Err(PoolEventsError::RecorderInstalled);
+        }
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        let mut registered = LISTENERS.write().unwrap();
+        let mut updated = Vec::clone(&registered);
+        updated.extend(listeners.into_iter().map(|listener| (id, listener)));
+        *registered = Arc::new(updated);
+
+        Ok(Some(Self { id }))
+    }
+}
+
+impl Drop for PoolEventSubscription {
+    fn drop(&mut self) {
+        let mut registered = LISTENERS.write().unwrap();
+        let updated = registered
+            .iter()
+            .filter(|(id, _)| *id != self.id)
+            .cloned()
+            .collect();
+        *registered = Arc::new(updated);
+    }
+}
+
+fn emit(event: PoolEvent, times: u64) {
+    let listeners = LISTENERS.read().unwrap().clone();
+
+    for _ in 0..times {
+        for (_, listener) in listeners.iter() {
+            listener(&event);
+        }
+    }
+}
+
+/// The pool's metrics that events are derived from.
+/// They're matched by suffix since the engine may prefix them with either `mobc_` or `prisma_`.
+#[derive(Clone, Copy)]
+enum PoolMetric {
+    Opened,
+    Closed,
+    Busy,
+    WaitDuration,
+}
+
+impl PoolMetric {
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            ("pool_connections_opened_total", Self::Opened),
+            ("pool_connections_closed_total", Self::Closed),
+            ("pool_connections_busy", Self::Busy),
+            ("client_queries_wait_histogram_ms", Self::WaitDuration),
+        ]
+        .into_iter()
+        .find(|(suffix, _)| name.ends_with(suffix))
+        .map(|(_, metric)| metric)
+    }
+}
+
+impl CounterFn for PoolMetric {
+    fn increment(&self, value: u64) {
+        match self {
+            Self::Opened => emit(PoolEvent::ConnectionCreated, value),
+            Self::Closed => emit(PoolEvent::ConnectionClosed, value),
+            _ => {}
+        }
+    }
+
+    fn absolute(&self, _: u64) {}
+}
+
+impl GaugeFn for PoolMetric {
+    fn increment(&self, value: f64) {
+        if let Self::Busy = self {
+            emit(PoolEvent::ConnectionAcquired, value as u64)
+        }
+    }
+
+    fn decrement(&self, value: f64) {
+        if let Self::Busy = self {
+            emit(PoolEvent::ConnectionReleased, value as u64)
+        }
+    }
+
+    fn set(&self, _: f64) {}
+}
+
+impl HistogramFn for PoolMetric {
+    fn record(&self, value: f64) {
+        if let Self::WaitDuration = self {
+            let waited = Duration::from_secs_f64(value.max(0.0) / 1000.0);
+
+            emit(PoolEvent::Waited(waited), 1)
+        }
+    }
+}
+
+/// Forwards the pool's metrics to the registered listeners, ignoring every other metric.
+struct PoolEventRecorder;
+
+impl Recorder for PoolEventRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+    fn register_counter(&self, key: &Key) -> Counter {
+        match PoolMetric::from_name(key.name()) {
+            Some(metric) => Counter::from_arc(Arc::new(metric)),
+            None => Counter::noop(),
+        }
+    }
+
+    fn register_gauge(&self, key: &Key) -> Gauge {
+        match PoolMetric::from_name(key.name()) {
+            Some(metric) => Gauge::from_arc(Arc::new(metric)),
+            None => Gauge::noop(),
+        }
+    }
+
+    fn register_histogram(&self, key: &Key) -> Histogram {
+        match PoolMetric::from_name(key.name()) {
+            Some(metric) => Histogram::from_arc(Arc::new(metric)),
+            None => Histogram::noop(),
+        }
+    }
+}
@@ -74,7 +74,11 @@ impl From<prisma_models::PrismaValue> for PrismaValue {
             prisma_models::PrismaValue::String(value) => Self::String(value),
             prisma_models::PrismaValue::Boolean(value) => Self::Boolean(value),
             prisma_models::PrismaValue::Enum(value) => Self::Enum(value),
-            prisma_models::PrismaValue::Int(value) => Self::Int(value as i32),
+            // BigInt columns can come back as Int, which shouldn't be truncated
+            prisma_models::PrismaValue::Int(value) => match i32::try_from(value) {
+                Ok(value) => Self::Int(value),
+                Err(_) => Self::BigInt(value),
+            },
             prisma_models::PrismaValue::Uuid(value) => Self::Uuid(value),
             prisma_models::PrismaValue::List(value) => {
                 Self::List(value.into_iter().map(Into::into).collect())
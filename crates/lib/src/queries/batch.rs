@@ -89,7 +89,6 @@ pub async fn batch<'batch, 'b, T: BatchContainer<'batch, Marker>, Marker>(
     let operations = data.operations();
 
     let values = client
-        .engine
         .execute_all(operations)
         .await?
         .into_iter()
@@ -124,13 +123,9 @@ impl<'a, 'b, Q: Query<'a>> BatchItem<'b> for Q {
         _: &BatchItemDataMeta,
         values: &mut VecDeque<serde_value::Value>,
     ) -> super::Result<<Self as BatchItemParent>::ReturnValue> {
-        Q::convert(
-            values
-                .pop_front()
-                .unwrap()
-                .deserialize_into::<Q::RawType>()
-                .unwrap(),
-        )
+        Q::convert(super::deserialize_response::<Q::RawType>(
+            values.pop_front().unwrap(),
+        )?)
     }
 }
 
@@ -0,0 +1,77 @@
+use query_core::{ArgumentValue, Operation, Selection};
+use serde_json::{json, Map, Value};
+
+use crate::{operation_json::argument_to_json, Query};
+
+/// Builds a string identifying a query's model, operation, arguments and selection,
+/// suitable for use as a key in an application-level cache.
+///
+/// The key is the operation's kind followed by its selection as JSON, eg. `read:{"alias":null,...}`,
+/// with values written as they are by [`operation_to_json`](crate::operation_to_json).
+/// Object keys and nested selections are sorted, so queries that only differ in the order
+/// their filters or fields were provided in produce the same key.
+/// Lists such as `orderBy` keep their order since it affects the result.
+pub fn cache_key<'a, Q: Query<'a>>(query: Q) -> String {
+    operation_key(&query.graphql().0)
+}
+
+pub(crate) fn operation_key(operation: &Operation) -> String {
+    let (kind, selection) = match operation {
+        Operation::Read(selection) => ("read", selection),
+        Operation::Write(selection) => ("write", selection),
+    };
+
+    format!("{kind}:{}", selection_key(selection))
+}
+
+pub(crate) fn selection_key(selection: &Selection) -> String {
+    canonical(&selection_json(selection))
+}
+
+fn selection_json(selection: &Selection) -> Value {
+    let arguments = selection
+        .arguments()
+        .iter()
+        .map(|(name, value)| (name.clone(), argument_json(value)))
+        .collect::<Map<_, _>>();
+
+    let mut selections = selection
+        .nested_selections()
+        .iter()
+        .map(selection_json)
+        .collect::<Vec<_>>();
+    selections.sort_by_cached_key(canonical);
+
+    json!({
+        "name": selection.name(),
+        "alias": selection.alias(),
+        "arguments": arguments,
+        "selections": selections,
+    })
+}
+
+fn argument_json(value: &ArgumentValue) -> Value {
+    // only arguments built by the engine itself can't be converted, which queries never contain
+    argument_to_json(value).unwrap_or_else(|error| Value::String(error.to_string()))
+}
+
+/// `value` as JSON with the keys of every object sorted,
+/// which `serde_json` only does itself when its `preserve_order` feature is disabled.
+pub(crate) fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => {
+            let mut fields = fields
+                .iter()
+                .map(|(name, value)| format!("{}:{}", Value::from(name.as_str()), canonical(value)))
+                .collect::<Vec<_>>();
+            fields.sort();
+
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(canonical).collect::<Vec<_>>().join(",")
+        ),
+        value => value.to_string(),
+    }
+}
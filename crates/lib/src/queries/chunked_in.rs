@@ -0,0 +1,354 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+use serde::de::DeserializeOwned;
+
+use crate::{ExecutionEngine, PrismaClientInternals};
+
+use super::{deserialize_response, Result};
+
+/// An `orderBy` entry that can be applied to the merged results of each chunk.
+struct ScalarOrder {
+    field: String,
+    descending: bool,
+}
+
+struct Chunks {
+    selections: Vec<Selection>,
+    orders: Vec<ScalarOrder>,
+    skip: i64,
+    take: Option<i64>,
+}
+
+/// Splits a `findMany` whose largest top-level `in` filter has more than `size` values
+/// into one query per `size` values, or returns `None` if it doesn't need splitting
+/// or can't be split without changing its result.
+///
+/// Pagination is applied once the results are merged, so each chunk fetches `skip + take` records
+/// and `skip` is left out.
+fn split(selection: &Selection, size: usize) -> Option<Chunks> {
+    let argument = |name: &str| {
+        selection
+            .arguments()
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    };
+
+    // cursors and distinct records are relative to every record, not those of one chunk
+    if argument("cursor").is_some() || argument("distinct").is_some() {
+        return None;
+    }
+
+    let where_fields = match argument("where")? {
+        ArgumentValue::Scalar(PrismaValue::Object(fields)) => fields,
+        _ => return None,
+    };
+
+    let (field_index, values) = where_fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (_, value))| Some((i, in_values(value)?)))
+        .max_by_key(|(_, values)| values.len())?;
+
+    if values.len() <= size {
+        return None;
+    }
+
+    // a value in more than one chunk would fetch its records more than once
+    let mut seen = HashSet::new();
+    let values = values
+        .iter()
+        .filter(|value| seen.insert(format!("{value:?}")))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let orders = match argument("orderBy") {
+        Some(order_by) => scalar_orders(order_by)?,
+        None => vec![],
+    };
+
+    let skip = scalar_int(argument("skip"))?.unwrap_or(0);
+    let take = scalar_int(argument("take"))?;
+
+    // negative takes count back from the end of the results
+    if matches!(take, Some(take) if take < 0) {
+        return None;
+    }
+
+    let selections = values
+        .chunks(size.max(1))
+        .map(|chunk| {
+            let mut where_fields = where_fields.clone();
+
+            if let (_, PrismaValue::Object(filters)) = &mut where_fields[field_index] {
+                for (name, value) in filters.iter_mut() {
+                    if name == "in" {
+                        *value = PrismaValue::List(chunk.to_vec());
+                    }
+                }
+            }
+
+            let arguments = selection
+                .arguments()
+                .iter()
+                .filter(|(name, _)| name != "skip" && name != "take")
+                .map(|(name, value)| match name.as_str() {
+                    "where" => (
+                        name.clone(),
+                        PrismaValue::Object(where_fields.clone()).into(),
+                    ),
+                    _ => (name.clone(), value.clone()),
+                })
+                .chain(take.map(|take| ("take".to_string(), PrismaValue::Int(skip + take).into())))
+                .collect::<Vec<_>>();
+
+            Selection::new(
+                selection.name().to_string(),
+                selection.alias().clone(),
+                arguments,
+                selection.nested_selections().to_vec(),
+            )
+        })
+        .collect();
+
+    Some(Chunks {
+        selections,
+        orders,
+        skip,
+        take,
+    })
+}
+
+/// Replaces each top-level `notIn` filter with more than `size` values
+/// with an `AND` of `notIn` filters of at most `size` values each, which exclude the same records.
+/// Unlike `in` filters these stay in one query, since a record excluded by one chunk must be excluded by all.
+fn split_not_in(selection: Selection, size: usize) -> Selection {
+    let arguments = selection
+        .arguments()
+        .iter()
+        .map(|(name, value)| match (name.as_str(), value) {
+            ("where", ArgumentValue::Scalar(PrismaValue::Object(where_fields))) => {
+                let mut where_fields = where_fields.clone();
+                split_not_in_filters(&mut where_fields, size);
+
+                (name.clone(), PrismaValue::Object(where_fields).into())
+            }
+            _ => (name.clone(), value.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    Selection::new(
+        selection.name().to_string(),
+        selection.alias().clone(),
+        arguments,
+        selection.nested_selections().to_vec(),
+    )
+}
+
+fn split_not_in_filters(where_fields: &mut Vec<(String, PrismaValue)>, size: usize) {
+    let mut chunks = vec![];
+    let mut emptied = vec![];
+
+    for (field, value) in where_fields.iter_mut() {
+        let filters = match value {
+            PrismaValue::Object(filters) => filters,
+            _ => continue,
+        };
+
+        let index = filters.iter().position(|(name, value)| {
+            name == "notIn" && matches!(value, PrismaValue::List(values) if values.len() > size)
+        });
+
+        let values = match index.map(|i| filters.remove(i).1) {
+            Some(PrismaValue::List(values)) => values,
+            _ => continue,
+        };
+
+        if filters.is_empty() {
+            emptied.push(field.clone());
+        }
+
+        chunks.extend(values.chunks(size.max(1)).map(|chunk| {
+            PrismaValue::Object(vec![(
+                field.clone(),
+                PrismaValue::Object(vec![(
+                    "notIn".to_string(),
+                    PrismaValue::List(chunk.to_vec()),
+                )]),
+            )])
+        }));
+    }
+
+    if chunks.is_empty() {
+        return;
+    }
+
+    where_fields.retain(|(field, _)| !emptied.contains(field));
+
+    match where_fields.iter_mut().find(|(name, _)| name == "AND") {
+        Some((_, PrismaValue::List(and))) => and.extend(chunks),
+        Some((_, and)) => {
+            let existing = std::mem::replace(and, PrismaValue::Null);
+            *and = PrismaValue::List(std::iter::once(existing).chain(chunks).collect());
+        }
+        None => where_fields.push(("AND".to_string(), PrismaValue::List(chunks))),
+    }
+}
+
+fn in_values(value: &PrismaValue) -> Option<&Vec<PrismaValue>> {
+    match value {
+        PrismaValue::Object(filters) => filters.iter().find_map(|(name, value)| match value {
+            PrismaValue::List(values) if name == "in" => Some(values),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// The orderings in `order_by`, if they are all on scalar fields without a `nulls` option.
+fn scalar_orders(order_by: &ArgumentValue) -> Option<Vec<ScalarOrder>> {
+    let orders = match order_by {
+        ArgumentValue::Scalar(PrismaValue::List(orders)) => orders,
+        _ => return None,
+    };
+
+    orders
+        .iter()
+        .map(|order| match order {
+            PrismaValue::Object(fields) => match fields.as_slice() {
+                [(field, PrismaValue::String(direction))] => Some(ScalarOrder {
+                    field: field.clone(),
+                    descending: direction == "desc",
+                }),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// `Some(None)` if there's no argument, and `None` if it isn't an integer.
+fn scalar_int(argument: Option<&ArgumentValue>) -> Option<Option<i64>> {
+    match argument {
+        None => Some(None),
+        Some(ArgumentValue::Scalar(PrismaValue::Int(value))) => Some(Some(*value)),
+        Some(_) => None,
+    }
+}
+
+fn compare(a: &serde_value::Value, b: &serde_value::Value, orders: &[ScalarOrder]) -> Ordering {
+    let field = |value: &serde_value::Value, name: &str| match value {
+        serde_value::Value::Map(fields) => fields
+            .get(&serde_value::Value::String(name.to_string()))
+            .cloned(),
+        _ => None,
+    };
+
+    orders
+        .iter()
+        .map(|order| {
+            let ordering = field(a, &order.field).cmp(&field(b, &order.field));
+
+            match order.descending {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Executes a `findMany`, splitting it into a batch of queries with at most `size` values
+/// in its largest top-level `in` filter and merging their results.
+/// Top-level `notIn` filters are split into an `AND` of filters with at most `size` values each.
+pub(crate) async fn exec_chunked<T: DeserializeOwned>(
+    operation: Operation,
+    client: &PrismaClientInternals,
+    size: usize,
+) -> Result<Vec<T>> {
+    let failed_query = client.failed_query(&operation);
+
+    let value = execute(operation, client, size).await?;
+
+    let result = deserialize_response(value);
+
+    #[cfg(feature = "metrics")]
+    if let Err(e) = &result {
+        client.metrics.record_error(e);
+    }
+
+    result.map_err(|e| e.in_query(failed_query))
+}
+
+async fn execute(
+    operation: Operation,
+    client: &PrismaClientInternals,
+    size: usize,
+) -> Result<serde_value::Value> {
+    let operation = match operation {
+        Operation::Read(selection) => Operation::Read(split_not_in(selection, size)),
+        operation => operation,
+    };
+
+    let chunks = match &operation {
+        Operation::Read(selection) => split(selection, size),
+        Operation::Write(_) => None,
+    };
+
+    let Chunks {
+        selections,
+        orders,
+        skip,
+        take,
+    } = match chunks {
+        Some(chunks) => chunks,
+        None => return client.execute(operation).await,
+    };
+
+    let operations = selections
+        .into_iter()
+        .map(Operation::Read)
+        .collect::<Vec<_>>();
+
+    let values = match &client.engine {
+        // batches aren't run in the client's transaction, so the chunks are fetched one at a time
+        ExecutionEngine::Real { tx_id: Some(_), .. } => {
+            let mut values = vec![];
+
+            for operation in operations {
+                values.push(client.execute(operation).await?);
+            }
+
+            values
+        }
+        _ => client
+            .execute_all(operations)
+            .await?
+            .into_iter()
+            .collect::<Result<_>>()?,
+    };
+
+    let mut records = vec![];
+
+    for value in values {
+        match value {
+            serde_value::Value::Seq(values) => records.extend(values),
+            value => return Ok(value),
+        }
+    }
+
+    // each chunk is sorted by the engine, but the chunks themselves aren't
+    if !orders.is_empty() {
+        records.sort_by(|a, b| compare(a, b, &orders));
+    }
+
+    Ok(serde_value::Value::Seq(
+        records
+            .into_iter()
+            .skip(skip.max(0) as usize)
+            .take(take.map(|take| take as usize).unwrap_or(usize::MAX))
+            .collect(),
+    ))
+}
@@ -0,0 +1,72 @@
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+
+use super::{QueryError, Result};
+
+const SORT_KEY: &str = "sort";
+const COLLATION_KEY: &str = "collation";
+
+/// The bundled engine can't order by a collation,
+/// so queries that do are rejected before the engine fails to validate them.
+pub(crate) fn ensure_collated_order_supported(operation: &Operation) -> Result<()> {
+    let selection = match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    match orders_by_collation(selection) {
+        true => Err(QueryError::UnsupportedCollatedOrder),
+        false => Ok(()),
+    }
+}
+
+fn orders_by_collation(selection: &Selection) -> bool {
+    selection
+        .arguments()
+        .iter()
+        .any(|(name, value)| name == "orderBy" && argument_has_collation(value))
+        || selection
+            .nested_selections()
+            .iter()
+            .any(orders_by_collation)
+}
+
+// a model can have fields named `sort` and `collation`, so only the `{ sort, collation }` object
+// that `order_by_collated` builds counts, whose `collation` is a name rather than a direction
+fn argument_has_collation(value: &ArgumentValue) -> bool {
+    match value {
+        ArgumentValue::Object(fields) => {
+            (fields.len() == 2
+                && fields.contains_key(SORT_KEY)
+                && matches!(
+                    fields.get(COLLATION_KEY),
+                    Some(ArgumentValue::Scalar(PrismaValue::String(name))) if is_collation(name)
+                ))
+                || fields.values().any(argument_has_collation)
+        }
+        ArgumentValue::List(values) => values.iter().any(argument_has_collation),
+        ArgumentValue::Scalar(value) => value_has_collation(value),
+        _ => false,
+    }
+}
+
+fn value_has_collation(value: &PrismaValue) -> bool {
+    match value {
+        PrismaValue::Object(fields) => {
+            let field = |key: &str| fields.iter().find(|(name, _)| name == key).map(|(_, v)| v);
+
+            (fields.len() == 2
+                && field(SORT_KEY).is_some()
+                && matches!(
+                    field(COLLATION_KEY),
+                    Some(PrismaValue::String(name)) if is_collation(name)
+                ))
+                || fields.iter().any(|(_, value)| value_has_collation(value))
+        }
+        PrismaValue::List(values) => values.iter().any(value_has_collation),
+        _ => false,
+    }
+}
+
+fn is_collation(name: &str) -> bool {
+    !matches!(name, "asc" | "desc")
+}
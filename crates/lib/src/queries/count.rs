@@ -1,11 +1,13 @@
+use std::time::Duration;
+
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 use serde::Deserialize;
 
 use crate::{
-    merge_fields, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery,
-    PaginatedQuery, PrismaClientInternals, Query, QueryConvert, SerializedWhereInput, WhereInput,
-    WhereQuery,
+    merge_fields, ExecutionEngine, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
+    OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, SerializedWhereInput,
+    WhereInput, WhereQuery,
 };
 
 pub struct Count<'a, Actions: ModelTypes> {
@@ -17,6 +19,24 @@ pub struct Count<'a, Actions: ModelTypes> {
     pub take: Option<i64>,
 }
 
+impl<'a, Actions: ModelTypes> Clone for Count<'a, Actions>
+where
+    Actions::Where: Clone,
+    Actions::OrderBy: Clone,
+    Actions::Cursor: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            where_params: self.where_params.clone(),
+            order_by_params: self.order_by_params.clone(),
+            cursor_params: self.cursor_params.clone(),
+            skip: self.skip,
+            take: self.take,
+        }
+    }
+}
+
 impl<'a, Actions: ModelTypes> Count<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
         Self {
@@ -49,9 +69,63 @@ impl<'a, Actions: ModelTypes> Count<'a, Actions> {
         self
     }
 
+    /// See [`cache_key`](super::cache_key).
+    pub fn cache_key(&self) -> String
+    where
+        Self: Clone,
+    {
+        super::cache_key(self.clone())
+    }
+
+    /// Reuses the result of an identical count for up to `ttl`, see [`CachedCount`].
+    pub fn cached(self, ttl: Duration) -> CachedCount<'a, Actions> {
+        CachedCount { count: self, ttl }
+    }
+
     pub async fn exec(self) -> super::Result<i64> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+/// A [`Count`] whose result is kept in the client's [`CountCache`](crate::CountCache) for `ttl`.
+///
+/// Counts with the same filters and pagination share an entry, which isn't invalidated by writes,
+/// so the result can be up to `ttl` out of date.
+/// Once the entry expires, the next execution counts again and replaces it.
+/// Counts made with a transaction's client skip the cache.
+pub struct CachedCount<'a, Actions: ModelTypes> {
+    count: Count<'a, Actions>,
+    ttl: Duration,
+}
+
+impl<'a, Actions: ModelTypes> CachedCount<'a, Actions> {
+    pub async fn exec(self) -> super::Result<i64>
+    where
+        Count<'a, Actions>: Clone,
+    {
+        let client = self.count.client;
+
+        // the transaction's writes may not be visible to anyone else, or be rolled back
+        if let ExecutionEngine::Real { tx_id: Some(_), .. } = &client.engine {
+            return self.count.exec().await;
+        }
+
+        let key = self.count.cache_key();
+
+        if let Some(count) = client.count_cache.get(&key).await {
+            return Ok(count);
+        }
+
+        let count = self.count.exec().await?;
+
+        client.count_cache.set(&key, count, self.ttl).await;
+
+        Ok(count)
+    }
 }
 
 #[derive(Deserialize)]
@@ -2,8 +2,8 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, Query, QueryConvert,
+    merge_fields, BatchResult, CreateUnchecked, ExecutionEngine, ModelOperation, ModelQuery,
+    ModelTypes, ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, QueryError,
 };
 
 pub struct CreateMany<'a, Actions: ModelTypes> {
@@ -64,9 +64,60 @@ impl<'a, Actions: ModelTypes> CreateMany<'a, Actions> {
         )
     }
 
+    /// Returns the created records instead of how many there were.
+    #[cfg(any(feature = "sqlite", feature = "postgresql"))]
+    pub fn returning(self) -> CreateManyReturning<'a, Actions> {
+        CreateManyReturning { create_many: self }
+    }
+
     pub async fn exec(self) -> super::Result<i64> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+/// A [`CreateMany`] that returns the created records.
+///
+/// The bundled engine predates `createManyAndReturn`, so each record is created with `createOne`
+/// in a single batch, which the engine runs in a transaction.
+pub struct CreateManyReturning<'a, Actions: ModelTypes> {
+    create_many: CreateMany<'a, Actions>,
+}
+
+impl<'a, Actions: ModelTypes> CreateManyReturning<'a, Actions> {
+    pub async fn exec(self) -> super::Result<Vec<Actions::Data>> {
+        let CreateMany {
+            client,
+            set_params,
+            skip_duplicates,
+        } = self.create_many;
+
+        // `createOne` fails on duplicates rather than skipping them
+        if skip_duplicates {
+            return Err(QueryError::UnsupportedCreateManyReturning);
+        }
+
+        let creates = set_params
+            .into_iter()
+            .map(|set_params| CreateUnchecked::<Actions>::new(client, set_params));
+
+        match &client.engine {
+            // batches aren't run in the client's transaction, so the records are created one at a time
+            ExecutionEngine::Real { tx_id: Some(_), .. } => {
+                let mut records = vec![];
+
+                for create in creates {
+                    records.push(create.exec().await?);
+                }
+
+                Ok(records)
+            }
+            _ => super::batch(creates.collect::<Vec<_>>(), client).await,
+        }
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for CreateMany<'a, Actions> {
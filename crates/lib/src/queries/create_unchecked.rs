@@ -48,10 +48,13 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, S::Data> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(self.set_params, select.to_selections())),
         )
+        .with_computed(computed)
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -67,6 +70,10 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
     pub async fn exec(self) -> super::Result<Actions::Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for CreateUnchecked<'a, Actions> {
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{raw::Raw, PrismaClientInternals};
+
+use super::{QueryError, QueryRaw};
+
+/// The version of the database a client is connected to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbVersion {
+    pub major: u32,
+    pub minor: u32,
+    /// The version string as reported by the database, eg. `PostgreSQL 15.2 on x86_64-pc-linux-gnu, ...`
+    pub raw: String,
+}
+
+impl DbVersion {
+    /// Whether this version is `major.minor` or newer.
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// Parses the first `major.minor` number found in `raw`, ignoring a leading `v`.
+    fn parse(raw: String) -> Option<Self> {
+        let (major, minor) = raw.split_whitespace().find_map(|word| {
+            let mut parts = word.trim_start_matches('v').split('.');
+
+            let major = leading_number(parts.next()?)?;
+            let minor = leading_number(parts.next()?)?;
+
+            Some((major, minor))
+        })?;
+
+        Some(Self { major, minor, raw })
+    }
+}
+
+fn leading_number(part: &str) -> Option<u32> {
+    let end = part
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(part.len());
+
+    part[..end].parse().ok()
+}
+
+#[derive(Debug, Error)]
+pub enum DbVersionError {
+    #[error("Querying the database version is not supported for the '{0}' provider")]
+    UnsupportedProvider(&'static str),
+    #[error("Failed to query the database version: {0}")]
+    Query(#[from] QueryError),
+    #[error("Failed to parse database version '{0}'")]
+    Parse(String),
+}
+
+#[derive(Deserialize)]
+struct VersionRow {
+    version: String,
+}
+
+/// Queries the version of the database, using the function appropriate for `database`.
+pub async fn db_version(
+    client: &PrismaClientInternals,
+    database: &'static str,
+) -> Result<DbVersion, DbVersionError> {
+    let sql = match database {
+        "postgresql" | "cockroachdb" | "mysql" => "SELECT version() AS version",
+        "sqlite" => "SELECT sqlite_version() AS version",
+        _ => return Err(DbVersionError::UnsupportedProvider(database)),
+    };
+
+    let version = QueryRaw::<VersionRow>::new(client, Raw::new(sql, vec![]), database)
+        .exec()
+        .await?
+        .pop()
+        .map(|row| row.version)
+        .unwrap_or_default();
+
+    DbVersion::parse(version.clone()).ok_or(DbVersionError::Parse(version))
+}
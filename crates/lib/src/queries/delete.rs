@@ -1,14 +1,16 @@
-use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    Include, IncludeType, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
+    ExtendedWhereUnique, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
+    ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select, SelectType, WithQuery,
 };
 
+use super::unique_where;
+
 pub struct Delete<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
+    pub extra_where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
 }
 
@@ -21,6 +23,7 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
         Self {
             client,
             where_param,
+            extra_where_params: vec![],
             with_params,
         }
     }
@@ -32,12 +35,13 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
 
     fn to_selection(
         where_param: Actions::Where,
+        extra_where_params: Vec<Actions::Where>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
         Self::base_selection(
             [(
                 "where".to_string(),
-                PrismaValue::Object(vec![where_param.serialize().transform_equals()]).into(),
+                unique_where(where_param, extra_where_params).into(),
             )],
             nested_selections,
         )
@@ -47,10 +51,17 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, S::Data> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
-            Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
+            Operation::Write(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                select.to_selections(),
+            )),
         )
+        .with_computed(computed)
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -59,13 +70,30 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
     ) -> Include<'a, I::Data> {
         Include::new(
             self.client,
-            Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
+            Operation::Write(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                select.to_selections(),
+            )),
         )
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+impl<'a, Actions: ExtendedWhereUnique> Delete<'a, Actions> {
+    /// Adds filters on non-unique fields alongside the unique filter,
+    /// so that the record is only deleted if it also matches them.
+    pub fn and_where(mut self, params: Vec<Actions::Where>) -> Self {
+        self.extra_where_params.extend(params);
+        self
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for Delete<'a, Actions> {
@@ -84,7 +112,11 @@ impl<'a, Actions: ModelTypes> Query<'a> for Delete<'a, Actions> {
         scalar_selections.extend(self.with_params.into_iter().map(Into::into));
 
         (
-            Operation::Write(Self::to_selection(self.where_param, scalar_selections)),
+            Operation::Write(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                scalar_selections,
+            )),
             self.client,
         )
     }
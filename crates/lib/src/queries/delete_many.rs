@@ -9,6 +9,7 @@ use prisma_models::PrismaValue;
 pub struct DeleteMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
+    all: bool,
 }
 
 impl<'a, Actions: ModelTypes> DeleteMany<'a, Actions> {
@@ -16,9 +17,17 @@ impl<'a, Actions: ModelTypes> DeleteMany<'a, Actions> {
         Self {
             client,
             where_params,
+            all: false,
         }
     }
 
+    /// Allows the query to run without any filters, deleting EVERY record of the model.
+    /// Without this, an unfiltered `delete_many` fails with `QueryError::UnfilteredMutation`.
+    pub fn all(mut self) -> Self {
+        self.all = true;
+        self
+    }
+
     pub(crate) fn convert(raw: BatchResult) -> i64 {
         raw.count
     }
@@ -26,6 +35,10 @@ impl<'a, Actions: ModelTypes> DeleteMany<'a, Actions> {
     pub async fn exec(self) -> super::Result<i64> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for DeleteMany<'a, Actions> {
@@ -41,7 +54,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for DeleteMany<'a, Actions> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
         (
             Operation::Write(Self::base_selection(
-                [(!self.where_params.is_empty()).then(|| {
+                [(!self.where_params.is_empty() || self.all).then(|| {
                     (
                         "where".to_string(),
                         PrismaValue::Object(merge_fields(
@@ -1,6 +1,12 @@
+use std::fmt;
+
+use query_core::Operation;
 use serde::Serialize;
 use thiserror::Error;
-use user_facing_errors::UserFacingError;
+use user_facing_errors::{
+    query_engine::{RecordNotFound, RecordRequiredButNotFound, UniqueKeyViolation},
+    UserFacingError,
+};
 
 #[derive(Debug, Error, Serialize)]
 pub enum QueryError {
@@ -12,11 +18,77 @@ pub enum QueryError {
 
     #[error("Error deserializing query result into return type: {0}")]
     Deserialize(String),
+
+    /// Returned when the engine returns null for a field whose type isn't an `Option`,
+    /// usually because a column is nullable in the database but required in the schema.
+    /// `field` is the path to the value in the response, eg. `posts[0].title`.
+    #[error(
+        "Field '{field}' is null but isn't optional, check that the database matches the schema"
+    )]
+    UnexpectedNull { field: String },
+
+    /// Returned when a query is given a value for a field whose type is set with `type_overrides`
+    /// that fails to serialize, eg. a type for a `String` field that doesn't serialize to a string.
+    #[error("Error serializing the value of a field with an overridden type: {0}")]
+    SerializeOverride(String),
+
+    /// Returned by `update_if_version` when the record's version doesn't match the expected one,
+    /// or the record doesn't exist.
+    #[error("Record was not updated since its version has changed")]
+    VersionConflict,
+
+    /// Returned when a query sets a `relation_load_strategy` that the query engine doesn't support.
+    #[error(
+        "The query engine doesn't support relationLoadStrategy, which requires Prisma 5.8 or later"
+    )]
+    UnsupportedRelationLoadStrategy,
+
+    /// Returned when a query orders by a field's `order_by_path`, which the query engine doesn't support.
+    #[error("The query engine doesn't support ordering by a path inside a JSON field")]
+    UnsupportedJsonPathOrder,
+
+    /// Returned when a query orders by a field's `order_by_collated`, which the query engine doesn't support.
+    #[error("The query engine doesn't support ordering by a collation")]
+    UnsupportedCollatedOrder,
+
+    /// Returned when `create_many` is executed with both `returning` and `skip_duplicates`,
+    /// which needs the engine's `createManyAndReturn`.
+    #[error(
+        "The query engine doesn't support skip_duplicates with returning, which requires Prisma 5.14 or later"
+    )]
+    UnsupportedCreateManyReturning,
+
+    /// Returned when `delete_many` or `update_many` is executed without any filters.
+    /// Calling `all` on the query allows it to affect every record.
+    #[error("delete_many and update_many require a filter, or all() to affect every record")]
+    UnfilteredMutation,
+
+    /// Returned when a raw query references a parameter that wasn't provided,
+    /// eg. `$3` in a query given two parameters. `expected` is the position of the missing parameter.
+    #[error("Raw query references parameter {expected} but only {provided} were provided")]
+    RawParameters { expected: usize, provided: usize },
+
+    /// Returned when the task executing a query is cancelled before it finishes,
+    /// eg. because the client's runtime is shutting down.
+    #[error("Query was cancelled before it finished")]
+    Cancelled,
+
+    /// Returned by middleware to prevent a query from being executed, eg. if the client's context doesn't permit it.
+    #[error("Query rejected by middleware: {0}")]
+    Rejected(String),
+
+    /// `source` along with the query it occurred in,
+    /// returned instead of `source` by clients built with a [`QueryErrorDetail`] other than `None`.
+    #[error("{source} (in {query})")]
+    InQuery {
+        query: FailedQuery,
+        source: Box<QueryError>,
+    },
 }
 
 impl QueryError {
     pub fn is_prisma_error<T: UserFacingError>(&self) -> bool {
-        match self {
+        match self.inner() {
             Self::Execute(error) => error
                 .as_known()
                 .map(|e| e.error_code == <T as UserFacingError>::ERROR_CODE)
@@ -24,10 +96,113 @@ impl QueryError {
             _ => false,
         }
     }
+
+    /// The HTTP status code that the `axum` and `actix-web` conversions respond with:
+    /// 409 for unique constraint violations and version conflicts,
+    /// 404 for records that don't exist and 500 for everything else.
+    pub fn http_status(&self) -> u16 {
+        if self.is_prisma_error::<UniqueKeyViolation>()
+            || matches!(self.inner(), Self::VersionConflict)
+        {
+            409
+        } else if self.is_prisma_error::<RecordNotFound>()
+            || self.is_prisma_error::<RecordRequiredButNotFound>()
+        {
+            404
+        } else {
+            500
+        }
+    }
+
+    /// The error without the query attached to it by [`QueryError::InQuery`],
+    /// for matching on the kind of error regardless of the client's [`QueryErrorDetail`].
+    pub fn inner(&self) -> &QueryError {
+        match self {
+            Self::InQuery { source, .. } => source.inner(),
+            _ => self,
+        }
+    }
+
+    /// The query the error occurred in, if the client was configured to include it.
+    pub fn query(&self) -> Option<&FailedQuery> {
+        match self {
+            Self::InQuery { query, .. } => Some(query),
+            _ => None,
+        }
+    }
+
+    /// Attaches `query` to the error, unless it's `None` or the error already has a query.
+    pub(crate) fn in_query(self, query: Option<FailedQuery>) -> Self {
+        match (self, query) {
+            (error @ Self::InQuery { .. }, _) | (error, None) => error,
+            (source, Some(query)) => Self::InQuery {
+                query,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    #[cfg(any(feature = "axum", feature = "actix-web"))]
+    fn http_message(&self) -> &'static str {
+        match self.http_status() {
+            409 => "Record conflicts with an existing record",
+            404 => "Record not found",
+            _ => "Internal server error occurred while completing database operation!",
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, QueryError>;
 
+/// How much of a failing query a client includes in its errors,
+/// set with the generated client builder's `query_errors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryErrorDetail {
+    /// Errors are returned as they are, which is the default.
+    None,
+    /// Errors include the engine operation, eg. `findManyUser` or `executeRaw`.
+    Operation,
+    /// Errors include the operation's arguments and selection as well,
+    /// which contain the SQL and parameters of raw queries.
+    /// Since this writes the data being queried into error messages, it's intended for debugging.
+    Query,
+}
+
+/// The query a [`QueryError::InQuery`] occurred in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FailedQuery {
+    /// The engine operation, eg. `findManyUser`.
+    pub operation: String,
+    /// The operation with its arguments and selection, if the detail is [`QueryErrorDetail::Query`].
+    /// Arguments are sorted so that the same query is always displayed the same way.
+    pub query: Option<String>,
+}
+
+impl FailedQuery {
+    pub(crate) fn new(operation: &Operation, detail: QueryErrorDetail) -> Option<Self> {
+        let selection = match operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection,
+        };
+
+        let query = match detail {
+            QueryErrorDetail::None => return None,
+            QueryErrorDetail::Operation => None,
+            QueryErrorDetail::Query => Some(super::cache_key::selection_key(selection)),
+        };
+
+        Some(Self {
+            operation: selection.name().to_string(),
+            query,
+        })
+    }
+}
+
+impl fmt::Display for FailedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.query.as_deref().unwrap_or(&self.operation))
+    }
+}
+
 #[cfg(feature = "rspc")]
 impl From<QueryError> for rspc::Error {
     fn from(err: QueryError) -> Self {
@@ -38,3 +213,32 @@ impl From<QueryError> for rspc::Error {
         )
     }
 }
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for QueryError {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+
+        (status, self.http_message()).into_response()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl From<QueryError> for axum::response::Response {
+    fn from(err: QueryError) -> Self {
+        axum::response::IntoResponse::into_response(err)
+    }
+}
+
+#[cfg(feature = "actix-web")]
+impl actix_web::ResponseError for QueryError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::build(self.status_code()).body(self.http_message())
+    }
+}
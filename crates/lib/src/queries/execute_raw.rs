@@ -2,27 +2,46 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 use serde_json::Value;
 
-use crate::{raw::Raw, PrismaClientInternals, Query, QueryConvert};
+use crate::{raw::Raw, PrismaClientInternals, Query, QueryConvert, QueryError};
 
+/// A raw SQL statement that doesn't return rows, such as `INSERT`, `UPDATE` or `DELETE`.
+/// Resolves to the number of rows affected.
+/// Use [`QueryRaw`](super::QueryRaw) for statements that return rows.
 pub struct ExecuteRaw<'a> {
     client: &'a PrismaClientInternals,
     sql: String,
     params: Vec<Value>,
+    // returned by `exec` instead of executing the query
+    error: Option<QueryError>,
 }
 
 impl<'a> ExecuteRaw<'a> {
     pub fn new(client: &'a PrismaClientInternals, query: Raw, database: &'static str) -> Self {
-        let (sql, params) = query.convert(database);
+        let (sql, params, error) = match query.try_convert(database) {
+            Ok((sql, params)) => (sql, params, None),
+            Err(e) => (String::new(), vec![], Some(e)),
+        };
 
         Self {
             client,
             sql,
             params,
+            error,
         }
     }
 
-    pub async fn exec(self) -> super::Result<i64> {
-        super::exec(self).await
+    pub async fn exec(mut self) -> super::Result<i64> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => super::exec(self).await,
+        }
+    }
+
+    pub async fn exec_raw(mut self) -> super::Result<serde_json::Value> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => super::exec_raw(self).await,
+        }
     }
 }
 
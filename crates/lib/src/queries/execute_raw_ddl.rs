@@ -0,0 +1,128 @@
+use query_core::TransactionOptions;
+use thiserror::Error;
+
+use crate::{raw::Raw, transaction::RollbackOnDrop, ExecutionEngine, PrismaClientInternals};
+
+use super::{ExecuteRaw, QueryError};
+
+/// Raw DDL statements, such as `CREATE INDEX` or `ALTER TABLE`, executed one at a time in order
+/// since some databases only accept one statement per query.
+/// Resolves to the number of rows each statement affected.
+///
+/// On databases where DDL is transactional the statements are run in a transaction,
+/// so either all of them are applied or none are.
+/// MySQL commits each DDL statement as it runs,
+/// so statements that ran before a failing one stay applied.
+pub struct ExecuteRawDdl<'a> {
+    client: &'a PrismaClientInternals,
+    statements: Vec<Raw>,
+    database: &'static str,
+    timeout: u64,
+}
+
+impl<'a> ExecuteRawDdl<'a> {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        statements: impl IntoIterator<Item = Raw>,
+        database: &'static str,
+    ) -> Self {
+        Self {
+            client,
+            statements: statements.into_iter().collect(),
+            database,
+            timeout: 5000,
+        }
+    }
+
+    /// How long the transaction the statements run in is kept open for, in milliseconds.
+    pub fn with_timeout(self, timeout: u64) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Whether the statements are run in a transaction, which is the case for
+    /// PostgreSQL, CockroachDB, SQLite and SQL Server.
+    pub fn is_transactional(&self) -> bool {
+        matches!(
+            self.database,
+            "postgresql" | "cockroachdb" | "sqlite" | "sqlserver"
+        )
+    }
+
+    pub async fn exec(self) -> Result<Vec<i64>, ExecuteRawDdlError> {
+        let engine = match &self.client.engine {
+            // statements run in an existing transaction are committed or rolled back with it
+            ExecutionEngine::Real {
+                engine,
+                tx_id: None,
+            } if self.is_transactional() => engine.clone(),
+            _ => return execute(self.client, self.statements, self.database, false).await,
+        };
+
+        let guard = RollbackOnDrop::start(
+            self.client,
+            engine,
+            TransactionOptions::new(2000, self.timeout, None),
+        )
+        .await
+        .map_err(|source| ExecuteRawDdlError {
+            index: 0,
+            applied: vec![],
+            source,
+        })?;
+
+        let tx_client = self.client.with_tx_id(Some(guard.tx_id()));
+
+        let count = self.statements.len();
+        let result = execute(&tx_client, self.statements, self.database, true).await;
+
+        match guard.finish(result.is_ok()).await {
+            Ok(()) => result,
+            Err(source) => Err(ExecuteRawDdlError {
+                index: count,
+                applied: vec![],
+                source,
+            }),
+        }
+    }
+}
+
+async fn execute(
+    client: &PrismaClientInternals,
+    statements: Vec<Raw>,
+    database: &'static str,
+    rolls_back: bool,
+) -> Result<Vec<i64>, ExecuteRawDdlError> {
+    let mut applied = vec![];
+
+    for (index, statement) in statements.into_iter().enumerate() {
+        match ExecuteRaw::new(client, statement, database).exec().await {
+            Ok(rows) => applied.push(rows),
+            Err(source) => {
+                if rolls_back {
+                    applied.clear();
+                }
+
+                return Err(ExecuteRawDdlError {
+                    index,
+                    applied,
+                    source,
+                });
+            }
+        }
+    }
+
+    Ok(applied)
+}
+
+#[derive(Debug, Error)]
+#[error("DDL statement {index} failed: {source}")]
+pub struct ExecuteRawDdlError {
+    /// The position of the statement that failed,
+    /// or the number of statements if committing their transaction failed.
+    pub index: usize,
+    /// The results of the statements before the failing one that are still applied,
+    /// which is none of them if they were run in a transaction of their own.
+    pub applied: Vec<i64>,
+    #[source]
+    pub source: QueryError,
+}
@@ -0,0 +1,101 @@
+use serde_json::{Map, Value};
+
+use crate::{raw::Raw, PrismaClientInternals};
+
+use super::QueryRaw;
+
+/// Retrieves the database's plan for a raw SQL query by prefixing it with `EXPLAIN`.
+/// SQLite uses `EXPLAIN QUERY PLAN` instead, since its `EXPLAIN` returns bytecode.
+///
+/// The query is only planned, not executed, unless [`analyze`](Self::analyze) is called.
+///
+/// Model queries can't be explained, since the engine doesn't expose the SQL it generates for them.
+pub struct ExplainRaw<'a> {
+    client: &'a PrismaClientInternals,
+    query: Raw,
+    database: &'static str,
+    analyze: bool,
+}
+
+impl<'a> ExplainRaw<'a> {
+    pub fn new(client: &'a PrismaClientInternals, query: Raw, database: &'static str) -> Self {
+        Self {
+            client,
+            query,
+            database,
+            analyze: false,
+        }
+    }
+
+    /// Uses `EXPLAIN ANALYZE`, which executes the query and includes actual timings in the plan.
+    /// Any changes the query makes are applied, so wrap writes in a transaction that is rolled back.
+    ///
+    /// Has no effect on SQLite, which has no equivalent.
+    pub fn analyze(mut self) -> Self {
+        self.analyze = true;
+        self
+    }
+
+    fn prefix(&self) -> &'static str {
+        match (self.database, self.analyze) {
+            ("sqlite", _) => "EXPLAIN QUERY PLAN ",
+            (_, true) => "EXPLAIN ANALYZE ",
+            (_, false) => "EXPLAIN ",
+        }
+    }
+
+    pub async fn exec(self) -> super::Result<QueryPlan> {
+        let prefix = self.prefix();
+
+        let Self {
+            client,
+            mut query,
+            database,
+            ..
+        } = self;
+
+        query.query.insert_str(0, prefix);
+
+        let rows = QueryRaw::new(client, query, database).exec().await?;
+
+        Ok(QueryPlan { rows })
+    }
+}
+
+/// The rows returned by an `EXPLAIN` query.
+/// Their columns and contents are specific to each database.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    pub rows: Vec<Map<String, Value>>,
+}
+
+/// Columns that contain a line of the plan as text.
+const PLAN_COLUMNS: [&str; 4] = ["QUERY PLAN", "detail", "info", "EXPLAIN"];
+
+impl QueryPlan {
+    /// The plan as text, one row per line.
+    ///
+    /// Rows from databases that return the plan as text (PostgreSQL, CockroachDB, SQLite and
+    /// MySQL's `EXPLAIN ANALYZE`) are rendered as that text.
+    /// Tabular rows, such as those from MySQL's `EXPLAIN`, are rendered as `column: value` pairs.
+    pub fn text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                let line = PLAN_COLUMNS
+                    .iter()
+                    .find_map(|column| row.get(*column).and_then(Value::as_str));
+
+                match line {
+                    Some(line) => line.to_string(),
+                    None => row
+                        .iter()
+                        .map(|(column, value)| format!("{column}: {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
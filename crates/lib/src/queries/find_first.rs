@@ -1,22 +1,48 @@
+use std::sync::Arc;
+
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
     merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
-    WhereInput, WhereQuery, WithQuery,
+    OrderByQuery, PaginatedQuery, PartialData, PrismaClientInternals, Query, QueryConvert,
+    RelationLoadStrategy, Select, SelectType, WhereInput, WhereQuery, WithQuery,
 };
 
-use super::SerializedWhereInput;
+use super::{partial_selections, SerializedWhereInput};
 
 pub struct FindFirst<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
+    pub relation_load_strategy: Option<RelationLoadStrategy>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
+    has_default_order: bool,
+}
+
+impl<'a, Actions: ModelTypes> Clone for FindFirst<'a, Actions>
+where
+    Actions::Where: Clone,
+    Actions::With: Clone,
+    Actions::OrderBy: Clone,
+    Actions::Cursor: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            where_params: self.where_params.clone(),
+            with_params: self.with_params.clone(),
+            relation_load_strategy: self.relation_load_strategy,
+            order_by_params: self.order_by_params.clone(),
+            cursor_params: self.cursor_params.clone(),
+            skip: self.skip,
+            take: self.take,
+            has_default_order: self.has_default_order,
+        }
+    }
 }
 
 impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
@@ -25,11 +51,29 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
             client,
             where_params,
             with_params: vec![],
+            relation_load_strategy: None,
             order_by_params: vec![],
             cursor_params: vec![],
             skip: None,
             take: None,
+            has_default_order: false,
+        }
+    }
+
+    /// Orders by `params` unless `order_by` is called,
+    /// in which case they are replaced rather than added to.
+    pub fn default_order_by(mut self, params: Vec<Actions::OrderBy>) -> Self {
+        self.order_by_params = params;
+        self.has_default_order = true;
+        self
+    }
+
+    fn push_order_by(&mut self, param: Actions::OrderBy) {
+        if std::mem::take(&mut self.has_default_order) {
+            self.order_by_params.clear();
         }
+
+        self.order_by_params.push(param);
     }
 
     pub fn with(mut self, param: impl Into<Actions::With>) -> Self {
@@ -38,7 +82,7 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
     }
 
     pub fn order_by(mut self, param: Actions::OrderBy) -> Self {
-        self.order_by_params.push(param);
+        self.push_order_by(param);
         self
     }
 
@@ -57,12 +101,20 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         self
     }
 
+    /// Sets how the record's relations are loaded,
+    /// see [`RelationLoadStrategy`].
+    pub fn relation_load_strategy(mut self, strategy: RelationLoadStrategy) -> Self {
+        self.relation_load_strategy = Some(strategy);
+        self
+    }
+
     fn to_selection(
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
         skip: Option<i64>,
         take: Option<i64>,
+        relation_load_strategy: Option<RelationLoadStrategy>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
         Self::base_selection(
@@ -108,6 +160,7 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                 }),
                 skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
                 take.map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
+                relation_load_strategy.map(RelationLoadStrategy::argument),
             ]
             .into_iter()
             .flatten(),
@@ -119,6 +172,8 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, Option<S::Data>> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Read(Self::to_selection(
@@ -127,9 +182,28 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.relation_load_strategy,
                 select.to_selections(),
             )),
         )
+        .with_computed(computed)
+    }
+
+    /// Selects only `fields`, which can be chosen at runtime,
+    /// along with any relations fetched using `with`.
+    pub fn with_selection(self, fields: &[&str]) -> Select<'a, Option<PartialData>> {
+        Select::new(
+            self.client,
+            Operation::Read(Self::to_selection(
+                self.where_params,
+                self.order_by_params,
+                self.cursor_params,
+                self.skip,
+                self.take,
+                self.relation_load_strategy,
+                partial_selections(fields, self.with_params),
+            )),
+        )
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -144,14 +218,37 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.relation_load_strategy,
                 include.to_selections(),
             )),
         )
     }
 
+    /// See [`cache_key`](super::cache_key).
+    pub fn cache_key(&self) -> String
+    where
+        Self: Clone,
+    {
+        super::cache_key(self.clone())
+    }
+
     pub async fn exec(self) -> super::Result<Option<Actions::Data>> {
         super::exec(self).await
     }
+
+    /// See [`FindMany::exec_shared`](super::FindMany::exec_shared).
+    pub async fn exec_shared(self) -> super::Result<Option<Arc<Actions::Data>>> {
+        self.exec().await.map(|record| record.map(Arc::new))
+    }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+
+    /// See [`FindMany::exec_as`](super::FindMany::exec_as).
+    pub async fn exec_as<T: serde::de::DeserializeOwned>(self) -> super::Result<Option<T>> {
+        super::exec_as(self).await
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for FindFirst<'a, Actions> {
@@ -176,6 +273,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindFirst<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.relation_load_strategy,
                 scalar_selections,
             )),
             self.client,
@@ -203,7 +301,7 @@ impl<'a, Actions: ModelTypes> WithQuery<'a> for FindFirst<'a, Actions> {
 
 impl<'a, Actions: ModelTypes> OrderByQuery<'a> for FindFirst<'a, Actions> {
     fn add_order_by(&mut self, param: Actions::OrderBy) {
-        self.order_by_params.push(param);
+        self.push_order_by(param);
     }
 }
 
@@ -1,22 +1,53 @@
+use std::sync::Arc;
+
 use prisma_models::PrismaValue;
 use query_core::{ArgumentValue, Operation, Selection};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
+    merge_fields, ExecutionEngine, Include, IncludeType, ModelOperation, ModelQuery,
+    ModelReadOperation, ModelTypes, OrderByQuery, PaginatedQuery, PartialData,
+    PrismaClientInternals, Query, QueryConvert, RelationLoadStrategy, Select, SelectType,
     WhereInput, WhereQuery, WithQuery,
 };
 
-use super::SerializedWhereInput;
+use super::{partial_selections, Count, SerializedWhereInput};
 
 pub struct FindMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
+    pub relation_load_strategy: Option<RelationLoadStrategy>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
+    pub distinct_params: Vec<Actions::ScalarField>,
+    pub in_chunk_size: Option<usize>,
+}
+
+impl<'a, Actions: ModelTypes> Clone for FindMany<'a, Actions>
+where
+    Actions::Where: Clone,
+    Actions::With: Clone,
+    Actions::OrderBy: Clone,
+    Actions::Cursor: Clone,
+    Actions::ScalarField: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            where_params: self.where_params.clone(),
+            with_params: self.with_params.clone(),
+            relation_load_strategy: self.relation_load_strategy,
+            order_by_params: self.order_by_params.clone(),
+            cursor_params: self.cursor_params.clone(),
+            skip: self.skip,
+            take: self.take,
+            distinct_params: self.distinct_params.clone(),
+            in_chunk_size: self.in_chunk_size,
+        }
+    }
 }
 
 impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
@@ -25,10 +56,13 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
             client,
             where_params,
             with_params: vec![],
+            relation_load_strategy: None,
             order_by_params: vec![],
             cursor_params: vec![],
             skip: None,
             take: None,
+            distinct_params: vec![],
+            in_chunk_size: None,
         }
     }
 
@@ -57,12 +91,47 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self
     }
 
+    /// Takes the last `n` records in the query's order, returning them in that order.
+    /// The same as `take(-n)`, which counts back from the cursor if there is one.
+    pub fn take_last(mut self, n: i64) -> Self {
+        self.take = Some(-n.saturating_abs());
+        self
+    }
+
+    /// Only returns the first record for each combination of `field` and any other distinct fields,
+    /// in the query's order. `skip` and `take` apply to the distinct records.
+    pub fn distinct(mut self, field: Actions::ScalarField) -> Self {
+        self.distinct_params.push(field);
+        self
+    }
+
+    /// Splits the query into a batch of queries with at most `size` values each
+    /// if its largest top-level `in` filter has more than that, overriding the client's `in_chunk_size`.
+    ///
+    /// The batch's results are merged and then ordered, skipped and taken as a single query's would be.
+    /// Queries that use `cursor`, `distinct` or order by anything other than scalar fields aren't split.
+    /// Top-level `not_in` filters with more than `size` values are replaced by an `AND` of `not_in` filters
+    /// with at most `size` values each, in the same query.
+    pub fn chunked_in(mut self, size: usize) -> Self {
+        self.in_chunk_size = Some(size);
+        self
+    }
+
+    /// Sets how relations fetched with `with`, `select` or `include` are loaded,
+    /// instead of using the engine's default.
+    pub fn relation_load_strategy(mut self, strategy: RelationLoadStrategy) -> Self {
+        self.relation_load_strategy = Some(strategy);
+        self
+    }
+
     fn to_selection(
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
         skip: Option<i64>,
         take: Option<i64>,
+        distinct_params: Vec<Actions::ScalarField>,
+        relation_load_strategy: Option<RelationLoadStrategy>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
         Self::base_selection(
@@ -108,6 +177,19 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 }),
                 skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
                 take.map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
+                (!distinct_params.is_empty()).then(|| {
+                    (
+                        "distinct".to_string(),
+                        PrismaValue::List(
+                            distinct_params
+                                .into_iter()
+                                .map(|field| PrismaValue::String(field.to_string()))
+                                .collect(),
+                        )
+                        .into(),
+                    )
+                }),
+                relation_load_strategy.map(RelationLoadStrategy::argument),
             ]
             .into_iter()
             .flatten(),
@@ -119,6 +201,8 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, Vec<S::Data>> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Read(Self::to_selection(
@@ -127,9 +211,30 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.distinct_params,
+                self.relation_load_strategy,
                 select.to_selections(),
             )),
         )
+        .with_computed(computed)
+    }
+
+    /// Selects only `fields`, which can be chosen at runtime,
+    /// along with any relations fetched using `with`.
+    pub fn with_selection(self, fields: &[&str]) -> Select<'a, Vec<PartialData>> {
+        Select::new(
+            self.client,
+            Operation::Read(Self::to_selection(
+                self.where_params,
+                self.order_by_params,
+                self.cursor_params,
+                self.skip,
+                self.take,
+                self.distinct_params,
+                self.relation_load_strategy,
+                partial_selections(fields, self.with_params),
+            )),
+        )
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -144,14 +249,98 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.distinct_params,
+                self.relation_load_strategy,
                 include.to_selections(),
             )),
         )
     }
 
+    /// See [`cache_key`](super::cache_key).
+    pub fn cache_key(&self) -> String
+    where
+        Self: Clone,
+    {
+        super::cache_key(self.clone())
+    }
+
     pub async fn exec(self) -> super::Result<Vec<Actions::Data>> {
-        super::exec(self).await
+        match self.in_chunk_size.or(self.client.in_chunk_size) {
+            Some(size) => {
+                let (op, client) = self.graphql();
+
+                super::chunked_in::exec_chunked(op, client, size).await
+            }
+            None => super::exec(self).await,
+        }
+    }
+
+    /// Executes the query with each record in an `Arc`,
+    /// so that records passed on to many consumers, eg. subscribers of a broadcast, are never cloned.
+    pub async fn exec_shared(self) -> super::Result<Vec<Arc<Actions::Data>>> {
+        self.exec()
+            .await
+            .map(|records| records.into_iter().map(Arc::new).collect())
+    }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+
+    /// Executes the query, deserializing each record into `T` rather than the model's `Data`.
+    /// Fields are matched by their names in the schema, so `T`'s fields must be named or renamed to match.
+    pub async fn exec_as<T: serde::de::DeserializeOwned>(self) -> super::Result<Vec<T>> {
+        super::exec_as(self).await
     }
+
+    /// Fetches page `page` (starting at 1) of `per_page` records,
+    /// counting every record matching the query's filters in the same batch.
+    ///
+    /// Any `skip` and `take` already set are replaced,
+    /// and `page` and `per_page` are treated as 1 if they are less than that.
+    /// Inside of a transaction the page and count are run one after the other instead,
+    /// so that they see the transaction's writes.
+    pub async fn paginate(mut self, page: i64, per_page: i64) -> super::Result<Page<Actions::Data>>
+    where
+        Actions::Where: Clone,
+    {
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+
+        let client = self.client;
+        let count = Count::<Actions>::new(client, self.where_params.clone());
+
+        self.skip = Some((page - 1).saturating_mul(per_page));
+        self.take = Some(per_page);
+
+        let (items, total) = match &client.engine {
+            // batches run in a transaction of their own, so inside of one the queries are run in turn
+            ExecutionEngine::Real { tx_id: Some(_), .. } => {
+                (self.exec().await?, count.exec().await?)
+            }
+            _ => super::batch((self, count), client).await?,
+        };
+
+        Ok(Page {
+            items,
+            total,
+            page,
+            per_page,
+            total_pages: total / per_page + (total % per_page != 0) as i64,
+        })
+    }
+}
+
+/// A page of records returned by [`FindMany::paginate`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The number of records matching the query, across every page.
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    /// 0 if there are no records, otherwise the number of the last page.
+    pub total_pages: i64,
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for FindMany<'a, Actions> {
@@ -176,6 +365,8 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindMany<'a, Actions> {
                 self.cursor_params,
                 self.skip,
                 self.take,
+                self.distinct_params,
+                self.relation_load_strategy,
                 scalar_selections,
             )),
             self.client,
@@ -268,6 +459,12 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
         self
     }
 
+    /// See [`FindMany::take_last`].
+    pub fn take_last(mut self, n: i64) -> Self {
+        self.take = Some(-n.saturating_abs());
+        self
+    }
+
     pub fn to_graphql(self) -> (Vec<(String, ArgumentValue)>, Vec<Selection>) {
         let arguments = [
             (!self.where_params.is_empty()).then(|| {
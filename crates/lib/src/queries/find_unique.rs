@@ -1,26 +1,49 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
-use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
+    ExtendedWhereUnique, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation,
+    ModelTypes, PartialData, PrismaClientInternals, Query, QueryConvert, RelationLoadStrategy,
+    Select, SelectType, WithQuery,
 };
 
+use super::{partial_selections, unique_where};
+
 pub struct FindUnique<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
+    pub extra_where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
+    pub relation_load_strategy: Option<RelationLoadStrategy>,
     _data: PhantomData<(Actions::Set, Actions::Data)>,
 }
 
+impl<'a, Actions: ModelTypes> Clone for FindUnique<'a, Actions>
+where
+    Actions::Where: Clone,
+    Actions::With: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            where_param: self.where_param.clone(),
+            extra_where_params: self.extra_where_params.clone(),
+            with_params: self.with_params.clone(),
+            relation_load_strategy: self.relation_load_strategy,
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_param: Actions::Where) -> Self {
         Self {
             client,
             where_param,
+            extra_where_params: vec![],
             with_params: vec![],
+            relation_load_strategy: None,
             _data: PhantomData,
         }
     }
@@ -30,15 +53,29 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
         self
     }
 
+    /// Sets how the record's relations are loaded,
+    /// see [`RelationLoadStrategy`].
+    pub fn relation_load_strategy(mut self, strategy: RelationLoadStrategy) -> Self {
+        self.relation_load_strategy = Some(strategy);
+        self
+    }
+
     fn to_selection(
         where_param: Actions::Where,
+        extra_where_params: Vec<Actions::Where>,
+        relation_load_strategy: Option<RelationLoadStrategy>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
         Self::base_selection(
-            [(
-                "where".to_string(),
-                PrismaValue::Object(vec![where_param.serialize().transform_equals()]).into(),
-            )],
+            [
+                Some((
+                    "where".to_string(),
+                    unique_where(where_param, extra_where_params).into(),
+                )),
+                relation_load_strategy.map(RelationLoadStrategy::argument),
+            ]
+            .into_iter()
+            .flatten(),
             nested_selections,
         )
     }
@@ -47,9 +84,31 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, Option<S::Data>> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
-            Operation::Read(Self::to_selection(self.where_param, select.to_selections())),
+            Operation::Read(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                self.relation_load_strategy,
+                select.to_selections(),
+            )),
+        )
+        .with_computed(computed)
+    }
+
+    /// Selects only `fields`, which can be chosen at runtime,
+    /// along with any relations fetched using `with`.
+    pub fn with_selection(self, fields: &[&str]) -> Select<'a, Option<PartialData>> {
+        Select::new(
+            self.client,
+            Operation::Read(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                self.relation_load_strategy,
+                partial_selections(fields, self.with_params),
+            )),
         )
     }
 
@@ -61,14 +120,47 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
             self.client,
             Operation::Read(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
+                self.relation_load_strategy,
                 include.to_selections(),
             )),
         )
     }
 
+    /// See [`cache_key`](super::cache_key).
+    pub fn cache_key(&self) -> String
+    where
+        Self: Clone,
+    {
+        super::cache_key(self.clone())
+    }
+
     pub async fn exec(self) -> super::Result<Option<Actions::Data>> {
         super::exec(self).await
     }
+
+    /// See [`FindMany::exec_shared`](super::FindMany::exec_shared).
+    pub async fn exec_shared(self) -> super::Result<Option<Arc<Actions::Data>>> {
+        self.exec().await.map(|record| record.map(Arc::new))
+    }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+
+    /// See [`FindMany::exec_as`](super::FindMany::exec_as).
+    pub async fn exec_as<T: serde::de::DeserializeOwned>(self) -> super::Result<Option<T>> {
+        super::exec_as(self).await
+    }
+}
+
+impl<'a, Actions: ExtendedWhereUnique> FindUnique<'a, Actions> {
+    /// Adds filters on non-unique fields alongside the unique filter,
+    /// returning `None` if the record doesn't match them.
+    pub fn and_where(mut self, params: Vec<Actions::Where>) -> Self {
+        self.extra_where_params.extend(params);
+        self
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for FindUnique<'a, Actions> {
@@ -87,7 +179,12 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindUnique<'a, Actions> {
         scalar_selections.extend(self.with_params.into_iter().map(Into::into));
 
         (
-            Operation::Read(Self::to_selection(self.where_param, scalar_selections)),
+            Operation::Read(Self::to_selection(
+                self.where_param,
+                self.extra_where_params,
+                self.relation_load_strategy,
+                scalar_selections,
+            )),
             self.client,
         )
     }
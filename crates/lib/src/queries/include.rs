@@ -28,9 +28,18 @@ impl<'a, Data: query::Data> Include<'a, Data> {
         }
     }
 
+    /// See [`cache_key`](super::cache_key).
+    pub fn cache_key(&self) -> String {
+        super::cache_key::operation_key(&self.operation)
+    }
+
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Data: query::Data> QueryConvert for Include<'a, Data> {
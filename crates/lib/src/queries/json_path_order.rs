@@ -0,0 +1,57 @@
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+
+use super::{QueryError, Result};
+
+pub(crate) const PATH_KEY: &str = "path";
+
+/// The bundled engine can't order by a path inside a JSON field,
+/// so queries that do are rejected before the engine fails to validate them.
+pub(crate) fn ensure_json_path_order_supported(operation: &Operation) -> Result<()> {
+    let selection = match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    match orders_by_json_path(selection) {
+        true => Err(QueryError::UnsupportedJsonPathOrder),
+        false => Ok(()),
+    }
+}
+
+fn orders_by_json_path(selection: &Selection) -> bool {
+    selection
+        .arguments()
+        .iter()
+        .any(|(name, value)| name == "orderBy" && argument_has_path(value))
+        || selection
+            .nested_selections()
+            .iter()
+            .any(orders_by_json_path)
+}
+
+// relation and aggregate orderings never contain lists, so a list under `path` is a JSON path
+fn argument_has_path(value: &ArgumentValue) -> bool {
+    match value {
+        ArgumentValue::Object(fields) => fields.iter().any(|(name, value)| {
+            (name == PATH_KEY
+                && matches!(
+                    value,
+                    ArgumentValue::List(_) | ArgumentValue::Scalar(PrismaValue::List(_))
+                ))
+                || argument_has_path(value)
+        }),
+        ArgumentValue::List(values) => values.iter().any(argument_has_path),
+        ArgumentValue::Scalar(value) => value_has_path(value),
+        _ => false,
+    }
+}
+
+fn value_has_path(value: &PrismaValue) -> bool {
+    match value {
+        PrismaValue::Object(fields) => fields.iter().any(|(name, value)| {
+            (name == PATH_KEY && matches!(value, PrismaValue::List(_))) || value_has_path(value)
+        }),
+        PrismaValue::List(values) => values.iter().any(value_has_path),
+        _ => false,
+    }
+}
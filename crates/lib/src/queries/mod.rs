@@ -1,43 +1,63 @@
 mod batch;
+mod cache_key;
+mod chunked_in;
+#[cfg(feature = "collated-order")]
+mod collated_order;
 mod count;
 mod create;
 mod create_many;
 mod create_unchecked;
+mod db_version;
 mod delete;
 mod delete_many;
 mod error;
 mod execute_raw;
+mod execute_raw_ddl;
+mod explain_raw;
 mod find_first;
 mod find_many;
 mod find_unique;
 mod include;
+#[cfg(feature = "json-path-order")]
+mod json_path_order;
 mod mongo_raw;
+mod partial_data;
 mod query;
 mod query_raw;
+mod relation_load_strategy;
 mod select;
+mod unfiltered_mutation;
 mod update;
+mod update_if_version;
 mod update_many;
 mod update_unchecked;
 mod upsert;
 
 pub use batch::*;
+pub use cache_key::*;
 pub use count::*;
 pub use create::*;
 pub use create_many::*;
 pub use create_unchecked::*;
+pub use db_version::*;
 pub use delete::*;
 pub use delete_many::*;
 pub use error::*;
 pub use execute_raw::*;
+pub use execute_raw_ddl::*;
+pub use explain_raw::*;
 pub use find_first::*;
 pub use find_many::*;
 pub use find_unique::*;
 pub use include::*;
 pub use mongo_raw::*;
+pub use partial_data::*;
 pub use query::*;
 pub use query_raw::*;
+pub use relation_load_strategy::RelationLoadStrategy;
 pub use select::*;
 pub use update::*;
+pub use update_if_version::*;
 pub use update_many::*;
 pub use update_unchecked::*;
 pub use upsert::*;
@@ -45,10 +65,16 @@ pub use upsert::*;
 use futures::FutureExt;
 pub use query_core::{schema::QuerySchemaRef, Operation, Selection};
 use serde::de::IntoDeserializer;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 use std::future::Future;
 
 use crate::ExecutionEngine;
+#[cfg(feature = "collated-order")]
+pub(crate) use collated_order::ensure_collated_order_supported;
+#[cfg(feature = "json-path-order")]
+pub(crate) use json_path_order::ensure_json_path_order_supported;
+pub(crate) use relation_load_strategy::ensure_relation_load_strategy_supported;
+pub(crate) use unfiltered_mutation::ensure_mutation_filtered;
 
 pub enum SerializedWhereValue {
     Object(Vec<(String, prisma_models::PrismaValue)>),
@@ -110,23 +136,88 @@ impl Into<(String, prisma_models::PrismaValue)> for SerializedWhereInput {
     }
 }
 
+/// The `where` argument of a unique query, with any `extendedWhereUnique` filters next to the unique one.
+pub(crate) fn unique_where<W: crate::WhereInput>(
+    where_param: W,
+    extra_where_params: Vec<W>,
+) -> prisma_models::PrismaValue {
+    prisma_models::PrismaValue::Object(
+        [where_param.serialize().transform_equals()]
+            .into_iter()
+            .chain(extra_where_params.into_iter().map(|p| p.serialize().into()))
+            .collect(),
+    )
+}
+
+/// Deserializes a query response, reporting nulls in fields that aren't optional as `QueryError::UnexpectedNull`.
+pub(crate) fn deserialize_response<T: DeserializeOwned>(value: serde_value::Value) -> Result<T> {
+    use serde_value::{DeserializerError, Unexpected};
+
+    serde_path_to_error::deserialize(value.into_deserializer()).map_err(|e| match e.inner() {
+        DeserializerError::InvalidType(Unexpected::Option | Unexpected::Unit, _) => {
+            QueryError::UnexpectedNull {
+                field: e.path().to_string(),
+            }
+        }
+        _ => QueryError::Deserialize(e.into_inner().to_string()),
+    })
+}
+
 pub fn exec<'a, Q: Query<'a> + 'a>(
     query: Q,
 ) -> impl Future<Output = Result<<Q as QueryConvert>::ReturnValue>> + 'a {
     let (op, client) = query.graphql();
+    let failed_query = client.failed_query(&op);
 
-    client.execute(op).map(|value| {
+    client.execute(op).map(move |value| {
         let value = value?;
 
-        Ok(match client.engine {
-            ExecutionEngine::Real { .. } => Q::RawType::deserialize(value.into_deserializer())
-                .map_err(|e| e.to_string())
-                .map_err(QueryError::Deserialize)
-                .and_then(Q::convert)?,
+        let result = match client.engine {
+            ExecutionEngine::Real { .. } => {
+                deserialize_response::<Q::RawType>(value).and_then(Q::convert)
+            }
             #[cfg(feature = "mocking")]
-            ExecutionEngine::Mock(_) => Q::ReturnValue::deserialize(value.into_deserializer())
-                .map_err(|e| e.to_string())
-                .map_err(QueryError::Deserialize)?,
-        })
+            ExecutionEngine::Mock(_) => deserialize_response(value),
+        };
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            client.metrics.record_error(e);
+        }
+
+        result.map_err(|e| e.in_query(failed_query))
+    })
+}
+
+/// Executes `query`, deserializing the response into `T` instead of the query's return type.
+pub fn exec_as<'a, T: DeserializeOwned, Q: Query<'a> + 'a>(
+    query: Q,
+) -> impl Future<Output = Result<T>> + 'a {
+    let (op, client) = query.graphql();
+    let failed_query = client.failed_query(&op);
+
+    client.execute(op).map(move |value| {
+        let result = value.and_then(deserialize_response);
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            client.metrics.record_error(e);
+        }
+
+        result.map_err(|e| e.in_query(failed_query))
+    })
+}
+
+/// Executes `query` without deserializing the response into the query's return type,
+/// useful for inspecting what the engine returned when deserialization fails.
+pub fn exec_raw<'a, Q: Query<'a> + 'a>(
+    query: Q,
+) -> impl Future<Output = Result<serde_json::Value>> + 'a {
+    let (op, client) = query.graphql();
+    let failed_query = client.failed_query(&op);
+
+    client.execute(op).map(move |value| {
+        serde_json::Value::deserialize(value?.into_deserializer())
+            .map_err(|e| QueryError::Deserialize(e.to_string()).in_query(failed_query))
     })
 }
@@ -36,6 +36,10 @@ where
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Data> QueryConvert for RunCommandRaw<'a, Data>
@@ -107,6 +111,10 @@ where
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Types, Data> QueryConvert for FindRaw<'a, Types, Data>
@@ -194,6 +202,10 @@ where
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
 }
 
 impl<'a, Types, Data> QueryConvert for AggregateRaw<'a, Types, Data>
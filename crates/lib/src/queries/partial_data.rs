@@ -0,0 +1,50 @@
+use query_core::Selection;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::sel;
+
+/// A record containing only the fields chosen at runtime with `with_selection`,
+/// keyed by their names in the Prisma schema.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PartialData(Map<String, Value>);
+
+impl PartialData {
+    /// Returns the value of `field` as a `T`,
+    /// or `None` if it wasn't selected, is null, or isn't a `T`.
+    pub fn get<T: DeserializeOwned>(&self, field: &str) -> Option<T> {
+        self.0
+            .get(field)
+            .cloned()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// Returns the unconverted value of `field`.
+    pub fn get_raw(&self, field: &str) -> Option<&Value> {
+        self.0.get(field)
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.0.contains_key(field)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn into_inner(self) -> Map<String, Value> {
+        self.0
+    }
+}
+
+pub(crate) fn partial_selections(
+    fields: &[&str],
+    with_params: impl IntoIterator<Item = impl Into<Selection>>,
+) -> Vec<Selection> {
+    fields
+        .iter()
+        .map(|field| sel(field))
+        .chain(with_params.into_iter().map(Into::into))
+        .collect()
+}
@@ -25,12 +25,35 @@ pub trait ModelTypes {
     type With: Into<Selection>;
     type OrderBy: Into<(String, PrismaValue)>;
     type Cursor: Into<Self::Where>;
+    /// The model's scalar fields, named as they are in the schema by `to_string`.
+    type ScalarField: ToString;
 
     const MODEL: &'static str;
 
     fn scalar_selections() -> Vec<Selection>;
 }
 
+/// Implemented by the generator for models of schemas with the `extendedWhereUnique` preview feature,
+/// allowing unique queries to filter on non-unique fields as well.
+pub trait ExtendedWhereUnique: ModelTypes {}
+
+/// Implemented by the generator for models with a primary key,
+/// allowing records that have already been fetched to be queried again.
+pub trait PrimaryKeyFilter: ModelTypes {
+    /// A filter matching `records` and nothing else.
+    fn primary_key_filter(records: &[Self::Data]) -> Self::Where;
+}
+
+/// Implemented by the generator for the `Data` of models with a primary key,
+/// so that code generic over models can key records by their primary key.
+pub trait HasId {
+    /// The type of the primary key, which is a tuple for compound primary keys.
+    /// Also available as the model module's `Id`.
+    type Id: Clone;
+
+    fn id(&self) -> Self::Id;
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ModelReadOperation {
     FindUnique,
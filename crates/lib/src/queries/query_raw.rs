@@ -10,6 +10,8 @@ use crate::{
     PrismaClientInternals, Query, QueryConvert, QueryError,
 };
 
+/// A raw SQL query whose rows are deserialized into `Data`.
+/// Use [`ExecuteRaw`](super::ExecuteRaw) for statements that only modify data.
 pub struct QueryRaw<'a, Data>
 where
     Data: DeserializeOwned,
@@ -17,6 +19,8 @@ where
     client: &'a PrismaClientInternals,
     sql: String,
     params: Vec<Value>,
+    // returned by `exec` instead of executing the query
+    error: Option<QueryError>,
     _data: PhantomData<Data>,
 }
 
@@ -25,12 +29,16 @@ where
     Data: DeserializeOwned + 'static,
 {
     pub fn new(client: &'a PrismaClientInternals, query: Raw, database: &'static str) -> Self {
-        let (sql, params) = query.convert(database);
+        let (sql, params, error) = match query.try_convert(database) {
+            Ok((sql, params)) => (sql, params, None),
+            Err(e) => (String::new(), vec![], Some(e)),
+        };
 
         Self {
             client,
             sql,
             params,
+            error,
             _data: PhantomData,
         }
     }
@@ -60,8 +68,18 @@ where
             .map_err(Into::into)
     }
 
-    pub async fn exec(self) -> super::Result<Vec<Data>> {
-        super::exec(self).await
+    pub async fn exec(mut self) -> super::Result<Vec<Data>> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => super::exec(self).await,
+        }
+    }
+
+    pub async fn exec_raw(mut self) -> super::Result<serde_json::Value> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => super::exec_raw(self).await,
+        }
     }
 }
 
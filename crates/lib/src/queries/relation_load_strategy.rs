@@ -0,0 +1,49 @@
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation};
+
+use super::{QueryError, Result};
+
+/// How the engine fetches the relations selected by a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationLoadStrategy {
+    /// Fetches relations in the same database query using joins.
+    Join,
+    /// Fetches each relation with a separate database query and merges the results.
+    Query,
+}
+
+impl RelationLoadStrategy {
+    const ARGUMENT: &'static str = "relationLoadStrategy";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Join => "join",
+            Self::Query => "query",
+        }
+    }
+
+    pub(crate) fn argument(self) -> (String, ArgumentValue) {
+        (
+            Self::ARGUMENT.to_string(),
+            PrismaValue::Enum(self.as_str().to_string()).into(),
+        )
+    }
+}
+
+/// The bundled engine predates `relationLoadStrategy`,
+/// so queries using it are rejected before the engine fails to validate them.
+pub(crate) fn ensure_relation_load_strategy_supported(operation: &Operation) -> Result<()> {
+    let selection = match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    if selection
+        .arguments()
+        .iter()
+        .any(|(name, _)| name == RelationLoadStrategy::ARGUMENT)
+    {
+        return Err(QueryError::UnsupportedRelationLoadStrategy);
+    }
+
+    Ok(())
+}
@@ -2,20 +2,93 @@ use std::marker::PhantomData;
 
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
 
-use crate::{Data, PrismaClientInternals, Query, QueryConvert};
+use crate::{
+    Data, ExecutionEngine, PrismaClientInternals, Query, QueryConvert, QueryError, QueryRaw,
+};
 
 pub trait SelectType {
     // TODO: ModelActions
     type Data: Data;
     type ModelData;
 
+    /// The fields configured in `computed_fields` that are selected, if any.
+    /// Must be called before `to_selections`.
+    fn computed(&self) -> Option<ComputedSelection> {
+        None
+    }
+
     fn to_selections(self) -> Vec<Selection>;
 }
 
+/// Builds the raw query computing the given fields for the records with the given primary keys,
+/// returning rows with the primary key and each field under their names.
+pub type ComputedQuery = for<'a> fn(
+    &'a PrismaClientInternals,
+    &[&'static str],
+    Vec<Value>,
+) -> super::Result<QueryRaw<'a, Map<String, Value>>>;
+
+/// Fields configured in `computed_fields` that a `select!` selection includes.
+/// The engine can't compute them, so `exec` fills them in with a raw query
+/// once the rest of the selection has been fetched.
+#[derive(Clone)]
+pub struct ComputedSelection {
+    /// The name of the model's primary key, which records are matched to their computed values by.
+    pub id: &'static str,
+    /// Whether the primary key is only selected for matching, and removed from the records afterwards.
+    pub remove_id: bool,
+    pub fields: Vec<&'static str>,
+    pub query: ComputedQuery,
+}
+
+impl ComputedSelection {
+    /// Adds the computed fields to each record in `value`,
+    /// which is either a list of records, a single record or null.
+    async fn fill(&self, client: &PrismaClientInternals, value: &mut Value) -> super::Result<()> {
+        let records: Vec<&mut Value> = match value {
+            Value::Array(records) => records.iter_mut().collect(),
+            Value::Null => vec![],
+            record => vec![record],
+        };
+
+        let ids = records
+            .iter()
+            .filter_map(|record| record.get(self.id).cloned())
+            .collect::<Vec<_>>();
+
+        let rows = match ids.is_empty() {
+            true => vec![],
+            false => (self.query)(client, &self.fields, ids)?.exec().await?,
+        };
+
+        for record in records {
+            if let Value::Object(record) = record {
+                let row = rows
+                    .iter()
+                    .find(|row| row.get(self.id) == record.get(self.id));
+
+                for field in &self.fields {
+                    let value = row.and_then(|row| row.get(*field)).cloned();
+
+                    record.insert(field.to_string(), value.unwrap_or(Value::Null));
+                }
+
+                if self.remove_id {
+                    record.remove(self.id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct Select<'a, Data: DeserializeOwned> {
     operation: Operation,
     client: &'a PrismaClientInternals,
+    computed: Option<ComputedSelection>,
     _data: PhantomData<Data>,
 }
 
@@ -24,12 +97,47 @@ impl<'a, Data: crate::Data> Select<'a, Data> {
         Self {
             client,
             operation,
+            computed: None,
             _data: PhantomData {},
         }
     }
 
-    pub async fn exec(self) -> super::Result<Data> {
-        super::exec(self).await
+    /// Fills in `computed` once the operation has been executed, see [`SelectType::computed`].
+    /// Computed fields are only filled in by `exec`, not when the query is batched.
+    pub fn with_computed(mut self, computed: Option<ComputedSelection>) -> Self {
+        self.computed = computed;
+        self
+    }
+
+    /// See [`cache_key`](super::cache_key).
+    /// Computed fields aren't part of the operation, so they're appended to its key.
+    pub fn cache_key(&self) -> String {
+        let key = super::cache_key::operation_key(&self.operation);
+
+        match &self.computed {
+            Some(computed) => format!("{key}+computed:{}", computed.fields.join(",")),
+            None => key,
+        }
+    }
+
+    pub async fn exec(mut self) -> super::Result<Data> {
+        let computed = match (self.computed.take(), &self.client.engine) {
+            (Some(computed), ExecutionEngine::Real { .. }) => computed,
+            // mocked values already have the computed fields
+            _ => return super::exec(self).await,
+        };
+        let client = self.client;
+
+        let mut value = super::exec_as::<Value, _>(self).await?;
+        computed.fill(client, &mut value).await?;
+
+        super::deserialize_response(
+            serde_value::to_value(value).map_err(|e| QueryError::Deserialize(e.to_string()))?,
+        )
+    }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
     }
 }
 
@@ -0,0 +1,29 @@
+use query_core::Operation;
+
+use super::{ModelWriteOperation, QueryError, Result};
+
+/// `delete_many` and `update_many` without any filters affect every record,
+/// so they are rejected unless `all` was called, which sends an empty `where` instead of none.
+pub(crate) fn ensure_mutation_filtered(operation: &Operation) -> Result<()> {
+    let selection = match operation {
+        Operation::Write(selection) => selection,
+        Operation::Read(_) => return Ok(()),
+    };
+
+    let is_many_mutation = [
+        ModelWriteOperation::DeleteMany,
+        ModelWriteOperation::UpdateMany,
+    ]
+    .iter()
+    .any(|op| selection.name().starts_with(op.name()));
+
+    let filtered = selection
+        .arguments()
+        .iter()
+        .any(|(name, _)| name == "where");
+
+    match is_many_mutation && !filtered {
+        true => Err(QueryError::UnfilteredMutation),
+        false => Ok(()),
+    }
+}
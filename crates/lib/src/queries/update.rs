@@ -2,14 +2,17 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
-    ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select, SelectType, SetQuery,
-    WhereInput, WithQuery,
+    merge_fields, ExtendedWhereUnique, Include, IncludeType, ModelOperation, ModelQuery,
+    ModelTypes, ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select,
+    SelectType, SetQuery, WithQuery,
 };
 
+use super::unique_where;
+
 pub struct Update<'a, Actions: ModelTypes> {
-    client: &'a PrismaClientInternals,
+    pub(crate) client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
+    pub extra_where_params: Vec<Actions::Where>,
     pub set_params: Vec<Actions::Set>,
     pub with_params: Vec<Actions::With>,
 }
@@ -24,6 +27,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
         Self {
             client,
             where_param,
+            extra_where_params: vec![],
             set_params,
             with_params,
         }
@@ -34,8 +38,15 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
         self
     }
 
+    /// Adds the params of a sparse input, such as a model's generated `UpdateInput`.
+    pub fn with_input(mut self, input: impl Into<Vec<Actions::Set>>) -> Self {
+        self.set_params.extend(input.into());
+        self
+    }
+
     fn to_selection(
         where_param: Actions::Where,
+        extra_where_params: Vec<Actions::Where>,
         set_params: Vec<Actions::Set>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
@@ -43,7 +54,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
             [
                 (
                     "where".to_string(),
-                    PrismaValue::Object(vec![where_param.serialize().transform_equals()]).into(),
+                    unique_where(where_param, extra_where_params).into(),
                 ),
                 (
                     "data".to_string(),
@@ -61,14 +72,18 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, S::Data> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 select.to_selections(),
             )),
         )
+        .with_computed(computed)
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -79,6 +94,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 include.to_selections(),
             )),
@@ -88,6 +104,21 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
     pub async fn exec(self) -> super::Result<Actions::Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+impl<'a, Actions: ExtendedWhereUnique> Update<'a, Actions> {
+    /// Adds filters on non-unique fields alongside the unique filter,
+    /// so that the record is only updated if it also matches them.
+    /// Filtering on a version field this way allows for optimistic concurrency,
+    /// with a `RecordRequiredButNotFound` error returned if the version has changed.
+    pub fn and_where(mut self, params: Vec<Actions::Where>) -> Self {
+        self.extra_where_params.extend(params);
+        self
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for Update<'a, Actions> {
@@ -108,6 +139,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for Update<'a, Actions> {
         (
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 scalar_selections,
             )),
@@ -0,0 +1,64 @@
+use user_facing_errors::query_engine::RecordRequiredButNotFound;
+
+use crate::{FindUnique, ModelTypes, QueryError, Update};
+
+/// The cause the engine gives when an update's own filter matches no record,
+/// rather than the filter of one of its nested writes.
+const RECORD_TO_UPDATE_NOT_FOUND: &str = "Record to update not found.";
+
+/// An [`Update`] filtered on the record's version field, created with a model's `update_if_version`.
+pub struct UpdateIfVersion<'a, Actions: ModelTypes> {
+    update: Update<'a, Actions>,
+}
+
+impl<'a, Actions: ModelTypes> UpdateIfVersion<'a, Actions> {
+    pub fn new(update: Update<'a, Actions>) -> Self {
+        Self { update }
+    }
+
+    pub fn with(self, param: impl Into<Actions::With>) -> Self {
+        Self {
+            update: self.update.with(param),
+        }
+    }
+
+    /// Fails with [`QueryError::VersionConflict`] if the record exists but doesn't have the expected version.
+    /// Other errors, including the record not existing, are returned as they are.
+    pub async fn exec(self) -> super::Result<Actions::Data>
+    where
+        Actions::Where: Clone,
+    {
+        let client = self.update.client;
+        let where_param = self.update.where_param.clone();
+
+        match self.update.exec().await {
+            Err(e) if is_record_to_update_not_found(&e) => {
+                // without the version filter, the record only isn't found if it doesn't exist
+                let exists = FindUnique::<Actions>::new(client, where_param)
+                    .exec()
+                    .await?
+                    .is_some();
+
+                match exists {
+                    true => Err(QueryError::VersionConflict),
+                    false => Err(e),
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+fn is_record_to_update_not_found(error: &QueryError) -> bool {
+    if !error.is_prisma_error::<RecordRequiredButNotFound>() {
+        return false;
+    }
+
+    match error.inner() {
+        QueryError::Execute(error) => error
+            .as_known()
+            .map(|e| e.meta["cause"] == RECORD_TO_UPDATE_NOT_FOUND)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
@@ -1,15 +1,17 @@
 use prisma_models::PrismaValue;
-use query_core::Operation;
+use query_core::{Operation, TransactionOptions};
 
 use crate::{
-    merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, Query, QueryConvert, SetQuery, WhereInput, WhereQuery,
+    merge_fields, transaction::RollbackOnDrop, BatchResult, ExecutionEngine, FindMany,
+    ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation, PrimaryKeyFilter,
+    PrismaClientInternals, Query, QueryConvert, QueryError, SetQuery, WhereInput, WhereQuery,
 };
 
 pub struct UpdateMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
     pub set_params: Vec<Actions::Set>,
+    all: bool,
 }
 
 impl<'a, Actions: ModelTypes> UpdateMany<'a, Actions> {
@@ -22,12 +24,118 @@ impl<'a, Actions: ModelTypes> UpdateMany<'a, Actions> {
             client,
             where_params,
             set_params,
+            all: false,
         }
     }
 
+    /// Allows the query to run without any filters, updating EVERY record of the model.
+    /// Without this, an unfiltered `update_many` fails with `QueryError::UnfilteredMutation`.
+    pub fn all(mut self) -> Self {
+        self.all = true;
+        self
+    }
+
     pub async fn exec(self) -> super::Result<i64> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+impl<'a, Actions: PrimaryKeyFilter> UpdateMany<'a, Actions> {
+    /// Returns the updated records instead of how many there were.
+    pub fn returning(self) -> UpdateManyReturning<'a, Actions> {
+        UpdateManyReturning {
+            update_many: self,
+            timeout: 5000,
+        }
+    }
+}
+
+/// An [`UpdateMany`] that returns the updated records.
+///
+/// The engine's `updateMany` only returns a count, so the matching records are found,
+/// updated by their primary keys, and then fetched again, all in one transaction.
+pub struct UpdateManyReturning<'a, Actions: ModelTypes> {
+    update_many: UpdateMany<'a, Actions>,
+    timeout: u64,
+}
+
+impl<'a, Actions: PrimaryKeyFilter> UpdateManyReturning<'a, Actions> {
+    /// How long the transaction the queries run in is kept open for, in milliseconds.
+    /// Has no effect when already inside a transaction.
+    pub fn with_timeout(self, timeout: u64) -> Self {
+        Self { timeout, ..self }
+    }
+
+    pub async fn exec(self) -> super::Result<Vec<Actions::Data>> {
+        let UpdateMany {
+            client,
+            where_params,
+            set_params,
+            all,
+        } = self.update_many;
+
+        // the update itself is filtered by primary key, so the guard is applied here instead
+        if where_params.is_empty() && !all {
+            return Err(QueryError::UnfilteredMutation);
+        }
+
+        let (tx_client, guard) = match &client.engine {
+            ExecutionEngine::Real {
+                engine,
+                tx_id: None,
+            } => {
+                let guard = RollbackOnDrop::start(
+                    client,
+                    engine.clone(),
+                    TransactionOptions::new(2000, self.timeout, None),
+                )
+                .await?;
+
+                (client.with_tx_id(Some(guard.tx_id())), Some(guard))
+            }
+            // already inside a transaction, or using a mock engine
+            _ => (client.clone(), None),
+        };
+
+        let result = Self::update_and_fetch(&tx_client, where_params, set_params).await;
+
+        if let Some(guard) = guard {
+            guard.finish(result.is_ok()).await?;
+        }
+
+        result
+    }
+
+    async fn update_and_fetch(
+        client: &PrismaClientInternals,
+        where_params: Vec<Actions::Where>,
+        set_params: Vec<Actions::Set>,
+    ) -> super::Result<Vec<Actions::Data>> {
+        let records = FindMany::<Actions>::new(client, where_params)
+            .exec()
+            .await?;
+
+        if records.is_empty() {
+            return Ok(records);
+        }
+
+        // the updated records may no longer match `where_params`
+        UpdateMany::<Actions>::new(
+            client,
+            vec![Actions::primary_key_filter(&records)],
+            set_params,
+        )
+        .exec()
+        .await?;
+
+        FindMany::<Actions>::new(client, vec![Actions::primary_key_filter(&records)])
+            .exec()
+            .await
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for UpdateMany<'a, Actions> {
@@ -55,7 +163,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateMany<'a, Actions> {
                         ))
                         .into(),
                     )),
-                    (!self.where_params.is_empty()).then(|| {
+                    (!self.where_params.is_empty() || self.all).then(|| {
                         (
                             "where".to_string(),
                             PrismaValue::Object(merge_fields(
@@ -2,14 +2,17 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
-    ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
-    UncheckedSetQuery, WhereInput, WithQuery,
+    merge_fields, ExtendedWhereUnique, Include, IncludeType, ModelOperation, ModelQuery,
+    ModelTypes, ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select,
+    SelectType, UncheckedSetQuery, WithQuery,
 };
 
+use super::unique_where;
+
 pub struct UpdateUnchecked<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
+    pub extra_where_params: Vec<Actions::Where>,
     pub set_params: Vec<Actions::UncheckedSet>,
     pub with_params: Vec<Actions::With>,
 }
@@ -24,6 +27,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
         Self {
             client,
             where_param,
+            extra_where_params: vec![],
             set_params,
             with_params,
         }
@@ -36,6 +40,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
 
     fn to_selection(
         where_param: Actions::Where,
+        extra_where_params: Vec<Actions::Where>,
         set_params: Vec<Actions::UncheckedSet>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
@@ -43,7 +48,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
             [
                 (
                     "where".to_string(),
-                    PrismaValue::Object(vec![where_param.serialize().transform_equals()]).into(),
+                    unique_where(where_param, extra_where_params).into(),
                 ),
                 (
                     "data".to_string(),
@@ -61,14 +66,18 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, S::Data> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 select.to_selections(),
             )),
         )
+        .with_computed(computed)
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -79,6 +88,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 include.to_selections(),
             )),
@@ -88,6 +98,18 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
     pub async fn exec(self) -> super::Result<Actions::Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+impl<'a, Actions: ExtendedWhereUnique> UpdateUnchecked<'a, Actions> {
+    /// Adds filters on non-unique fields alongside the unique filter, the same as [`Update::and_where`](crate::Update::and_where).
+    pub fn and_where(mut self, params: Vec<Actions::Where>) -> Self {
+        self.extra_where_params.extend(params);
+        self
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for UpdateUnchecked<'a, Actions> {
@@ -108,6 +130,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateUnchecked<'a, Actions> {
         (
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.set_params,
                 scalar_selections,
             )),
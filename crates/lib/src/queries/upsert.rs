@@ -2,13 +2,16 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    Include, IncludeType, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
+    ExtendedWhereUnique, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
+    ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select, SelectType, WithQuery,
 };
 
+use super::unique_where;
+
 pub struct Upsert<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
+    pub extra_where_params: Vec<Actions::Where>,
     pub create_params: Vec<Actions::Set>,
     pub update_params: Vec<Actions::Set>,
     pub with_params: Vec<Actions::With>,
@@ -24,6 +27,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
         Self {
             client,
             where_param,
+            extra_where_params: vec![],
             create_params,
             update_params,
             with_params: vec![],
@@ -37,6 +41,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
 
     fn to_selection(
         where_param: Actions::Where,
+        extra_where_params: Vec<Actions::Where>,
         create_params: Vec<Actions::Set>,
         update_params: Vec<Actions::Set>,
         nested_selections: impl IntoIterator<Item = Selection>,
@@ -45,7 +50,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
             [
                 (
                     "where".to_string(),
-                    PrismaValue::Object(vec![where_param.serialize().transform_equals()]).into(),
+                    unique_where(where_param, extra_where_params).into(),
                 ),
                 (
                     "create".to_string(),
@@ -64,15 +69,19 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
         self,
         select: S,
     ) -> Select<'a, S::Data> {
+        let computed = select.computed();
+
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.create_params,
                 self.update_params,
                 select.to_selections(),
             )),
         )
+        .with_computed(computed)
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -83,6 +92,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.create_params,
                 self.update_params,
                 select.to_selections(),
@@ -93,6 +103,20 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
     pub async fn exec(self) -> super::Result<Actions::Data> {
         super::exec(self).await
     }
+
+    pub async fn exec_raw(self) -> super::Result<serde_json::Value> {
+        super::exec_raw(self).await
+    }
+}
+
+impl<'a, Actions: ExtendedWhereUnique> Upsert<'a, Actions> {
+    /// Adds filters on non-unique fields alongside the unique filter.
+    /// If the existing record doesn't match them a new record is created,
+    /// which can fail if it violates the unique constraint.
+    pub fn and_where(mut self, params: Vec<Actions::Where>) -> Self {
+        self.extra_where_params.extend(params);
+        self
+    }
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for Upsert<'a, Actions> {
@@ -113,6 +137,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for Upsert<'a, Actions> {
         (
             Operation::Write(Self::to_selection(
                 self.where_param,
+                self.extra_where_params,
                 self.create_params,
                 self.update_params,
                 scalar_selections,
@@ -6,13 +6,20 @@ use serde::{Deserialize, Serialize, Serializer};
 use serde_json::{json, Value};
 use std::str::FromStr;
 
+use crate::{
+    raw_placeholders::{placeholders, Placeholder},
+    QueryError,
+};
+
+/// Creates a [`Raw`] query, checking at compile time that the number of placeholders
+/// in a string literal query matches the number of parameters provided.
+///
+/// Placeholders can be written as `{}`, `$1`, `$2`... or, for SQLite and MySQL, `?`,
+/// and are converted to the syntax of the database being queried.
 #[macro_export]
 macro_rules! raw {
-    ($e: expr) => {
-        $crate::Raw::new($e, vec![]);
-    };
-    ($e: expr, $($params:expr),+) => {
-        $crate::Raw::new($e, vec![$($params),+])
+    ($($tt:tt)*) => {
+        $crate::macros::raw!($crate; $($tt)*)
     };
 }
 
@@ -38,25 +45,65 @@ impl Raw {
         }
     }
 
+    /// Converts the query's placeholders to those of `database`.
+    ///
+    /// Panics if the query references more parameters than were provided,
+    /// see [`try_convert`](Self::try_convert) to get an error instead.
     pub fn convert(self, database: &'static str) -> (String, Vec<Value>) {
-        let Self { mut query, values } = self;
+        self.try_convert(database)
+            .unwrap_or_else(|_| panic!("Raw query has not been given enough parameters"))
+    }
+
+    /// Converts the query's placeholders to those of `database`,
+    /// failing if it references more parameters than were provided.
+    pub fn try_convert(self, database: &'static str) -> crate::Result<(String, Vec<Value>)> {
+        let Self { query, values } = self;
+
+        let positional_database = match database {
+            "postgresql" | "cockroachdb" => true,
+            "sqlite" | "mysql" => false,
+            _ => panic!("Raw queries are not supported with database '{database}'"),
+        };
 
-        for i in 1..=values.len() {
-            let variable_indicator = match database {
-                "postgresql" | "cockroachdb" => format!("${i}"),
-                "sqlite" | "mysql" => "?".to_string(),
-                _ => panic!("Raw queries are not supported with database '{database}'"),
+        let mut converted = String::with_capacity(query.len());
+        let mut converted_values = vec![];
+        let mut last = 0;
+
+        for (i, (range, placeholder)) in placeholders(&query, !positional_database)
+            .into_iter()
+            .enumerate()
+        {
+            converted.push_str(&query[last..range.start]);
+            last = range.end;
+
+            let position = match placeholder {
+                Placeholder::Positional(position) => position,
+                _ => i + 1,
             };
 
-            query = query.replacen("{}", &variable_indicator, 1);
-        }
+            let value = values.get(position - 1).ok_or(QueryError::RawParameters {
+                expected: position,
+                provided: values.len(),
+            })?;
 
-        // TODO: do this at compile time
-        if query.contains("{}") {
-            panic!("Raw query has not been given enough parameters");
+            if positional_database {
+                converted.push_str(&format!("${position}"));
+            } else {
+                converted.push('?');
+                converted_values.push(value.clone());
+            }
         }
 
-        (query, values)
+        converted.push_str(&query[last..]);
+
+        Ok((
+            converted,
+            if positional_database {
+                values
+            } else {
+                converted_values
+            },
+        ))
     }
 }
 
@@ -0,0 +1,133 @@
+//! Placeholder detection for raw queries, shared by [`Raw`](crate::Raw) and the `raw!` macro's
+//! compile-time check so that both agree on what a query expects.
+//! Only depends on `std`, since the macro crate includes this file directly.
+
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Placeholder {
+    Braces,
+    Positional(usize),
+    Question,
+}
+
+/// Finds the placeholders in a raw query, skipping quoted strings and identifiers,
+/// `--` and `/* */` comments and `$$`/`$tag$` dollar-quoted bodies.
+/// Only one style is used per query: `{}` takes precedence over `$n`, which takes precedence over `?`.
+/// `?` is only a placeholder if `questions` is set, since it's also one of PostgreSQL's JSON operators.
+pub(crate) fn placeholders(query: &str, questions: bool) -> Vec<(Range<usize>, Placeholder)> {
+    let bytes = query.as_bytes();
+    let mut found = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i = skip_past(query, i + 1, &[quote]);
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = skip_past(query, i + 2, b"\n");
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_block_comment(bytes, i + 2);
+                continue;
+            }
+            b'{' if bytes.get(i + 1) == Some(&b'}') => {
+                found.push((i..i + 2, Placeholder::Braces));
+                i += 2;
+                continue;
+            }
+            b'?' if questions => found.push((i..i + 1, Placeholder::Question)),
+            b'$' => {
+                let digits = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+
+                if digits > 0 {
+                    let end = i + 1 + digits;
+
+                    if let Ok(position) = query[i + 1..end].parse::<usize>() {
+                        if position > 0 {
+                            found.push((i..end, Placeholder::Positional(position)));
+                        }
+                    }
+
+                    i = end;
+                    continue;
+                }
+
+                if let Some(tag_len) = dollar_tag(bytes, i) {
+                    let tag = &query[i..i + tag_len];
+                    i = match query[i + tag_len..].find(tag) {
+                        Some(end) => i + tag_len + end + tag_len,
+                        None => bytes.len(),
+                    };
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    let style = |p: &Placeholder| match p {
+        Placeholder::Braces => 0,
+        Placeholder::Positional(_) => 1,
+        Placeholder::Question => 2,
+    };
+
+    if let Some(used) = found.iter().map(|(_, p)| style(p)).min() {
+        found.retain(|(_, p)| style(p) == used);
+    }
+
+    found
+}
+
+/// Index just after the first of `terminator` at or after `from`, or the end of `query`.
+fn skip_past(query: &str, from: usize, terminator: &[u8]) -> usize {
+    query.as_bytes()[from..]
+        .windows(terminator.len())
+        .position(|w| w == terminator)
+        .map(|p| from + p + terminator.len())
+        .unwrap_or(query.len())
+}
+
+/// Index just after the `*/` closing a block comment, which can be nested in PostgreSQL.
+fn skip_block_comment(bytes: &[u8], mut i: usize) -> usize {
+    let mut depth = 1;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 {
+                    return i;
+                }
+            }
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// Length of the `$$` or `$tag$` starting a dollar-quoted body at `start`, if there is one.
+/// Tags can't start with a digit, but those are already taken as positional placeholders.
+fn dollar_tag(bytes: &[u8], start: usize) -> Option<usize> {
+    let tag = bytes[start + 1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count();
+
+    (bytes.get(start + 1 + tag) == Some(&b'$')).then_some(tag + 2)
+}
@@ -0,0 +1,117 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use psl::ValidatedSchema;
+use query_core::{schema::QuerySchema, CoreError};
+use thiserror::Error;
+
+use crate::{pool::set_url_params, ExecutorConnector, QueryEngine};
+
+#[derive(Debug, Error)]
+pub enum WithSchemaError {
+    #[error("'{0}' is not a valid schema name")]
+    InvalidName(String),
+    #[error("Schema overrides are only supported by PostgreSQL and CockroachDB, not '{0}'")]
+    UnsupportedProvider(String),
+    #[error("Schema overrides can't be used with models that declare a schema with @@schema")]
+    MultiSchema,
+    #[error("Schema overrides aren't available to custom engines or transactions")]
+    UnsupportedClient,
+    #[error("Error loading database executor: {0}")]
+    Executor(#[from] CoreError),
+    #[error("Error getting database connection: {0}")]
+    Connection(#[from] query_core::ConnectorError),
+    #[error("The client's runtime shut down before the schema's engine was loaded")]
+    Cancelled,
+}
+
+/// Whether `name` can be used as a schema without quoting,
+/// which rules out anything that could escape the `schema` URL parameter.
+pub fn is_valid_schema_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        // postgres truncates identifiers longer than this
+        && name.len() <= 63
+}
+
+/// The engines of a client's schema overrides, each connecting with a different `schema` URL parameter.
+///
+/// The engine qualifies table names with the connection's schema,
+/// so each schema needs a connection pool of its own.
+/// Engines are kept until they're evicted, as only the caller knows when a tenant has gone idle.
+pub(crate) struct SchemaEngines {
+    schema: Arc<ValidatedSchema>,
+    query_schema: Arc<QuerySchema>,
+    url: String,
+    engines: Mutex<HashMap<String, Arc<dyn QueryEngine>>>,
+}
+
+impl SchemaEngines {
+    pub fn new(schema: Arc<ValidatedSchema>, query_schema: Arc<QuerySchema>, url: String) -> Self {
+        Self {
+            schema,
+            query_schema,
+            url,
+            engines: Default::default(),
+        }
+    }
+
+    /// The engine for `name`, which is connected the first time it's requested.
+    pub async fn get(&self, name: &str) -> Result<Arc<dyn QueryEngine>, WithSchemaError> {
+        if !is_valid_schema_name(name) {
+            return Err(WithSchemaError::InvalidName(name.to_string()));
+        }
+
+        let config = &self.schema.configuration;
+        let source = config
+            .datasources
+            .first()
+            .expect("Please supply a datasource in your schema.prisma file");
+
+        if !matches!(source.active_provider, "postgresql" | "cockroachdb") {
+            return Err(WithSchemaError::UnsupportedProvider(
+                source.active_provider.to_string(),
+            ));
+        }
+
+        if self.schema.db.walk_models().any(|m| m.schema().is_some()) {
+            return Err(WithSchemaError::MultiSchema);
+        }
+
+        if let Some(engine) = self.engines.lock().unwrap().get(name) {
+            return Ok(engine.clone());
+        }
+
+        let url = set_url_params(&self.url, vec![("schema", name.to_string())]);
+
+        let executor =
+            request_handlers::load_executor(source, config.preview_features(), &url).await?;
+
+        executor.primary_connector().get_connection().await?;
+
+        let engine: Arc<dyn QueryEngine> = Arc::new(ExecutorConnector {
+            executor,
+            query_schema: self.query_schema.clone(),
+            url,
+        });
+
+        // another task may have connected to the same schema in the meantime
+        Ok(self
+            .engines
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert(engine)
+            .clone())
+    }
+
+    /// Forgets the engine for `name`, returning whether there was one.
+    /// Its connection pool closes once the clients already using it are dropped.
+    pub fn evict(&self, name: &str) -> bool {
+        self.engines.lock().unwrap().remove(name).is_some()
+    }
+}
@@ -1,3 +1,9 @@
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+use serde::Serialize;
+
+use crate::{QueryError, SerializedWhereValue};
+
 // From serde_with
 pub mod double_option {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -21,3 +27,282 @@ pub mod double_option {
         }
     }
 }
+
+/// Converts the value of a `Json` field that has had its type overridden
+/// into the value sent to the query engine.
+pub fn to_json_value<T: Serialize>(value: T) -> Result<serde_json::Value, QueryError> {
+    serde_json::to_value(value).map_err(|e| QueryError::SerializeOverride(e.to_string()))
+}
+
+/// Converts the value of a `String` field that has had its type overridden
+/// into the value sent to the query engine.
+/// The type must serialize as a string, like it is deserialized from one.
+pub fn to_string_value<T: Serialize>(value: T) -> Result<String, QueryError> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => Ok(value),
+        Ok(value) => Err(QueryError::SerializeOverride(format!(
+            "expected the value of a String field to serialize to a string, got {value}"
+        ))),
+        Err(e) => Err(QueryError::SerializeOverride(e.to_string())),
+    }
+}
+
+// the key of the object that stands in for a value that failed to serialize,
+// which no query the client builds contains otherwise
+const SERIALIZE_ERROR_KEY: &str = "$prismaClientRustSerializeError";
+
+/// The param of a field that has had its type overridden, which generated `SetParam`s and `WhereParam`s hold.
+///
+/// Params are built by infallible functions like `set` and `equals`,
+/// so a value that fails to serialize is kept as an error instead,
+/// and fails the query it's used in with [`QueryError::SerializeOverride`] when executed.
+#[derive(Debug, Clone)]
+pub enum Overridden<P> {
+    Param(P),
+    Error(String),
+}
+
+impl<P> Overridden<P> {
+    /// `param` built from `value` once it's been converted with [`to_json_value`] or [`to_string_value`].
+    pub fn new<T>(value: Result<T, QueryError>, param: impl FnOnce(T) -> P) -> Self {
+        match value {
+            Ok(value) => Self::Param(param(value)),
+            Err(QueryError::SerializeOverride(message)) => Self::Error(message),
+            Err(e) => Self::Error(e.to_string()),
+        }
+    }
+}
+
+fn error_value(message: String) -> PrismaValue {
+    PrismaValue::Object(vec![(
+        SERIALIZE_ERROR_KEY.to_string(),
+        PrismaValue::String(message),
+    )])
+}
+
+impl<P> From<P> for Overridden<P> {
+    fn from(param: P) -> Self {
+        Self::Param(param)
+    }
+}
+
+impl<P: Into<PrismaValue>> From<Overridden<P>> for PrismaValue {
+    fn from(overridden: Overridden<P>) -> Self {
+        match overridden {
+            Overridden::Param(param) => param.into(),
+            Overridden::Error(message) => error_value(message),
+        }
+    }
+}
+
+impl<P: Into<SerializedWhereValue>> From<Overridden<P>> for SerializedWhereValue {
+    fn from(overridden: Overridden<P>) -> Self {
+        match overridden {
+            Overridden::Param(param) => param.into(),
+            Overridden::Error(message) => Self::Value(error_value(message)),
+        }
+    }
+}
+
+/// Fails `operation` with the error of the first value in it that failed to serialize.
+pub(crate) fn ensure_serialized(operation: &Operation) -> crate::Result<()> {
+    let selection = match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    match selection_error(selection) {
+        Some(message) => Err(QueryError::SerializeOverride(message.to_string())),
+        None => Ok(()),
+    }
+}
+
+fn selection_error(selection: &Selection) -> Option<&str> {
+    selection
+        .arguments()
+        .iter()
+        .find_map(|(_, value)| argument_error(value))
+        .or_else(|| {
+            selection
+                .nested_selections()
+                .iter()
+                .find_map(selection_error)
+        })
+}
+
+fn argument_error(value: &ArgumentValue) -> Option<&str> {
+    match value {
+        ArgumentValue::Object(fields) => match fields.get(SERIALIZE_ERROR_KEY) {
+            Some(ArgumentValue::Scalar(PrismaValue::String(message))) => Some(message),
+            _ => fields.values().find_map(argument_error),
+        },
+        ArgumentValue::List(values) => values.iter().find_map(argument_error),
+        ArgumentValue::Scalar(value) => value_error(value),
+        _ => None,
+    }
+}
+
+fn value_error(value: &PrismaValue) -> Option<&str> {
+    match value {
+        PrismaValue::Object(fields) => fields.iter().find_map(|(key, value)| match value {
+            PrismaValue::String(message) if key == SERIALIZE_ERROR_KEY => Some(message.as_str()),
+            value => value_error(value),
+        }),
+        PrismaValue::List(values) => values.iter().find_map(value_error),
+        _ => None,
+    }
+}
+
+/// Deserialization of fields with a [`FieldCodec`](crate::FieldCodec),
+/// which generated `Data` structs use to decode their stored values.
+pub mod codec {
+    use serde::{Deserialize, Deserializer};
+
+    use crate::{Decoded, FieldCodec};
+
+    pub fn deserialize<'de, C: FieldCodec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        Decoded::<C>::deserialize(deserializer).map(|Decoded(value, _)| value)
+    }
+
+    pub fn deserialize_option<'de, C: FieldCodec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Option::<Decoded<C>>::deserialize(deserializer)
+            .map(|value| value.map(|Decoded(value, _)| value))
+    }
+}
+
+/// (De)serialization of `BigInt` fields, which generated `Data` structs use for `i64`,
+/// `Option<i64>` and `Vec<i64>` fields of that type.
+///
+/// Deserializing accepts numbers and strings,
+/// since engines speaking Prisma's JSON protocol send BigInt values as strings.
+/// Serializing writes a number, or a string with [`as_string`] for consumers
+/// such as JavaScript that lose precision on integers past 2^53.
+pub mod bigint {
+    use std::fmt;
+
+    use serde::{
+        de::{self, DeserializeOwned, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    pub enum Repr {
+        Int(i64),
+        String(String),
+    }
+
+    // a visitor rather than an untagged enum, so that nulls are reported as invalid types
+    impl<'de> Deserialize<'de> for Repr {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct ReprVisitor;
+
+            impl<'de> Visitor<'de> for ReprVisitor {
+                type Value = Repr;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a BigInt as a number or string")
+                }
+
+                fn visit_i64<E: de::Error>(self, value: i64) -> Result<Repr, E> {
+                    Ok(Repr::Int(value))
+                }
+
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<Repr, E> {
+                    i64::try_from(value)
+                        .map(Repr::Int)
+                        .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+                }
+
+                fn visit_str<E: de::Error>(self, value: &str) -> Result<Repr, E> {
+                    Ok(Repr::String(value.to_string()))
+                }
+            }
+
+            deserializer.deserialize_any(ReprVisitor)
+        }
+    }
+
+    /// Types built from BigInt values.
+    pub trait BigIntField: Sized {
+        type Repr: DeserializeOwned;
+        type Strings: Serialize;
+
+        fn from_repr(repr: Self::Repr) -> Result<Self, String>;
+        fn to_strings(&self) -> Self::Strings;
+    }
+
+    impl BigIntField for i64 {
+        type Repr = Repr;
+        type Strings = String;
+
+        fn from_repr(repr: Repr) -> Result<Self, String> {
+            match repr {
+                Repr::Int(value) => Ok(value),
+                Repr::String(value) => value
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not a valid BigInt")),
+            }
+        }
+
+        fn to_strings(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl<T: BigIntField> BigIntField for Option<T> {
+        type Repr = Option<T::Repr>;
+        type Strings = Option<T::Strings>;
+
+        fn from_repr(repr: Self::Repr) -> Result<Self, String> {
+            repr.map(T::from_repr).transpose()
+        }
+
+        fn to_strings(&self) -> Self::Strings {
+            self.as_ref().map(T::to_strings)
+        }
+    }
+
+    impl<T: BigIntField> BigIntField for Vec<T> {
+        type Repr = Vec<T::Repr>;
+        type Strings = Vec<T::Strings>;
+
+        fn from_repr(repr: Self::Repr) -> Result<Self, String> {
+            repr.into_iter().map(T::from_repr).collect()
+        }
+
+        fn to_strings(&self) -> Self::Strings {
+            self.iter().map(T::to_strings).collect()
+        }
+    }
+
+    pub fn deserialize<'de, T: BigIntField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::from_repr(T::Repr::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    /// Like [`bigint`](self), but serializes values as strings.
+    pub mod as_string {
+        use serde::{Serialize, Serializer};
+
+        use super::BigIntField;
+
+        pub use super::deserialize;
+
+        pub fn serialize<T: BigIntField, S: Serializer>(
+            value: &T,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.to_strings().serialize(serializer)
+        }
+    }
+}
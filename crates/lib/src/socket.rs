@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::pool::set_url_params;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SocketError {
+    #[error("Unix sockets cannot be used with the '{0}' provider")]
+    UnsupportedProvider(String),
+    #[error("Socket path '{}' must be absolute", .0.display())]
+    RelativePath(PathBuf),
+    #[error("Socket path '{}' is not valid UTF-8", .0.display())]
+    NonUtf8Path(PathBuf),
+}
+
+/// The URL parameter `provider` reads a Unix socket path from, if it can connect over one.
+///
+/// Postgres treats a `host` starting with `/` as the directory containing the socket,
+/// while MySQL takes the path of the socket file itself.
+fn socket_param(provider: &str) -> Option<&'static str> {
+    match provider {
+        "postgresql" | "cockroachdb" => Some("host"),
+        "mysql" => Some("socket"),
+        _ => None,
+    }
+}
+
+/// Validates `socket` for `provider` and writes it into `url`'s query string,
+/// taking precedence over any socket already given by the URL.
+pub fn apply_socket_to_url(
+    socket: Option<&Path>,
+    provider: &str,
+    url: &str,
+) -> Result<String, SocketError> {
+    let socket = match socket {
+        Some(socket) => socket,
+        None => return Ok(url.to_string()),
+    };
+
+    let param = socket_param(provider)
+        .ok_or_else(|| SocketError::UnsupportedProvider(provider.to_string()))?;
+
+    if !socket.is_absolute() {
+        return Err(SocketError::RelativePath(socket.to_path_buf()));
+    }
+
+    let path = socket
+        .to_str()
+        .ok_or_else(|| SocketError::NonUtf8Path(socket.to_path_buf()))?;
+
+    Ok(set_url_params(url, vec![(param, encode_query_value(path))]))
+}
+
+// the engine percent-decodes query parameters, so anything that isn't safe in a URL is escaped
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
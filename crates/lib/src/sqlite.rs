@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::{pool::set_url_params, QueryError};
+
+/// Settings for reducing "database is locked" errors when using SQLite.
+///
+/// `busy_timeout` is passed to the engine as the `socket_timeout` URL parameter,
+/// which it uses as SQLite's busy timeout for every connection in the pool.
+/// `wal_mode` runs `PRAGMA journal_mode = WAL` after connecting.
+/// WAL mode is stored in the database file, so it persists for other connections and processes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqliteConfig {
+    pub busy_timeout: Option<Duration>,
+    pub wal_mode: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum SqliteConfigError {
+    #[error("SQLite options cannot be used with the '{0}' provider")]
+    UnsupportedProvider(String),
+    #[error("busy_timeout must be at least one second")]
+    BusyTimeoutTooShort,
+    #[error("Failed to enable WAL mode: {0}")]
+    WalMode(QueryError),
+}
+
+impl SqliteConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = Some(timeout);
+        self
+    }
+
+    pub fn wal_mode(mut self, enabled: bool) -> Self {
+        self.wal_mode = enabled;
+        self
+    }
+
+    /// Validates the config for `provider` and writes `busy_timeout` into `url`'s query string.
+    pub fn apply_to_url(&self, provider: &str, url: &str) -> Result<String, SqliteConfigError> {
+        if self == &Self::default() {
+            return Ok(url.to_string());
+        }
+
+        if provider != "sqlite" {
+            return Err(SqliteConfigError::UnsupportedProvider(provider.to_string()));
+        }
+
+        let params = match self.busy_timeout {
+            Some(timeout) if timeout.as_secs() == 0 => {
+                return Err(SqliteConfigError::BusyTimeoutTooShort)
+            }
+            Some(timeout) => vec![("socket_timeout", timeout.as_secs().to_string())],
+            None => vec![],
+        };
+
+        Ok(set_url_params(url, params))
+    }
+}
@@ -5,3 +5,39 @@ pub trait FromOptionalUniqueArg<Field> {
     where
         Self: Sized;
 }
+
+/// Converts a value passed to a unique `equals_ref` filter into the field's type,
+/// allowing filters to be built from borrowed values without cloning at the call site.
+pub trait UniqueArg<T> {
+    fn into_unique_arg(self) -> T;
+}
+
+impl<T> UniqueArg<T> for T {
+    fn into_unique_arg(self) -> T {
+        self
+    }
+}
+
+impl<T: Clone> UniqueArg<T> for &T {
+    fn into_unique_arg(self) -> T {
+        self.clone()
+    }
+}
+
+impl UniqueArg<String> for &str {
+    fn into_unique_arg(self) -> String {
+        self.to_string()
+    }
+}
+
+impl UniqueArg<String> for uuid::Uuid {
+    fn into_unique_arg(self) -> String {
+        self.to_string()
+    }
+}
+
+impl UniqueArg<String> for &uuid::Uuid {
+    fn into_unique_arg(self) -> String {
+        self.to_string()
+    }
+}
@@ -1,8 +1,103 @@
-use std::{future::Future, marker::PhantomData};
+use std::{future::Future, marker::PhantomData, sync::Arc};
 
-use query_core::{protocol::EngineProtocol, TransactionOptions, TxId};
+use query_core::{TransactionOptions, TxId};
+#[cfg(any(feature = "cancel-safe", feature = "with-runtime"))]
+use tokio::runtime::Handle;
 
-use crate::{ExecutionEngine, PrismaClient, PrismaClientInternals, QueryError};
+use crate::{
+    engine::detached, identity_map::IdentityMap, ExecutionEngine, PrismaClient,
+    PrismaClientInternals, QueryEngine,
+};
+
+/// Rolls back a transaction if `run` is dropped before the transaction's closure finishes,
+/// instead of leaving the transaction open until it times out.
+///
+/// The rollback is spawned on the client's runtime, or on the current one with the `cancel-safe` feature,
+/// so without either the transaction is still left to time out.
+pub(crate) struct RollbackOnDrop {
+    engine: Arc<dyn QueryEngine>,
+    tx_id: Option<TxId>,
+    /// The client's runtime, if it has one, which the rollback is spawned on
+    /// when the guard is dropped outside of a runtime.
+    #[cfg(feature = "with-runtime")]
+    runtime: Option<Handle>,
+}
+
+impl RollbackOnDrop {
+    /// Starts a transaction on `client`'s engine and guards it.
+    ///
+    /// Inside of a runtime the transaction is started in a task of its own, which owns the guard once it has started,
+    /// so that dropping the returned future while the engine is still starting it rolls it back too.
+    pub(crate) async fn start(
+        #[cfg_attr(not(feature = "with-runtime"), allow(unused_variables))]
+        client: &PrismaClientInternals,
+        engine: Arc<dyn QueryEngine>,
+        options: TransactionOptions,
+    ) -> super::Result<Self> {
+        #[cfg(feature = "with-runtime")]
+        let runtime = client.runtime.clone();
+
+        detached(async move {
+            let tx_id = engine.start_tx(options).await?;
+
+            Ok(Self {
+                engine,
+                tx_id: Some(tx_id),
+                #[cfg(feature = "with-runtime")]
+                runtime,
+            })
+        })
+        .await
+    }
+
+    pub(crate) fn tx_id(&self) -> TxId {
+        self.tx_id.clone().unwrap()
+    }
+
+    fn disarm(mut self) -> TxId {
+        self.tx_id.take().unwrap()
+    }
+
+    /// Commits the transaction, or rolls it back if `commit` is false.
+    /// Errors from rolling back are ignored since the transaction's result is already an error.
+    pub(crate) async fn finish(self, commit: bool) -> super::Result<()> {
+        let engine = self.engine.clone();
+        let tx_id = self.disarm();
+
+        if commit {
+            detached(async move { engine.commit_tx(tx_id).await }).await
+        } else {
+            detached(async move { engine.rollback_tx(tx_id).await })
+                .await
+                .ok();
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(any(feature = "cancel-safe", feature = "with-runtime"))]
+impl Drop for RollbackOnDrop {
+    fn drop(&mut self) {
+        #[cfg(feature = "with-runtime")]
+        let handle = self.runtime.clone();
+        #[cfg(not(feature = "with-runtime"))]
+        let handle = None::<Handle>;
+        #[cfg(feature = "cancel-safe")]
+        let handle = handle.or_else(|| Handle::try_current().ok());
+
+        let (tx_id, handle) = match (self.tx_id.take(), handle) {
+            (Some(tx_id), Some(handle)) => (tx_id, handle),
+            _ => return,
+        };
+
+        let engine = self.engine.clone();
+
+        handle.spawn(async move {
+            engine.rollback_tx(tx_id).await.ok();
+        });
+    }
+}
 
 pub struct TransactionBuilder<'a, TClient> {
     client: &'a TClient,
@@ -10,6 +105,7 @@ pub struct TransactionBuilder<'a, TClient> {
     timeout: u64,
     max_wait: u64,
     isolation_level: Option<String>,
+    identity_map: bool,
 }
 
 impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
@@ -20,6 +116,7 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
             timeout: 5000,
             max_wait: 2000,
             isolation_level: None,
+            identity_map: false,
         }
     }
 
@@ -38,66 +135,99 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         }
     }
 
+    /// Memoizes `find_unique` queries made with the transaction's client,
+    /// so that reading the same record multiple times only queries the database once.
+    ///
+    /// Any write made with the transaction's client clears the memoized results,
+    /// but writes made outside of the transaction are not seen until then.
+    pub fn with_identity_map(self) -> Self {
+        Self {
+            identity_map: true,
+            ..self
+        }
+    }
+
+    fn tx_client(&self, tx_id: Option<TxId>) -> TClient {
+        let mut client = self.client.with_tx_id(tx_id);
+
+        if self.identity_map {
+            client.internals_mut().identity_map = Some(Arc::new(IdentityMap::default()));
+        }
+
+        client
+    }
+
     pub async fn run<TErr, TRet, TFut, TFn>(self, tx: TFn) -> Result<TRet, TErr>
+    where
+        TFut: Future<Output = Result<TRet, TErr>>,
+        TFn: FnOnce(TClient) -> TFut,
+        TErr: From<crate::QueryError>,
+    {
+        self.run_inner(tx, true).await
+    }
+
+    /// Runs `tx` like [`run`](Self::run), but always rolls the transaction back afterwards,
+    /// returning the closure's result.
+    /// Useful for tests that make real writes without leaving them in the database.
+    ///
+    /// Mock clients have no transactions to roll back, so their writes are kept.
+    pub async fn run_and_roll_back<TErr, TRet, TFut, TFn>(self, tx: TFn) -> Result<TRet, TErr>
+    where
+        TFut: Future<Output = Result<TRet, TErr>>,
+        TFn: FnOnce(TClient) -> TFut,
+        TErr: From<crate::QueryError>,
+    {
+        self.run_inner(tx, false).await
+    }
+
+    async fn run_inner<TErr, TRet, TFut, TFn>(self, tx: TFn, commit: bool) -> Result<TRet, TErr>
     where
         TFut: Future<Output = Result<TRet, TErr>>,
         TFn: FnOnce(TClient) -> TFut,
         TErr: From<crate::QueryError>,
     {
         match &self.internals.engine {
-            ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
-                    .start_tx(
-                        connector.query_schema.clone(),
-                        EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
-
-                match tx(self.client.with_tx_id(Some(new_tx_id.clone()))).await {
-                    result @ Ok(_) => {
-                        connector
-                            .executor
-                            .commit_tx(new_tx_id)
-                            .await
-                            .map_err(|e| QueryError::Execute(e.into()))?;
-
-                        result
-                    }
-                    err @ Err(_) => {
-                        connector.executor.rollback_tx(new_tx_id).await.ok();
-
-                        err
-                    }
-                }
+            ExecutionEngine::Real { engine, .. } => {
+                let guard = RollbackOnDrop::start(
+                    self.internals,
+                    engine.clone(),
+                    TransactionOptions::new(
+                        self.max_wait,
+                        self.timeout,
+                        self.isolation_level.clone(),
+                    ),
+                )
+                .await?;
+
+                let result = tx(self.tx_client(Some(guard.tx_id()))).await;
+
+                guard.finish(commit && result.is_ok()).await?;
+
+                result
             }
-            _ => tx(self.client.with_tx_id(None)).await,
+            _ => tx(self.tx_client(None)).await,
         }
     }
 
     pub async fn begin(self) -> super::Result<(TransactionController<TClient>, TClient)> {
         Ok(match &self.internals.engine {
-            ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
-                    .start_tx(
-                        connector.query_schema.clone(),
-                        EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+            ExecutionEngine::Real { engine, .. } => {
+                let new_tx_id = engine
+                    .start_tx(TransactionOptions::new(
+                        self.max_wait,
+                        self.timeout,
+                        self.isolation_level.clone(),
+                    ))
+                    .await?;
 
                 (
                     TransactionController::new(new_tx_id.clone()),
-                    self.client.with_tx_id(Some(new_tx_id)),
+                    self.tx_client(Some(new_tx_id)),
                 )
             }
             _ => (
                 TransactionController::new("".to_string().into()),
-                self.client.with_tx_id(None),
+                self.tx_client(None),
             ),
         })
     }
@@ -118,19 +248,15 @@ impl<TClient: PrismaClient> TransactionController<TClient> {
 
     pub async fn commit(self, client: TClient) -> super::Result<()> {
         Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => connector
-                .executor
-                .commit_tx(self.tx_id)
-                .await
-                .map_err(|e| QueryError::Execute(e.into()))?,
+            ExecutionEngine::Real { engine, .. } => engine.commit_tx(self.tx_id).await?,
             _ => {}
         })
     }
 
     pub async fn rollback(self, client: TClient) -> super::Result<()> {
         Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => {
-                connector.executor.rollback_tx(self.tx_id).await.ok();
+            ExecutionEngine::Real { engine, .. } => {
+                engine.rollback_tx(self.tx_id).await.ok();
             }
             _ => {}
         })
@@ -0,0 +1,39 @@
+use std::sync::Once;
+
+/// The version of this crate, which generated clients are checked against.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Whether `version` is the version of this crate.
+/// This is a `const fn` so generated clients can assert it at compile time.
+pub const fn is_runtime_version(version: &str) -> bool {
+    let (a, b) = (version.as_bytes(), VERSION.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Prints a warning to stderr the first time it is called with a version other than this crate's,
+/// for clients generated with `version_check = "warn"`.
+pub fn warn_version_mismatch(generated_with: &str) {
+    static WARNED: Once = Once::new();
+
+    if !is_runtime_version(generated_with) {
+        WARNED.call_once(|| {
+            eprintln!(
+                "warning: the Prisma client was generated by prisma-client-rust {generated_with}, \
+                 but version {VERSION} is in use. Regenerate the client to avoid unexpected behaviour."
+            )
+        });
+    }
+}
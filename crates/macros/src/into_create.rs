@@ -0,0 +1,320 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{
+    bracketed, ext::IdentExt, parse::Parse, parse_macro_input, punctuated::Punctuated, Attribute,
+    Data, DeriveInput, Fields, GenericArgument, LitStr, Path, PathArguments, Token, Type,
+};
+
+enum PrismaArg {
+    Model(Path),
+    Field(LitStr),
+    Skip,
+}
+
+impl Parse for PrismaArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        match name.to_string().as_str() {
+            "model" => {
+                input.parse::<Token![=]>()?;
+                Ok(Self::Model(input.parse()?))
+            }
+            "field" => {
+                input.parse::<Token![=]>()?;
+                Ok(Self::Field(input.parse()?))
+            }
+            "skip" => Ok(Self::Skip),
+            _ => Err(syn::Error::new(
+                name.span(),
+                "expected `model`, `field` or `skip`",
+            )),
+        }
+    }
+}
+
+fn prisma_args(attrs: &[Attribute]) -> syn::Result<Vec<PrismaArg>> {
+    let mut args = vec![];
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("prisma")) {
+        args.extend(attr.parse_args_with(Punctuated::<PrismaArg, Token![,]>::parse_terminated)?);
+    }
+
+    Ok(args)
+}
+
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let args = match prisma_args(&input.attrs) {
+        Ok(args) => args,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let model_module = args.into_iter().find_map(|arg| match arg {
+        PrismaArg::Model(path) => Some(path),
+        _ => None,
+    });
+
+    match model_module {
+        // the model's fields are only known to its module's `into_create!`
+        Some(model_module) => quote!(#model_module::into_create! { #input }),
+        None => syn::Error::new(
+            Span::call_site(),
+            "IntoPrismaCreate requires a model module, eg. `#[prisma(model = crate::prisma::user)]`",
+        )
+        .into_compile_error(),
+    }
+    .into()
+}
+
+enum FieldKind {
+    Required,
+    Optional,
+    Nullable,
+}
+
+struct ModelField {
+    name: Ident,
+    db_name: LitStr,
+    kind: FieldKind,
+}
+
+impl Parse for ModelField {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = Ident::parse_any(input)?;
+        input.parse::<Token![:]>()?;
+        let db_name = input.parse()?;
+        let kind: Ident = input.parse()?;
+
+        Ok(Self {
+            name,
+            db_name,
+            kind: match kind.to_string().as_str() {
+                "required" => FieldKind::Required,
+                "optional" => FieldKind::Optional,
+                "nullable" => FieldKind::Nullable,
+                _ => return Err(syn::Error::new(kind.span(), "unknown field kind")),
+            },
+        })
+    }
+}
+
+struct IntoCreateInput {
+    model_module: Path,
+    model_name: LitStr,
+    fields: Punctuated<ModelField, Token![,]>,
+    data: DeriveInput,
+}
+
+impl Parse for IntoCreateInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            model_module: input.parse()?,
+            model_name: input.parse()?,
+            fields: {
+                let content;
+                bracketed!(content in input);
+                Punctuated::<ModelField, Token![,]>::parse_terminated(&content)?
+            },
+            data: input.parse()?,
+        })
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(path) => path,
+        _ => return false,
+    };
+
+    path.path.segments.last().map_or(false, |segment| {
+        segment.ident == "Option"
+            && matches!(
+                &segment.arguments,
+                PathArguments::AngleBracketed(args)
+                    if matches!(args.args.first(), Some(GenericArgument::Type(_)))
+            )
+    })
+}
+
+pub fn proc_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as IntoCreateInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn expand(
+    IntoCreateInput {
+        model_module,
+        model_name,
+        fields: model_fields,
+        data,
+    }: IntoCreateInput,
+) -> syn::Result<TokenStream> {
+    let dto_fields = match &data.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "IntoPrismaCreate requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &data.ident,
+                "IntoPrismaCreate can only be derived for structs",
+            ))
+        }
+    };
+
+    // model field index -> DTO field
+    let mut matched = vec![None; model_fields.len()];
+
+    for dto_field in dto_fields {
+        let ident = dto_field.ident.as_ref().unwrap();
+
+        let mut rename = None;
+        let mut skip = false;
+
+        for arg in prisma_args(&dto_field.attrs)? {
+            match arg {
+                PrismaArg::Field(name) => rename = Some(name),
+                PrismaArg::Skip => skip = true,
+                PrismaArg::Model(path) => {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`model` can only be set on the struct",
+                    ))
+                }
+            }
+        }
+
+        if skip {
+            continue;
+        }
+
+        let index = model_fields.iter().position(|field| match &rename {
+            Some(rename) => field.db_name.value() == rename.value(),
+            None => field.name.unraw() == ident.unraw(),
+        });
+
+        match index {
+            Some(index) if matched[index].is_none() => matched[index] = Some(dto_field),
+            Some(index) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "field `{}` of model `{}` is already set by another field",
+                        model_fields[index].db_name.value(),
+                        model_name.value()
+                    ),
+                ))
+            }
+            None => {
+                let name = rename
+                    .as_ref()
+                    .map(LitStr::value)
+                    .unwrap_or_else(|| ident.unraw().to_string());
+
+                return Err(syn::Error::new(
+                    rename.as_ref().map_or(ident.span(), LitStr::span),
+                    format!(
+                        "model `{}` has no writable field `{name}`, add `#[prisma(skip)]` to leave it out",
+                        model_name.value()
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut required_args = vec![];
+    let mut missing = vec![];
+    let mut optional_pushes = vec![];
+
+    for (model_field, dto_field) in model_fields.iter().zip(&matched) {
+        let name = &model_field.name;
+
+        match (&model_field.kind, dto_field) {
+            (FieldKind::Required, Some(dto_field)) => {
+                let ident = &dto_field.ident;
+
+                required_args.push(quote!(self.#ident));
+            }
+            (FieldKind::Required, None) => missing.push(model_field.db_name.value()),
+            (FieldKind::Optional, Some(dto_field)) if is_option(&dto_field.ty) => {
+                let ident = &dto_field.ident;
+
+                // leaving it out falls back to the field's default
+                optional_pushes.push(quote! {
+                    if let Some(value) = self.#ident {
+                        _params.push(#model_module::#name::set(value));
+                    }
+                });
+            }
+            (FieldKind::Nullable, Some(dto_field)) if !is_option(&dto_field.ty) => {
+                let ident = &dto_field.ident;
+
+                optional_pushes.push(quote! {
+                    _params.push(#model_module::#name::set(Some(self.#ident)));
+                });
+            }
+            (_, Some(dto_field)) => {
+                let ident = &dto_field.ident;
+
+                optional_pushes.push(quote! {
+                    _params.push(#model_module::#name::set(self.#ident));
+                });
+            }
+            (_, None) => {}
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &data.ident,
+            format!(
+                "missing {} required to create a `{}`: {}",
+                match missing.len() {
+                    1 => "field",
+                    _ => "fields",
+                },
+                model_name.value(),
+                missing
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+    }
+
+    let ident = &data.ident;
+    let (impl_generics, ty_generics, where_clause) = data.generics.split_for_impl();
+
+    let create = quote!(#model_module::CreateUnchecked);
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn into_create(self) -> #create {
+                #[allow(unused_mut)]
+                let mut _params: Vec<#model_module::UncheckedSetParam> = vec![];
+
+                #(#optional_pushes)*
+
+                #model_module::create_unchecked(#(#required_args,)* _params)
+            }
+        }
+
+        impl #impl_generics From<#ident #ty_generics> for #create #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                value.into_create()
+            }
+        }
+    })
+}
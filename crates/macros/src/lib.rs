@@ -1,5 +1,7 @@
 mod filter;
+mod into_create;
 mod partial_unchecked;
+mod raw;
 
 #[proc_macro]
 pub fn to_pascal_case(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -21,3 +23,18 @@ pub fn filter(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 pub fn filter_factory(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     filter::proc_macro_factory(input)
 }
+
+#[proc_macro]
+pub fn raw(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    raw::proc_macro(input)
+}
+
+#[proc_macro_derive(IntoPrismaCreate, attributes(prisma))]
+pub fn into_prisma_create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    into_create::derive(input)
+}
+
+#[proc_macro]
+pub fn into_create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    into_create::proc_macro(input)
+}
@@ -0,0 +1,136 @@
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Expr, ExprLit, Lit, LitStr, Path, Token,
+};
+
+#[path = "../../lib/src/raw_placeholders.rs"]
+mod raw_placeholders;
+
+use raw_placeholders::{placeholders, Placeholder};
+
+struct RawInput {
+    crate_path: Path,
+    query: Expr,
+    params: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for RawInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let crate_path = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        let query = input.parse()?;
+
+        let params = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+
+        Ok(Self {
+            crate_path,
+            query,
+            params,
+        })
+    }
+}
+
+enum ExpectedParams {
+    Exactly(usize),
+    /// `?` is only a placeholder on SQLite and MySQL,
+    /// so a query with none of the other placeholders may also be a PostgreSQL query using JSON operators.
+    Questions(usize),
+}
+
+/// Number of parameters a query expects, using the same placeholder detection as
+/// `prisma_client_rust::Raw::convert`.
+fn expected_params(query: &LitStr) -> syn::Result<ExpectedParams> {
+    let found = placeholders(&query.value(), true);
+
+    let positions = found
+        .iter()
+        .filter_map(|(_, p)| match p {
+            Placeholder::Positional(position) => Some(*position),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    match found.first().map(|(_, p)| p) {
+        Some(Placeholder::Braces) => Ok(ExpectedParams::Exactly(found.len())),
+        Some(Placeholder::Positional(_)) => {
+            let max = positions.iter().copied().max().unwrap_or(0);
+
+            if let Some(missing) = (1..=max).find(|i| !positions.contains(i)) {
+                return Err(syn::Error::new_spanned(
+                    query,
+                    format!("raw query uses ${max} but never references ${missing}"),
+                ));
+            }
+
+            Ok(ExpectedParams::Exactly(max))
+        }
+        Some(Placeholder::Question) => Ok(ExpectedParams::Questions(found.len())),
+        None => Ok(ExpectedParams::Exactly(0)),
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+pub fn proc_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let RawInput {
+        crate_path,
+        query,
+        params,
+    } = parse_macro_input!(input as RawInput);
+
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = &query
+    {
+        let provided = params.len();
+
+        let mismatch = match expected_params(lit) {
+            Ok(ExpectedParams::Exactly(expected)) if expected != provided => Some(format!(
+                "raw query expects {expected} parameter{}",
+                plural(expected)
+            )),
+            Ok(ExpectedParams::Questions(expected)) if provided != 0 && expected != provided => {
+                Some(format!(
+                    "raw query expects {expected} parameter{}, or none on PostgreSQL",
+                    plural(expected)
+                ))
+            }
+            Ok(_) => None,
+            Err(e) => return e.into_compile_error().into(),
+        };
+
+        if let Some(mismatch) = mismatch {
+            return syn::Error::new_spanned(
+                lit,
+                format!(
+                    "{mismatch} but {provided} {} provided",
+                    if provided == 1 { "was" } else { "were" }
+                ),
+            )
+            .into_compile_error()
+            .into();
+        }
+    }
+
+    let params = params.iter();
+
+    quote! {
+        #crate_path::Raw::new(#query, vec![#(#params),*])
+    }
+    .into()
+}
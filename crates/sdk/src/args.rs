@@ -10,7 +10,7 @@ use std::collections::HashSet;
 use dmmf::{DataModelMetaFormat, DmmfInputField, DmmfInputType, DmmfSchema, TypeLocation};
 use proc_macro2::TokenStream;
 
-use crate::{dmmf::EngineDMMF, prelude::*};
+use crate::{dmmf::EngineDMMF, prelude::*, shared_config::SharedConfig};
 
 pub struct GenerateArgs<'a> {
     pub schema: &'a ValidatedSchema,
@@ -19,6 +19,7 @@ pub struct GenerateArgs<'a> {
     pub read_filters: Vec<Filter<'a>>,
     pub write_params: Vec<Filter<'a>>,
     pub connector: &'static dyn Connector,
+    pub shared_config: &'a SharedConfig,
 }
 
 impl<'a> GenerateArgs<'a> {
@@ -26,8 +27,10 @@ impl<'a> GenerateArgs<'a> {
         schema: &'a ValidatedSchema,
         dmmf: &'a DataModelMetaFormat,
         engine_dmmf: EngineDMMF,
+        shared_config: &'a SharedConfig,
     ) -> Self {
-        let scalars = dmmf
+        // sorted so that filters are generated in the same order every time
+        let mut scalars = dmmf
             .schema
             .input_object_types
             .get("prisma")
@@ -42,7 +45,10 @@ impl<'a> GenerateArgs<'a> {
                     })
                 })
             })
-            .collect::<HashSet<_>>();
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        scalars.sort_by(|a, b| a.as_str().cmp(b.as_str()));
 
         let read_filters = {
             let mut filters = vec![];
@@ -77,6 +83,8 @@ impl<'a> GenerateArgs<'a> {
 
             for enm in schema.db.walk_enums() {
                 let possible_filters = [
+                    "Enum".to_string() + &enm.ast_enum().name.name + "ListFilter",
+                    "Enum".to_string() + &enm.ast_enum().name.name + "NullableListFilter",
                     "Enum".to_string() + &enm.ast_enum().name.name + "Filter",
                     "Enum".to_string() + &enm.ast_enum().name.name + "NullableFilter",
                 ];
@@ -138,13 +146,15 @@ impl<'a> GenerateArgs<'a> {
             let mut filters = vec![];
 
             filters.extend(scalars.iter().flat_map(|scalar| {
+                // nullable Json fields take `DbNull` and `JsonNull` instead of null,
+                // so their params are generated separately
                 if matches!(scalar, ScalarType::Json) {
-                    let mut s = "Json".to_string();
-
-                    return vec![Filter {
-                        name: s,
-                        fields: vec![],
-                    }];
+                    return ["Json", "JsonNullable"]
+                        .map(|name| Filter {
+                            name: name.to_string(),
+                            fields: vec![],
+                        })
+                        .to_vec();
                 }
 
                 let possible_inputs = [
@@ -241,11 +251,14 @@ impl<'a> GenerateArgs<'a> {
                         let scalar_name = {
                             let mut scalar_name = None;
 
+                            // enum lists (eg. `Role[]`) reference their enum type rather than a scalar
                             fields.extend(input_type.fields.iter().filter_map(|field| {
                                 if field.name == "set" {
                                     for input_type in &field.input_types {
                                         match input_type.location {
-                                            TypeLocation::Scalar if input_type.typ != "null" => {
+                                            TypeLocation::Scalar | TypeLocation::EnumTypes
+                                                if input_type.typ != "null" =>
+                                            {
                                                 scalar_name = Some(input_type.typ.clone() + "List");
                                             }
                                             _ => {}
@@ -257,7 +270,11 @@ impl<'a> GenerateArgs<'a> {
                                     .input_types
                                     .iter()
                                     .find(|input_type| match input_type.location {
-                                        TypeLocation::Scalar if input_type.typ != "null" => true,
+                                        TypeLocation::Scalar | TypeLocation::EnumTypes
+                                            if input_type.typ != "null" =>
+                                        {
+                                            true
+                                        }
                                         _ => false,
                                     })
                                     .map(|_| field)
@@ -303,6 +320,7 @@ impl<'a> GenerateArgs<'a> {
             read_filters,
             write_params: write_filters,
             connector,
+            shared_config,
         }
     }
 
@@ -341,6 +359,15 @@ impl<'a> GenerateArgs<'a> {
             .iter()
             .find(|f| f.name == format!("{base}{postfix}"))
     }
+
+    /// Whether the generator block enables the preview feature `name`, eg. `extendedWhereUnique`.
+    pub fn has_preview_feature(&self, name: &str) -> bool {
+        self.engine_dmmf
+            .generator
+            .preview_features
+            .iter()
+            .any(|f| f == name)
+    }
 }
 
 pub trait DmmfSchemaExt {
@@ -0,0 +1,144 @@
+use prisma_models::walkers::{ModelWalker, RefinedFieldWalker, RelationFieldWalker};
+use psl::{parser_database::ast::Span, ValidatedSchema};
+
+use crate::{prelude::*, shared_config::SharedConfig, GeneratorError};
+
+/// Removes the models and fields listed in `exclude_models` and `exclude_fields` from `datamodel`,
+/// so that nothing is generated for them.
+///
+/// Relation fields pointing at an excluded model or relation field are removed too,
+/// since generating them would reference types that don't exist.
+/// Each of these removals is returned as a warning.
+///
+/// Only the schema used for generation is affected,
+/// the query engine still receives the full schema.
+pub fn exclude(
+    schema: &ValidatedSchema,
+    datamodel: &str,
+    config: &SharedConfig,
+) -> Result<(String, Vec<String>), GeneratorError> {
+    let invalid = |entry: &str, message: &str| {
+        GeneratorError::InvalidExclusion(format!("'{entry}' {message}"))
+    };
+
+    let find_model = |name: &str| schema.db.walk_models().find(|m| m.name() == name);
+
+    let mut excluded_models = vec![];
+
+    for name in &config.exclude_models {
+        let model = find_model(name).ok_or_else(|| invalid(name, "is not a model"))?;
+
+        excluded_models.push(model);
+    }
+
+    let is_excluded_model = |model: ModelWalker| excluded_models.iter().any(|m| m.id == model.id);
+
+    let mut spans = excluded_models
+        .iter()
+        .map(|m| m.ast_model().span)
+        .collect::<Vec<_>>();
+    let mut warnings = vec![];
+    let mut excluded_relations: Vec<RelationFieldWalker> = vec![];
+
+    for entry in &config.exclude_fields {
+        let (model_name, field_name) = entry
+            .split_once('.')
+            .ok_or_else(|| invalid(entry, "must be of the form `Model.field`"))?;
+
+        let model = find_model(model_name)
+            .ok_or_else(|| invalid(entry, &format!("references unknown model '{model_name}'")))?;
+
+        let field = model
+            .fields()
+            .find(|f| f.name() == field_name)
+            .ok_or_else(|| invalid(entry, &format!("is not a field of '{model_name}'")))?;
+
+        if is_excluded_model(model) {
+            continue;
+        }
+
+        match field.refine() {
+            RefinedFieldWalker::Scalar(scalar_field) => {
+                let id = scalar_field.field_id();
+
+                let in_primary_key = model
+                    .primary_key()
+                    .map(|pk| pk.fields().any(|f| f.field_id() == id))
+                    .unwrap_or(false);
+                let in_index = model
+                    .indexes()
+                    .any(|index| index.fields().any(|f| f.field_id() == id));
+
+                if in_primary_key || in_index || model.scalar_field_has_relation(scalar_field) {
+                    return Err(invalid(
+                        entry,
+                        "is part of a primary key, index or relation and cannot be excluded",
+                    ));
+                }
+            }
+            RefinedFieldWalker::Relation(relation_field) => excluded_relations.push(relation_field),
+        }
+
+        if field.required_on_create() {
+            warnings.push(format!(
+                "'{entry}' is required when creating '{model_name}', so its create actions will fail"
+            ));
+        }
+
+        spans.push(field.ast_field().span);
+    }
+
+    for model in schema.db.walk_models().filter(|m| !is_excluded_model(*m)) {
+        for relation_field in model.relation_fields() {
+            if excluded_relations.iter().any(|f| f.id == relation_field.id) {
+                continue;
+            }
+
+            let opposite = relation_field.opposite_relation_field();
+
+            let reason = if is_excluded_model(relation_field.related_model()) {
+                format!(
+                    "model '{}' is excluded",
+                    relation_field.related_model().name()
+                )
+            } else if let Some(opposite) =
+                opposite.filter(|o| excluded_relations.iter().any(|f| f.id == o.id))
+            {
+                format!(
+                    "its opposite field '{}.{}' is excluded",
+                    opposite.model().name(),
+                    opposite.name()
+                )
+            } else {
+                continue;
+            };
+
+            warnings.push(format!(
+                "Relation field '{}.{}' will not be generated since {reason}",
+                model.name(),
+                relation_field.name()
+            ));
+
+            spans.push(relation_field.ast_field().span);
+        }
+    }
+
+    Ok((remove_spans(datamodel, spans), warnings))
+}
+
+fn remove_spans(datamodel: &str, mut spans: Vec<Span>) -> String {
+    spans.sort_by_key(|span| span.start);
+    spans.dedup_by_key(|span| span.start);
+
+    let mut trimmed = String::with_capacity(datamodel.len());
+    let mut position = 0;
+
+    for span in spans {
+        trimmed.push_str(&datamodel[position..span.start]);
+        position = span.end;
+    }
+
+    trimmed.push_str(&datamodel[position..]);
+
+    trimmed
+}
@@ -0,0 +1,99 @@
+use dmmf::DataModelMetaFormat;
+use serde_json::{json, Map, Value};
+
+use crate::SharedConfig;
+
+/// A JSON Schema document with a definition for each model, composite type and enum,
+/// describing their generated `Data` structs as they are serialized.
+pub fn generate(dmmf: &DataModelMetaFormat, config: &SharedConfig) -> Value {
+    let data_model = &dmmf.data_model;
+
+    let mut defs = Map::new();
+
+    for model in data_model.models.iter().chain(&data_model.types) {
+        // composite types always serialize BigInt as a number
+        let bigint_as_string =
+            config.bigint_as_string && data_model.models.iter().any(|m| m.name == model.name);
+
+        let mut properties = Map::new();
+        let mut required = vec![];
+
+        for field in &model.fields {
+            let name = config.serde_name(&model.name, &field.name);
+            let is_relation = field.relation_name.is_some();
+
+            let mut schema = match config.type_override(&model.name, &field.name) {
+                // the schema of an arbitrary type can't be known
+                Some(_) => json!({}),
+                None => {
+                    let typ = match field.kind.as_str() {
+                        "scalar" if field.field_type == "BigInt" && bigint_as_string => {
+                            json!({ "type": "string", "format": "int64" })
+                        }
+                        "scalar" => scalar_schema(&field.field_type),
+                        _ => json!({ "$ref": format!("#/$defs/{}", field.field_type) }),
+                    };
+
+                    let typ = match field.is_list {
+                        true => json!({ "type": "array", "items": typ }),
+                        false => typ,
+                    };
+
+                    // relations are only serialized once they've been fetched
+                    match field.is_required && !is_relation {
+                        true => typ,
+                        false => json!({ "anyOf": [typ, { "type": "null" }] }),
+                    }
+                }
+            };
+
+            if let Some(docs) = &field.documentation {
+                schema["description"] = json!(docs);
+            }
+
+            // optional relations are skipped entirely when they aren't fetched
+            if !is_relation || field.is_required || field.is_list {
+                required.push(name.clone());
+            }
+
+            properties.insert(name, schema);
+        }
+
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+
+        if let Some(docs) = &model.documentation {
+            schema["description"] = json!(docs);
+        }
+
+        defs.insert(model.name.clone(), schema);
+    }
+
+    for e in &data_model.enums {
+        let values = e.values.iter().map(|v| &v.name).collect::<Vec<_>>();
+
+        defs.insert(e.name.clone(), json!({ "type": "string", "enum": values }));
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": defs,
+    })
+}
+
+fn scalar_schema(typ: &str) -> Value {
+    match typ {
+        "String" => json!({ "type": "string" }),
+        "Int" => json!({ "type": "integer", "format": "int32" }),
+        "BigInt" => json!({ "type": "integer", "format": "int64" }),
+        "Float" => json!({ "type": "number", "format": "double" }),
+        "Decimal" => json!({ "type": "string", "format": "decimal" }),
+        "Boolean" => json!({ "type": "boolean" }),
+        "DateTime" => json!({ "type": "string", "format": "date-time" }),
+        "Bytes" => json!({ "type": "array", "items": { "type": "integer" } }),
+        _ => json!({}),
+    }
+}
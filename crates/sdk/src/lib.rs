@@ -1,14 +1,19 @@
 mod args;
 mod casing;
 pub mod dmmf;
+mod exclude;
 mod extensions;
+mod json_schema;
 mod jsonrpc;
 mod keywords;
 mod runtime;
 mod shared_config;
 mod utils;
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use proc_macro2::TokenStream;
 use serde::{de::DeserializeOwned, Serialize};
@@ -21,6 +26,9 @@ pub use args::GenerateArgs;
 pub use casing::*;
 pub use extensions::*;
 pub use quote::quote;
+pub use shared_config::{
+    deserialize_parsed, ClientFormat, ModuleLayout, SerdeRename, SharedConfig,
+};
 
 use crate::prelude::snake_ident;
 
@@ -62,20 +70,40 @@ pub type GenerateResult = Result<Module, GeneratorError>;
 pub enum GeneratorError {
     #[error("Schema contains invalid names \n{0}")]
     ReservedNames(String),
-    #[error("Failed to create client file: {0}")]
-    FileCreate(std::io::Error),
-    #[error("Failed to write generated client to file: {0}")]
-    FileWrite(std::io::Error),
+    #[error("Failed to create client file '{}': {source}", .path.display())]
+    FileCreate {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to write generated client to file '{}': {source}", .path.display())]
+    FileWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid output path '{}': {message}", .path.display())]
+    InvalidOutputPath {
+        path: PathBuf,
+        message: &'static str,
+    },
+    #[error("Invalid exclusion: {0}")]
+    InvalidExclusion(String),
+    #[error("Invalid client_name '{0}': must be a valid Rust identifier")]
+    InvalidClientName(String),
+    #[error("Invalid allow_lints entry '{0}': must be a lint name like `clippy::all`")]
+    InvalidLint(String),
     #[error("Failed to deserialize generator arguments: {0}")]
     ArgDeserialize(serde_json::Error),
     #[error("Generator {name} failed: \n{message}")]
     InternalError { name: &'static str, message: String },
 }
 
+#[derive(Clone)]
 pub struct Module {
     pub name: String,
     pub contents: TokenStream,
     pub submodules: Vec<Module>,
+    /// Used to group modules when using `ModuleLayout::Grouped`.
+    pub namespace: Option<String>,
 }
 
 impl Module {
@@ -84,9 +112,15 @@ impl Module {
             name: name.to_string(),
             contents,
             submodules: vec![],
+            namespace: None,
         }
     }
 
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
     pub fn add_submodule(&mut self, submodule: Module) {
         self.submodules.push(submodule);
     }
@@ -116,12 +150,60 @@ impl Module {
         }
     }
 
-    pub fn get_all_paths(&self, parent_path: &Path) -> Vec<PathBuf> {
+    /// Moves submodules with a namespace into a module for that namespace.
+    /// Their contents are re-exported so that paths to them are unchanged,
+    /// and the namespace module imports its parent so `super::` paths keep working.
+    pub fn group_by_namespace(&self) -> Self {
+        let mut groups = BTreeMap::new();
+        let mut submodules = vec![];
+
+        for submodule in &self.submodules {
+            match &submodule.namespace {
+                Some(namespace) => groups
+                    .entry(namespace.clone())
+                    .or_insert_with(|| Module::new(namespace, quote! { use super::*; }))
+                    .add_submodule(submodule.clone()),
+                None => submodules.push(submodule.clone()),
+            }
+        }
+
+        let contents = &self.contents;
+        let group_names = groups.keys().map(|name| snake_ident(name));
+
+        Self {
+            name: self.name.clone(),
+            contents: quote! {
+                #contents
+
+                #(pub use self::#group_names::*;)*
+            },
+            submodules: submodules.into_iter().chain(groups.into_values()).collect(),
+            namespace: self.namespace.clone(),
+        }
+    }
+
+    /// Every file that will be written for this module with the given layout.
+    pub fn get_all_paths(&self, parent_path: &Path, layout: ModuleLayout) -> Vec<PathBuf> {
+        match layout {
+            ModuleLayout::Nested => self.nested_paths(parent_path),
+            ModuleLayout::Flat => [parent_path.join("mod.rs")]
+                .into_iter()
+                .chain(self.submodules.iter().map(|sm| {
+                    parent_path
+                        .join(sm.name.to_case(Case::Snake, true))
+                        .with_extension("rs")
+                }))
+                .collect(),
+            ModuleLayout::Grouped => self.group_by_namespace().nested_paths(parent_path),
+        }
+    }
+
+    fn nested_paths(&self, parent_path: &Path) -> Vec<PathBuf> {
         if self.submodules.len() > 0 {
             [parent_path.join("mod.rs")]
                 .into_iter()
                 .chain(self.submodules.iter().flat_map(|sm| {
-                    sm.get_all_paths(&parent_path.join(&sm.name.to_case(Case::Snake, true)))
+                    sm.nested_paths(&parent_path.join(&sm.name.to_case(Case::Snake, true)))
                 }))
                 .collect()
         } else {
@@ -1,20 +1,28 @@
 use std::{
-    fs::{self, remove_dir_all, File},
+    collections::hash_map::DefaultHasher,
+    env,
+    fs::{self, File},
+    hash::{Hash, Hasher},
     io::{stderr, stdin, BufRead, BufReader, Write},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use crate::{
     prelude::*,
-    shared_config::{ClientFormat, SharedConfig},
+    shared_config::{ClientFormat, ModuleLayout, SharedConfig},
 };
 
-use dmmf::from_precomputed_parts;
+use dmmf::{from_precomputed_parts, DataModelMetaFormat};
 use query_core::schema;
 
 use crate::{
-    args::GenerateArgs, dmmf::EngineDMMF, jsonrpc, utils::rustfmt, GenerateFn, GeneratorError,
+    args::GenerateArgs,
+    dmmf::EngineDMMF,
+    exclude::exclude,
+    json_schema, jsonrpc,
+    utils::{output_path, remove_output, rustfmt},
+    GenerateFn, GeneratorError,
 };
 
 pub struct GeneratorMetadata {
@@ -98,92 +106,323 @@ impl GeneratorMetadata {
     }
 
     fn generate(&self, engine_dmmf: EngineDMMF) -> Result<(), GeneratorError> {
-        let schema = Arc::new(
-            psl::parse_schema(engine_dmmf.datamodel.as_str())
-                .expect("Datamodel is invalid after being verified by CLI?!"),
-        );
-        let query_schema = Arc::new(schema::build(schema.clone(), true));
-        let dmmf = from_precomputed_parts(&query_schema);
-
-        let output_str = engine_dmmf.generator.output.get_value();
-        let root_output_path = Path::new(&output_str);
+        let root_output_path = output_path(Path::new(&engine_dmmf.generator.output.get_value()));
+        let root_output_path = root_output_path.as_path();
 
         let config = engine_dmmf.generator.config.clone();
 
         let shared_config: SharedConfig =
-            serde_json::from_value(serde_json::Value::Object(config.clone())).unwrap();
+            serde_json::from_value(serde_json::Value::Object(config.clone()))
+                .map_err(GeneratorError::ArgDeserialize)?;
+
+        let invalid_output_path = |message| GeneratorError::InvalidOutputPath {
+            path: root_output_path.to_path_buf(),
+            message,
+        };
 
         match shared_config.client_format {
             ClientFormat::Folder if root_output_path.extension().is_some() => {
-                panic!("The output path must be a directory when using the folder format.")
+                return Err(invalid_output_path(
+                    "must be a directory when using the folder format",
+                ))
             }
             ClientFormat::File if root_output_path.extension().is_none() => {
-                panic!("The output path must be a file when using the file format.")
+                return Err(invalid_output_path(
+                    "must be a file when using the file format",
+                ))
             }
             _ => {}
         }
 
-        let root_module =
-            (self.generate_fn)(GenerateArgs::new(&schema, &dmmf, engine_dmmf), config)?;
+        // the name the output is declared with by default, eg. `prisma` for `prisma.rs`
+        let output_module_name = match shared_config.client_format {
+            ClientFormat::Folder => root_output_path.file_name(),
+            ClientFormat::File => root_output_path.file_stem(),
+        }
+        .and_then(|name| name.to_str());
+        let output_file_name = root_output_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        if let Some(lint) = shared_config
+            .allow_lints
+            .iter()
+            .find(|lint| syn::parse_str::<syn::Path>(lint).is_err())
+        {
+            return Err(GeneratorError::InvalidLint(lint.clone()));
+        }
 
-        remove_dir_all(root_output_path).ok();
+        let path_attribute = match &shared_config.client_name {
+            Some(name) if syn::parse_str::<syn::Ident>(name).is_err() => {
+                return Err(GeneratorError::InvalidClientName(name.clone()))
+            }
+            Some(name) if output_module_name != Some(name.as_str()) => {
+                let declaration = match shared_config.client_format {
+                    ClientFormat::Folder => {
+                        format!("#[path = \"{output_file_name}/mod.rs\"] mod {name};")
+                    }
+                    ClientFormat::File => format!("#[path = \"{output_file_name}\"] mod {name};"),
+                };
 
-        let header = format!("// File generated by {}. DO NOT EDIT\n\n", self.name);
+                eprintln!(
+                    "warning: the client is named '{name}' but its output is not, declare it with `{declaration}`"
+                );
 
-        match shared_config.client_format {
-            ClientFormat::Folder => {
-                write_module_to_file(&root_module, root_output_path, &header);
+                Some(declaration)
             }
-            ClientFormat::File => write_to_file(&root_module.flatten(), root_output_path, &header),
+            _ => None,
+        };
+
+        // named after the generator's block, so that clients generated into the same directory don't share one
+        let checksum_file = format!(".prisma-client.{}.checksum", engine_dmmf.generator.name);
+        let checksum_path = match shared_config.client_format {
+            ClientFormat::Folder => root_output_path.join(checksum_file),
+            ClientFormat::File => root_output_path.with_file_name(checksum_file),
+        };
+        let checksum = checksum(self.name, &engine_dmmf);
+
+        if is_up_to_date(&checksum_path, &checksum) {
+            return Ok(());
         }
 
-        rustfmt(&root_module.get_all_paths(root_output_path));
+        let schema = psl::parse_schema(engine_dmmf.datamodel.as_str())
+            .expect("Datamodel is invalid after being verified by CLI?!");
+
+        let schema = Arc::new(
+            if shared_config.exclude_models.is_empty() && shared_config.exclude_fields.is_empty() {
+                schema
+            } else {
+                let (datamodel, warnings) =
+                    exclude(&schema, &engine_dmmf.datamodel, &shared_config)?;
+
+                // the Prisma CLI logs lines on stderr that aren't JSON-RPC responses
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+
+                psl::parse_schema(&datamodel).map_err(|e| {
+                    GeneratorError::InvalidExclusion(format!(
+                        "the schema is invalid once excluded items are removed\n{e}"
+                    ))
+                })?
+            },
+        );
+        let query_schema = Arc::new(schema::build(schema.clone(), true));
+        let dmmf = from_precomputed_parts(&query_schema);
+
+        let root_module = (self.generate_fn)(
+            GenerateArgs::new(&schema, &dmmf, engine_dmmf, &shared_config),
+            config,
+        )?;
+
+        remove_output(root_output_path).map_err(|source| GeneratorError::FileWrite {
+            path: root_output_path.to_path_buf(),
+            source,
+        })?;
+
+        let header = match path_attribute {
+            Some(declaration) => format!(
+                "// File generated by {}. DO NOT EDIT\n// Declare with `{declaration}`\n\n",
+                self.name
+            ),
+            None => format!("// File generated by {}. DO NOT EDIT\n\n", self.name),
+        };
+
+        // inner attributes are valid at the top of both a single file and each file of a folder
+        let header = match shared_config.allow_lints.is_empty() {
+            true => header,
+            false => format!(
+                "{header}#![allow({})]\n\n",
+                shared_config.allow_lints.join(", ")
+            ),
+        };
+
+        let output_paths = match shared_config.client_format {
+            ClientFormat::Folder => {
+                let layout = shared_config.module_layout;
+
+                write_module_to_file(&root_module, root_output_path, &header, layout)?;
+                root_module.get_all_paths(root_output_path, layout)
+            }
+            ClientFormat::File => {
+                write_to_file(&root_module.flatten(), root_output_path, &header)?;
+                vec![root_output_path.to_path_buf()]
+            }
+        };
+
+        rustfmt(&output_paths);
+
+        let output_paths = match shared_config.schema_json {
+            true => {
+                let schema_path = root_output_path.with_extension("schema.json");
+
+                write_json_schema(&dmmf, &shared_config, &schema_path)?;
+                output_paths.into_iter().chain([schema_path]).collect()
+            }
+            false => output_paths,
+        };
+
+        write_checksum(&checksum_path, &checksum, &output_paths);
 
         Ok(())
     }
 }
 
-fn write_module_to_file(module: &Module, parent_path: &Path, header: &str) {
+/// Hashes everything that affects the generated client.
+/// The generator executable's modification time is included
+/// so that rebuilding it with different code or features invalidates the checksum.
+fn checksum(generator_name: &str, engine_dmmf: &EngineDMMF) -> String {
+    let mut hasher = DefaultHasher::new();
+
+    generator_name.hash(&mut hasher);
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    serde_json::to_string(engine_dmmf)
+        .expect("Failed to serialize DMMF")
+        .hash(&mut hasher);
+    env::current_exe()
+        .and_then(fs::metadata)
+        .and_then(|m| m.modified())
+        .ok()
+        .hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// The checksum file contains the checksum on its first line,
+/// followed by each file that was generated alongside it.
+fn is_up_to_date(checksum_path: &Path, checksum: &str) -> bool {
+    let contents = match fs::read_to_string(checksum_path) {
+        Ok(contents) => contents,
+        Err(_) => return false,
+    };
+
+    let mut lines = contents.lines();
+
+    lines.next() == Some(checksum) && lines.all(|path| Path::new(path).exists())
+}
+
+fn write_checksum(checksum_path: &Path, checksum: &str, output_paths: &[PathBuf]) {
+    let contents = [checksum.to_string()]
+        .into_iter()
+        .chain(output_paths.iter().map(|p| p.display().to_string()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    fs::write(checksum_path, contents).ok();
+}
+
+fn write_module_to_file(
+    module: &Module,
+    parent_path: &Path,
+    header: &str,
+    layout: ModuleLayout,
+) -> Result<(), GeneratorError> {
+    match layout {
+        ModuleLayout::Nested => write_nested_module(module, parent_path, header),
+        ModuleLayout::Flat => {
+            for child in &module.submodules {
+                write_to_file(
+                    &child.flatten(),
+                    &parent_path
+                        .join(child.name.to_case(Case::Snake, true))
+                        .with_extension("rs"),
+                    header,
+                )?;
+            }
+
+            write_to_file(
+                &with_submodule_decls(module),
+                &parent_path.join("mod.rs"),
+                header,
+            )
+        }
+        ModuleLayout::Grouped => {
+            write_nested_module(&module.group_by_namespace(), parent_path, header)
+        }
+    }
+}
+
+fn write_nested_module(
+    module: &Module,
+    parent_path: &Path,
+    header: &str,
+) -> Result<(), GeneratorError> {
     if module.submodules.len() > 0 {
         for child in &module.submodules {
-            write_module_to_file(
+            write_nested_module(
                 child,
                 &parent_path.join(&child.name.to_case(Case::Snake, true)),
                 header,
-            );
+            )?;
         }
 
-        let contents = &module.contents;
-        let submodule_decls = module.submodules.iter().map(|sm| {
-            let name = snake_ident(&sm.name);
-            quote!(pub mod #name;)
-        });
-
         write_to_file(
-            &quote! {
-                #(#submodule_decls)*
-
-                #contents
-            },
+            &with_submodule_decls(module),
             &parent_path.join("mod.rs"),
             header,
-        );
+        )
     } else {
-        write_to_file(&module.contents, &parent_path.with_extension("rs"), header);
+        write_to_file(&module.contents, &parent_path.with_extension("rs"), header)
     }
 }
 
-fn write_to_file(contents: &TokenStream, path: &Path, header: &str) {
-    let mut file = create_generated_file(path).unwrap();
+/// A module's contents preceded by `pub mod` declarations for its submodules' files,
+/// sorted by name so the output doesn't depend on the order models are declared in.
+fn with_submodule_decls(module: &Module) -> TokenStream {
+    let contents = &module.contents;
+
+    let mut submodule_names = module
+        .submodules
+        .iter()
+        .map(|sm| snake_ident(&sm.name))
+        .collect::<Vec<_>>();
+    submodule_names.sort();
+
+    let submodule_decls = submodule_names.iter().map(|name| quote!(pub mod #name;));
+
+    quote! {
+        #(#submodule_decls)*
 
-    file.write((header.to_string() + &contents.to_string()).as_bytes())
-        .unwrap();
+        #contents
+    }
+}
+
+fn write_to_file(contents: &TokenStream, path: &Path, header: &str) -> Result<(), GeneratorError> {
+    let mut file = create_generated_file(path)?;
+
+    file.write_all((header.to_string() + &contents.to_string()).as_bytes())
+        .map_err(|source| GeneratorError::FileWrite {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+fn write_json_schema(
+    dmmf: &DataModelMetaFormat,
+    config: &SharedConfig,
+    path: &Path,
+) -> Result<(), GeneratorError> {
+    let contents = serde_json::to_string_pretty(&json_schema::generate(dmmf, config))
+        .expect("Failed to serialize JSON schema");
+
+    create_generated_file(path)?
+        .write_all(contents.as_bytes())
+        .map_err(|source| GeneratorError::FileWrite {
+            path: path.to_path_buf(),
+            source,
+        })
 }
 
 fn create_generated_file(path: &Path) -> Result<File, GeneratorError> {
+    let file_create_error = |source| GeneratorError::FileCreate {
+        path: path.to_path_buf(),
+        source,
+    };
+
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(GeneratorError::FileCreate)?;
+        fs::create_dir_all(parent).map_err(file_create_error)?;
     }
 
-    File::create(&path).map_err(GeneratorError::FileCreate)
+    File::create(path).map_err(file_create_error)
 }
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
-#[derive(Default, Deserialize)]
+use crate::casing::{Case, Casing};
+
+#[derive(Default, Deserialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum ClientFormat {
     #[default]
@@ -8,8 +12,231 @@ pub enum ClientFormat {
     Folder,
 }
 
-#[derive(Deserialize)]
+/// How modules are laid out on disk when using the folder format.
+#[derive(Default, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ModuleLayout {
+    /// A directory with a `mod.rs` for every module that has submodules.
+    #[default]
+    Nested,
+    /// A single file in the output root for each top-level module,
+    /// with its submodules written inline.
+    Flat,
+    /// Like `Nested`, but modules with a namespace (eg. a model's `@@schema`)
+    /// are placed in a directory for that namespace.
+    Grouped,
+}
+
+/// Casing applied to field names when generated structs are serialized.
+/// Deserialization always uses the names from the Prisma schema,
+/// since that is what the query engine returns.
+#[derive(Default, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerdeRename {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+}
+
+#[derive(Deserialize, Clone)]
 pub struct SharedConfig {
     #[serde(default)]
     pub client_format: ClientFormat,
+    #[serde(default)]
+    pub module_layout: ModuleLayout,
+    /// The name of the module the client is declared as, eg. `db` for `mod db;`.
+    /// Defaults to the name of the output file or directory.
+    #[serde(default)]
+    pub client_name: Option<String>,
+    #[serde(default)]
+    pub serde_rename: SerdeRename,
+    /// Entries of the form `Model.field=name`, taking precedence over `serde_rename`.
+    #[serde(default)]
+    pub serde_rename_overrides: Vec<String>,
+    /// Marks generated `Data` structs and param enums `#[non_exhaustive]`.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub non_exhaustive: bool,
+    /// Models that nothing is generated for.
+    #[serde(default)]
+    pub exclude_models: Vec<String>,
+    /// Entries of the form `Model.field` that are left out of the generated client.
+    #[serde(default)]
+    pub exclude_fields: Vec<String>,
+    /// Entries of the form `Model.field=path::to::Type`, replacing the Rust type generated for a field.
+    #[serde(default)]
+    pub type_overrides: Vec<String>,
+    /// Entries of the form `Model.field=path::to::Codec`, passing a `String` field's values
+    /// through a `FieldCodec` as they're written and read.
+    #[serde(default)]
+    pub field_codecs: Vec<String>,
+    /// Models mapped to the field their `Data` implements `Display` by printing, eg. `{ "User": "email" }`.
+    #[serde(default, deserialize_with = "deserialize_model_fields")]
+    pub display_fields: BTreeMap<String, String>,
+    /// Entries of the form `Model.field: Type = SQL`, computed with `Actions::computed` or by selecting them in `select!`.
+    #[serde(default)]
+    pub computed_fields: Vec<String>,
+    /// Pairs of enums to generate `From` or `TryFrom` between, matching variants by name.
+    #[serde(default)]
+    pub enum_conversions: Vec<Vec<String>>,
+    /// Writes a JSON Schema describing each model next to the client, eg. `prisma.schema.json` for `prisma.rs`.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub schema_json: bool,
+    /// Implements `Eq` and `Ord` for the `Data` of models with a single field primary key,
+    /// comparing only the primary key.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub ord_by_id: bool,
+    /// Generates a newtype such as `UserId` for each model with a single `String` or `Int` primary key,
+    /// used by the key and by the fields of relations to it so that keys of different models can't be mixed up.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub id_newtypes: bool,
+    /// Uses narrower Rust types for fields with certain `@db.*` attributes, eg. `i16` for `@db.SmallInt`.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub native_types: bool,
+    /// Serializes `BigInt` fields as strings, for consumers that can't represent every `i64` as a number.
+    #[serde(default, deserialize_with = "deserialize_parsed")]
+    pub bigint_as_string: bool,
+    /// Lints allowed by an inner attribute at the top of each generated file,
+    /// so that workspaces denying warnings aren't broken by code they can't change.
+    #[serde(default = "default_allow_lints")]
+    pub allow_lints: Vec<String>,
+}
+
+fn default_allow_lints() -> Vec<String> {
+    ["clippy::all", "dead_code", "unused_imports"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// Deserializes a generator option that isn't a string, such as a `bool` or a number.
+/// Options are passed to generators as strings, so `"true"` and `"8"` are parsed,
+/// but the values themselves are accepted too.
+pub fn deserialize_parsed<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + std::str::FromStr,
+    T::Err: std::fmt::Display,
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value<T> {
+        Value(T),
+        String(String),
+    }
+
+    match Value::deserialize(deserializer)? {
+        Value::Value(v) => Ok(v),
+        Value::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Deserializes a map of models to one of their fields.
+/// Generator options in a Prisma schema can't be objects, so a list of `Model.field` entries is accepted too,
+/// eg. `["User.email"]` for `{ "User": "email" }`.
+pub fn deserialize_model_fields<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Map(BTreeMap<String, String>),
+        Entries(Vec<String>),
+    }
+
+    let entries = match Value::deserialize(deserializer)? {
+        Value::Map(map) => return Ok(map),
+        Value::Entries(entries) => entries,
+    };
+
+    let mut map = BTreeMap::new();
+
+    for entry in &entries {
+        let (model, field) = entry.trim().split_once('.').ok_or_else(|| {
+            serde::de::Error::custom(format!("'{entry}' isn't of the form `Model.field`"))
+        })?;
+
+        if map.insert(model.to_string(), field.to_string()).is_some() {
+            return Err(serde::de::Error::custom(format!(
+                "model '{model}' is listed more than once"
+            )));
+        }
+    }
+
+    Ok(map)
+}
+
+// entries of the form `Model.field=path`
+fn parse_field_paths(entries: &[String]) -> Vec<Result<(&str, &str, &str), &str>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (path, typ) = entry.split_once('=').ok_or(entry.as_str())?;
+            let (model, field) = path.trim().split_once('.').ok_or(entry.as_str())?;
+
+            Ok((model, field, typ.trim()))
+        })
+        .collect()
+}
+
+impl SharedConfig {
+    /// The `(model, field, type)` of each entry in `type_overrides`, or the entry itself if it is malformed.
+    pub fn parsed_type_overrides(&self) -> Vec<Result<(&str, &str, &str), &str>> {
+        parse_field_paths(&self.type_overrides)
+    }
+
+    /// The type `field` of `model` has been overridden with, if any.
+    pub fn type_override(&self, model: &str, field: &str) -> Option<&str> {
+        self.parsed_type_overrides()
+            .into_iter()
+            .flatten()
+            .find(|(m, f, _)| *m == model && *f == field)
+            .map(|(_, _, typ)| typ)
+    }
+
+    /// The `(model, field, codec)` of each entry in `field_codecs`, or the entry itself if it is malformed.
+    pub fn parsed_field_codecs(&self) -> Vec<Result<(&str, &str, &str), &str>> {
+        parse_field_paths(&self.field_codecs)
+    }
+
+    /// The codec configured for `field` of `model`, if any.
+    pub fn field_codec(&self, model: &str, field: &str) -> Option<&str> {
+        self.parsed_field_codecs()
+            .into_iter()
+            .flatten()
+            .find(|(m, f, _)| *m == model && *f == field)
+            .map(|(_, _, codec)| codec)
+    }
+
+    /// The field configured in `display_fields` for `model`, if any.
+    pub fn display_field(&self, model: &str) -> Option<&str> {
+        self.display_fields.get(model).map(String::as_str)
+    }
+
+    /// The name `field` of `model` (or composite type) should be serialized with.
+    pub fn serde_name(&self, model: &str, field: &str) -> String {
+        let overridden = self.serde_rename_overrides.iter().find_map(|entry| {
+            let (path, name) = entry.split_once('=')?;
+            let (entry_model, entry_field) = path.trim().split_once('.')?;
+
+            (entry_model == model && entry_field == field).then(|| name.trim().to_string())
+        });
+
+        if let Some(name) = overridden {
+            return name;
+        }
+
+        match self.serde_rename {
+            SerdeRename::None => field.to_string(),
+            SerdeRename::CamelCase => field.to_case(Case::Camel, true),
+            SerdeRename::SnakeCase => field.to_case(Case::Snake, true),
+            SerdeRename::PascalCase => field.to_case(Case::Pascal, true),
+        }
+    }
 }
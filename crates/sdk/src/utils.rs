@@ -1,9 +1,91 @@
-use std::{path::PathBuf, process::Command};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 pub fn rustfmt(paths: &[PathBuf]) {
     Command::new("rustfmt")
         .arg("--edition=2021")
-        .args(paths.iter().map(|p| p.to_str().unwrap()))
+        .args(paths)
         .output()
         .ok();
 }
+
+/// Converts `path` into a form that can exceed `MAX_PATH` on Windows,
+/// which deeply nested clients for large schemas can easily do.
+/// Verbatim (`\\?\`) paths must be absolute, use backslashes
+/// and not contain `.` or `..`, so they are normalized first.
+#[cfg(windows)]
+pub fn output_path(path: &Path) -> PathBuf {
+    use std::path::{Component, Prefix};
+
+    let absolute = std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+
+    let is_verbatim = matches!(
+        absolute.components().next(),
+        Some(Component::Prefix(p)) if matches!(
+            p.kind(),
+            Prefix::Verbatim(_) | Prefix::VerbatimUNC(..) | Prefix::VerbatimDisk(_) | Prefix::DeviceNS(_)
+        )
+    );
+
+    if is_verbatim {
+        return absolute;
+    }
+
+    let mut prefix = String::new();
+    let mut parts = vec![];
+
+    for component in absolute.components() {
+        match component {
+            Component::Prefix(p) => match p.kind() {
+                Prefix::UNC(server, share) => {
+                    prefix = format!(
+                        r"\\?\UNC\{}\{}",
+                        server.to_string_lossy(),
+                        share.to_string_lossy()
+                    )
+                }
+                Prefix::Disk(disk) => prefix = format!(r"\\?\{}:", disk as char),
+                _ => {}
+            },
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part),
+        }
+    }
+
+    let mut normalized = PathBuf::from(prefix + r"\");
+
+    normalized.extend(parts);
+
+    normalized
+}
+
+#[cfg(not(windows))]
+pub fn output_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// Removes a previously generated client.
+/// If `path` is a symlink or junction only the link itself is removed, never its target.
+pub fn remove_output(path: &Path) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    if metadata.file_type().is_symlink() {
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    } else if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        Ok(())
+    }
+}
@@ -67,9 +67,21 @@ pub async fn main() {
 
     println!("User posts: {:?}", user_with_posts.posts().unwrap());
 
-    let deleted_posts_count = client.post().delete_many(vec![]).exec().await.unwrap();
+    let deleted_posts_count = client
+        .post()
+        .delete_many(vec![])
+        .all()
+        .exec()
+        .await
+        .unwrap();
     println!("Deleted {} posts", deleted_posts_count);
 
-    let deleted_users_count = client.user().delete_many(vec![]).exec().await.unwrap();
+    let deleted_users_count = client
+        .user()
+        .delete_many(vec![])
+        .all()
+        .exec()
+        .await
+        .unwrap();
     println!("Deleted {} users", deleted_users_count);
 }
@@ -104,6 +104,7 @@ async fn error() -> TestResult {
         .unwrap_err();
 
     assert!(error.is_prisma_error::<UniqueKeyViolation>());
+    assert_eq!(error.http_status(), 409);
 
     cleanup(client).await
 }
@@ -373,3 +374,28 @@ async fn allows_empty() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn named() -> TestResult {
+    let client = client().await;
+
+    let created = prisma_client_rust::batch!(client, {
+        brendan: client.user().create("Brendan".to_string(), vec![]),
+        oscar: client.user().create("Oscar".to_string(), vec![]),
+        count: client.user().count(vec![]),
+    })
+    .await?;
+
+    assert_eq!(&created.brendan.name, "Brendan");
+    assert_eq!(&created.oscar.name, "Oscar");
+    assert_eq!(created.count, 2);
+
+    let found = prisma_client_rust::batch!(client, {
+        user: client.user().find_unique(user::id::equals(created.brendan.id.clone())),
+    })
+    .await?;
+
+    assert_eq!(found.user.map(|u| u.id), Some(created.brendan.id));
+
+    cleanup(client).await
+}
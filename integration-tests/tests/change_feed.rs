@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn change_feed() -> TestResult {
+    let client = client().await;
+
+    for name in ["a", "b", "c"] {
+        client
+            .category()
+            .create(name.to_string(), vec![])
+            .exec()
+            .await?;
+    }
+
+    let first = client
+        .category()
+        .find_first(vec![category::name::equals("a".to_string())])
+        .exec()
+        .await?
+        .unwrap();
+
+    let feed = client
+        .category()
+        .find_many(vec![])
+        .change_feed(category::id::feed_cursor())
+        .interval(Duration::from_millis(50))
+        .batch_size(1)
+        .after(first.id)
+        .into_stream();
+
+    let names = feed
+        .take(2)
+        .map(|record| record.map(|r| r.name))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(names, vec!["b", "c"]);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn change_feed_tied_cursor() -> TestResult {
+    let client = client().await;
+
+    for views in [10, 20, 20] {
+        client
+            .post()
+            .create("title".to_string(), false, vec![post::views::set(views)])
+            .exec()
+            .await?;
+    }
+
+    let cursor = post::views::feed_cursor();
+
+    // a batch of one cuts between the records that share a view count
+    let records = client
+        .post()
+        .find_many(vec![])
+        .change_feed(post::views::feed_cursor())
+        .interval(Duration::from_millis(50))
+        .batch_size(1)
+        .into_stream()
+        .take(3)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(
+        records.iter().map(|r| r.views).collect::<Vec<_>>(),
+        vec![10, 20, 20]
+    );
+    assert_ne!(records[1].id, records[2].id);
+
+    let mut feed = Box::pin(
+        client
+            .post()
+            .find_many(vec![])
+            .change_feed(post::views::feed_cursor())
+            .interval(Duration::from_millis(50))
+            .after(cursor.value_of(&records[1]))
+            .into_stream(),
+    );
+
+    let record = feed.next().await.unwrap()?;
+
+    assert_eq!(record.id, records[2].id);
+
+    client
+        .post()
+        .create("title".to_string(), false, vec![post::views::set(30)])
+        .exec()
+        .await?;
+
+    let record = feed.next().await.unwrap()?;
+
+    assert_eq!(record.views, 30);
+    assert_eq!(cursor.value_of(&record), (30, record.id.clone()));
+
+    cleanup(client).await
+}
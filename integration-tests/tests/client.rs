@@ -0,0 +1,843 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use prisma_client_rust::{
+    bigdecimal::BigDecimal,
+    operation_from_json, operation_to_json,
+    query_core::{Operation, TransactionOptions, TxId},
+    raw, response_from_json, serde_json, serde_value, DisconnectOnDrop, EngineLogLevel,
+    NewClientError, PoolConfig, PoolConfigError, PoolEvent, QueryEngine, QueryError,
+    QueryErrorDetail, SqliteConfigError, WithSchemaError,
+};
+use serde::Deserialize;
+use tokio::runtime::Handle;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn missing_env() {
+    let result = PrismaClient::_builder()
+        .with_env("PCR_INTEGRATION_TESTS_MISSING_URL")
+        .build()
+        .await;
+
+    match result {
+        Err(NewClientError::MissingEnv(name)) => {
+            assert_eq!(name, "PCR_INTEGRATION_TESTS_MISSING_URL")
+        }
+        result => panic!("Expected MissingEnv error, got {result:?}"),
+    }
+}
+
+#[tokio::test]
+async fn url_overrides_env() {
+    let client = PrismaClient::_builder()
+        .with_env("PCR_INTEGRATION_TESTS_MISSING_URL")
+        .with_url("file:dev.db".to_string())
+        .build()
+        .await;
+
+    assert!(client.is_ok());
+}
+
+#[tokio::test]
+async fn eager_connect() -> TestResult {
+    let url = "file:./missing-directory/dev.db".to_string();
+
+    let result = PrismaClient::_builder().with_url(url.clone()).build().await;
+
+    assert!(matches!(result, Err(NewClientError::Connection(_))));
+
+    let client = PrismaClient::_builder()
+        .with_url(url)
+        .eager_connect(false)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(client.user().count(vec![]).exec().await.is_err());
+
+    let client = PrismaClient::_new().await.unwrap();
+
+    client.user().count(vec![]).exec().await?;
+
+    Ok(())
+}
+
+#[test]
+fn generated_with_version() {
+    assert_eq!(GENERATED_WITH_VERSION, prisma_client_rust::VERSION);
+    assert!(prisma_client_rust::is_runtime_version(
+        GENERATED_WITH_VERSION
+    ));
+    assert!(!prisma_client_rust::is_runtime_version("0.0.0"));
+
+    // only warns, and doesn't panic for mismatched versions
+    prisma_client_rust::warn_version_mismatch("0.0.0");
+}
+
+#[tokio::test]
+async fn sqlite_wal_mode() -> TestResult {
+    // WAL mode is stored in the database file, so it's enabled on one of its own
+    let client = PrismaClient::_builder()
+        .with_url("file:wal-mode.db".to_string())
+        .busy_timeout(Duration::from_secs(5))
+        .wal_mode(true)
+        .build()
+        .await
+        .unwrap();
+
+    #[derive(Deserialize)]
+    struct JournalMode {
+        journal_mode: String,
+    }
+
+    let mode: Vec<JournalMode> = client
+        ._query_raw(raw!("PRAGMA journal_mode"))
+        .exec()
+        .await?;
+
+    assert_eq!(mode[0].journal_mode, "wal");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn sqlite_busy_timeout_too_short() {
+    let result = PrismaClient::_builder()
+        .busy_timeout(Duration::from_millis(100))
+        .build()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(NewClientError::SqliteConfig(
+            SqliteConfigError::BusyTimeoutTooShort
+        ))
+    ));
+}
+
+#[test]
+fn pool_config_timeouts() {
+    let url = PoolConfig::new()
+        .pool_timeout(Duration::ZERO)
+        .connect_timeout(Duration::from_secs(3))
+        .apply_to_url("file:dev.db")
+        .unwrap();
+    assert_eq!(url, "file:dev.db?pool_timeout=0&connect_timeout=3");
+
+    for (config, error) in [
+        (
+            PoolConfig::new().pool_timeout(Duration::from_millis(500)),
+            PoolConfigError::FractionalTimeout("pool_timeout"),
+        ),
+        (
+            PoolConfig::new().idle_timeout(Duration::from_millis(1500)),
+            PoolConfigError::FractionalTimeout("idle_timeout"),
+        ),
+        (
+            PoolConfig::new().connect_timeout(Duration::ZERO),
+            PoolConfigError::TimeoutTooShort("connect_timeout"),
+        ),
+        (
+            PoolConfig::new().connection_limit(0),
+            PoolConfigError::ZeroConnectionLimit,
+        ),
+    ] {
+        assert_eq!(config.validate(), Err(error));
+    }
+}
+
+#[tokio::test]
+async fn pool_timeout_too_precise() {
+    let result = PrismaClient::_builder()
+        .pool_timeout(Duration::from_millis(2500))
+        .build()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(NewClientError::PoolConfig(
+            PoolConfigError::FractionalTimeout("pool_timeout")
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn db_version() -> TestResult {
+    let client = client().await;
+
+    let version = client._db_version().await.unwrap();
+
+    assert_eq!(version.major, 3);
+    assert!(version.raw.starts_with(&format!("3.{}", version.minor)));
+    assert!(version.at_least(3, 0));
+    assert!(!version.at_least(4, 0));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn disconnect_on_drop() -> TestResult {
+    for mode in [
+        DisconnectOnDrop::Block(Handle::current(), Duration::from_secs(5)),
+        DisconnectOnDrop::Spawn(Handle::current()),
+    ] {
+        let client = PrismaClient::_builder()
+            .disconnect_on_drop(mode)
+            .build()
+            .await
+            .unwrap();
+
+        let (tx, tx_client) = client._transaction().begin().await?;
+        tx_client.user().count(vec![]).exec().await?;
+        tx.commit(tx_client).await?;
+
+        client.user().count(vec![]).exec().await?;
+
+        drop(client);
+    }
+
+    let client = client().await;
+
+    client.user().count(vec![]).exec().await?;
+
+    cleanup(client).await
+}
+
+#[test]
+fn with_runtime() -> TestResult {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let handle = runtime.handle().clone();
+
+    let (stop_tx, stop_rx) = futures::channel::oneshot::channel::<()>();
+    let driver = std::thread::spawn(move || {
+        runtime.block_on(stop_rx).ok();
+    });
+
+    // there's no runtime on this thread, so the client has to use the one it was given
+    let result = futures::executor::block_on(async {
+        let client = PrismaClient::_builder()
+            .with_runtime(handle)
+            .build()
+            .await
+            .unwrap();
+
+        cleanup(client.clone()).await?;
+
+        client
+            .user()
+            .create("Brendan".to_string(), vec![])
+            .exec()
+            .await?;
+
+        let (tx, tx_client) = client._transaction().begin().await?;
+        tx_client
+            .user()
+            .create("Oscar".to_string(), vec![])
+            .exec()
+            .await?;
+        tx.commit(tx_client).await?;
+
+        assert_eq!(client.user().count(vec![]).exec().await?, 2);
+
+        cleanup(client).await
+    });
+
+    stop_tx.send(()).ok();
+    driver.join().unwrap();
+
+    result
+}
+
+#[tokio::test]
+async fn pool_events() -> TestResult {
+    let events = Arc::new(Mutex::new(vec![]));
+
+    let client = PrismaClient::_builder()
+        .on_pool_event({
+            let events = events.clone();
+            move |event| events.lock().unwrap().push(*event)
+        })
+        .build()
+        .await
+        .unwrap();
+
+    client.user().count(vec![]).exec().await?;
+
+    // events of other tests' clients are received too, so only their presence is checked
+    {
+        let events = events.lock().unwrap();
+
+        assert!(events.contains(&PoolEvent::ConnectionCreated));
+        assert!(events.contains(&PoolEvent::ConnectionAcquired));
+        assert!(events.contains(&PoolEvent::ConnectionReleased));
+    }
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn engine_logs() -> TestResult {
+    let logs = Arc::new(Mutex::new(vec![]));
+
+    let client = PrismaClient::_builder()
+        .engine_log_level(EngineLogLevel::Query)
+        .on_engine_log({
+            let logs = logs.clone();
+            move |log| logs.lock().unwrap().push(log.clone())
+        })
+        .build()
+        .await
+        .unwrap();
+
+    client.user().count(vec![]).exec().await?;
+
+    {
+        let logs = logs.lock().unwrap();
+
+        let query = logs
+            .iter()
+            .find(|log| log.level == EngineLogLevel::Query)
+            .expect("query log");
+
+        assert!(query.query.as_ref().unwrap().contains("SELECT COUNT"));
+        assert!(query.duration.is_some());
+    }
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_error_detail() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        ._execute_raw(raw!("DELETE FROM missing_table"))
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(error.query().is_none());
+    assert!(!error.to_string().contains("(in "));
+
+    for (detail, expected) in [
+        (QueryErrorDetail::Operation, "(in executeRaw)"),
+        (QueryErrorDetail::Query, "DELETE FROM missing_table"),
+    ] {
+        let client = PrismaClient::_builder()
+            .query_errors(detail)
+            .build()
+            .await
+            .unwrap();
+
+        let error = client
+            ._execute_raw(raw!("DELETE FROM missing_table"))
+            .exec()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error.inner(), QueryError::Execute(_)));
+        assert_eq!(error.query().unwrap().operation, "executeRaw");
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+
+    cleanup(client).await
+}
+
+struct RecordingEngine {
+    inner: Arc<dyn QueryEngine>,
+    operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingEngine {
+    fn record(&self, operation: &Operation) {
+        let selection = match operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection,
+        };
+
+        self.operations
+            .lock()
+            .unwrap()
+            .push(selection.name().to_string());
+    }
+}
+
+impl QueryEngine for RecordingEngine {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<serde_value::Value>> {
+        self.record(&operation);
+        self.inner.execute(tx_id, operation)
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<
+        '_,
+        prisma_client_rust::Result<Vec<prisma_client_rust::Result<serde_value::Value>>>,
+    > {
+        operations.iter().for_each(|op| self.record(op));
+        self.inner.execute_all(operations)
+    }
+
+    fn start_tx(
+        &self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<TxId>> {
+        self.inner.start_tx(options)
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.inner.commit_tx(tx_id)
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.inner.rollback_tx(tx_id)
+    }
+
+    fn url(&self) -> &str {
+        self.inner.url()
+    }
+}
+
+#[tokio::test]
+async fn custom_engine() -> TestResult {
+    let native = client().await;
+    let operations = Arc::new(Mutex::new(vec![]));
+
+    let client = PrismaClient::_builder()
+        .with_engine(RecordingEngine {
+            inner: native._query_engine().unwrap(),
+            operations: operations.clone(),
+        })
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let (count, users) = client
+        ._transaction()
+        .run(|tx| async move {
+            let count = tx.user().count(vec![]).exec().await?;
+            let users = tx.user().find_many(vec![]).exec().await?;
+
+            Ok::<_, QueryError>((count, users))
+        })
+        .await?;
+
+    assert_eq!(count, 1);
+    assert_eq!(users[0].name, "Brendan");
+    assert_eq!(
+        *operations.lock().unwrap(),
+        ["createOneUser", "aggregateUser", "findManyUser"]
+    );
+
+    drop(client);
+
+    cleanup(native).await
+}
+
+#[tokio::test]
+async fn model_actions() -> TestResult {
+    let client = client().await;
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let actions = client._model_actions();
+    let users = actions
+        .iter()
+        .find(|actions| actions.model_name() == "User")
+        .unwrap();
+
+    assert!(actions.iter().any(|actions| actions.model_name() == "Post"));
+
+    assert_eq!(users.count().await?, 1);
+
+    let records = users.find_many(None, Some(10)).await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["name"], "Brendan");
+
+    assert_eq!(users.delete_many().await?, 1);
+    assert_eq!(users.count().await?, 0);
+
+    drop(actions);
+
+    cleanup(client).await
+}
+
+struct Tenant(&'static str);
+
+#[tokio::test]
+async fn middleware_context() -> TestResult {
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let client = PrismaClient::_builder()
+        .with_middleware({
+            let seen = seen.clone();
+
+            move |query| {
+                let tenant = query.context::<Tenant>().map(|tenant| tenant.0);
+                seen.lock()
+                    .unwrap()
+                    .push((query.name().to_string(), tenant));
+
+                Ok(())
+            }
+        })
+        .with_middleware(|query| match query.context::<Tenant>() {
+            Some(Tenant("readonly")) if query.is_write() => {
+                Err(QueryError::Rejected("tenant is read-only".to_string()))
+            }
+            _ => Ok(()),
+        })
+        .build()
+        .await
+        .unwrap();
+
+    client.user().find_many(vec![]).exec().await?;
+
+    let acme = client._with_context(Tenant("acme"));
+    acme.user().find_many(vec![]).exec().await?;
+    acme._transaction()
+        .run(|tx| async move { tx.user().find_first(vec![]).exec().await })
+        .await?;
+
+    // the original client is left without a context
+    client.user().find_first(vec![]).exec().await?;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            ("findManyUser".to_string(), None),
+            ("findManyUser".to_string(), Some("acme")),
+            ("findFirstUser".to_string(), Some("acme")),
+            ("findFirstUser".to_string(), None),
+        ]
+    );
+
+    let readonly = client._with_context(Tenant("readonly"));
+    let result = readonly
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await;
+    assert!(matches!(result, Err(QueryError::Rejected(_))));
+    assert!(readonly.user().find_first(vec![]).exec().await?.is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn clone_across_tasks() -> TestResult {
+    let client = client().await;
+
+    let tasks = ["Brendan", "Oscar", "Jamie"].map(|name| {
+        let client = client.clone();
+
+        tokio::spawn(async move { client.user().create(name.to_string(), vec![]).exec().await })
+    });
+
+    for task in tasks {
+        task.await.unwrap()?;
+    }
+
+    // the clones share the original's engine, so their writes are visible to it
+    assert_eq!(client.user().count(vec![]).exec().await?, 3);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn cancelled_exec() -> TestResult {
+    let client = client().await;
+
+    // polls the query once, then drops it while the engine is still running it
+    let create = client.user().create("Brendan".to_string(), vec![]).exec();
+    assert!(tokio::time::timeout(Duration::ZERO, create).await.is_err());
+
+    client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // the dropped query still finishes in the background
+    let mut count = 0;
+    for _ in 0..50 {
+        count = client.user().count(vec![]).exec().await?;
+
+        if count == 2 {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(count, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn with_schema() -> TestResult {
+    let client = client().await;
+
+    assert!(matches!(
+        client._with_schema("tenant_1\"; DROP TABLE User; --").await,
+        Err(WithSchemaError::InvalidName(_))
+    ));
+    assert!(matches!(
+        client._with_schema("tenant_1").await,
+        Err(WithSchemaError::UnsupportedProvider(provider)) if provider == "sqlite"
+    ));
+
+    client
+        ._transaction()
+        .run(|tx| async move {
+            assert!(matches!(
+                tx._with_schema("tenant_1").await,
+                Err(WithSchemaError::UnsupportedClient)
+            ));
+
+            Ok::<_, QueryError>(())
+        })
+        .await?;
+
+    cleanup(client).await
+}
+
+/// Nulls every `name` field in responses,
+/// as if the column were nullable in the database but required in the schema.
+struct NullNameEngine(Arc<dyn QueryEngine>);
+
+fn null_names(value: &mut serde_value::Value) {
+    match value {
+        serde_value::Value::Seq(values) => values.iter_mut().for_each(null_names),
+        serde_value::Value::Map(map) => {
+            if let Some(name) = map.get_mut(&serde_value::Value::String("name".to_string())) {
+                *name = serde_value::Value::Option(None);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl QueryEngine for NullNameEngine {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<serde_value::Value>> {
+        async move {
+            let mut value = self.0.execute(tx_id, operation).await?;
+            null_names(&mut value);
+            Ok(value)
+        }
+        .boxed()
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<
+        '_,
+        prisma_client_rust::Result<Vec<prisma_client_rust::Result<serde_value::Value>>>,
+    > {
+        self.0.execute_all(operations)
+    }
+
+    fn start_tx(
+        &self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<TxId>> {
+        self.0.start_tx(options)
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.0.commit_tx(tx_id)
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.0.rollback_tx(tx_id)
+    }
+
+    fn url(&self) -> &str {
+        self.0.url()
+    }
+}
+
+#[tokio::test]
+async fn unexpected_null() -> TestResult {
+    let native = client().await;
+
+    native
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let client = PrismaClient::_builder()
+        .with_engine(NullNameEngine(native._query_engine().unwrap()))
+        .build()
+        .await
+        .unwrap();
+
+    let result = client.user().find_many(vec![]).exec().await;
+    assert!(matches!(
+        result,
+        Err(QueryError::UnexpectedNull { field }) if field == "[0].name"
+    ));
+
+    // selections that leave out the field are unaffected
+    let result = client
+        .user()
+        .find_many(vec![])
+        .select(user::select!({ id email }))
+        .exec()
+        .await?;
+    assert_eq!(result.len(), 1);
+
+    drop(client);
+
+    cleanup(native).await
+}
+
+/// Sends operations and their responses through JSON, as an engine in another process would.
+struct JsonEngine(Arc<dyn QueryEngine>);
+
+impl JsonEngine {
+    async fn execute_json(
+        &self,
+        tx_id: Option<TxId>,
+        operation: serde_json::Value,
+    ) -> prisma_client_rust::Result<serde_json::Value> {
+        let value = self
+            .0
+            .execute(tx_id, operation_from_json(operation)?)
+            .await?;
+
+        Ok(serde_json::to_value(value).unwrap())
+    }
+}
+
+impl QueryEngine for JsonEngine {
+    fn execute(
+        &self,
+        tx_id: Option<TxId>,
+        operation: Operation,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<serde_value::Value>> {
+        async move {
+            let operation = operation_to_json(&operation)?;
+
+            response_from_json(self.execute_json(tx_id, operation).await?)
+        }
+        .boxed()
+    }
+
+    fn execute_all(
+        &self,
+        operations: Vec<Operation>,
+    ) -> BoxFuture<
+        '_,
+        prisma_client_rust::Result<Vec<prisma_client_rust::Result<serde_value::Value>>>,
+    > {
+        self.0.execute_all(operations)
+    }
+
+    fn start_tx(
+        &self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<TxId>> {
+        self.0.start_tx(options)
+    }
+
+    fn commit_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.0.commit_tx(tx_id)
+    }
+
+    fn rollback_tx(&self, tx_id: TxId) -> BoxFuture<'_, prisma_client_rust::Result<()>> {
+        self.0.rollback_tx(tx_id)
+    }
+
+    fn url(&self) -> &str {
+        self.0.url()
+    }
+}
+
+#[tokio::test]
+async fn json_engine() -> TestResult {
+    let native = client().await;
+
+    let client = PrismaClient::_builder()
+        .with_engine(JsonEngine(native._query_engine().unwrap()))
+        .build()
+        .await
+        .unwrap();
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // DateTime values survive the round trip
+    let found = client
+        .user()
+        .find_many(vec![user::created_at::equals(user.created_at)])
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, user.id);
+
+    // as do BigInt and Decimal ones
+    let decimal = BigDecimal::from_str("1.25").unwrap();
+    client
+        .types()
+        .create(vec![
+            types::bigint::set(i64::MAX),
+            types::decimal::set(Some(decimal.clone())),
+        ])
+        .exec()
+        .await?;
+
+    let found = client
+        .types()
+        .find_many(vec![
+            types::bigint::equals(i64::MAX),
+            types::decimal::equals(Some(decimal.clone())),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].decimal, Some(decimal));
+
+    // and so do transactions, which pass their id alongside the operation
+    let count = client
+        ._transaction()
+        .run(|tx| async move { tx.user().count(vec![]).exec().await })
+        .await?;
+    assert_eq!(count, 1);
+
+    drop(client);
+
+    cleanup(native).await
+}
@@ -1,3 +1,11 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use prisma_client_rust::{CountCache, MemoryCountCache};
+
 use crate::db::*;
 use crate::utils::*;
 
@@ -91,3 +99,96 @@ async fn skip() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn cached() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let ttl = Duration::from_secs(60);
+
+    assert_eq!(client.post().cached_count(ttl, vec![]).exec().await?, 3);
+
+    create_posts(&client).await?;
+
+    // within the ttl the cached count is returned, even though it's out of date
+    assert_eq!(client.post().cached_count(ttl, vec![]).exec().await?, 3);
+    assert_eq!(client.post().count(vec![]).exec().await?, 6);
+
+    // counts with different filters are cached separately
+    let published = client
+        .post()
+        .cached_count(ttl, vec![post::published::equals(true)])
+        .exec()
+        .await?;
+    assert_eq!(published, 4);
+
+    // once expired, the next count refreshes the cache
+    let short_ttl = Duration::from_millis(50);
+    let filters = || vec![post::published::equals(false)];
+
+    assert_eq!(
+        client
+            .post()
+            .cached_count(short_ttl, filters())
+            .exec()
+            .await?,
+        2
+    );
+    create_posts(&client).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        client
+            .post()
+            .cached_count(short_ttl, filters())
+            .exec()
+            .await?,
+        3
+    );
+
+    cleanup(client).await
+}
+
+#[derive(Default)]
+struct RecordingCache {
+    inner: MemoryCountCache,
+    keys: Mutex<Vec<String>>,
+}
+
+impl CountCache for RecordingCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<i64>> {
+        self.inner.get(key)
+    }
+
+    fn set<'a>(&'a self, key: &'a str, count: i64, ttl: Duration) -> BoxFuture<'a, ()> {
+        self.keys.lock().unwrap().push(key.to_string());
+        self.inner.set(key, count, ttl)
+    }
+}
+
+#[tokio::test]
+async fn custom_cache() -> TestResult {
+    let cache = Arc::new(RecordingCache::default());
+
+    let client = PrismaClient::_builder()
+        .with_count_cache(cache.clone())
+        .build()
+        .await
+        .unwrap();
+
+    create_posts(&client).await?;
+
+    let query = || client.post().cached_count(Duration::from_secs(60), vec![]);
+
+    assert_eq!(query().exec().await?, 3);
+    assert_eq!(query().exec().await?, 3);
+
+    // only the first count is stored, under the query's cache key
+    assert_eq!(
+        *cache.keys.lock().unwrap(),
+        [client.post().count(vec![]).cache_key()]
+    );
+
+    cleanup(client).await
+}
@@ -139,3 +139,204 @@ async fn from_struct() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn nested_create_many() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![
+                user::posts::create_params("First".to_string(), true, vec![]),
+                user::posts::create_params("Second".to_string(), false, vec![post::views::set(2)]),
+            ])],
+        )
+        .with(user::posts::fetch(vec![]).order_by(post::title::order(SortOrder::Asc)))
+        .exec()
+        .await?;
+
+    let posts = user.posts.unwrap();
+
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[0].title, "First");
+    assert_eq!(posts[0].author_id, Some(user.id.clone()));
+    assert_eq!(posts[1].title, "Second");
+    assert_eq!(posts[1].views, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_create_one() -> TestResult {
+    let client = client().await;
+
+    // profile.user is required, but is supplied by the nesting
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::profile::create(user::profile::create_params(
+                "Bio".to_string(),
+                "Australia".to_string(),
+                vec![],
+            ))],
+        )
+        .with(user::profile::fetch())
+        .exec()
+        .await?;
+
+    let profile = user.profile.unwrap().unwrap();
+
+    assert_eq!(profile.bio, "Bio");
+    assert_eq!(profile.user_id, user.id);
+
+    let post = client
+        .post()
+        .create(
+            "Post".to_string(),
+            true,
+            vec![post::author::create(post::author::create_params(
+                "Oscar".to_string(),
+                vec![],
+            ))],
+        )
+        .with(post::author::fetch())
+        .exec()
+        .await?;
+
+    assert_eq!(post.author.unwrap().unwrap().name, "Oscar");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn create_empty() -> TestResult {
+    let client = client().await;
+
+    let record = client.types().create_empty().exec().await?;
+
+    assert_eq!(record.id, 0);
+    assert_eq!(record.string, "");
+    assert_eq!(record.decimal, None);
+
+    client
+        .types()
+        .delete(types::id_string(0, "".to_string()))
+        .exec()
+        .await?;
+
+    let record = types::Create::default().to_query(&client).exec().await?;
+
+    assert_eq!(record.integer, 0);
+    assert!(!record.bool_);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn display() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    assert_eq!(user.to_string(), "Brendan");
+    assert_eq!(format!("[{:>9}]", user), "[  Brendan]");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn create_input() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // fields with defaults and nullable fields can be left out
+    let input: post::CreateInput = serde_json::from_value(serde_json::json!({
+        "title": "Hi from Prisma!",
+        "published": true,
+        "author_id": user.id
+    }))
+    .unwrap();
+    assert_eq!(input.views, None);
+    assert_eq!(input.desc, None);
+
+    let post = input.to_query(&client).exec().await?;
+    assert_eq!(post.title, "Hi from Prisma!");
+    assert_eq!(post.views, 0);
+    assert_eq!(post.desc, None);
+    assert_eq!(post.author_id, Some(user.id));
+
+    // required fields can't be
+    let result = serde_json::from_value::<post::CreateInput>(serde_json::json!({
+        "title": "Missing published"
+    }));
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("missing field `published`"));
+
+    cleanup(client).await
+}
+
+#[derive(prisma_client_rust::IntoPrismaCreate)]
+#[prisma(model = crate::db::post)]
+struct NewPost {
+    #[prisma(field = "title")]
+    heading: String,
+    published: bool,
+    views: Option<i32>,
+    desc: String,
+    #[prisma(skip)]
+    #[allow(unused)]
+    tags: Vec<String>,
+}
+
+#[tokio::test]
+async fn into_create() -> TestResult {
+    let client = client().await;
+
+    let new_post = NewPost {
+        heading: "Hi from Prisma!".to_string(),
+        published: true,
+        views: None,
+        desc: "Prisma is a database toolkit that makes databases easy.".to_string(),
+        tags: vec!["prisma".to_string()],
+    };
+
+    let post = new_post.into_create().to_query(&client).exec().await?;
+
+    assert_eq!(post.title, "Hi from Prisma!");
+    assert_eq!(post.published, true);
+    // left out when `None`, so the default applies
+    assert_eq!(post.views, 0);
+    assert_eq!(
+        post.desc,
+        Some("Prisma is a database toolkit that makes databases easy.".to_string())
+    );
+
+    // `None`s aren't included in the params
+    let params = NewPost {
+        heading: "Another post".to_string(),
+        published: false,
+        views: None,
+        desc: "Second".to_string(),
+        tags: vec![],
+    }
+    .into_create()
+    .to_params();
+
+    assert_eq!(params.len(), 3);
+
+    cleanup(client).await
+}
@@ -1,5 +1,6 @@
 use crate::db::*;
 use crate::utils::*;
+use prisma_client_rust::QueryError;
 
 /// Using create_many with SQLite is currently unstable
 /// Enabling the CLI's `sqlite-create-many` is unsafe and
@@ -51,3 +52,56 @@ async fn skip_duplicates() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn returning() -> TestResult {
+    let client = client().await;
+
+    let titles = ["Post 1", "Post 2", "Post 3"];
+
+    let posts = client
+        .post()
+        .create_many(
+            titles
+                .iter()
+                .map(|title| post::create_unchecked(title.to_string(), true, vec![]))
+                .collect(),
+        )
+        .returning()
+        .exec()
+        .await?;
+
+    assert_eq!(
+        posts.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        titles
+    );
+    assert!(posts.iter().all(|p| !p.id.is_empty()));
+
+    // the returned ids are those of the created records
+    let found = client
+        .post()
+        .find_many(vec![post::id::in_vec(
+            posts.iter().map(|p| p.id.clone()).collect(),
+        )])
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 3);
+
+    let result = client
+        .post()
+        .create_many(vec![post::create_unchecked(
+            "Post 4".to_string(),
+            true,
+            vec![],
+        )])
+        .skip_duplicates()
+        .returning()
+        .exec()
+        .await;
+    assert!(matches!(
+        result,
+        Err(QueryError::UnsupportedCreateManyReturning)
+    ));
+
+    cleanup(client).await
+}
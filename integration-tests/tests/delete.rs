@@ -65,6 +65,7 @@ async fn record_not_found() -> TestResult {
         .unwrap_err();
 
     assert!(error.is_prisma_error::<RecordRequiredButNotFound>());
+    assert_eq!(error.http_status(), 404);
 
     cleanup(client).await
 }
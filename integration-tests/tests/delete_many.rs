@@ -1,3 +1,5 @@
+use prisma_client_rust::QueryError;
+
 use crate::{db::*, utils::*};
 
 #[tokio::test]
@@ -17,7 +19,7 @@ async fn test() -> TestResult {
             .await?,
     ];
 
-    let count = client.post().delete_many(vec![]).exec().await?;
+    let count = client.post().delete_many(vec![]).all().exec().await?;
     assert!(count >= 1);
 
     for post in posts {
@@ -31,3 +33,53 @@ async fn test() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn unfiltered() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("Foo post".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let result = client.post().delete_many(vec![]).exec().await;
+    assert!(matches!(result, Err(QueryError::UnfilteredMutation)));
+
+    let result = client
+        .post()
+        .update_many(vec![], vec![post::published::set(true)])
+        .exec()
+        .await;
+    assert!(matches!(result, Err(QueryError::UnfilteredMutation)));
+
+    let result = client
+        .post()
+        .update_many(vec![], vec![post::published::set(true)])
+        .returning()
+        .exec()
+        .await;
+    assert!(matches!(result, Err(QueryError::UnfilteredMutation)));
+
+    // batches are guarded too
+    let result = client._batch(vec![client.post().delete_many(vec![])]).await;
+    assert!(matches!(result, Err(QueryError::UnfilteredMutation)));
+
+    // nothing was changed
+    let post = client.post().find_first(vec![]).exec().await?.unwrap();
+    assert!(!post.published);
+
+    let count = client
+        .post()
+        .update_many(vec![], vec![post::published::set(true)])
+        .all()
+        .exec()
+        .await?;
+    assert_eq!(count, 1);
+
+    let count = client.post().delete_many(vec![]).all().exec().await?;
+    assert_eq!(count, 1);
+
+    cleanup(client).await
+}
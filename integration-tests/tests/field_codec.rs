@@ -0,0 +1,105 @@
+use prisma_client_rust::{prisma_models::PrismaValue, raw, QueryError};
+use serde::Deserialize;
+
+use crate::{db::*, utils::*};
+
+#[derive(Deserialize)]
+struct StoredCity {
+    city: Option<String>,
+}
+
+#[tokio::test]
+async fn encoded_field() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let profile = client
+        .profile()
+        .create(
+            user::id::equals(user.id.clone()),
+            "Brendan's profile".to_string(),
+            "Australia".to_string(),
+            vec![profile::city::set(Some("Perth".to_string()))],
+        )
+        .exec()
+        .await?;
+    assert_eq!(profile.city.as_deref(), Some("Perth"));
+
+    let stored: Vec<StoredCity> = client
+        ._query_raw(raw!(
+            "SELECT city FROM Profile WHERE id = {}",
+            PrismaValue::Int(profile.id as i64)
+        ))
+        .exec()
+        .await?;
+    assert_eq!(stored[0].city.as_deref(), Some("rev:htreP"));
+
+    // equals compares encoded values
+    let found = client
+        .profile()
+        .find_first(vec![profile::city::equals(Some("Perth".to_string()))])
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(found.id, profile.id);
+
+    let users = client
+        .user()
+        .find_many(vec![])
+        .include(user::include!({ profile }))
+        .exec()
+        .await?;
+    assert_eq!(
+        users[0].profile.as_ref().unwrap().city.as_deref(),
+        Some("Perth")
+    );
+
+    let users = client
+        .user()
+        .find_many(vec![])
+        .select(user::select!({
+            profile: select {
+                city
+            }
+        }))
+        .exec()
+        .await?;
+    assert_eq!(
+        users[0].profile.as_ref().unwrap().city.as_deref(),
+        Some("Perth")
+    );
+
+    let updated = client
+        .profile()
+        .update(
+            profile::id::equals(profile.id),
+            vec![profile::city::set(Some("Sydney".to_string()))],
+        )
+        .exec()
+        .await?;
+    assert_eq!(updated.city.as_deref(), Some("Sydney"));
+
+    // values that the codec can't decode fail the query
+    client
+        ._execute_raw(raw!(
+            "UPDATE Profile SET city = {} WHERE id = {}",
+            PrismaValue::String("Melbourne".to_string()),
+            PrismaValue::Int(profile.id as i64)
+        ))
+        .exec()
+        .await?;
+
+    let result = client
+        .profile()
+        .find_unique(profile::id::equals(profile.id))
+        .exec()
+        .await;
+    assert!(matches!(result, Err(QueryError::Deserialize(_))));
+
+    cleanup(client).await
+}
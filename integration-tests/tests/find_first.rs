@@ -356,3 +356,45 @@ async fn list_wrapper_query_transformation() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn first_and_last() -> TestResult {
+    let client = client().await;
+
+    for id in ["b", "a", "c"] {
+        client
+            .post()
+            .create(
+                format!("Post {id}"),
+                id != "c",
+                vec![post::id::set(id.to_string())],
+            )
+            .exec()
+            .await?;
+    }
+
+    let first = client.post().first(vec![]).exec().await?.unwrap();
+    assert_eq!(first.id, "a");
+
+    let last = client.post().last(vec![]).exec().await?.unwrap();
+    assert_eq!(last.id, "c");
+
+    let last_published = client
+        .post()
+        .last(vec![post::published::equals(true)])
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(last_published.id, "b");
+
+    let first_by_title = client
+        .post()
+        .first(vec![])
+        .order_by(post::title::order(SortOrder::Desc))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(first_by_title.id, "c");
+
+    cleanup(client).await
+}
@@ -1,4 +1,7 @@
-use prisma_client_rust::or;
+use std::{collections::BTreeSet, sync::Arc};
+
+use prisma_client_rust::{or, QueryError};
+use serde::Deserialize;
 
 use crate::{db::*, utils::*};
 
@@ -92,6 +95,122 @@ async fn query() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn exec_as() -> TestResult {
+    #[derive(Deserialize)]
+    struct PostSummary {
+        title: String,
+        #[serde(rename = "published")]
+        is_published: bool,
+    }
+
+    let client = client().await;
+
+    client
+        .post()
+        .create("Test post".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    let posts: Vec<PostSummary> = client.post().find_many(vec![]).exec_as().await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Test post");
+    assert!(posts[0].is_published);
+
+    // records are objects, so they can only be deserialized into types with fields
+    let error = client
+        .post()
+        .find_many(vec![])
+        .exec_as::<Vec<String>>()
+        .await;
+    assert!(matches!(error, Err(QueryError::Deserialize(_))));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn exec_shared() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("Test post".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    let posts = client.post().find_many(vec![]).exec_shared().await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].id, post.id);
+
+    // consumers share the record rather than cloning it
+    let shared = posts[0].clone();
+    assert!(Arc::ptr_eq(&shared, &posts[0]));
+
+    let found = client
+        .post()
+        .find_unique(post::id::equals(post.id.clone()))
+        .exec_shared()
+        .await?;
+    assert_eq!(found.unwrap().title, "Test post");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn bool_filters() -> TestResult {
+    let client = client().await;
+
+    let key = |filter: post::WhereParam| client.post().find_many(vec![filter]).cache_key();
+
+    assert_eq!(
+        key(post::published::is_true()),
+        key(post::published::equals(true))
+    );
+    assert_eq!(
+        key(post::published::is_false()),
+        key(post::published::equals(false))
+    );
+    assert_eq!(
+        key(post::featured::is_null()),
+        key(post::featured::equals(None))
+    );
+    assert_eq!(
+        key(post::featured::is_not_null()),
+        key(post::featured::not(None))
+    );
+
+    let featured = client
+        .post()
+        .create(
+            "Featured".to_string(),
+            true,
+            vec![post::featured::set(Some(true))],
+        )
+        .exec()
+        .await?;
+    let draft = client
+        .post()
+        .create("Draft".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    for (filter, expected) in [
+        (post::published::is_true(), &featured),
+        (post::published::is_false(), &draft),
+        (post::published::not(true), &draft),
+        (post::featured::is_true(), &featured),
+        (post::featured::is_null(), &draft),
+        (post::featured::is_not_null(), &featured),
+    ] {
+        let posts = client.post().find_many(vec![filter]).exec().await?;
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, expected.id);
+    }
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn cursor() -> TestResult {
     let client = client().await;
@@ -186,6 +305,151 @@ async fn cursor_order() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn take_last() -> TestResult {
+    let client = client().await;
+
+    let mut posts = vec![];
+
+    for views in 1..=4 {
+        posts.push(
+            client
+                .post()
+                .create(format!("Foo {views}"), false, vec![post::views::set(views)])
+                .exec()
+                .await?,
+        );
+    }
+
+    // the last records are still returned in the query's order
+    let found = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::views::order(SortOrder::Asc))
+        .take_last(2)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    let found = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::views::order(SortOrder::Asc))
+        .take(-2)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    // with a cursor, records are taken backwards from and including it
+    let found = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::views::order(SortOrder::Asc))
+        .cursor(post::id::equals(posts[2].id.clone()))
+        .take_last(2)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+
+    let found = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::views::order(SortOrder::Asc))
+        .cursor(post::id::equals(posts[2].id.clone()))
+        .skip(1)
+        .take_last(2)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn find_many_by_unique() -> TestResult {
+    let client = client().await;
+
+    let users = vec![
+        client
+            .user()
+            .create("Brendan".to_string(), vec![])
+            .exec()
+            .await?,
+        client
+            .user()
+            .create("Oscar".to_string(), vec![])
+            .exec()
+            .await?,
+    ];
+
+    client
+        .file_path()
+        .create_many(
+            users
+                .iter()
+                .flat_map(|user| {
+                    (0..3).map(|id| {
+                        file_path::create_unchecked(
+                            id,
+                            format!("{} {id}", user.name),
+                            user.id.clone(),
+                            vec![],
+                        )
+                    })
+                })
+                .collect(),
+        )
+        .exec()
+        .await?;
+
+    // the same local id belongs to both users, so only the compound key identifies a record
+    let file_paths = client
+        .file_path()
+        .find_many_by_unique(vec![
+            file_path::user_id_local_id(&users[0].id, 1),
+            file_path::user_id_local_id(&users[1].id, 2),
+        ])
+        .order_by(file_path::path::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(
+        file_paths
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Brendan 1", "Oscar 2"]
+    );
+
+    let users_found = client
+        .user()
+        .find_many_by_unique(vec![user::id::equals_ref(&users[1].id)])
+        .exec()
+        .await?;
+    assert_eq!(users_found.len(), 1);
+    assert_eq!(users_found[0].id, users[1].id);
+
+    let none = client
+        .file_path()
+        .find_many_by_unique(vec![])
+        .exec()
+        .await?;
+    assert!(none.is_empty());
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn filtering_one_to_one_relation() -> TestResult {
     let client = client().await;
@@ -274,6 +538,67 @@ async fn filtering_one_to_one_relation() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn orphan_records() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Authored".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(user.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create("Orphan".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    let titles = |posts: Vec<post::Data>| posts.into_iter().map(|p| p.title).collect::<Vec<_>>();
+
+    let orphans = client
+        .post()
+        .find_many(vec![post::author::is_null()])
+        .exec()
+        .await?;
+    assert_eq!(titles(orphans), vec!["Orphan"]);
+
+    // the relation and its foreign key agree on which posts have no author
+    let orphans = client
+        .post()
+        .find_many(vec![post::author_id::equals(None)])
+        .exec()
+        .await?;
+    assert_eq!(titles(orphans), vec!["Orphan"]);
+
+    let authored = client
+        .post()
+        .find_many(vec![post::author::is_not_null()])
+        .exec()
+        .await?;
+    assert_eq!(titles(authored), vec!["Authored"]);
+
+    // relations without a foreign key on the filtered model work the same way
+    let users = client
+        .user()
+        .find_many(vec![user::profile::is_not_null()])
+        .exec()
+        .await?;
+    assert!(users.is_empty());
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn filtering_one_to_many_relation() -> TestResult {
     let client = client().await;
@@ -382,6 +707,77 @@ async fn filtering_one_to_many_relation() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn relation_existence() -> TestResult {
+    let client = client().await;
+
+    let author = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "My first post".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(author.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // the helpers send the same filters as `some` and `none` with no conditions
+    assert_eq!(
+        client
+            .user()
+            .find_many(vec![user::with_posts()])
+            .cache_key(),
+        client
+            .user()
+            .find_many(vec![user::posts::some(vec![])])
+            .cache_key()
+    );
+    assert_eq!(
+        client
+            .user()
+            .find_many(vec![user::without_posts()])
+            .cache_key(),
+        client
+            .user()
+            .find_many(vec![user::posts::none(vec![])])
+            .cache_key()
+    );
+
+    let users = client
+        .user()
+        .find_many(vec![user::with_posts()])
+        .exec()
+        .await?;
+    assert_eq!(
+        users.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(),
+        vec!["Brendan"]
+    );
+
+    let users = client
+        .user()
+        .find_many(vec![user::without_posts(), user::without_favourite_posts()])
+        .exec()
+        .await?;
+    assert_eq!(
+        users.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(),
+        vec!["Oscar"]
+    );
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn ordering() -> TestResult {
     let client = client().await;
@@ -428,6 +824,156 @@ async fn ordering() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn distinct() -> TestResult {
+    let client = client().await;
+
+    for (views, (title, published)) in [
+        ("A", false),
+        ("A", true),
+        ("B", false),
+        ("B", false),
+        ("C", true),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        client
+            .post()
+            .create(
+                title.to_string(),
+                published,
+                vec![post::views::set(views as i32)],
+            )
+            .exec()
+            .await?;
+    }
+
+    // SQLite has no `DISTINCT ON`, so the engine keeps the first record
+    // of each title in the query's order
+    let found = client
+        .post()
+        .find_many(vec![])
+        .distinct(post::ScalarField::Title)
+        .order_by(post::views::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(
+        found
+            .iter()
+            .map(|p| (p.title.as_str(), p.views))
+            .collect::<Vec<_>>(),
+        vec![("C", 4), ("B", 3), ("A", 1)]
+    );
+
+    let found = client
+        .post()
+        .find_many(vec![])
+        .distinct(post::ScalarField::Title)
+        .distinct(post::ScalarField::Published)
+        .order_by(post::views::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![0, 1, 2, 4]
+    );
+
+    // pagination applies to the distinct records rather than every record
+    let found = client
+        .post()
+        .find_many(vec![])
+        .distinct(post::ScalarField::Title)
+        .order_by(post::views::order(SortOrder::Asc))
+        .skip(1)
+        .take(2)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        vec!["B", "C"]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn chunked_in() -> TestResult {
+    let client = client().await;
+
+    let mut ids = vec![];
+
+    for views in 0..10 {
+        let post = client
+            .post()
+            .create(
+                format!("Post {views}"),
+                false,
+                vec![post::views::set(views)],
+            )
+            .exec()
+            .await?;
+
+        ids.push(post.id);
+    }
+
+    // more values than SQLite allows parameters in a single query,
+    // with the records' ids spread across several chunks
+    let ids = (0..1200)
+        .map(|i| match i % 120 {
+            0 => ids[i / 120].clone(),
+            _ => format!("missing {i}"),
+        })
+        .collect::<Vec<_>>();
+
+    let found = client
+        .post()
+        .find_many(vec![post::id::in_vec(ids.clone())])
+        .chunked_in(500)
+        .order_by(post::views::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        (0..10).rev().collect::<Vec<_>>()
+    );
+
+    // pagination applies to the merged records rather than each chunk
+    let found = client
+        .post()
+        .find_many(vec![post::id::in_vec(ids.clone())])
+        .chunked_in(500)
+        .order_by(post::views::order(SortOrder::Asc))
+        .skip(2)
+        .take(3)
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+
+    // excluded values are split into an AND of not_in filters, each excluding its chunk
+    let excluded = ids
+        .into_iter()
+        .filter(|id| !id.starts_with("missing") && id != &found[0].id)
+        .chain((0..1200).map(|i| format!("missing {i}")))
+        .collect::<Vec<_>>();
+
+    let remaining = client
+        .post()
+        .find_many(vec![post::id::not_in_vec(excluded)])
+        .chunked_in(500)
+        .exec()
+        .await?;
+    assert_eq!(
+        remaining.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn select() -> TestResult {
     let client = client().await;
@@ -512,3 +1058,403 @@ async fn filter() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn cache_key() -> TestResult {
+    let client = client().await;
+
+    let key = client
+        .post()
+        .find_many(vec![
+            post::title::equals("Hi".to_string()),
+            post::published::equals(true),
+        ])
+        .take(5)
+        .cache_key();
+
+    let selection: serde_json::Value =
+        serde_json::from_str(key.strip_prefix("read:").unwrap()).unwrap();
+    assert_eq!(selection["name"], "findManyPost");
+    assert_eq!(selection["arguments"]["take"], 5);
+
+    // filters provided in a different order give the same key
+    let reordered = client
+        .post()
+        .find_many(vec![
+            post::published::equals(true),
+            post::title::equals("Hi".to_string()),
+        ])
+        .take(5)
+        .cache_key();
+    assert_eq!(key, reordered);
+
+    let different_filter = client
+        .post()
+        .find_many(vec![
+            post::title::equals("Hi".to_string()),
+            post::published::equals(false),
+        ])
+        .take(5)
+        .cache_key();
+    assert_ne!(key, different_filter);
+
+    let different_selection = client
+        .post()
+        .find_many(vec![
+            post::title::equals("Hi".to_string()),
+            post::published::equals(true),
+        ])
+        .take(5)
+        .select(post::select!({ id }))
+        .cache_key();
+    assert_ne!(key, different_selection);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate() -> TestResult {
+    let client = client().await;
+
+    let page = client.post().find_many(vec![]).paginate(1, 2).await?;
+    assert!(page.items.is_empty());
+    assert_eq!(page.total, 0);
+    assert_eq!(page.total_pages, 0);
+
+    for i in 1..=5 {
+        client
+            .post()
+            .create(format!("Post {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Asc))
+        .paginate(2, 2)
+        .await?;
+    assert_eq!(
+        page.items
+            .iter()
+            .map(|p| p.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Post 3", "Post 4"]
+    );
+    assert_eq!(page.total, 5);
+    assert_eq!(page.page, 2);
+    assert_eq!(page.per_page, 2);
+    assert_eq!(page.total_pages, 3);
+
+    // the last page is partially filled
+    let page = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Asc))
+        .paginate(3, 2)
+        .await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].title, "Post 5");
+
+    // the total only counts records matching the filters
+    let page = client
+        .post()
+        .find_many(vec![post::published::equals(true)])
+        .paginate(1, 2)
+        .await?;
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.total, 2);
+    assert_eq!(page.total_pages, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate_large_per_page() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("Post".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .paginate(1, i64::MAX)
+        .await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total_pages, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate_in_transaction() -> TestResult {
+    let client = client().await;
+
+    let page = client
+        ._transaction()
+        .run_and_roll_back(|client| async move {
+            client
+                .post()
+                .create("Post".to_string(), false, vec![])
+                .exec()
+                .await?;
+
+            client.post().find_many(vec![]).paginate(1, 2).await
+        })
+        .await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn ord_by_id() -> TestResult {
+    let client = client().await;
+
+    for i in 1..=3 {
+        client
+            .post()
+            .create(format!("Post {i}"), false, vec![])
+            .exec()
+            .await?;
+    }
+
+    let mut posts = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    posts.sort();
+
+    let mut ids = posts.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+    ids.sort();
+    assert_eq!(posts.iter().map(|p| p.id.clone()).collect::<Vec<_>>(), ids);
+
+    // records are compared by primary key, not by their other fields
+    let mut updated = posts[0].clone();
+    updated.title = "Updated".to_string();
+    assert_eq!(updated, posts[0]);
+
+    let set = posts.into_iter().chain([updated]).collect::<BTreeSet<_>>();
+    assert_eq!(set.len(), 3);
+
+    cleanup(client).await
+}
+
+/// Categories containing posts written by users with profiles,
+/// for filtering across three levels of relations.
+async fn create_nested_relations(client: &PrismaClient) -> Result<(), QueryError> {
+    let mut post_ids = vec![];
+
+    for (name, country) in [("Brendan", "Australia"), ("Oscar", "New Zealand")] {
+        let user = client
+            .user()
+            .create(name.to_string(), vec![])
+            .exec()
+            .await?;
+
+        client
+            .profile()
+            .create(
+                user::id::equals(user.id.clone()),
+                format!("{name}'s bio"),
+                country.to_string(),
+                vec![],
+            )
+            .exec()
+            .await?;
+
+        let post = client
+            .post()
+            .create(
+                format!("{name}'s post"),
+                true,
+                vec![post::author::connect(user::id::equals(user.id))],
+            )
+            .exec()
+            .await?;
+
+        post_ids.push(post.id);
+    }
+
+    for (name, posts) in [
+        ("Brendan only", vec![post_ids[0].clone()]),
+        ("Oscar only", vec![post_ids[1].clone()]),
+        ("Both", post_ids.clone()),
+    ] {
+        client
+            .category()
+            .create(
+                name.to_string(),
+                vec![category::posts::connect(
+                    posts.into_iter().map(post::id::equals).collect(),
+                )],
+            )
+            .exec()
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn names(categories: Vec<category::Data>) -> Vec<String> {
+    let mut names = categories.into_iter().map(|c| c.name).collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn filtering_nested_relations() -> TestResult {
+    let client = client().await;
+
+    create_nested_relations(&client).await?;
+
+    let from_australia = || {
+        post::author::is(vec![user::profile::is(vec![profile::country::equals(
+            "Australia".to_string(),
+        )])])
+    };
+
+    let some = client
+        .category()
+        .find_many(vec![category::posts::some(vec![from_australia()])])
+        .exec()
+        .await?;
+    assert_eq!(names(some), vec!["Both", "Brendan only"]);
+
+    let every = client
+        .category()
+        .find_many(vec![category::posts::every(vec![from_australia()])])
+        .exec()
+        .await?;
+    assert_eq!(names(every), vec!["Brendan only"]);
+
+    let none = client
+        .category()
+        .find_many(vec![category::posts::none(vec![from_australia()])])
+        .exec()
+        .await?;
+    assert_eq!(names(none), vec!["Oscar only"]);
+
+    let is_not = client
+        .category()
+        .find_many(vec![category::posts::some(vec![post::author::is_not(
+            vec![user::profile::is(vec![profile::country::equals(
+                "Australia".to_string(),
+            )])],
+        )])])
+        .exec()
+        .await?;
+    assert_eq!(names(is_not), vec!["Both", "Oscar only"]);
+
+    let profiles = client
+        .profile()
+        .find_many(vec![profile::user::is(vec![user::posts::some(vec![
+            post::categories::some(vec![category::name::equals("Oscar only".to_string())]),
+        ])])])
+        .exec()
+        .await?;
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0].country, "New Zealand");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn conditional_query_in_spawned_task() -> TestResult {
+    let client = Arc::new(client().await);
+
+    for name in ["Brendan", "Oscar", "Jamie"] {
+        client
+            .user()
+            .create(name.to_string(), vec![])
+            .exec()
+            .await?;
+    }
+
+    let spawn_query = |name_filter: Option<&'static str>, limit: Option<i64>| {
+        let client = client.clone();
+
+        tokio::spawn(async move {
+            let mut filters = vec![];
+
+            if let Some(name) = name_filter {
+                filters.push(user::name::contains(name.to_string()));
+            }
+
+            let mut query = client
+                .user()
+                .find_many(filters)
+                .order_by(user::name::order(SortOrder::Asc));
+
+            if let Some(limit) = limit {
+                query = query.take(limit);
+            }
+
+            // the builder is held across an await point before being executed
+            tokio::task::yield_now().await;
+
+            query.exec().await
+        })
+    };
+
+    let filtered = spawn_query(Some("a"), None).await.unwrap()?;
+    assert_eq!(
+        filtered.into_iter().map(|u| u.name).collect::<Vec<_>>(),
+        vec!["Brendan", "Jamie", "Oscar"]
+    );
+
+    let limited = spawn_query(Some("a"), Some(2)).await.unwrap()?;
+    assert_eq!(
+        limited.into_iter().map(|u| u.name).collect::<Vec<_>>(),
+        vec!["Brendan", "Jamie"]
+    );
+
+    let all = spawn_query(None, None).await.unwrap()?;
+    assert_eq!(all.len(), 3);
+
+    cleanup(Arc::try_unwrap(client).expect("spawned tasks have finished")).await
+}
+
+#[test]
+fn unindexed_filters() {
+    use prisma_client_rust::{IndexDescriptor, IndexKind};
+
+    assert_eq!(
+        post::INDEXES,
+        &[
+            IndexDescriptor {
+                kind: IndexKind::PrimaryKey,
+                fields: &["id"],
+            },
+            IndexDescriptor {
+                kind: IndexKind::Unique,
+                fields: &["title", "author_id"],
+            },
+            IndexDescriptor {
+                kind: IndexKind::Normal,
+                fields: &["published", "created_at"],
+            },
+        ]
+    );
+
+    assert!(post::title::INDEXED);
+    assert!(post::created_at::INDEXED);
+    assert!(!post::views::INDEXED);
+
+    assert_eq!(
+        post::unindexed_filters(&[
+            post::title::equals("Title".to_string()),
+            post::published::equals(true),
+            or![post::views::gt(1), post::author_id::equals(None)],
+        ]),
+        ["views", "author_id"]
+    );
+}
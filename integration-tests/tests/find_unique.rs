@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use prisma_client_rust::HasId;
+
 use crate::{db::*, utils::*};
 
 #[tokio::test]
@@ -88,6 +92,57 @@ async fn compound() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn compound_unique_input() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Title".to_string(),
+            false,
+            vec![post::author::connect(user::id::equals(user.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_unique(
+            post::TitleAuthorIdCompoundUniqueInput {
+                title: post.title.clone(),
+                author_id: user.id.clone(),
+            }
+            .into(),
+        )
+        .exec()
+        .await?;
+    assert_eq!(found.map(|p| p.id), Some(post.id.clone()));
+
+    // the key of an existing record can be taken from its data
+    let input = post::TitleAuthorIdCompoundUniqueInput::try_from(&post).unwrap();
+    assert_eq!(input.author_id, user.id);
+
+    // author_id is optional, so a post without an author can't be identified this way
+    let orphan = client
+        .post()
+        .create("Orphan".to_string(), false, vec![])
+        .exec()
+        .await?;
+    assert_eq!(
+        post::TitleAuthorIdCompoundUniqueInput::try_from(&orphan).unwrap_err(),
+        prisma_client_rust::NullUniqueFieldError("author_id")
+    );
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn no_match() -> TestResult {
     let client = client().await;
@@ -101,3 +156,138 @@ async fn no_match() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn exec_raw() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_unique(post::id::equals(post.id.clone()))
+        .exec_raw()
+        .await?;
+
+    assert_eq!(found["id"], post.id);
+    assert_eq!(found["title"], "My post title!");
+    assert_eq!(found["published"], false);
+
+    let missing = client
+        .post()
+        .find_unique(post::id::equals("missing".to_string()))
+        .exec_raw()
+        .await?;
+
+    assert!(missing.is_null());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn borrowed_args() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Title".to_string(),
+            false,
+            vec![post::author::connect(user::id::equals_ref(&user.id))],
+        )
+        .exec()
+        .await?;
+
+    let owned = client
+        .post()
+        .find_unique(post::id::equals(post.id.clone()))
+        .exec()
+        .await?;
+    let borrowed = client
+        .post()
+        .find_unique(post::id::equals_ref(&post.id))
+        .exec()
+        .await?;
+    let str = client
+        .post()
+        .find_unique(post::id::equals_ref(post.id.as_str()))
+        .exec()
+        .await?;
+    assert_eq!(owned.unwrap().id, post.id);
+    assert_eq!(borrowed.unwrap().id, post.id);
+    assert_eq!(str.unwrap().id, post.id);
+
+    // equals still takes the field's type, so its argument can be inferred
+    let into = client
+        .post()
+        .find_unique(post::id::equals(post.id.as_str().into()))
+        .exec()
+        .await?;
+    let default = client
+        .post()
+        .find_unique(post::id::equals(Default::default()))
+        .exec()
+        .await?;
+    assert_eq!(into.unwrap().id, post.id);
+    assert!(default.is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn id_accessor() -> TestResult {
+    let client = client().await;
+
+    // generic over any model, keyed by its primary key
+    fn by_id<T: HasId>(records: Vec<T>) -> HashMap<T::Id, T>
+    where
+        T::Id: std::hash::Hash + Eq,
+    {
+        records.into_iter().map(|r| (r.id(), r)).collect()
+    }
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    let user_id: user::Id = user.id();
+    assert_eq!(user_id, user.id);
+
+    client
+        .file_path()
+        .create_many(
+            (0..2)
+                .map(|id| {
+                    file_path::create_unchecked(id, format!("Path {id}"), user.id.clone(), vec![])
+                })
+                .collect(),
+        )
+        .exec()
+        .await?;
+
+    // compound primary keys are tuples in the order of `@@id`
+    let file_paths = by_id(client.file_path().find_many(vec![]).exec().await?);
+    let id: file_path::Id = (user.id.clone(), 1);
+    assert_eq!(file_paths[&id].path, "Path 1");
+
+    let found = client
+        .file_path()
+        .find_unique(file_path::user_id_local_id(id.0, id.1))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(found.id(), (user.id, 1));
+
+    cleanup(client).await
+}
@@ -0,0 +1,56 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn read_files(path: &Path, files: &mut BTreeMap<PathBuf, Vec<u8>>) {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).unwrap() {
+            read_files(&entry.unwrap().path(), files);
+        }
+    } else {
+        files.insert(path.to_path_buf(), fs::read(path).unwrap());
+    }
+}
+
+/// Regenerates the client these tests use and returns the contents of every file written.
+fn generate() -> BTreeMap<PathBuf, Vec<u8>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = root.join("tests/db");
+
+    // the checksum inside the output would otherwise skip generation
+    fs::remove_dir_all(&output).unwrap();
+
+    let status = Command::new(env!("CARGO"))
+        .args(["prisma", "generate"])
+        .current_dir(root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut files = BTreeMap::new();
+    read_files(&output, &mut files);
+    read_files(&root.join("tests/db.schema.json"), &mut files);
+    files
+}
+
+#[test]
+fn deterministic() {
+    let first = generate();
+    let second = generate();
+
+    assert_eq!(
+        first.keys().collect::<Vec<_>>(),
+        second.keys().collect::<Vec<_>>()
+    );
+
+    for (path, contents) in &first {
+        assert!(
+            &second[path] == contents,
+            "{} differs between generations",
+            path.display()
+        );
+    }
+}
@@ -142,3 +142,93 @@ async fn external_selection_args() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn relation_counts() -> TestResult {
+    let client = client().await;
+
+    let author = client
+        .user()
+        .create("Author".to_string(), vec![])
+        .exec()
+        .await?;
+    let fans = client
+        ._batch((
+            client.user().create("Fan 1".to_string(), vec![]),
+            client.user().create("Fan 2".to_string(), vec![]),
+        ))
+        .await?;
+    let rust = client
+        .category()
+        .create("rust".to_string(), vec![])
+        .exec()
+        .await?;
+
+    for (title, favouriters, categorised) in [
+        ("Unnoticed", vec![], false),
+        ("Popular", vec![&fans.0, &fans.1], true),
+        ("Liked", vec![&fans.0], true),
+    ] {
+        let mut params = vec![
+            post::author::connect(user::id::equals(author.id.clone())),
+            post::favouriters::connect(
+                favouriters
+                    .into_iter()
+                    .map(|fan| user::id::equals(fan.id.clone()))
+                    .collect(),
+            ),
+        ];
+        if categorised {
+            params.push(post::categories::connect(vec![category::id::equals(
+                rust.id,
+            )]));
+        }
+
+        client
+            .post()
+            .create(title.to_string(), true, params)
+            .exec()
+            .await?;
+    }
+
+    let author = client
+        .user()
+        .find_unique(user::id::equals(author.id))
+        .include(user::include!({
+            // ordered by how many users have favourited each post
+            posts(vec![])
+                .order_by(post::favouriters::order(vec![user::_count::order(SortOrder::Desc)])): include {
+                _count: select { favouriters categories }
+                categories: include {
+                    _count: select { posts }
+                }
+            }
+            _count: select { posts favourite_posts }
+        }))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(
+        author
+            .posts
+            .iter()
+            .map(|p| p.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Popular", "Liked", "Unnoticed"]
+    );
+    assert_eq!(author.posts[0]._count.favouriters, 2);
+    assert_eq!(author.posts[0]._count.categories, 1);
+    assert_eq!(author.posts[0].categories[0]._count.posts, 2);
+    assert_eq!(author.posts[2]._count.favouriters, 0);
+
+    assert_eq!(author._count.posts, 3);
+    assert_eq!(author._count.favourite_posts, 0);
+    // counts are serialized with the schema's field names
+    assert_eq!(
+        serde_json::to_value(&author._count).unwrap(),
+        serde_json::json!({ "posts": 3, "favouritePosts": 0 })
+    );
+
+    cleanup(client).await
+}
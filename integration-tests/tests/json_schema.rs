@@ -0,0 +1,38 @@
+use serde_json::{json, Value};
+
+#[test]
+fn models() {
+    let schema: Value = serde_json::from_str(include_str!("db.schema.json")).unwrap();
+    let defs = &schema["$defs"];
+
+    let post = &defs["Post"];
+    assert_eq!(post["properties"]["title"], json!({ "type": "string" }));
+    assert_eq!(
+        post["properties"]["desc"],
+        json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+    );
+    assert_eq!(
+        post["properties"]["created_at"],
+        json!({ "type": "string", "format": "date-time" })
+    );
+    assert_eq!(
+        post["properties"]["author"],
+        json!({ "anyOf": [{ "$ref": "#/$defs/User" }, { "type": "null" }] })
+    );
+
+    let required = post["required"].as_array().unwrap();
+    assert!(required.contains(&json!("title")));
+    assert!(required.contains(&json!("desc")));
+    assert!(!required.contains(&json!("author")));
+
+    assert_eq!(
+        defs["User"]["properties"]["posts"],
+        json!({
+            "anyOf": [
+                { "type": "array", "items": { "$ref": "#/$defs/Post" } },
+                { "type": "null" }
+            ]
+        })
+    );
+    assert_eq!(defs["Types"]["properties"]["status"], json!({}));
+}
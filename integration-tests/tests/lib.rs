@@ -23,18 +23,26 @@ async fn aaaa_run_migrations() -> TestResult {
 
 mod batch;
 // mod callbacks;
+mod change_feed;
+mod client;
 mod count;
 mod create;
 mod create_many;
 mod delete;
 mod delete_many;
+mod field_codec;
 mod find_first;
 mod find_many;
 mod find_unique;
+mod generate;
 mod include;
+mod json_schema;
+mod metrics;
 mod mock;
 mod partial;
 mod raw;
+mod repository;
+mod reset;
 mod select;
 mod specta;
 mod types;
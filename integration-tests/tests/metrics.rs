@@ -0,0 +1,64 @@
+use prisma_client_rust::prisma_errors::query_engine::UniqueKeyViolation;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn counts_queries_and_errors() -> TestResult {
+    let client = client().await;
+
+    let before = client._metrics();
+
+    client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::id::set("metrics".to_string())],
+        )
+        .exec()
+        .await?;
+
+    client.user().find_many(vec![]).exec().await?;
+
+    let error = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::id::set("metrics".to_string())],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(error.is_prisma_error::<UniqueKeyViolation>());
+
+    let after = client._metrics();
+
+    assert_eq!(after.total_queries() - before.total_queries(), 3);
+    assert_eq!(after.queries.get("createOneUser"), Some(&2));
+    assert_eq!(after.queries.get("findManyUser"), Some(&1));
+    assert_eq!(after.errors.get("P2002"), Some(&1));
+    assert_eq!(after.duration.count - before.duration.count, 3);
+    assert_eq!(after.pool.in_flight, 0);
+    assert!(after.pool.max_in_flight >= 1);
+
+    let exported = after.to_prometheus();
+    assert!(exported.contains("prisma_client_queries_total{operation=\"findManyUser\"} 1"));
+    assert!(exported.contains("prisma_client_errors_total{kind=\"P2002\"} 1"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn shared_with_transactions() -> TestResult {
+    let client = client().await;
+
+    let before = client._metrics().total_queries();
+
+    client
+        ._transaction()
+        .run(|tx| async move { tx.user().find_many(vec![]).exec().await })
+        .await?;
+
+    assert_eq!(client._metrics().total_queries() - before, 1);
+
+    cleanup(client).await
+}
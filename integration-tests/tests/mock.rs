@@ -79,3 +79,24 @@ async fn delete_many() -> TestResult {
 }
 
 // TODO: Errors
+
+#[test]
+fn mock_data() {
+    let post = post::Data {
+        title: "Hello".to_string(),
+        ..post::Data::mock()
+    };
+
+    assert_eq!(post.title, "Hello");
+    assert_eq!(post.views, 0);
+    assert!(!post.published);
+    assert!(post.desc.is_none());
+    assert!(post.author().is_err());
+
+    // the overridden type of `status` has no placeholder, so it's passed in
+    let types = types::Data::mock(Status::Archived);
+
+    assert_eq!(types.status, Status::Archived);
+    assert_eq!(types.string, "");
+    assert_eq!(types.datetime.timestamp(), 0);
+}
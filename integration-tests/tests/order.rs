@@ -1,4 +1,4 @@
-use prisma_client_rust::or;
+use prisma_client_rust::{or, QueryError};
 
 use crate::{db::*, utils::*};
 
@@ -95,3 +95,32 @@ async fn relation() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn collated_unsupported_by_bundled_engine() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order_by_collated(SortOrder::Asc, "NOCASE"))
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, QueryError::UnsupportedCollatedOrder));
+
+    // orderings nested in a relation's fetch are rejected too
+    let error = client
+        .user()
+        .find_many(vec![])
+        .with(
+            user::posts::fetch(vec![])
+                .order_by(post::title::order_by_collated(SortOrder::Asc, "NOCASE")),
+        )
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, QueryError::UnsupportedCollatedOrder));
+
+    cleanup(client).await
+}
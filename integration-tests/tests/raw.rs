@@ -1,4 +1,7 @@
-use prisma_client_rust::{prisma_models::PrismaValue, raw};
+use prisma_client_rust::{
+    prisma_errors::query_engine::RawQueryFailed, prisma_models::PrismaValue, raw, QueryError, Raw,
+};
+use serde::Deserialize;
 
 use crate::{db::*, utils::*};
 
@@ -93,4 +96,285 @@ async fn execute_raw_no_result() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn execute_raw_insert_and_delete() -> TestResult {
+    let client = client().await;
+
+    let inserted: i64 = client
+        ._execute_raw(raw!(
+            "INSERT INTO Post (id, title, published, updated_at) \
+             VALUES ({}, {}, {}, CURRENT_TIMESTAMP), ({}, {}, {}, CURRENT_TIMESTAMP)",
+            PrismaValue::String("a".to_string()),
+            PrismaValue::String("Post A".to_string()),
+            PrismaValue::Boolean(false),
+            PrismaValue::String("b".to_string()),
+            PrismaValue::String("Post B".to_string()),
+            PrismaValue::Boolean(true)
+        ))
+        .exec()
+        .await?;
+    assert_eq!(inserted, 2);
+
+    let deleted: i64 = client._execute_raw(raw!("DELETE FROM Post")).exec().await?;
+    assert_eq!(deleted, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn execute_raw_invalid_statement() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        ._execute_raw(raw!("UPDATE NotATable SET title = 'title'"))
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(error.is_prisma_error::<RawQueryFailed>());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn execute_raw_missing_parameter() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        ._execute_raw(Raw::new(
+            "UPDATE Post SET title = $2 WHERE id = $1",
+            vec![PrismaValue::String("id".to_string())],
+        ))
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.inner(),
+        QueryError::RawParameters {
+            expected: 2,
+            provided: 1
+        }
+    ));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_raw_positional_placeholders() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let result: Vec<post::Data> = client
+        ._query_raw(raw!(
+            "SELECT * FROM Post WHERE id = $1 AND (title = $2 OR id = $1)",
+            PrismaValue::String(post.id.clone()),
+            PrismaValue::String("My post title!".to_string())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(&result[0].id, &post.id);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_raw_question_placeholders() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let result: Vec<post::Data> = client
+        ._query_raw(raw!(
+            "SELECT * FROM Post WHERE id = ? AND title != '?'",
+            PrismaValue::String(post.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(&result[0].id, &post.id);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_raw_placeholders_in_comments() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let result: Vec<post::Data> = client
+        ._query_raw(raw!(
+            "SELECT * FROM Post -- not a placeholder: ?\n WHERE /* nor {} */ id = ?",
+            PrismaValue::String(post.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(&result[0].id, &post.id);
+
+    cleanup(client).await
+}
+
+#[test]
+fn convert_skips_comments_and_dollar_quotes() {
+    let (sql, values) = raw!(
+        "SELECT $body$ {} $1 $body$, $$ ? $$ /* $2 */ FROM t -- {}\nWHERE id = {}",
+        PrismaValue::Int(1)
+    )
+    .convert("postgresql");
+
+    assert_eq!(
+        sql,
+        "SELECT $body$ {} $1 $body$, $$ ? $$ /* $2 */ FROM t -- {}\nWHERE id = $1"
+    );
+    assert_eq!(values.len(), 1);
+
+    let query = "SELECT '$1', \"{}\", `?` FROM t WHERE id = $1";
+
+    let (sql, values) = Raw::new(query, vec![PrismaValue::Int(1)]).convert("sqlite");
+    assert_eq!(sql, "SELECT '$1', \"{}\", `?` FROM t WHERE id = ?");
+    assert_eq!(values.len(), 1);
+
+    assert!(matches!(
+        Raw::new(query, vec![]).try_convert("sqlite"),
+        Err(QueryError::RawParameters {
+            expected: 1,
+            provided: 0
+        })
+    ));
+}
+
+#[tokio::test]
+async fn explain_raw() -> TestResult {
+    let client = client().await;
+
+    let plan = client
+        ._explain_raw(raw!(
+            "SELECT * FROM Post WHERE id = {}",
+            PrismaValue::String("123".to_string())
+        ))
+        .exec()
+        .await?;
+
+    assert!(!plan.rows.is_empty());
+    assert!(plan.text().contains("Post"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn execute_raw_ddl() -> TestResult {
+    let client = client().await;
+
+    let results = client
+        ._execute_raw_ddl(vec![
+            raw!("CREATE TABLE DdlTest (id TEXT PRIMARY KEY, title TEXT NOT NULL)"),
+            raw!("CREATE INDEX DdlTest_title_idx ON DdlTest (title)"),
+            raw!("INSERT INTO DdlTest (id, title) VALUES ('1', 'title')"),
+            raw!("DROP TABLE DdlTest"),
+        ])
+        .exec()
+        .await?;
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[2], 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn execute_raw_ddl_rollback() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        ._execute_raw_ddl(vec![
+            raw!("CREATE TABLE DdlRollback (id TEXT PRIMARY KEY)"),
+            raw!("CREATE INDEX DdlRollback_idx ON NotATable (id)"),
+        ])
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.index, 1);
+    assert!(error.applied.is_empty());
+    assert!(error.source.is_prisma_error::<RawQueryFailed>());
+
+    #[derive(Deserialize)]
+    struct Tables {
+        count: i64,
+    }
+
+    let tables: Vec<Tables> = client
+        ._query_raw(raw!(
+            "SELECT COUNT(*) AS count FROM sqlite_master WHERE name = 'DdlRollback'"
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(tables[0].count, 0);
+
+    cleanup(client).await
+}
+
 // query_first?
+
+#[tokio::test]
+async fn computed_fields() -> TestResult {
+    let client = client().await;
+
+    let brendan = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    let oscar = client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Hi".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(brendan.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    let mut computed = client
+        .user()
+        .computed(vec![brendan.id.clone(), oscar.id.clone()])
+        .exec()
+        .await?;
+    computed.sort_by_key(|c| c.post_count);
+
+    assert_eq!(computed.len(), 2);
+    assert_eq!(computed[0].id, oscar.id);
+    assert_eq!(computed[0].post_count, Some(0));
+    assert_eq!(computed[1].id, brendan.id);
+    assert_eq!(computed[1].post_count, Some(1));
+
+    assert!(client.user().computed(vec![]).exec().await?.is_empty());
+
+    cleanup(client).await
+}
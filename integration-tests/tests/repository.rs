@@ -0,0 +1,122 @@
+use prisma_client_rust::{futures::future::BoxFuture, QueryError};
+
+use crate::{db::*, utils::*};
+
+async fn rename_user<R: user::Repository>(
+    repo: &R,
+    id: String,
+    name: String,
+) -> Result<Option<user::Data>, QueryError> {
+    if repo.count(vec![user::id::equals(id.clone())]).await? == 0 {
+        return Ok(None);
+    }
+
+    repo.update(user::id::equals(id), vec![user::name::set(name)])
+        .await
+        .map(Some)
+}
+
+#[tokio::test]
+async fn client_implementation() -> TestResult {
+    let client = client().await;
+
+    let user =
+        user::Repository::create(&client, user::create("Brendan".to_string(), vec![])).await?;
+
+    let renamed = rename_user(&client, user.id.clone(), "Oscar".to_string())
+        .await?
+        .unwrap();
+    assert_eq!(renamed.name, "Oscar");
+
+    let found = user::Repository::find_unique(&client, user::id::equals(user.id.clone()))
+        .await?
+        .unwrap();
+    assert_eq!(found.name, "Oscar");
+
+    user::Repository::delete(&client, user::id::equals(user.id.clone())).await?;
+    assert_eq!(user::Repository::find_many(&client, vec![]).await?.len(), 0);
+
+    cleanup(client).await
+}
+
+/// A repository that doesn't store anything, so its writes return a record that was never saved.
+struct NoUsers;
+
+impl NoUsers {
+    fn unsaved(name: String) -> user::Data {
+        serde_json::from_value(serde_json::json!({
+            "id": "",
+            "name": name,
+            "email": null,
+            "createdAt": "1970-01-01T00:00:00+00:00",
+            "underscored_": null,
+        }))
+        .unwrap()
+    }
+}
+
+impl user::Repository for NoUsers {
+    fn find_unique(
+        &self,
+        _where: user::UniqueWhereParam,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<Option<user::Data>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn find_first(
+        &self,
+        _where: Vec<user::WhereParam>,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<Option<user::Data>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn find_many(
+        &self,
+        _where: Vec<user::WhereParam>,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<Vec<user::Data>>> {
+        Box::pin(async { Ok(vec![]) })
+    }
+
+    fn count(
+        &self,
+        _where: Vec<user::WhereParam>,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<i64>> {
+        Box::pin(async { Ok(0) })
+    }
+
+    fn create(
+        &self,
+        create: user::Create,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<user::Data>> {
+        Box::pin(async move { Ok(Self::unsaved(create.name)) })
+    }
+
+    fn update(
+        &self,
+        _where: user::UniqueWhereParam,
+        _params: Vec<user::SetParam>,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<user::Data>> {
+        Box::pin(async { Ok(Self::unsaved(String::new())) })
+    }
+
+    fn delete(
+        &self,
+        _where: user::UniqueWhereParam,
+    ) -> BoxFuture<'_, prisma_client_rust::Result<user::Data>> {
+        Box::pin(async { Ok(Self::unsaved(String::new())) })
+    }
+}
+
+#[tokio::test]
+async fn custom_implementation() -> TestResult {
+    let renamed = rename_user(&NoUsers, "123".to_string(), "Oscar".to_string()).await?;
+
+    assert!(renamed.is_none());
+
+    let created =
+        user::Repository::create(&NoUsers, user::create("Brendan".to_string(), vec![])).await?;
+    assert_eq!(created.name, "Brendan");
+    assert_eq!(user::Repository::count(&NoUsers, vec![]).await?, 0);
+
+    Ok(())
+}
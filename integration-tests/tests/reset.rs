@@ -0,0 +1,45 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn reset() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // references the user, so it has to be deleted first
+    let post = client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(user.id.clone()))],
+        )
+        .exec()
+        .await?;
+    client
+        .category()
+        .create(
+            "Prisma".to_string(),
+            vec![category::posts::connect(vec![post::id::equals(post.id)])],
+        )
+        .exec()
+        .await?;
+    client
+        .file_path()
+        .create_unchecked(0, "/".to_string(), user.id, vec![])
+        .exec()
+        .await?;
+
+    crate::db::reset(&client).await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+    assert_eq!(client.post().count(vec![]).exec().await?, 0);
+    assert_eq!(client.category().count(vec![]).exec().await?, 0);
+    assert_eq!(client.file_path().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
@@ -167,3 +167,187 @@ async fn external_selection_arguments() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn runtime_selection() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await?;
+
+    let fields = vec![user::id::NAME, user::email::NAME];
+
+    let users = client
+        .user()
+        .find_many(vec![])
+        .with(user::posts::fetch(vec![]))
+        .with_selection(&fields)
+        .exec()
+        .await?;
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(
+        users[0].get::<String>(user::id::NAME),
+        Some(user.id.clone())
+    );
+    assert_eq!(
+        users[0].get::<String>(user::email::NAME),
+        Some("brendan@example.com".to_string())
+    );
+    assert!(!users[0].contains(user::name::NAME));
+    assert_eq!(
+        users[0]
+            .get::<Vec<post::Data>>(user::posts::NAME)
+            .map(|p| p.len()),
+        Some(0)
+    );
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .with_selection(&[user::name::NAME])
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(
+        found.get::<String>(user::name::NAME),
+        Some("Brendan".to_string())
+    );
+    assert_eq!(found.get::<String>(user::id::NAME), None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn all_scalars() -> TestResult {
+    let client = client().await;
+
+    user::select!(user_scalars {
+        #scalars
+        posts: select {
+            #scalars
+        }
+    });
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Title".to_string(),
+            false,
+            vec![post::author::connect(user::id::equals_ref(&user.id))],
+        )
+        .exec()
+        .await?;
+
+    let found: user_scalars::Data = client
+        .user()
+        .find_unique(user::id::equals_ref(&user.id))
+        .select(user_scalars::select())
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.id, user.id);
+    assert_eq!(found.name, user.name);
+    assert_eq!(found.email, user.email);
+    assert_eq!(found.created_at, user.created_at);
+    assert_eq!(found.posts.len(), 1);
+    assert_eq!(found.posts[0].title, "Title");
+    assert_eq!(found.posts[0].author_id, Some(user.id.clone()));
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals_ref(&user.id))
+        .select(user::select!({ #scalars profile }))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.name, user.name);
+    assert!(found.profile.is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn computed_fields() -> TestResult {
+    let client = client().await;
+
+    let brendan = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Hi".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(brendan.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    // without the id, which is only selected to match the computed values
+    let users = client
+        .user()
+        .find_many(vec![])
+        .order_by(user::name::order(SortOrder::Asc))
+        .select(user::select!({ name post_count }))
+        .exec()
+        .await?;
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].name, "Brendan");
+    assert_eq!(users[0].post_count, Some(1));
+    assert_eq!(users[1].name, "Oscar");
+    assert_eq!(users[1].post_count, Some(0));
+    assert_eq!(
+        serde_json::to_value(&users[0]).unwrap(),
+        serde_json::json!({ "name": "Brendan", "post_count": 1 })
+    );
+
+    user::select!(user_post_count { id post_count posts: select { title } });
+
+    let found: user_post_count::Data = client
+        .user()
+        .find_unique(user::id::equals_ref(&brendan.id))
+        .select(user_post_count::select())
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.id, brendan.id);
+    assert_eq!(found.post_count, Some(1));
+    assert_eq!(found.posts[0].title, "Hi");
+
+    let missing = client
+        .user()
+        .find_unique(user::id::equals("missing".to_string()))
+        .select(user::select!({ post_count }))
+        .exec()
+        .await?;
+
+    assert!(missing.is_none());
+
+    cleanup(client).await
+}
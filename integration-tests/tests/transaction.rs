@@ -145,6 +145,51 @@ async fn timeout() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn dropped() -> TestResult {
+    let client = client().await;
+
+    let run = client
+        ._transaction()
+        .with_timeout(60000)
+        .run(|client| async move {
+            client
+                .user()
+                .create("Brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            futures::future::pending::<Result<(), QueryError>>().await
+        });
+
+    assert!(tokio::time::timeout(Duration::from_millis(500), run)
+        .await
+        .is_err());
+
+    // lets the rollback spawned when `run` was dropped release the write lock,
+    // which would otherwise be held until the transaction times out
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let names = client
+        .user()
+        .find_many(vec![])
+        .exec()
+        .await?
+        .into_iter()
+        .map(|user| user.name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, vec!["Oscar".to_string()]);
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn batch() -> TestResult {
     let client = client().await;
@@ -166,6 +211,49 @@ async fn batch() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn test_transaction() -> TestResult {
+    let client = client().await;
+
+    let (user, count) = client
+        ._test_transaction(|client| async move {
+            let user = client
+                .user()
+                .create("brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            // writes are visible inside the transaction
+            let count = client.user().count(vec![]).exec().await?;
+
+            Ok::<_, QueryError>((user, count))
+        })
+        .await?;
+
+    assert_eq!(&user.name, "brendan");
+    assert_eq!(count, 1);
+    assert!(client.user().find_many(vec![]).exec().await?.is_empty());
+
+    // errors are returned, and rolled back like successes
+    let result = client
+        ._transaction()
+        .run_and_roll_back(|client| async move {
+            client
+                .user()
+                .create("oscar".to_string(), vec![])
+                .exec()
+                .await?;
+
+            Err::<(), _>(QueryError::Deserialize("failed".to_string()))
+        })
+        .await;
+
+    assert!(matches!(result, Err(QueryError::Deserialize(_))));
+    assert!(client.user().find_many(vec![]).exec().await?.is_empty());
+
+    cleanup(client).await
+}
+
 // Imperative
 
 #[tokio::test]
@@ -256,3 +344,64 @@ async fn rollback() -> TestResult {
 
     cleanup(client).await
 }
+
+// Identity map
+
+#[tokio::test]
+async fn identity_map() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let before = client._metrics();
+
+    let (first, second, updated) = client
+        ._transaction()
+        .with_identity_map()
+        .run(|client| async move {
+            let first = client
+                .user()
+                .find_unique(user::id::equals(user.id.clone()))
+                .exec()
+                .await?;
+            let second = client
+                .user()
+                .find_unique(user::id::equals(user.id.clone()))
+                .exec()
+                .await?;
+
+            client
+                .user()
+                .update(
+                    user::id::equals(user.id.clone()),
+                    vec![user::name::set("oscar".to_string())],
+                )
+                .exec()
+                .await?;
+
+            client
+                .user()
+                .find_unique(user::id::equals(user.id.clone()))
+                .exec()
+                .await
+                .map(|updated| (first, second, updated))
+        })
+        .await?;
+
+    let after = client._metrics();
+
+    assert_eq!(first.unwrap().name, "brendan");
+    assert_eq!(second.unwrap().name, "brendan");
+    assert_eq!(updated.unwrap().name, "oscar");
+    assert_eq!(
+        after.queries.get("findUniqueUser").copied().unwrap_or(0)
+            - before.queries.get("findUniqueUser").copied().unwrap_or(0),
+        2
+    );
+
+    cleanup(client).await
+}
@@ -1,6 +1,10 @@
 use prisma_client_rust::{
-    bigdecimal::BigDecimal, prisma_errors::query_engine::RecordRequiredButNotFound,
+    bigdecimal::BigDecimal,
+    chrono::{DateTime, Duration},
+    prisma_errors::query_engine::RecordRequiredButNotFound,
+    prisma_models::PrismaValue,
     queries::QueryError,
+    raw,
 };
 
 use crate::{db::*, utils::*};
@@ -22,3 +26,203 @@ async fn decimal() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn bigint() -> TestResult {
+    let client = client().await;
+
+    for (id, value) in [(1, i64::MAX), (2, i64::MAX - 1), (3, i64::MIN)] {
+        let record = client
+            .types()
+            .create(vec![types::id::set(id), types::bigint::set(value)])
+            .exec()
+            .await?;
+        assert_eq!(record.bigint, value);
+    }
+
+    let found = client
+        .types()
+        .find_many(vec![types::bigint::gt(i64::MAX - 2)])
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 2);
+
+    // engines speaking the JSON protocol send BigInt values as strings
+    let mut json = serde_json::to_value(&found[0]).unwrap();
+    assert_eq!(json["bigint"], serde_json::json!(found[0].bigint));
+
+    json["bigint"] = serde_json::json!(i64::MAX.to_string());
+    let record: types::Data = serde_json::from_value(json).unwrap();
+    assert_eq!(record.bigint, i64::MAX);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn in_and_not_in_filters() -> TestResult {
+    let client = client().await;
+
+    let datetime = DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z").unwrap();
+
+    for i in 1..=3 {
+        client
+            .types()
+            .create(vec![
+                types::id::set(i),
+                types::string::set(format!("string {i}")),
+                types::integer::set(i * 10),
+                types::float_::set(i as f64 / 2.0),
+                types::datetime::set(datetime + Duration::days(i as i64)),
+                types::decimal::set(Some(BigDecimal::from(i))),
+            ])
+            .exec()
+            .await?;
+    }
+
+    let ids = |records: Vec<types::Data>| records.into_iter().map(|r| r.id).collect::<Vec<_>>();
+
+    let found = client
+        .types()
+        .find_many(vec![types::id::in_vec(vec![1, 3])])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![1, 3]);
+
+    let found = client
+        .types()
+        .find_many(vec![types::string::not_in_vec(
+            vec!["string 1".to_string()],
+        )])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![2, 3]);
+
+    let found = client
+        .types()
+        .find_many(vec![types::integer::in_vec(vec![20])])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![2]);
+
+    let found = client
+        .types()
+        .find_many(vec![types::float_::in_vec(vec![0.5, 1.5])])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![1, 3]);
+
+    let found = client
+        .types()
+        .find_many(vec![types::datetime::not_in_vec(vec![
+            datetime + Duration::days(1),
+        ])])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![2, 3]);
+
+    let found = client
+        .types()
+        .find_many(vec![types::decimal::in_vec(vec![BigDecimal::from(2)])])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec![2]);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn unsupported_type_override() -> TestResult {
+    let client = client().await;
+
+    client
+        ._execute_raw(raw!(
+            "INSERT INTO Unsupported (unsupprted) VALUES ({})",
+            PrismaValue::String("value".to_string())
+        ))
+        .exec()
+        .await?;
+
+    #[derive(serde::Deserialize)]
+    struct Row {
+        unsupprted: unsupported::unsupprted::Type,
+    }
+
+    let rows: Vec<Row> = client
+        ._query_raw(raw!("SELECT unsupprted FROM Unsupported"))
+        .exec()
+        .await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].unsupprted, "value");
+
+    client
+        ._execute_raw(raw!("DELETE FROM Unsupported"))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn string_type_override() -> TestResult {
+    let client = client().await;
+
+    let default = client
+        .types()
+        .create(vec![types::id::set(1)])
+        .exec()
+        .await?;
+
+    assert_eq!(default.status, Status::Active);
+
+    let archived = client
+        .types()
+        .create(vec![
+            types::id::set(2),
+            types::status::set(Status::Archived),
+        ])
+        .exec()
+        .await?;
+
+    assert_eq!(archived.status, Status::Archived);
+
+    let found = client
+        .types()
+        .find_many(vec![types::status::equals(Status::Archived)])
+        .exec()
+        .await?;
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn type_override_serialize_error() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        .types()
+        .create(vec![
+            types::id::set(1),
+            types::status::set(Status::Invalid(1)),
+        ])
+        .exec()
+        .await;
+
+    assert!(matches!(result, Err(QueryError::SerializeOverride(_))));
+
+    let result = client
+        .types()
+        .find_many(vec![types::status::equals(Status::Invalid(1))])
+        .exec()
+        .await;
+
+    assert!(matches!(result, Err(QueryError::SerializeOverride(_))));
+
+    // the query isn't sent to the engine
+    assert_eq!(client.types().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
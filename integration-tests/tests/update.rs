@@ -72,8 +72,6 @@ async fn query() -> TestResult {
     cleanup(client).await
 }
 
-// TODO: update with nested create & delete/disconnect
-
 #[tokio::test]
 async fn disconnect() -> TestResult {
     let client = client().await;
@@ -132,6 +130,251 @@ async fn disconnect() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn set_and_disconnect_many() -> TestResult {
+    let client = client().await;
+
+    let (rust, prisma, sqlite) = client
+        ._batch((
+            client.category().create("rust".to_string(), vec![]),
+            client.category().create("prisma".to_string(), vec![]),
+            client.category().create("sqlite".to_string(), vec![]),
+        ))
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Tagged".to_string(),
+            true,
+            vec![post::categories::connect(vec![
+                category::id::equals(rust.id),
+                category::id::equals(prisma.id),
+            ])],
+        )
+        .exec()
+        .await?;
+
+    let updated = client
+        .post()
+        .update(
+            post::id::equals(post.id.clone()),
+            vec![post::categories::set(vec![
+                category::id::equals(prisma.id),
+                category::id::equals(sqlite.id),
+            ])],
+        )
+        .with(post::categories::fetch(vec![]).order_by(category::id::order(SortOrder::Asc)))
+        .exec()
+        .await?;
+    let categories = updated.categories.unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0].id, prisma.id);
+    assert_eq!(categories[1].id, sqlite.id);
+
+    let updated = client
+        .post()
+        .update(
+            post::id::equals(post.id.clone()),
+            vec![post::categories::disconnect(vec![category::id::equals(
+                prisma.id,
+            )])],
+        )
+        .with(post::categories::fetch(vec![]))
+        .exec()
+        .await?;
+    let categories = updated.categories.unwrap();
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0].id, sqlite.id);
+
+    // disconnecting doesn't delete the category itself
+    assert_eq!(client.category().count(vec![]).exec().await?, 3);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_update_and_delete_many() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![
+                user::posts::create_params("First".to_string(), true, vec![]),
+                user::posts::create_params("Second".to_string(), false, vec![]),
+                user::posts::create_params("Third".to_string(), false, vec![]),
+            ])],
+        )
+        .exec()
+        .await?;
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![
+                user::posts::update(vec![(
+                    post::title_author_id("First".to_string(), user.id.clone()),
+                    vec![post::views::set(10)],
+                )]),
+                user::posts::update_many(
+                    vec![post::published::equals(false)],
+                    vec![post::desc::set(Some("Draft".to_string()))],
+                ),
+            ],
+        )
+        .with(user::posts::fetch(vec![]).order_by(post::title::order(SortOrder::Asc)))
+        .exec()
+        .await?;
+    let posts = updated.posts.unwrap();
+    assert_eq!(posts[0].views, 10);
+    assert_eq!(posts[1].desc, Some("Draft".to_string()));
+    assert_eq!(posts[2].desc, Some("Draft".to_string()));
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![
+                user::posts::delete(vec![post::id::equals(posts[0].id.clone())]),
+                user::posts::delete_many(vec![post::title::equals("Second".to_string())]),
+            ],
+        )
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?;
+    let remaining = updated.posts.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].title, "Third");
+
+    // deleted posts are removed entirely, not just disconnected
+    assert_eq!(client.post().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_update_and_delete_one() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::profile::create(user::profile::create_params(
+                "Bio".to_string(),
+                "Australia".to_string(),
+                vec![],
+            ))],
+        )
+        .exec()
+        .await?;
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::profile::update(vec![profile::bio::set(
+                "New bio".to_string(),
+            )])],
+        )
+        .with(user::profile::fetch())
+        .exec()
+        .await?;
+    assert_eq!(updated.profile.unwrap().unwrap().bio, "New bio");
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::profile::delete()],
+        )
+        .with(user::profile::fetch())
+        .exec()
+        .await?;
+    assert!(updated.profile.unwrap().is_none());
+    assert_eq!(client.profile().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn connect_or_create_compound_unique() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![user::posts::create_params(
+                "First".to_string(),
+                true,
+                vec![],
+            )])],
+        )
+        .exec()
+        .await?;
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::posts::connect_or_create(vec![
+                (
+                    post::title_author_id("First".to_string(), user.id.clone()),
+                    user::posts::create_params("First".to_string(), false, vec![]),
+                ),
+                (
+                    post::title_author_id("Second".to_string(), user.id.clone()),
+                    user::posts::create_params("Second".to_string(), false, vec![]),
+                ),
+            ])],
+        )
+        .with(user::posts::fetch(vec![]).order_by(post::title::order(SortOrder::Asc)))
+        .exec()
+        .await?;
+    let posts = updated.posts.unwrap();
+
+    // the existing post is connected rather than created again
+    assert_eq!(posts.len(), 2);
+    assert!(posts[0].published);
+    assert_eq!(posts[1].title, "Second");
+    assert_eq!(client.post().count(vec![]).exec().await?, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn connect_or_create_one() -> TestResult {
+    let client = client().await;
+
+    let email = "brendan@example.com".to_string();
+
+    let create_post = |title: &str| {
+        client.post().create(
+            title.to_string(),
+            true,
+            vec![post::author::connect_or_create(
+                user::email::equals(email.clone()),
+                post::author::create_params(
+                    "Brendan".to_string(),
+                    vec![user::email::set(Some(email.clone()))],
+                ),
+            )],
+        )
+    };
+
+    let first = create_post("First").exec().await?;
+    let second = create_post("Second").exec().await?;
+
+    assert_eq!(first.author_id, second.author_id);
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn unchecked() -> TestResult {
     let client = client().await;
@@ -445,6 +688,7 @@ async fn set_many_none() -> TestResult {
     let count = client
         .post()
         .update_many(vec![], vec![post::desc::set(None)])
+        .all()
         .exec()
         .await?;
     assert_eq!(count, 1);
@@ -461,3 +705,291 @@ async fn set_many_none() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn many_returning() -> TestResult {
+    let client = client().await;
+
+    for (title, published) in [("Foo", false), ("Bar", false), ("Baz", true)] {
+        client
+            .post()
+            .create(title.to_string(), published, vec![])
+            .exec()
+            .await?;
+    }
+
+    // the updated records are returned even though they no longer match the filter
+    let mut posts = client
+        .post()
+        .update_many(
+            vec![post::published::equals(false)],
+            vec![post::published::set(true)],
+        )
+        .returning()
+        .exec()
+        .await?;
+    posts.sort_by(|a, b| a.title.cmp(&b.title));
+    assert_eq!(
+        posts.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        vec!["Bar", "Foo"]
+    );
+    assert!(posts.iter().all(|p| p.published));
+
+    let posts = client
+        .post()
+        .update_many(
+            vec![post::published::equals(false)],
+            vec![post::published::set(true)],
+        )
+        .returning()
+        .with_timeout(10_000)
+        .exec()
+        .await?;
+    assert!(posts.is_empty());
+
+    // compound primary keys
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .file_path()
+        .create_many(
+            (0..3)
+                .map(|id| {
+                    file_path::create_unchecked(id, format!("Path {id}"), user.id.clone(), vec![])
+                })
+                .collect(),
+        )
+        .exec()
+        .await?;
+
+    let file_paths = client
+        .file_path()
+        .update_many(
+            vec![file_path::local_id::gte(1)],
+            vec![file_path::path::set("Moved".to_string())],
+        )
+        .returning()
+        .exec()
+        .await?;
+    assert_eq!(file_paths.len(), 2);
+    assert!(file_paths
+        .iter()
+        .all(|f| f.path == "Moved" && f.local_id >= 1));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn update_input() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await?;
+
+    // fields left as None aren't touched
+    let updated = client
+        .user()
+        .update(user::id::equals(user.id.clone()), vec![])
+        .with_input(user::UpdateInput {
+            name: Some("Oscar".to_string()),
+            ..Default::default()
+        })
+        .exec()
+        .await?;
+    assert_eq!(updated.name, "Oscar");
+    assert_eq!(updated.email, Some("brendan@example.com".to_string()));
+
+    // a missing key is skipped, while null sets the field to null
+    let input: user::UpdateInput = serde_json::from_value(serde_json::json!({
+        "email": null
+    }))
+    .unwrap();
+    assert_eq!(input.name, None);
+    assert_eq!(input.email, Some(None));
+
+    let updated = client
+        .user()
+        .update(user::id::equals(user.id.clone()), vec![])
+        .with_input(input)
+        .exec()
+        .await?;
+    assert_eq!(updated.name, "Oscar");
+    assert_eq!(updated.email, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn to_unique() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let updated = client
+        .user()
+        .update(user.to_unique(), vec![user::name::set("Oscar".to_string())])
+        .exec()
+        .await?;
+
+    assert_eq!(updated.id, user.id);
+    assert_eq!(updated.name, "Oscar");
+
+    let file_path = client
+        .file_path()
+        .create(
+            0,
+            "path".to_string(),
+            user::id::equals(user.id.clone()),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let updated = client
+        .file_path()
+        .update(
+            (&file_path).into(),
+            vec![file_path::path::set("new path".to_string())],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.path, "new path");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn extended_where_unique() -> TestResult {
+    let client = client().await;
+
+    let user_id = create_user(&client).await?;
+
+    let stale = client
+        .user()
+        .update(
+            user::id::equals(user_id.clone()),
+            vec![user::name::set("Oscar".to_string())],
+        )
+        .and_where(vec![user::name::equals("Someone else".to_string())])
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(stale.is_prisma_error::<RecordRequiredButNotFound>());
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user_id.clone()),
+            vec![user::name::set("Oscar".to_string())],
+        )
+        .and_where(vec![user::name::equals("Brendan".to_string())])
+        .exec()
+        .await?;
+
+    assert_eq!(updated.name, "Oscar");
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .and_where(vec![user::name::equals("Brendan".to_string())])
+        .exec()
+        .await?;
+
+    assert!(found.is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn update_if_version() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("Title".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    assert_eq!(post.version, 0);
+
+    let updated = client
+        .post()
+        .update_if_version(
+            post::id::equals(post.id.clone()),
+            post.version,
+            vec![post::title::set("New Title".to_string())],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.title, "New Title");
+    assert_eq!(updated.version, 1);
+
+    let conflict = client
+        .post()
+        .update_if_version(
+            post::id::equals(post.id.clone()),
+            post.version,
+            vec![post::title::set("Stale Title".to_string())],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(conflict, QueryError::VersionConflict));
+
+    let post = client
+        .post()
+        .find_unique(post::id::equals(post.id))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(post.title, "New Title");
+
+    let missing = client
+        .post()
+        .update_if_version(
+            post::id::equals("missing".to_string()),
+            0,
+            vec![post::title::set("Missing Title".to_string())],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(missing.is_prisma_error::<RecordRequiredButNotFound>());
+
+    let failed_connect = client
+        .post()
+        .update_if_version(
+            post::id::equals(post.id.clone()),
+            post.version,
+            vec![post::author::connect(user::id::equals(
+                "missing".to_string(),
+            ))],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(!matches!(failed_connect, QueryError::VersionConflict));
+
+    cleanup(client).await
+}
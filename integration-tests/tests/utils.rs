@@ -3,17 +3,44 @@ use prisma_client_rust::QueryError;
 
 pub type TestResult = Result<(), QueryError>;
 
+/// The type of `Types.status`, set using `type_overrides`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Active,
+    Archived,
+    /// Serializes to an object rather than a string, so queries given it fail.
+    Invalid(i32),
+}
+
+/// The codec of `Profile.city`, set using `field_codecs`.
+/// Stores values reversed behind a prefix, so that decoding can fail.
+pub struct ReversedCodec;
+
+impl prisma_client_rust::FieldCodec for ReversedCodec {
+    fn encode(value: String) -> String {
+        format!("rev:{}", value.chars().rev().collect::<String>())
+    }
+
+    fn decode(stored: String) -> Result<String, String> {
+        stored
+            .strip_prefix("rev:")
+            .map(|value| value.chars().rev().collect())
+            .ok_or_else(|| format!("'{stored}' wasn't encoded"))
+    }
+}
+
 pub async fn client() -> PrismaClient {
     let client = PrismaClient::_builder().build().await.unwrap();
 
     client
         ._batch((
-            client.file_path().delete_many(vec![]),
-            client.category().delete_many(vec![]),
-            client.post().delete_many(vec![]),
-            client.profile().delete_many(vec![]),
-            client.user().delete_many(vec![]),
-            client.types().delete_many(vec![]),
+            client.file_path().delete_many(vec![]).all(),
+            client.category().delete_many(vec![]).all(),
+            client.post().delete_many(vec![]).all(),
+            client.profile().delete_many(vec![]).all(),
+            client.user().delete_many(vec![]).all(),
+            client.types().delete_many(vec![]).all(),
         ))
         .await
         .unwrap();
@@ -24,14 +51,14 @@ pub async fn client() -> PrismaClient {
 pub async fn cleanup(client: PrismaClient) -> TestResult {
     client
         ._batch((
-            client.file_path().delete_many(vec![]),
-            client.category().delete_many(vec![]),
-            client.post().delete_many(vec![]),
-            client.profile().delete_many(vec![]),
-            client.user().delete_many(vec![]),
-            client.types().delete_many(vec![]),
-            client.child().delete_many(vec![]),
-            client.parent().delete_many(vec![]),
+            client.file_path().delete_many(vec![]).all(),
+            client.category().delete_many(vec![]).all(),
+            client.post().delete_many(vec![]).all(),
+            client.profile().delete_many(vec![]).all(),
+            client.user().delete_many(vec![]).all(),
+            client.types().delete_many(vec![]).all(),
+            client.child().delete_many(vec![]).all(),
+            client.parent().delete_many(vec![]).all(),
         ))
         .await
         .unwrap();
@@ -1,4 +1,4 @@
-use prisma_client_rust::{or, queries::QueryError};
+use prisma_client_rust::{or, queries::QueryError, RelationLoadStrategy};
 
 use crate::{db::*, utils::*};
 
@@ -280,6 +280,111 @@ async fn nested_with() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn relation_load_strategy() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let error = client
+        .user()
+        .find_unique(user::id::equals(user_id))
+        .with(user::posts::fetch(vec![]))
+        .relation_load_strategy(RelationLoadStrategy::Join)
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, QueryError::UnsupportedRelationLoadStrategy));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn load_relations() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+
+    let posts = user
+        .load_posts(&client)
+        .order_by(post::title::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 4);
+    assert_eq!(posts[0].title, "post 4");
+
+    let author = posts[0]
+        .load_author(&client)
+        .unwrap()
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(author.id, user_id);
+
+    let categories = posts[3].load_categories(&client).exec().await?;
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0].name, "My category");
+
+    let category_posts = categories[0].load_posts(&client).exec().await?;
+    assert_eq!(category_posts.len(), 2);
+
+    let orphan = client
+        .post()
+        .create("orphan".to_string(), false, vec![])
+        .exec()
+        .await?;
+    assert!(orphan.load_author(&client).is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn load_relations_from_partials() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![post::author_id::equals(Some(user_id.clone()))])
+        .order_by(post::title::order(SortOrder::Asc))
+        .select(post::select!({ id title author_id }))
+        .exec()
+        .await?;
+
+    let author = posts[0]
+        .load_author(&client)
+        .unwrap()
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(author.id, user_id);
+
+    let categories = posts[0].load_categories(&client).exec().await?;
+    assert_eq!(categories.len(), 1);
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .include(user::include!({ profile }))
+        .exec()
+        .await?
+        .unwrap();
+
+    let user_posts = user.load_posts(&client).exec().await?;
+    assert_eq!(user_posts.len(), 4);
+
+    cleanup(client).await
+}
+
 // TODO: Nested create
 
 // #[tokio::test]
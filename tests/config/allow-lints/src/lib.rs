@@ -0,0 +1,8 @@
+//! A client generated with `allow_lints = ["warnings", "clippy::all"]`.
+//!
+//! The crate denies warnings and declares the client without an `#[allow]`,
+//! relying on the attribute at the top of each generated file.
+
+#![deny(warnings)]
+
+pub mod db;
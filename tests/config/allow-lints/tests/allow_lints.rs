@@ -0,0 +1,34 @@
+use std::{fs, path::Path};
+
+fn generated_files(dir: &Path, files: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            generated_files(&path, files);
+        } else if path.extension().map_or(false, |ext| ext == "rs") {
+            files.push(fs::read_to_string(path).unwrap());
+        }
+    }
+}
+
+#[test]
+fn every_file_allows_lints() {
+    let mut files = vec![];
+    generated_files(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/db"),
+        &mut files,
+    );
+
+    // the root module, `_prisma` and the files of each model and its fields
+    assert!(files.len() > 3);
+
+    for contents in files {
+        let first_item = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with("//"));
+
+        assert_eq!(first_item, Some("#![allow(warnings, clippy::all)]"));
+    }
+}
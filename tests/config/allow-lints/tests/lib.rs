@@ -0,0 +1 @@
+mod allow_lints;
@@ -0,0 +1,4 @@
+//! A client generated with `bigint_as_string = true`.
+
+#[allow(warnings, unused)]
+pub mod db;
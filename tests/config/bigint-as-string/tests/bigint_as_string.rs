@@ -0,0 +1,66 @@
+use bigint_as_string_tests::db::*;
+use serde_json::json;
+
+use crate::utils::*;
+
+#[tokio::test]
+async fn serializes_as_strings() -> TestResult {
+    let client = client().await;
+
+    let counter = client
+        .counter()
+        .create(i64::MAX, vec![counter::previous::set(Some(i64::MIN))])
+        .exec()
+        .await?;
+    assert_eq!(counter.value, i64::MAX);
+    assert_eq!(counter.previous, Some(i64::MIN));
+
+    let json = serde_json::to_value(&counter).unwrap();
+    assert_eq!(json["value"], json!("9223372036854775807"));
+    assert_eq!(json["previous"], json!("-9223372036854775808"));
+
+    let counter = client
+        .counter()
+        .update(
+            counter::id::equals(counter.id),
+            vec![counter::value::decrement(1), counter::previous::set(None)],
+        )
+        .exec()
+        .await?;
+    assert_eq!(counter.value, i64::MAX - 1);
+
+    let json = serde_json::to_value(&counter).unwrap();
+    assert_eq!(json["value"], json!("9223372036854775806"));
+    assert_eq!(json["previous"], json!(null));
+
+    cleanup(client).await
+}
+
+#[test]
+fn deserializes_strings_and_numbers() {
+    let from_strings: counter::Data = serde_json::from_value(json!({
+        "id": 1,
+        "value": "9223372036854775807",
+        "previous": "-9223372036854775808",
+    }))
+    .unwrap();
+    assert_eq!(from_strings.value, i64::MAX);
+    assert_eq!(from_strings.previous, Some(i64::MIN));
+
+    let from_numbers: counter::Data = serde_json::from_value(json!({
+        "id": 1,
+        "value": i64::MAX,
+        "previous": null,
+    }))
+    .unwrap();
+    assert_eq!(from_numbers.value, i64::MAX);
+    assert_eq!(from_numbers.previous, None);
+
+    // values past the range of a BigInt column are rejected rather than truncated
+    assert!(serde_json::from_value::<counter::Data>(json!({
+        "id": 1,
+        "value": "9223372036854775808",
+        "previous": null,
+    }))
+    .is_err());
+}
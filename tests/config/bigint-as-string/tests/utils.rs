@@ -0,0 +1,22 @@
+use bigint_as_string_tests::db::PrismaClient;
+use prisma_client_rust::QueryError;
+
+pub type TestResult = Result<(), QueryError>;
+
+pub async fn client() -> PrismaClient {
+    let client = PrismaClient::_builder().build().await.unwrap();
+
+    cleanup_all(&client).await;
+
+    client
+}
+
+pub async fn cleanup(client: PrismaClient) -> TestResult {
+    cleanup_all(&client).await;
+
+    Ok(())
+}
+
+async fn cleanup_all(client: &PrismaClient) {
+    client.counter().delete_many(vec![]).exec().await.unwrap();
+}
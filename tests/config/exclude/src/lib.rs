@@ -0,0 +1,40 @@
+//! A client generated with `exclude_models` and `exclude_fields`.
+//!
+//! Models and fields that aren't excluded are generated as usual:
+//!
+//! ```
+//! use exclude_tests::db::*;
+//!
+//! let _ = user::name::equals("Brendan".to_string());
+//! ```
+//!
+//! but excluded models have no module:
+//!
+//! ```compile_fail
+//! use exclude_tests::db::audit_log;
+//! ```
+//!
+//! ```compile_fail
+//! use exclude_tests::db::*;
+//!
+//! let _ = user::audit_logs::fetch(vec![]);
+//! ```
+//!
+//! and excluded fields have no accessors:
+//!
+//! ```compile_fail
+//! use exclude_tests::db::*;
+//!
+//! let _ = user::internal_note::equals(None);
+//! ```
+//!
+//! ```compile_fail
+//! use exclude_tests::db::*;
+//!
+//! fn note(user: user::Data) -> Option<String> {
+//!     user.internal_note
+//! }
+//! ```
+
+#[allow(warnings, unused)]
+pub mod db;
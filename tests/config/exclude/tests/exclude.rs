@@ -0,0 +1,81 @@
+use exclude_tests::db::*;
+use prisma_client_rust::{prisma_models::PrismaValue, raw};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::utils::*;
+
+#[tokio::test]
+async fn excluded_model_and_field() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // the excluded column and table still exist in the database
+    client
+        ._execute_raw(raw!(
+            "UPDATE User SET internal_note = {} WHERE id = {}",
+            PrismaValue::String("Flagged".to_string()),
+            PrismaValue::String(user.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    client
+        ._execute_raw(raw!(
+            "INSERT INTO AuditLog (action, user_id) VALUES ({}, {})",
+            PrismaValue::String("signup".to_string()),
+            PrismaValue::String(user.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(found.id, user.id);
+
+    // but the client never reads them
+    let found = serde_json::to_value(&found).unwrap();
+    assert_eq!(
+        found.as_object().unwrap().keys().collect::<Vec<_>>(),
+        vec!["id", "name"]
+    );
+
+    #[derive(Deserialize)]
+    struct Count {
+        count: i64,
+    }
+
+    client
+        .user()
+        .delete(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    let logs: Vec<Count> = client
+        ._query_raw(raw!("SELECT COUNT(*) AS count FROM AuditLog"))
+        .exec()
+        .await?;
+    assert_eq!(logs[0].count, 0);
+
+    cleanup(client).await
+}
+
+#[test]
+fn excluded_from_schema_json() {
+    let schema: Value = serde_json::from_str(include_str!("../src/db.schema.json")).unwrap();
+    let defs = &schema["$defs"];
+
+    assert!(defs.get("AuditLog").is_none());
+    assert!(defs["User"]["properties"].get("internal_note").is_none());
+    assert!(defs["User"]["properties"].get("audit_logs").is_none());
+    assert!(defs["User"]["properties"].get("name").is_some());
+}
@@ -0,0 +1,54 @@
+//! A client generated with `id_newtypes = true`.
+//!
+//! Filters and foreign keys take the newtype of the model they refer to:
+//!
+//! ```
+//! use id_newtypes_tests::db::*;
+//!
+//! let user_id = user::UserId::from("abc");
+//!
+//! let _: user::UniqueWhereParam = user::id::equals_ref(&user_id);
+//! let _: user::UniqueWhereParam = user::id::equals("abc".into());
+//! let _: profile::UniqueWhereParam = profile::user_id::equals(user_id.clone());
+//! let _ = post::author_id::equals(Some(user_id.clone()));
+//! let _: post::UncheckedSetParam = post::author_id::set(Some(user_id));
+//! let _: post::UniqueWhereParam = post::id::equals(post::PostId(1));
+//! ```
+//!
+//! so passing one model's id where another's is expected doesn't compile,
+//! even when both are `String`s:
+//!
+//! ```compile_fail
+//! use id_newtypes_tests::db::*;
+//!
+//! let _: user::UniqueWhereParam = user::id::equals(tag::TagId::from("abc"));
+//! ```
+//!
+//! ```compile_fail
+//! use id_newtypes_tests::db::*;
+//!
+//! let _ = post::author_id::equals(Some(tag::TagId::from("abc")));
+//! ```
+//!
+//! ```compile_fail
+//! use id_newtypes_tests::db::*;
+//!
+//! let _: post::UncheckedSetParam = post::author_id::set(Some(tag::TagId::from("abc")));
+//! ```
+//!
+//! ```compile_fail
+//! use id_newtypes_tests::db::*;
+//!
+//! let _: profile::UniqueWhereParam = profile::user_id::equals(tag::TagId::from("abc"));
+//! ```
+//!
+//! and neither does passing the value the newtype wraps:
+//!
+//! ```compile_fail
+//! use id_newtypes_tests::db::*;
+//!
+//! let _: user::UniqueWhereParam = user::id::equals("abc".to_string());
+//! ```
+
+#[allow(warnings, unused)]
+pub mod db;
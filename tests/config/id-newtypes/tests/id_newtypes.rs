@@ -0,0 +1,275 @@
+use id_newtypes_tests::db::*;
+use prisma_client_rust::{HasId, IntoPrismaCreate, QueryError};
+
+use crate::utils::*;
+
+#[tokio::test]
+async fn filters_and_foreign_keys() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    let user_id: user::UserId = user.id.clone();
+
+    let post = client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            vec![post::author::connect(user::id::equals_ref(&user_id))],
+        )
+        .exec()
+        .await?;
+    let post_id: post::PostId = post.id.clone();
+    assert_eq!(post.author_id, Some(user_id.clone()));
+
+    client
+        .post()
+        .create_unchecked(
+            "Unchecked".to_string(),
+            vec![post::author_id::set(Some(user_id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    client
+        .profile()
+        .create(
+            user::id::equals(user_id.clone()),
+            "Brendan's profile".to_string(),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(found.id, user_id);
+
+    let posts = client
+        .post()
+        .find_many(vec![post::author_id::equals(Some(user_id.clone()))])
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 2);
+
+    let posts = client
+        .post()
+        .find_many(vec![post::id::in_vec(vec![post_id.clone()])])
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].id, post_id);
+
+    let profile = client
+        .profile()
+        .find_unique(profile::user_id::equals(user_id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(profile.user_id, user_id);
+
+    // the newtypes hold the value stored in the database
+    assert_eq!(String::from(user_id), user.id.0);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn id_accessor() -> TestResult {
+    let client = client().await;
+
+    fn ids<T: HasId>(records: &[T]) -> Vec<T::Id> {
+        records.iter().map(HasId::id).collect()
+    }
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    let user_id: user::Id = user.id();
+    assert_eq!(user_id, user.id);
+
+    let users = client.user().find_many(vec![]).exec().await?;
+    let user_ids: Vec<user::UserId> = ids(&users);
+    assert_eq!(user_ids, vec![user.id.clone()]);
+
+    let post = client
+        .post()
+        .create("Hi from Prisma!".to_string(), vec![])
+        .exec()
+        .await?;
+    let post_id: <post::Data as HasId>::Id = post.id();
+    assert_eq!(post_id, post.id);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn to_unique() -> TestResult {
+    let client = client().await;
+
+    let users = client
+        ._batch(vec![
+            client.user().create("Brendan".to_string(), vec![]),
+            client.user().create("Oscar".to_string(), vec![]),
+            client.user().create("Sam".to_string(), vec![]),
+        ])
+        .await?;
+
+    let renamed = client
+        .user()
+        .update(
+            users[0].to_unique(),
+            vec![user::name::set("Brendan A".to_string())],
+        )
+        .exec()
+        .await?;
+    assert_eq!(renamed.id, users[0].id);
+    assert_eq!(renamed.name, "Brendan A");
+
+    let found = client
+        .user()
+        .find_many_by_unique(vec![
+            users[1].to_unique(),
+            user::id::equals(users[2].id.clone()),
+        ])
+        .order_by(user::name::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(
+        found.iter().map(|user| user.id.clone()).collect::<Vec<_>>(),
+        vec![users[1].id.clone(), users[2].id.clone()]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn load_relations() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let tag = client
+        .tag()
+        .create("prisma".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            vec![
+                post::author::connect(user::id::equals(user.id.clone())),
+                post::tags::connect(vec![tag::id::equals(tag.id.clone())]),
+            ],
+        )
+        .exec()
+        .await?;
+
+    client
+        .profile()
+        .create(
+            user::id::equals(user.id.clone()),
+            "Brendan's profile".to_string(),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let posts = user.load_posts(&client).exec().await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].id, post.id);
+
+    let author = post.load_author(&client).unwrap().exec().await?.unwrap();
+    assert_eq!(author.id, user.id);
+
+    let profile = user.load_profile(&client).exec().await?.unwrap();
+    assert_eq!(profile.user_id, user.id);
+
+    let tags = post.load_tags(&client).exec().await?;
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].id, tag.id);
+
+    let tag_posts = tag.load_posts(&client).exec().await?;
+    assert_eq!(tag_posts.len(), 1);
+    assert_eq!(tag_posts[0].id, post.id);
+
+    cleanup(client).await
+}
+
+async fn rename_user<R: user::Repository>(
+    repo: &R,
+    id: user::UserId,
+    name: String,
+) -> Result<user::Data, QueryError> {
+    repo.update(user::id::equals(id), vec![user::name::set(name)])
+        .await
+}
+
+#[tokio::test]
+async fn repository() -> TestResult {
+    let client = client().await;
+
+    let user =
+        user::Repository::create(&client, user::create("Brendan".to_string(), vec![])).await?;
+
+    let renamed = rename_user(&client, user.id.clone(), "Oscar".to_string()).await?;
+    assert_eq!(renamed.id, user.id);
+    assert_eq!(renamed.name, "Oscar");
+
+    let posts = post::Repository::find_many(
+        &client,
+        vec![post::author_id::equals(Some(user.id.clone()))],
+    )
+    .await?;
+    assert!(posts.is_empty());
+
+    user::Repository::delete(&client, user::id::equals(user.id.clone())).await?;
+    assert_eq!(user::Repository::count(&client, vec![]).await?, 0);
+
+    cleanup(client).await
+}
+
+#[derive(IntoPrismaCreate)]
+#[prisma(model = id_newtypes_tests::db::post)]
+struct NewPost {
+    title: String,
+    author_id: Option<user::UserId>,
+}
+
+#[tokio::test]
+async fn into_create() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = NewPost {
+        title: "Hi from Prisma!".to_string(),
+        author_id: Some(user.id.clone()),
+    }
+    .into_create()
+    .to_query(&client)
+    .exec()
+    .await?;
+    assert_eq!(post.author_id, Some(user.id));
+
+    cleanup(client).await
+}
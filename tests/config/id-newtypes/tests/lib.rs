@@ -0,0 +1,15 @@
+use id_newtypes_tests::db;
+use utils::{cleanup, TestResult};
+
+mod utils;
+
+#[tokio::test]
+async fn aaaa_run_migrations() -> TestResult {
+    let client = db::new_client().await.unwrap();
+
+    client._db_push().accept_data_loss().await.unwrap();
+
+    cleanup(client).await
+}
+
+mod id_newtypes;
@@ -0,0 +1,30 @@
+use id_newtypes_tests::db::PrismaClient;
+use prisma_client_rust::QueryError;
+
+pub type TestResult = Result<(), QueryError>;
+
+pub async fn client() -> PrismaClient {
+    let client = PrismaClient::_builder().build().await.unwrap();
+
+    cleanup_all(&client).await;
+
+    client
+}
+
+pub async fn cleanup(client: PrismaClient) -> TestResult {
+    cleanup_all(&client).await;
+
+    Ok(())
+}
+
+async fn cleanup_all(client: &PrismaClient) {
+    client
+        ._batch((
+            client.profile().delete_many(vec![]).all(),
+            client.post().delete_many(vec![]).all(),
+            client.tag().delete_many(vec![]).all(),
+            client.user().delete_many(vec![]).all(),
+        ))
+        .await
+        .unwrap();
+}
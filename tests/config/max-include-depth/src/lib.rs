@@ -0,0 +1,70 @@
+//! A client generated with `max_include_depth = "2"`.
+//!
+//! Selections can be nested two levels deep:
+//!
+//! ```
+//! use max_include_depth_tests::db::*;
+//!
+//! let _ = user::include!({
+//!     posts: include {
+//!         author: include {
+//!             posts
+//!         }
+//!     }
+//! });
+//!
+//! let _ = user::select!({
+//!     posts: select {
+//!         author: select {
+//!             name
+//!         }
+//!     }
+//! });
+//! ```
+//!
+//! but not any deeper:
+//!
+//! ```compile_fail
+//! use max_include_depth_tests::db::*;
+//!
+//! let _ = user::include!({
+//!     posts: include {
+//!         author: include {
+//!             posts: include {
+//!                 author
+//!             }
+//!         }
+//!     }
+//! });
+//! ```
+//!
+//! ```compile_fail
+//! use max_include_depth_tests::db::*;
+//!
+//! let _ = user::select!({
+//!     posts: select {
+//!         author: select {
+//!             posts: select {
+//!                 title
+//!             }
+//!         }
+//!     }
+//! });
+//! ```
+//!
+//! ```compile_fail
+//! use max_include_depth_tests::db::*;
+//!
+//! user::include!(deep_posts {
+//!     posts: include {
+//!         author: include {
+//!             posts: include {
+//!                 author
+//!             }
+//!         }
+//!     }
+//! });
+//! ```
+
+#[allow(warnings, unused)]
+pub mod db;
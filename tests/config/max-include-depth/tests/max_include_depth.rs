@@ -0,0 +1,44 @@
+use max_include_depth_tests::db::*;
+
+use crate::utils::*;
+
+#[tokio::test]
+async fn include_at_max_depth() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            user::id::equals(user.id.clone()),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .include(user::include!({
+            posts: include {
+                author: include {
+                    posts
+                }
+            }
+        }))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.posts.len(), 1);
+    assert_eq!(found.posts[0].author.id, user.id);
+    assert_eq!(found.posts[0].author.posts.len(), 1);
+
+    cleanup(client).await
+}
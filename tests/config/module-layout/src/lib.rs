@@ -0,0 +1,4 @@
+//! A client generated in the folder format with `module_layout = "flat"`.
+
+#[allow(warnings, unused)]
+pub mod db;
@@ -0,0 +1,59 @@
+use std::{fs, path::Path};
+
+use module_layout_tests::db::*;
+
+use crate::utils::*;
+
+#[test]
+fn one_file_per_module() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/db");
+
+    let mut files = vec![];
+    for entry in fs::read_dir(root).unwrap() {
+        let entry = entry.unwrap();
+
+        // field modules are written inline instead of in a directory per model
+        assert!(!entry.file_type().unwrap().is_dir());
+
+        let name = entry.file_name().into_string().unwrap();
+        if name.ends_with(".rs") {
+            files.push(name);
+        }
+    }
+    files.sort();
+
+    assert_eq!(files, vec!["_prisma.rs", "mod.rs", "post.rs", "user.rs"]);
+}
+
+#[tokio::test]
+async fn paths_are_unchanged() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            user::id::equals(user.id.clone()),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![post::author::is(vec![user::name::equals(
+            "Brendan".to_string(),
+        )])])
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].author_id, user.id);
+
+    cleanup(client).await
+}
@@ -0,0 +1,63 @@
+//! A client generated with `non_exhaustive = true`.
+//!
+//! Doctests are compiled as a separate crate, so `#[non_exhaustive]` applies to them.
+//! Fields of `Data` can still be read, and destructured with `..`:
+//!
+//! ```
+//! use non_exhaustive_tests::db::*;
+//!
+//! fn name(user: user::Data) -> String {
+//!     let user::Data { name, .. } = user;
+//!     name
+//! }
+//! ```
+//!
+//! but `Data` can't be constructed with a struct literal:
+//!
+//! ```compile_fail
+//! use non_exhaustive_tests::db::*;
+//!
+//! let _ = user::Data {
+//!     id: "abc".to_string(),
+//!     name: "Brendan".to_string(),
+//! };
+//! ```
+//!
+//! or destructured without `..`:
+//!
+//! ```compile_fail
+//! use non_exhaustive_tests::db::*;
+//!
+//! fn name(user: user::Data) -> String {
+//!     let user::Data { id, name } = user;
+//!     name
+//! }
+//! ```
+//!
+//! Matching on param enums requires a wildcard arm:
+//!
+//! ```
+//! use non_exhaustive_tests::db::*;
+//!
+//! fn field(param: user::UncheckedSetParam) -> &'static str {
+//!     match param {
+//!         user::UncheckedSetParam::Id(_) => "id",
+//!         user::UncheckedSetParam::Name(_) => "name",
+//!         _ => "other",
+//!     }
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use non_exhaustive_tests::db::*;
+//!
+//! fn field(param: user::UncheckedSetParam) -> &'static str {
+//!     match param {
+//!         user::UncheckedSetParam::Id(_) => "id",
+//!         user::UncheckedSetParam::Name(_) => "name",
+//!     }
+//! }
+//! ```
+
+#[allow(warnings, unused)]
+pub mod db;
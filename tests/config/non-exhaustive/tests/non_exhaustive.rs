@@ -0,0 +1,34 @@
+use non_exhaustive_tests::db::*;
+
+use crate::utils::*;
+
+#[tokio::test]
+async fn queries_are_unaffected() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let user::Data { id, .. } = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::name::set("Oscar".to_string())],
+        )
+        .exec()
+        .await?;
+    assert_eq!(id, user.id);
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(id))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(found.name, "Oscar");
+
+    cleanup(client).await
+}
@@ -0,0 +1,5 @@
+//! A client generated with `serde_rename = "camelCase"`
+//! and `serde_rename_overrides = ["User.display_name=name"]`.
+
+#[allow(warnings, unused)]
+pub mod db;
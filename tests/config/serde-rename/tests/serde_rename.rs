@@ -0,0 +1,76 @@
+use serde_json::{json, Value};
+use serde_rename_tests::db::*;
+
+use crate::utils::*;
+
+fn keys(value: &Value) -> Vec<&str> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+#[tokio::test]
+async fn serializes_renamed_fields() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Hi from Prisma!".to_string(),
+            user::id::equals(user.id.clone()),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?
+        .unwrap();
+
+    let value = serde_json::to_value(&user).unwrap();
+
+    // `display_name` is overridden, the other fields are camelCase
+    assert_eq!(keys(&value), vec!["createdAt", "id", "name", "posts"]);
+    assert_eq!(value["name"], "Brendan");
+    assert_eq!(
+        keys(&value["posts"][0]),
+        vec!["author", "authorId", "id", "postTitle"]
+    );
+    assert_eq!(value["posts"][0]["postTitle"], "Hi from Prisma!");
+
+    cleanup(client).await
+}
+
+#[test]
+fn deserializes_renamed_and_schema_names() {
+    let renamed: user::Data = serde_json::from_value(json!({
+        "id": "abc",
+        "name": "Brendan",
+        "createdAt": "2023-01-01T00:00:00+00:00",
+    }))
+    .unwrap();
+    assert_eq!(renamed.display_name, "Brendan");
+
+    // the names the query engine uses are still accepted
+    let schema: user::Data = serde_json::from_value(json!({
+        "id": "abc",
+        "display_name": "Brendan",
+        "created_at": "2023-01-01T00:00:00+00:00",
+    }))
+    .unwrap();
+    assert_eq!(schema.display_name, "Brendan");
+    assert_eq!(schema.created_at, renamed.created_at);
+}
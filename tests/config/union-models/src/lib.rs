@@ -0,0 +1,4 @@
+//! A client generated with `union_models = ["Event.kind", "Notification.kind"]`.
+
+#[allow(warnings, unused)]
+pub mod db;
@@ -0,0 +1 @@
+mod union_models;
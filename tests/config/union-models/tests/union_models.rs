@@ -0,0 +1,86 @@
+use prisma_client_rust::UnionConversionError;
+use union_models_tests::db::*;
+
+fn event(kind: EventKind, x: Option<i32>, y: Option<i32>, url: Option<String>) -> event::Data {
+    event::Data {
+        id: 1,
+        kind,
+        x,
+        y,
+        url,
+    }
+}
+
+#[test]
+fn variant_per_discriminator_value() {
+    let click = event::Union::try_from(event(EventKind::Click, Some(4), Some(2), None)).unwrap();
+    assert!(matches!(click, event::Union::Click { id: 1, x: 4, y: 2 }));
+
+    let view = event::Union::try_from(event(
+        EventKind::View,
+        None,
+        None,
+        Some("/home".to_string()),
+    ))
+    .unwrap();
+    match view {
+        event::Union::View { id, url } => {
+            assert_eq!(id, 1);
+            assert_eq!(url, "/home");
+        }
+        other => panic!("expected a view, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_variant_field() {
+    let error = event::Union::try_from(event(EventKind::Click, Some(4), None, None)).unwrap_err();
+    assert_eq!(
+        error,
+        UnionConversionError::MissingField {
+            variant: "Click",
+            field: "y"
+        }
+    );
+}
+
+#[test]
+fn fields_of_other_variants_are_ignored() {
+    let view = event::Union::try_from(event(
+        EventKind::View,
+        Some(4),
+        Some(2),
+        Some("/home".to_string()),
+    ))
+    .unwrap();
+    assert!(matches!(view, event::Union::View { .. }));
+}
+
+#[test]
+fn nullable_discriminator() {
+    let notification = |kind| notification::Data {
+        id: 1,
+        kind,
+        message: "Hello".to_string(),
+        url: Some("/inbox".to_string()),
+    };
+
+    // fields without a `@variant` belong to every variant
+    match notification::Union::try_from(notification(Some(EventKind::View))).unwrap() {
+        notification::Union::View { message, url, .. } => {
+            assert_eq!(message, "Hello");
+            assert_eq!(url, "/inbox");
+        }
+        other => panic!("expected a view, got {other:?}"),
+    }
+
+    assert!(matches!(
+        notification::Union::try_from(notification(Some(EventKind::Click))).unwrap(),
+        notification::Union::Click { id: 1, .. }
+    ));
+
+    assert_eq!(
+        notification::Union::try_from(notification(None)).unwrap_err(),
+        UnionConversionError::MissingDiscriminator("kind")
+    );
+}
@@ -362,3 +362,107 @@ async fn aggregate_raw() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn emulated_cascade_delete() -> TestResult {
+    let client = client().await;
+
+    let author = client
+        .author()
+        .create("Ursula".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .book()
+        .create_many(vec![
+            book::create_unchecked(
+                "A Wizard of Earthsea".to_string(),
+                author.id.clone(),
+                vec![],
+            ),
+            book::create_unchecked("The Dispossessed".to_string(), author.id.clone(), vec![]),
+        ])
+        .exec()
+        .await?;
+
+    let other_author = client
+        .author()
+        .create("Iain".to_string(), vec![])
+        .exec()
+        .await?;
+    client
+        .book()
+        .create(
+            "Excession".to_string(),
+            author::id::equals(other_author.id.clone()),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    client
+        .author()
+        .delete(author::id::equals(author.id.clone()))
+        .exec()
+        .await?;
+
+    // only the deleted author's books are removed along with them
+    let books = client.book().find_many(vec![]).exec().await?;
+    assert_eq!(books.len(), 1);
+    assert_eq!(books[0].author_id, other_author.id);
+
+    // delete_many cascades too
+    client
+        .author()
+        .delete_many(vec![author::id::equals(other_author.id)])
+        .exec()
+        .await?;
+    assert_eq!(client.book().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn emulated_set_null() -> TestResult {
+    let client = client().await;
+
+    let author = client
+        .author()
+        .create("Ursula".to_string(), vec![])
+        .exec()
+        .await?;
+    let series = client
+        .series()
+        .create("Earthsea".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let book = client
+        .book()
+        .create(
+            "A Wizard of Earthsea".to_string(),
+            author::id::equals(author.id.clone()),
+            vec![book::series::connect(series::id::equals(series.id.clone()))],
+        )
+        .exec()
+        .await?;
+    assert_eq!(book.series_id, Some(series.id.clone()));
+
+    client
+        .series()
+        .delete(series::id::equals(series.id))
+        .exec()
+        .await?;
+
+    // the book stays, but no longer points at the deleted series
+    let book = client
+        .book()
+        .find_unique(book::id::equals(book.id))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(book.series_id, None);
+
+    cleanup(client).await
+}
@@ -7,7 +7,11 @@ pub async fn client() -> PrismaClient {
     let client = PrismaClient::_builder().build().await.unwrap();
 
     client
-        ._batch(vec![client.post().delete_many(vec![])])
+        ._batch((
+            client.book().delete_many(vec![]).all(),
+            client.author().delete_many(vec![]).all(),
+            client.post().delete_many(vec![]).all(),
+        ))
         .await
         .unwrap();
 
@@ -16,7 +20,11 @@ pub async fn client() -> PrismaClient {
 
 pub async fn cleanup(client: PrismaClient) -> TestResult {
     client
-        ._batch(vec![client.post().delete_many(vec![])])
+        ._batch((
+            client.book().delete_many(vec![]).all(),
+            client.author().delete_many(vec![]).all(),
+            client.post().delete_many(vec![]).all(),
+        ))
         .await
         .unwrap();
 
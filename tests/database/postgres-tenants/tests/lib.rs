@@ -0,0 +1,16 @@
+#[allow(warnings, unused)]
+mod db;
+mod utils;
+
+use utils::*;
+
+#[tokio::test]
+async fn aaaa_run_migrations() -> TestResult {
+    let client = db::new_client().await.unwrap();
+
+    client._db_push().accept_data_loss().await.unwrap();
+
+    cleanup(client).await
+}
+
+mod schema_override;
@@ -0,0 +1,92 @@
+use prisma_client_rust::raw;
+use serde::Deserialize;
+
+use crate::{db::*, utils::*};
+
+/// Creates the schema `tenant` with the same table as the datasource's, and empties it.
+async fn create_tenant(client: &PrismaClient, tenant: &str) -> TestResult {
+    client
+        ._execute_raw(raw!(&format!("CREATE SCHEMA IF NOT EXISTS \"{tenant}\"")))
+        .exec()
+        .await?;
+    client
+        ._execute_raw(raw!(&format!(
+            "CREATE TABLE IF NOT EXISTS \"{tenant}\".\"Item\" (id TEXT PRIMARY KEY, name TEXT NOT NULL)"
+        )))
+        .exec()
+        .await?;
+    client
+        ._execute_raw(raw!(&format!("DELETE FROM \"{tenant}\".\"Item\"")))
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Row {
+    name: String,
+}
+
+#[tokio::test]
+async fn queries_use_tenant_schema() -> TestResult {
+    let client = client().await;
+    let tenant_name = "tenant_queries";
+
+    create_tenant(&client, tenant_name).await?;
+
+    let tenant = client._with_schema(tenant_name).await.unwrap();
+
+    tenant
+        .item()
+        .create("a".to_string(), "In the tenant".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // the record was written to the tenant's table...
+    let rows: Vec<Row> = client
+        ._query_raw(raw!(&format!(
+            "SELECT name FROM \"{tenant_name}\".\"Item\""
+        )))
+        .exec()
+        .await?;
+    assert_eq!(
+        rows.into_iter().map(|row| row.name).collect::<Vec<_>>(),
+        vec!["In the tenant".to_string()]
+    );
+
+    // ...and not to the datasource's
+    assert_eq!(client.item().count(vec![]).exec().await?, 0);
+    assert_eq!(tenant.item().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn evict_schema() -> TestResult {
+    let client = client().await;
+    let tenant_name = "tenant_evict";
+
+    create_tenant(&client, tenant_name).await?;
+
+    assert!(!client._evict_schema(tenant_name));
+
+    let tenant = client._with_schema(tenant_name).await.unwrap();
+    tenant
+        .item()
+        .create("a".to_string(), "In the tenant".to_string(), vec![])
+        .exec()
+        .await?;
+
+    assert!(client._evict_schema(tenant_name));
+    assert!(!client._evict_schema(tenant_name));
+
+    // the evicted pool stays usable by the clients that already have it
+    assert_eq!(tenant.item().count(vec![]).exec().await?, 1);
+
+    // and the next override connects again
+    let reconnected = client._with_schema(tenant_name).await.unwrap();
+    assert_eq!(reconnected.item().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
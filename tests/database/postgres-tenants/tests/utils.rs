@@ -0,0 +1,24 @@
+use crate::db::PrismaClient;
+use prisma_client_rust::QueryError;
+
+pub type TestResult = Result<(), QueryError>;
+
+pub async fn client() -> PrismaClient {
+    let client = PrismaClient::_builder().build().await.unwrap();
+
+    client
+        .item()
+        .delete_many(vec![])
+        .all()
+        .exec()
+        .await
+        .unwrap();
+
+    client
+}
+
+pub async fn cleanup(client: PrismaClient) -> TestResult {
+    client.item().delete_many(vec![]).all().exec().await?;
+
+    Ok(())
+}
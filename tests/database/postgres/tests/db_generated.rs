@@ -0,0 +1,37 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn defaults_are_optional() -> TestResult {
+    let client = client().await;
+
+    let generated = client
+        .db_generated_model()
+        .create("generated".to_string(), vec![])
+        .exec()
+        .await?;
+    assert_eq!(generated.id.len(), 36);
+
+    let id = "00000000-0000-0000-0000-000000000001".to_string();
+
+    let provided = client
+        .db_generated_model()
+        .create(
+            "provided".to_string(),
+            vec![db_generated_model::id::set(id.clone())],
+        )
+        .exec()
+        .await?;
+    assert_eq!(provided.id, id);
+    assert!(provided.created_at >= generated.created_at);
+
+    client
+        .db_generated_model()
+        .delete_many(vec![db_generated_model::id::in_vec(vec![
+            generated.id,
+            provided.id,
+        ])])
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
@@ -0,0 +1,13 @@
+use crate::utils::*;
+
+#[tokio::test]
+async fn db_version() -> TestResult {
+    let client = client().await;
+
+    let version = client._db_version().await.unwrap();
+
+    assert!(version.raw.starts_with("PostgreSQL"));
+    assert!(version.at_least(9, 0));
+
+    cleanup(client).await
+}
@@ -0,0 +1,26 @@
+use std::convert::TryFrom;
+
+use prisma_client_rust::EnumConversionError;
+
+use crate::db::*;
+
+#[test]
+fn from_subset() {
+    assert_eq!(ExtendedEnum::from(SomeEnum::A), ExtendedEnum::A);
+
+    let extended: ExtendedEnum = SomeEnum::B.into();
+    assert_eq!(extended, ExtendedEnum::B);
+}
+
+#[test]
+fn try_from_superset() {
+    assert_eq!(SomeEnum::try_from(ExtendedEnum::A), Ok(SomeEnum::A));
+    assert_eq!(
+        SomeEnum::try_from(ExtendedEnum::C),
+        Err(EnumConversionError {
+            from: "ExtendedEnum",
+            to: "SomeEnum",
+            variant: "C",
+        })
+    );
+}
@@ -0,0 +1,50 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn in_and_not_in() -> TestResult {
+    let client = client().await;
+
+    for (id, value) in [
+        ("enum-filter-a", SomeEnum::A),
+        ("enum-filter-b", SomeEnum::B),
+    ] {
+        client
+            .some_model()
+            .create(id.to_string(), value, vec![])
+            .exec()
+            .await?;
+    }
+
+    let ids =
+        |records: Vec<some_model::Data>| records.into_iter().map(|r| r.id).collect::<Vec<_>>();
+
+    let found = client
+        .some_model()
+        .find_many(vec![
+            some_model::id::starts_with("enum-filter-".to_string()),
+            some_model::some_enum::in_vec(vec![SomeEnum::A]),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec!["enum-filter-a"]);
+
+    let found = client
+        .some_model()
+        .find_many(vec![
+            some_model::id::starts_with("enum-filter-".to_string()),
+            some_model::some_enum::not_in_vec(vec![SomeEnum::A]),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(ids(found), vec!["enum-filter-b"]);
+
+    client
+        .some_model()
+        .delete_many(vec![some_model::id::starts_with(
+            "enum-filter-".to_string(),
+        )])
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
@@ -0,0 +1,127 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn create_and_read() -> TestResult {
+    let client = client().await;
+
+    let created = client
+        .some_model()
+        .create(
+            "enum-list-create".to_string(),
+            SomeEnum::A,
+            vec![some_model::enum_list::set(vec![SomeEnum::A, SomeEnum::B])],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(created.enum_list, vec![SomeEnum::A, SomeEnum::B]);
+
+    let found = client
+        .some_model()
+        .find_unique(some_model::id::equals(created.id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.enum_list, vec![SomeEnum::A, SomeEnum::B]);
+
+    client
+        .some_model()
+        .delete(some_model::id::equals(created.id))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn update() -> TestResult {
+    let client = client().await;
+
+    let created = client
+        .some_model()
+        .create("enum-list-update".to_string(), SomeEnum::A, vec![])
+        .exec()
+        .await?;
+
+    assert!(created.enum_list.is_empty());
+
+    let updated = client
+        .some_model()
+        .update(
+            some_model::id::equals(created.id.clone()),
+            vec![some_model::enum_list::push(SomeEnum::B)],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.enum_list, vec![SomeEnum::B]);
+
+    let updated = client
+        .some_model()
+        .update(
+            some_model::id::equals(created.id.clone()),
+            vec![some_model::enum_list::push_many(vec![
+                SomeEnum::A,
+                SomeEnum::B,
+            ])],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(
+        updated.enum_list,
+        vec![SomeEnum::B, SomeEnum::A, SomeEnum::B]
+    );
+
+    client
+        .some_model()
+        .delete(some_model::id::equals(created.id))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn filters() -> TestResult {
+    let client = client().await;
+
+    let created = client
+        .some_model()
+        .create(
+            "enum-list-filters".to_string(),
+            SomeEnum::A,
+            vec![some_model::enum_list::set(vec![SomeEnum::B])],
+        )
+        .exec()
+        .await?;
+
+    let has = client
+        .some_model()
+        .find_many(vec![
+            some_model::id::equals(created.id.clone()),
+            some_model::enum_list::has(Some(SomeEnum::B)),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(has.len(), 1);
+
+    let has_some = client
+        .some_model()
+        .find_many(vec![
+            some_model::id::equals(created.id.clone()),
+            some_model::enum_list::has_some(vec![SomeEnum::A]),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(has_some.len(), 0);
+
+    client
+        .some_model()
+        .delete(some_model::id::equals(created.id))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
@@ -0,0 +1,33 @@
+use std::convert::TryFrom;
+
+use prisma_client_rust::ParseEnumError;
+
+use crate::db::*;
+
+#[test]
+fn parses_database_values() {
+    assert_eq!("active".parse::<MappedEnum>(), Ok(MappedEnum::Active));
+    assert_eq!(MappedEnum::try_from("archived"), Ok(MappedEnum::Archived));
+    assert_eq!("A".parse::<SomeEnum>(), Ok(SomeEnum::A));
+    assert_eq!(LargeEnum::try_from("C099"), Ok(LargeEnum::C099));
+}
+
+#[test]
+fn rejects_unknown_values() {
+    assert_eq!(
+        "Active".parse::<MappedEnum>(),
+        Err(ParseEnumError {
+            enum_name: "MappedEnum",
+            value: "Active".to_string(),
+        })
+    );
+    assert!(SomeEnum::try_from("a").is_err());
+}
+
+#[test]
+fn database_values() {
+    assert_eq!(MappedEnum::Archived.as_db_str(), "archived");
+    assert_eq!(MappedEnum::Archived.to_string(), "archived");
+    assert_eq!(format!("{}", SomeEnum::B), "B");
+    assert_eq!(SomeEnum::B.as_db_str(), "B");
+}
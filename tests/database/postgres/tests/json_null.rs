@@ -0,0 +1,98 @@
+use prisma_client_rust::{raw, serde_json::json, QueryError};
+use serde::Deserialize;
+
+use crate::{db::*, utils::*};
+
+#[derive(Deserialize)]
+struct Row {
+    id: String,
+    db_null: bool,
+}
+
+/// Whether each record's `some_column` is the database's `NULL`, by id.
+async fn db_nulls(client: &PrismaClient) -> Result<Vec<(String, bool)>, QueryError> {
+    let rows: Vec<Row> = client
+        ._query_raw(raw!(&format!(
+            "SELECT id, some_column IS NULL AS db_null FROM {} WHERE id LIKE 'json-null-%' ORDER BY id",
+            some_model::QUALIFIED_DB_NAME
+        )))
+        .exec()
+        .await?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.db_null)).collect())
+}
+
+#[tokio::test]
+async fn json_and_db_null() -> TestResult {
+    let client = client().await;
+
+    for (id, param) in [
+        ("json-null-db", some_model::some_column::set_db_null()),
+        ("json-null-json", some_model::some_column::set_json_null()),
+        ("json-null-none", some_model::some_column::set(None)),
+        (
+            "json-null-value",
+            some_model::some_column::set(Some(json!({ "a": 1 }))),
+        ),
+    ] {
+        client
+            .some_model()
+            .create(id.to_string(), SomeEnum::A, vec![param])
+            .exec()
+            .await?;
+    }
+
+    assert_eq!(
+        db_nulls(&client).await?,
+        [
+            ("json-null-db".to_string(), true),
+            ("json-null-json".to_string(), false),
+            ("json-null-none".to_string(), true),
+            ("json-null-value".to_string(), false),
+        ]
+    );
+
+    let value = client
+        .some_model()
+        .find_unique(some_model::id::equals("json-null-value".to_string()))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(value.some_column, Some(json!({ "a": 1 })));
+
+    // switching between the two kinds of null
+    client
+        .some_model()
+        .update(
+            some_model::id::equals("json-null-json".to_string()),
+            vec![some_model::some_column::set_db_null()],
+        )
+        .exec()
+        .await?;
+    client
+        .some_model()
+        .update(
+            some_model::id::equals("json-null-db".to_string()),
+            vec![some_model::some_column::set_json_null()],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(
+        db_nulls(&client).await?,
+        [
+            ("json-null-db".to_string(), false),
+            ("json-null-json".to_string(), true),
+            ("json-null-none".to_string(), true),
+            ("json-null-value".to_string(), false),
+        ]
+    );
+
+    client
+        .some_model()
+        .delete_many(vec![some_model::id::starts_with("json-null-".to_string())])
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
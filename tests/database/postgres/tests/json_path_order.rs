@@ -0,0 +1,23 @@
+use prisma_client_rust::QueryError;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn unsupported_by_bundled_engine() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        .some_model()
+        .find_many(vec![])
+        .order_by(some_model::some_column::order_by_path(
+            vec!["priority".to_string()],
+            SortOrder::Desc,
+        ))
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, QueryError::UnsupportedJsonPathOrder));
+
+    cleanup(client).await
+}
@@ -0,0 +1,19 @@
+use prisma_client_rust::serde_json;
+
+use crate::db::*;
+
+#[test]
+fn to_string() {
+    assert_eq!(LargeEnum::C000.to_string(), "C000");
+    assert_eq!(LargeEnum::C250.to_string(), "C250");
+    assert_eq!(LargeEnum::C499.to_string(), "C499");
+}
+
+#[test]
+fn serde_round_trip() {
+    let serialized = serde_json::to_string(&LargeEnum::C499).unwrap();
+    assert_eq!(serialized, "\"C499\"");
+
+    let deserialized: LargeEnum = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, LargeEnum::C499);
+}
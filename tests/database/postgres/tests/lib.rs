@@ -4,3 +4,20 @@ mod utils;
 
 use db::*;
 use utils::*;
+
+mod db_generated;
+mod db_version;
+mod enum_conversions;
+mod enum_filters;
+mod enum_list;
+mod enum_strings;
+mod json_null;
+mod json_path_order;
+mod large_enum;
+mod multi_schema;
+mod native_types;
+mod relevance_order;
+mod reset;
+mod scalar_list;
+mod socket;
+mod update_many_returning;
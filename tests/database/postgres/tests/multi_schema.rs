@@ -0,0 +1,70 @@
+use prisma_client_rust::{raw, PrismaValue, WithSchemaError};
+use serde::Deserialize;
+
+use crate::{db::*, utils::*};
+
+#[test]
+fn qualified_names() {
+    assert_eq!(other_schema_model::DB_NAME, "other_schema_model");
+    assert_eq!(other_schema_model::SCHEMA, Some("other"));
+    assert_eq!(
+        other_schema_model::QUALIFIED_DB_NAME,
+        "\"other\".\"other_schema_model\""
+    );
+    assert_eq!(some_model::SCHEMA, Some("public"));
+}
+
+#[tokio::test]
+async fn queries_target_schema() -> TestResult {
+    let client = client().await;
+
+    let created = client
+        .other_schema_model()
+        .create(
+            "multi-schema".to_string(),
+            "In another schema".to_string(),
+            vec![],
+        )
+        .exec()
+        .await?;
+
+    #[derive(Deserialize)]
+    struct Row {
+        name: String,
+    }
+
+    let rows: Vec<Row> = client
+        ._query_raw(raw!(
+            &format!(
+                "SELECT name FROM {} WHERE id = {{}}",
+                other_schema_model::QUALIFIED_DB_NAME
+            ),
+            PrismaValue::String(created.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].name, created.name);
+
+    client
+        .other_schema_model()
+        .delete(other_schema_model::id::equals(created.id))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn schema_override_unsupported() -> TestResult {
+    let client = client().await;
+
+    // models with @@schema ignore the connection's schema
+    assert!(matches!(
+        client._with_schema("tenant_1").await,
+        Err(WithSchemaError::MultiSchema)
+    ));
+
+    cleanup(client).await
+}
@@ -0,0 +1,56 @@
+use prisma_client_rust::chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn narrower_types() -> TestResult {
+    let client = client().await;
+
+    let timestamp = Utc.with_ymd_and_hms(2023, 1, 2, 3, 4, 5).unwrap();
+    let timestamptz = FixedOffset::east_opt(12 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2023, 1, 2, 3, 4, 5)
+        .unwrap();
+
+    let created = client
+        .native_types_model()
+        .create(
+            "native-types".to_string(),
+            -3,
+            timestamp,
+            timestamptz,
+            vec![native_types_model::small_opt::set(Some(i16::MAX))],
+        )
+        .exec()
+        .await?;
+
+    let small: i16 = created.small;
+    let small_opt: Option<i16> = created.small_opt;
+    let stored_timestamp: DateTime<Utc> = created.timestamp;
+    let stored_timestamptz: DateTime<FixedOffset> = created.timestamptz;
+
+    assert_eq!(small, -3);
+    assert_eq!(small_opt, Some(i16::MAX));
+    assert_eq!(stored_timestamp, timestamp);
+    assert_eq!(stored_timestamptz, timestamptz);
+
+    // equals takes the native type, other filters take the engine's
+    let found = client
+        .native_types_model()
+        .find_many(vec![
+            native_types_model::small::equals(-3i16),
+            native_types_model::small_opt::gt(0),
+            native_types_model::timestamp::equals(timestamp),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 1);
+
+    client
+        .native_types_model()
+        .delete(native_types_model::id::equals("native-types".to_string()))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
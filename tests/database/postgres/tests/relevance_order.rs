@@ -0,0 +1,42 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn order_by_relevance() -> TestResult {
+    let client = client().await;
+
+    let ids = ["relevance-1", "relevance-2", "relevance-3"];
+
+    client
+        .article()
+        .delete_many(vec![article::id::in_vec(ids.map(String::from).to_vec())])
+        .exec()
+        .await?;
+
+    for (id, body) in ids.iter().zip([
+        "cats and dogs",
+        "dogs, dogs and more dogs",
+        "nothing but birds",
+    ]) {
+        client
+            .article()
+            .create(id.to_string(), "pets".to_string(), body.to_string(), vec![])
+            .exec()
+            .await?;
+    }
+
+    let articles = client
+        .article()
+        .find_many(vec![article::id::in_vec(ids.map(String::from).to_vec())])
+        .order_by(article::order_by_relevance(
+            vec![article::RelevanceField::Body],
+            "dogs",
+            SortOrder::Desc,
+        ))
+        .exec()
+        .await?;
+
+    let ranked = articles.iter().map(|a| a.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ranked, ["relevance-2", "relevance-1", "relevance-3"]);
+
+    cleanup(client).await
+}
@@ -0,0 +1,32 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn reset_truncates() -> TestResult {
+    let client = client().await;
+
+    client
+        .some_model()
+        .create("reset".to_string(), SomeEnum::A, vec![])
+        .exec()
+        .await?;
+    // lives in the `other` schema, so the truncate has to qualify its table
+    client
+        .other_schema_model()
+        .create("reset".to_string(), "Reset".to_string(), vec![])
+        .exec()
+        .await?;
+    client
+        .db_generated_model()
+        .create("reset".to_string(), vec![])
+        .exec()
+        .await?;
+
+    crate::db::reset(&client).await?;
+
+    assert_eq!(client.some_model().count(vec![]).exec().await?, 0);
+    assert_eq!(client.other_schema_model().count(vec![]).exec().await?, 0);
+    assert_eq!(client.db_generated_model().count(vec![]).exec().await?, 0);
+    assert_eq!(client.article().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
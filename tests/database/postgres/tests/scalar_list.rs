@@ -0,0 +1,62 @@
+use prisma_client_rust::serde_json::json;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn set_and_push() -> TestResult {
+    let client = client().await;
+
+    let created = client
+        .another_model()
+        .create(
+            "scalar-list-set-and-push".to_string(),
+            json!({}),
+            vec![another_model::string_array::set(vec!["a".to_string()])],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(created.string_array, vec!["a".to_string()]);
+    assert!(created.another_array.is_empty());
+
+    let updated = client
+        .another_model()
+        .update(
+            another_model::id::equals(created.id.clone()),
+            vec![
+                another_model::string_array::push("b".to_string()),
+                another_model::another_array::push_many(vec!["c".to_string(), "d".to_string()]),
+            ],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.string_array, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        updated.another_array,
+        vec!["c".to_string(), "d".to_string()]
+    );
+
+    let updated = client
+        .another_model()
+        .update(
+            another_model::id::equals(created.id.clone()),
+            vec![
+                another_model::string_array::set(vec!["e".to_string()]),
+                another_model::another_array::set(vec![]),
+            ],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.string_array, vec!["e".to_string()]);
+    assert!(updated.another_array.is_empty());
+
+    client
+        .another_model()
+        .delete(another_model::id::equals(created.id))
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}
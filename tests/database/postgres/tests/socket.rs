@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use prisma_client_rust::{NewClientError, SocketError};
+
+use crate::db::*;
+
+#[tokio::test]
+async fn relative_socket_path() {
+    let result = PrismaClient::_builder()
+        .with_socket("var/run/postgresql")
+        .build()
+        .await;
+
+    match result {
+        Err(NewClientError::Socket(SocketError::RelativePath(path))) => {
+            assert_eq!(path, PathBuf::from("var/run/postgresql"))
+        }
+        result => panic!("Expected RelativePath error, got {result:?}"),
+    }
+}
+
+#[test]
+fn socket_replaces_url_host_param() {
+    let url = prisma_client_rust::apply_socket_to_url(
+        Some("/var/run/my socket".as_ref()),
+        "postgresql",
+        "postgresql://user@localhost/db?host=/tmp&schema=public",
+    )
+    .unwrap();
+
+    assert_eq!(
+        url,
+        "postgresql://user@localhost/db?schema=public&host=/var/run/my%20socket"
+    );
+}
@@ -0,0 +1,65 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn returns_updated_records() -> TestResult {
+    let client = client().await;
+
+    let names = ["returning-a", "returning-b"];
+
+    for name in names {
+        client
+            .db_generated_model()
+            .create(name.to_string(), vec![])
+            .exec()
+            .await?;
+    }
+
+    let updated = client
+        .db_generated_model()
+        .update_many(
+            vec![db_generated_model::name::starts_with(
+                "returning-".to_string(),
+            )],
+            vec![db_generated_model::name::set("returned".to_string())],
+        )
+        .returning()
+        .exec()
+        .await?;
+    assert_eq!(updated.len(), names.len());
+    assert!(updated.iter().all(|r| r.name == "returned"));
+
+    // updating inside a transaction uses the existing transaction
+    let (tx, tx_client) = client._transaction().begin().await?;
+
+    let updated_in_tx = tx_client
+        .db_generated_model()
+        .update_many(
+            vec![db_generated_model::name::equals("returned".to_string())],
+            vec![db_generated_model::name::set("rolled back".to_string())],
+        )
+        .returning()
+        .exec()
+        .await?;
+    assert_eq!(updated_in_tx.len(), names.len());
+
+    tx.rollback(tx_client).await?;
+
+    let count = client
+        .db_generated_model()
+        .count(vec![db_generated_model::name::equals(
+            "returned".to_string(),
+        )])
+        .exec()
+        .await?;
+    assert_eq!(count, names.len() as i64);
+
+    client
+        .db_generated_model()
+        .delete_many(vec![db_generated_model::id::in_vec(
+            updated.into_iter().map(|r| r.id).collect(),
+        )])
+        .exec()
+        .await?;
+
+    cleanup(client).await
+}